@@ -0,0 +1,42 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use mutagen_rs::common::groups::KeyGroups;
+
+/// `count` comments over `keys` distinct keys, e.g. per-track cuesheet
+/// comments (`CUE_TRACK01_TITLE=...`) in a large FLAC.
+fn comments(keys: usize, count: usize) -> Vec<(String, String)> {
+    (0..count).map(|i| (format!("CUE_TRACK{:03}_TITLE", i % keys), format!("value {}", i))).collect()
+}
+
+/// The previous grouping: a linear scan over the keys seen so far.
+fn group_linear(input: &[(String, String)]) -> Vec<(String, Vec<String>)> {
+    let mut groups: Vec<(String, Vec<String>)> = Vec::new();
+    for (key, value) in input {
+        if let Some(entry) = groups.iter_mut().find(|(k, _)| k == key) {
+            entry.1.push(value.clone());
+        } else {
+            groups.push((key.clone(), vec![value.clone()]));
+        }
+    }
+    groups
+}
+
+fn group_adaptive(input: &[(String, String)]) -> Vec<(String, Vec<String>)> {
+    let mut groups = KeyGroups::new();
+    for (key, value) in input {
+        groups.push(key.clone(), value.clone());
+    }
+    groups.into_vec()
+}
+
+fn bench_grouping(c: &mut Criterion) {
+    for (name, keys, count) in [("vc_group_500_keys", 500, 1000), ("vc_group_10_keys", 10, 20)] {
+        let input = comments(keys, count);
+        let mut group = c.benchmark_group(name);
+        group.bench_function("linear", |b| b.iter(|| group_linear(black_box(&input))));
+        group.bench_function("adaptive", |b| b.iter(|| group_adaptive(black_box(&input))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_grouping);
+criterion_main!(benches);
@@ -5,7 +5,11 @@ from .mutagen_rs import (
     MP3 as _RustMP3,
     FLAC as _RustFLAC,
     OggVorbis as _RustOggVorbis,
+    OggFLAC as _RustOggFLAC,
     MP4 as _RustMP4,
+    WavPack as _RustWavPack,
+    ASF as _RustASF,
+    MonkeysAudio as _RustMonkeysAudio,
     file_open as _rust_file_open,
 
     # Info types (re-exported as-is)
@@ -13,11 +17,17 @@ from .mutagen_rs import (
     StreamInfo,
     OggVorbisInfo,
     MP4Info,
+    WavPackInfo,
+    ASFInfo,
+    MonkeysAudioInfo,
 
     # Tag types (re-exported as-is)
     ID3,
+    EasyID3,
     VComment,
     MP4Tags,
+    APEv2,
+    ASFTags,
 
     # Batch API
     batch_open as _rust_batch_open,
@@ -33,8 +43,22 @@ from .mutagen_rs import (
     # Fast sequential batch read (single Rust call, no parallelism)
     _fast_read_seq,
 
+    # Format detection by content scoring
+    detect_format,
+
+    # Cover art across formats
+    front_cover,
+
+    # Length without parsing tags
+    duration,
+
+    # Chapter and cue markers across formats
+    markers,
+
     # Clear Rust-level caches
     clear_cache as _rust_clear_cache,
+    set_file_cache_capacity,
+    file_cache_info,
 
     # Error types (re-exported as-is)
     MutagenError,
@@ -46,6 +70,14 @@ from .mutagen_rs import (
     FLACNoHeaderError,
     OggError,
     MP4Error,
+    APEError,
+    WavPackError,
+    ASFError,
+    MonkeysAudioHeaderError,
+    TruncatedError,
+    VerificationError,
+    InvalidDataError,
+    EncodingError,
 )
 
 # Module-level cache: filename -> _CachedFile
@@ -81,6 +113,7 @@ class _InfoProxy:
         self.total_samples = d.get('total_samples')
         # MP4-specific
         self.codec = d.get('codec')
+        self.codec_description = d.get('codec_description')
 
     def pprint(self):
         return f"{self.length:.2f} seconds, {self.sample_rate} Hz"
@@ -102,8 +135,10 @@ class _CachedFile(dict):
 
     def save(self, *args, **kwargs):
         if self._native is not None:
-            self._native.save(*args, **kwargs)
-            _cache.pop(self.filename, None)
+            result = self._native.save(*args, **kwargs)
+            if not kwargs.get('dry_run'):
+                _cache.pop(self.filename, None)
+            return result
 
     def pprint(self):
         if self._native is not None:
@@ -179,6 +214,16 @@ def OggVorbis(filename):
     return w
 
 
+def OggFLAC(filename):
+    w = _cache.get(filename)
+    if w is not None:
+        return w
+    d = _fast_read(filename)
+    w = _make_cached_fast(d, filename)
+    _cache[filename] = w
+    return w
+
+
 def MP4(filename):
     w = _cache.get(filename)
     if w is not None:
@@ -189,6 +234,47 @@ def MP4(filename):
     return w
 
 
+def WavPack(filename):
+    w = _cache.get(filename)
+    if w is not None:
+        return w
+    d = _fast_read(filename)
+    w = _make_cached_fast(d, filename)
+    _cache[filename] = w
+    return w
+
+
+def ASF(filename):
+    w = _cache.get(filename)
+    if w is not None:
+        return w
+    d = _fast_read(filename)
+    w = _make_cached_fast(d, filename)
+    _cache[filename] = w
+    return w
+
+
+def MonkeysAudio(filename):
+    w = _cache.get(filename)
+    if w is not None:
+        return w
+    d = _fast_read(filename)
+    w = _make_cached_fast(d, filename)
+    _cache[filename] = w
+    return w
+
+
+# In-memory buffers bypass the path cache and return the native objects.
+for _factory, _native in (
+    (MP3, _RustMP3), (FLAC, _RustFLAC), (OggVorbis, _RustOggVorbis),
+    (OggFLAC, _RustOggFLAC),
+    (MP4, _RustMP4), (WavPack, _RustWavPack), (ASF, _RustASF),
+    (MonkeysAudio, _RustMonkeysAudio),
+):
+    _factory.from_bytes = _native.from_bytes
+del _factory, _native
+
+
 def File(filename, easy=False):
     w = _cache.get(filename)
     if w is not None:
@@ -214,3 +300,6 @@ def clear_cache():
     _last_batch[0] = None
     _last_batch[1] = None
     _rust_clear_cache()
+
+
+clear_file_cache = clear_cache
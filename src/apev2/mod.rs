@@ -0,0 +1,268 @@
+use crate::common::error::{MutagenError, Result};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// APEv2 header/footer preamble.
+const PREAMBLE: &[u8; 8] = b"APETAGEX";
+
+/// Tag flag: the tag has a header.
+const FLAG_HAS_HEADER: u32 = 1 << 31;
+
+/// Tag flag: set on the header, clear on the footer.
+const FLAG_IS_HEADER: u32 = 1 << 29;
+
+/// Tag flag: the tag is marked read-only.
+const FLAG_READ_ONLY: u32 = 1;
+
+/// Version written by `render` (APEv2).
+const VERSION: u32 = 2000;
+
+/// Kind of an APEv2 item value (bits 1-2 of the item flags).
+#[derive(Debug, Clone, PartialEq)]
+pub enum APEValue {
+    /// UTF-8 text; multiple values are separated by nulls on disk.
+    Text(Vec<String>),
+    /// Binary data (e.g. cover art).
+    Binary(Vec<u8>),
+    /// UTF-8 link to an external resource.
+    External(String),
+}
+
+/// Position of an APEv2 tag within file data.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct APEv2Location {
+    /// Start of the tag (header if present, otherwise first item).
+    pub start: usize,
+    /// End of the footer (exclusive).
+    pub end: usize,
+    /// Start of the first item.
+    pub items_start: usize,
+    pub item_count: u32,
+    pub version: u32,
+    /// Global tag flags from the footer.
+    pub flags: u32,
+}
+
+/// Find an APEv2 tag at the end of the file, or just before a trailing ID3v1 tag.
+pub fn find_apev2(data: &[u8]) -> Option<APEv2Location> {
+    let mut end = data.len();
+    if crate::id3::id3v1::find_id3v1(data).is_some() {
+        end -= 128;
+    }
+    if end < 32 || &data[end - 32..end - 24] != PREAMBLE {
+        return None;
+    }
+
+    let footer = &data[end - 32..end];
+    let version = u32::from_le_bytes([footer[8], footer[9], footer[10], footer[11]]);
+    // Size covers items + footer, but not the header
+    let size = u32::from_le_bytes([footer[12], footer[13], footer[14], footer[15]]) as usize;
+    let item_count = u32::from_le_bytes([footer[16], footer[17], footer[18], footer[19]]);
+    let flags = u32::from_le_bytes([footer[20], footer[21], footer[22], footer[23]]);
+
+    if size < 32 || size > end {
+        return None;
+    }
+    let items_start = end - size;
+    let start = if flags & FLAG_HAS_HEADER != 0
+        && items_start >= 32
+        && &data[items_start - 32..items_start - 24] == PREAMBLE
+    {
+        items_start - 32
+    } else {
+        items_start
+    };
+
+    Some(APEv2Location { start, end, items_start, item_count, version, flags })
+}
+
+/// APEv2 tag container. Keys keep their on-disk case; lookups are case-insensitive.
+#[derive(Debug, Clone, Default)]
+pub struct APEv2Tags {
+    pub items: Vec<(String, APEValue)>,
+    /// The tag's read-only flag, kept when the tag is written back.
+    pub read_only: bool,
+}
+
+impl APEv2Tags {
+    pub fn new() -> Self {
+        APEv2Tags { items: Vec::new(), read_only: false }
+    }
+
+    /// Find and parse the APEv2 tag in file data. Returns `Ok(None)` when there is no tag.
+    pub fn from_data(data: &[u8]) -> Result<Option<Self>> {
+        match find_apev2(data) {
+            Some(loc) => {
+                let items = &data[loc.items_start..loc.end - 32];
+                let mut tags = Self::parse_items(items, loc.item_count)?;
+                tags.read_only = loc.flags & FLAG_READ_ONLY != 0;
+                Ok(Some(tags))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Parse `count` items from the item area (between header and footer).
+    pub fn parse_items(data: &[u8], count: u32) -> Result<Self> {
+        let mut tags = APEv2Tags::new();
+        let mut pos = 0usize;
+
+        for _ in 0..count {
+            if pos + 8 > data.len() {
+                return Err(MutagenError::APE("item header extends past tag".into()));
+            }
+            let value_len = u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+            let flags = u32::from_le_bytes([data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]]);
+            pos += 8;
+
+            let key_end = match memchr::memchr(0, &data[pos..]) {
+                Some(n) => pos + n,
+                None => return Err(MutagenError::APE("unterminated item key".into())),
+            };
+            let key = String::from_utf8_lossy(&data[pos..key_end]).into_owned();
+            pos = key_end + 1;
+
+            if pos + value_len > data.len() {
+                return Err(MutagenError::APE(format!("value of {} extends past tag", key)));
+            }
+            let raw = &data[pos..pos + value_len];
+            pos += value_len;
+
+            let value = match (flags >> 1) & 3 {
+                1 => APEValue::Binary(raw.to_vec()),
+                2 => APEValue::External(String::from_utf8_lossy(raw).into_owned()),
+                _ => APEValue::Text(
+                    String::from_utf8_lossy(raw).split('\0').map(str::to_string).collect(),
+                ),
+            };
+            tags.items.push((key, value));
+        }
+
+        Ok(tags)
+    }
+
+    pub fn keys(&self) -> Vec<String> {
+        self.items.iter().map(|(k, _)| k.clone()).collect()
+    }
+
+    pub fn get(&self, key: &str) -> Option<&APEValue> {
+        self.items.iter().find(|(k, _)| k.eq_ignore_ascii_case(key)).map(|(_, v)| v)
+    }
+
+    /// Replace the value of `key` (matched case-insensitively), or append it.
+    pub fn set(&mut self, key: &str, value: APEValue) -> Result<()> {
+        if !is_valid_key(key) {
+            return Err(MutagenError::ValueError(format!("{:?} is not a valid APEv2 key", key)));
+        }
+        match self.items.iter_mut().find(|(k, _)| k.eq_ignore_ascii_case(key)) {
+            Some(item) => item.1 = value,
+            None => self.items.push((key.to_string(), value)),
+        }
+        Ok(())
+    }
+
+    /// Remove `key` (matched case-insensitively); returns whether it was present.
+    pub fn delete(&mut self, key: &str) -> bool {
+        let before = self.items.len();
+        self.items.retain(|(k, _)| !k.eq_ignore_ascii_case(key));
+        self.items.len() != before
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Serialize to a complete tag: header, items and footer.
+    pub fn render(&self) -> Vec<u8> {
+        let mut items = Vec::new();
+        for (key, value) in &self.items {
+            let (kind, raw) = match value {
+                APEValue::Text(values) => (0u32, values.join("\0").into_bytes()),
+                APEValue::Binary(data) => (1, data.clone()),
+                APEValue::External(link) => (2, link.clone().into_bytes()),
+            };
+            items.extend_from_slice(&(raw.len() as u32).to_le_bytes());
+            items.extend_from_slice(&(kind << 1).to_le_bytes());
+            items.extend_from_slice(key.as_bytes());
+            items.push(0);
+            items.extend_from_slice(&raw);
+        }
+
+        let mut flags = FLAG_HAS_HEADER;
+        if self.read_only {
+            flags |= FLAG_READ_ONLY;
+        }
+        let block = |flags: u32| {
+            let mut b = Vec::with_capacity(32);
+            b.extend_from_slice(PREAMBLE);
+            b.extend_from_slice(&VERSION.to_le_bytes());
+            b.extend_from_slice(&(items.len() as u32 + 32).to_le_bytes());
+            b.extend_from_slice(&(self.items.len() as u32).to_le_bytes());
+            b.extend_from_slice(&flags.to_le_bytes());
+            b.extend_from_slice(&[0u8; 8]);
+            b
+        };
+
+        let mut out = block(flags | FLAG_IS_HEADER);
+        out.extend_from_slice(&items);
+        out.extend_from_slice(&block(flags));
+        out
+    }
+}
+
+/// Keys are 2-255 printable ASCII characters, excluding a few that would be
+/// mistaken for other tag headers.
+fn is_valid_key(key: &str) -> bool {
+    (2..=255).contains(&key.len())
+        && key.bytes().all(|b| (0x20..=0x7E).contains(&b))
+        && !["OggS", "TAG", "ID3", "MP+"].contains(&key)
+}
+
+/// Write `tags` at the end of the file, replacing any existing APEv2 tag and
+/// keeping a trailing ID3v1 tag after it. An empty tag removes the block.
+pub fn save_apev2(path: &str, tags: &APEv2Tags) -> Result<()> {
+    let new_tag = if tags.is_empty() { Vec::new() } else { tags.render() };
+    write_tag(path, &new_tag)
+}
+
+/// Remove the APEv2 tag from a file, if it has one.
+pub fn delete_apev2(path: &str) -> Result<()> {
+    write_tag(path, &[])
+}
+
+fn write_tag(path: &str, new_tag: &[u8]) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)?;
+
+    let mut existing = Vec::new();
+    file.read_to_end(&mut existing)?;
+
+    let (start, end) = match find_apev2(&existing) {
+        Some(loc) => (loc.start, loc.end),
+        None if crate::id3::id3v1::find_id3v1(&existing).is_some() => {
+            (existing.len() - 128, existing.len() - 128)
+        }
+        None => (existing.len(), existing.len()),
+    };
+    if start == end && new_tag.is_empty() {
+        return Ok(());
+    }
+
+    file.seek(SeekFrom::Start(start as u64))?;
+    file.set_len(start as u64)?;
+    file.write_all(new_tag)?;
+    file.write_all(&existing[end..])?;
+    file.flush()?;
+    crate::common::cache::invalidate(path);
+
+    Ok(())
+}
@@ -0,0 +1,337 @@
+use crate::common::error::{MutagenError, Result};
+
+/// ASF object GUIDs, in on-disk (mixed-endian) byte order.
+const HEADER_OBJECT: [u8; 16] = guid(0x75B22630, 0x668E, 0x11CF, [0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C]);
+const FILE_PROPERTIES: [u8; 16] = guid(0x8CABDCA1, 0xA947, 0x11CF, [0x8E, 0xE4, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65]);
+const STREAM_PROPERTIES: [u8; 16] = guid(0xB7DC0791, 0xA9B7, 0x11CF, [0x8E, 0xE6, 0x00, 0xC0, 0x0C, 0x20, 0x53, 0x65]);
+const CONTENT_DESCRIPTION: [u8; 16] = guid(0x75B22633, 0x668E, 0x11CF, [0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C]);
+const EXTENDED_CONTENT_DESCRIPTION: [u8; 16] = guid(0xD2D0A440, 0xE307, 0x11D2, [0x97, 0xF0, 0x00, 0xA0, 0xC9, 0x5E, 0xA8, 0x50]);
+const CODEC_LIST: [u8; 16] = guid(0x86D15240, 0x311D, 0x11D0, [0xA3, 0xA4, 0x00, 0xA0, 0xC9, 0x03, 0x48, 0xF6]);
+const AUDIO_MEDIA: [u8; 16] = guid(0xF8699E40, 0x5B4D, 0x11CF, [0xA8, 0xFD, 0x00, 0x80, 0x5F, 0x5C, 0x44, 0x2B]);
+
+/// Build a GUID's byte representation: the first three fields are little-endian.
+const fn guid(d1: u32, d2: u16, d3: u16, d4: [u8; 8]) -> [u8; 16] {
+    let a = d1.to_le_bytes();
+    let b = d2.to_le_bytes();
+    let c = d3.to_le_bytes();
+    [a[0], a[1], a[2], a[3], b[0], b[1], c[0], c[1],
+     d4[0], d4[1], d4[2], d4[3], d4[4], d4[5], d4[6], d4[7]]
+}
+
+/// Names under which the Content Description object's fields are exposed.
+const CONTENT_DESCRIPTION_NAMES: [&str; 5] = ["Title", "Author", "Copyright", "Description", "Rating"];
+
+/// Typed value of an ASF attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ASFValue {
+    Unicode(String),
+    Bytes(Vec<u8>),
+    Bool(bool),
+    DWord(u32),
+    QWord(u64),
+    Word(u16),
+    Guid([u8; 16]),
+}
+
+impl ASFValue {
+    fn parse(value_type: u16, data: &[u8]) -> Result<Self> {
+        let int = |n: usize| -> Result<u64> {
+            if data.len() < n {
+                return Err(MutagenError::ASF("attribute value too short".into()));
+            }
+            let mut buf = [0u8; 8];
+            buf[..n].copy_from_slice(&data[..n]);
+            Ok(u64::from_le_bytes(buf))
+        };
+        Ok(match value_type {
+            0 => ASFValue::Unicode(decode_utf16(data)),
+            1 => ASFValue::Bytes(data.to_vec()),
+            // Bools are 32-bit in the Extended Content Description object
+            2 => ASFValue::Bool(int(data.len().min(4))? != 0),
+            3 => ASFValue::DWord(int(4)? as u32),
+            4 => ASFValue::QWord(int(8)?),
+            5 => ASFValue::Word(int(2)? as u16),
+            6 if data.len() >= 16 => {
+                let mut g = [0u8; 16];
+                g.copy_from_slice(&data[..16]);
+                ASFValue::Guid(g)
+            }
+            _ => return Err(MutagenError::ASF(format!("unknown attribute type {}", value_type))),
+        })
+    }
+
+    /// Text form of the value (numbers in decimal, binary as `None`).
+    pub fn as_text(&self) -> Option<String> {
+        match self {
+            ASFValue::Unicode(s) => Some(s.clone()),
+            ASFValue::Bool(b) => Some(b.to_string()),
+            ASFValue::DWord(n) => Some(n.to_string()),
+            ASFValue::QWord(n) => Some(n.to_string()),
+            ASFValue::Word(n) => Some(n.to_string()),
+            ASFValue::Bytes(_) | ASFValue::Guid(_) => None,
+        }
+    }
+}
+
+/// Decode UTF-16LE text, dropping the null terminator ASF strings carry.
+fn decode_utf16(data: &[u8]) -> String {
+    let (text, _, _) = encoding_rs::UTF_16LE.decode(data);
+    text.trim_end_matches('\0').to_string()
+}
+
+/// ASF attributes. A name may appear more than once.
+#[derive(Debug, Clone, Default)]
+pub struct ASFTags {
+    pub items: Vec<(String, ASFValue)>,
+}
+
+impl ASFTags {
+    pub fn new() -> Self {
+        ASFTags { items: Vec::new() }
+    }
+
+    /// Unique attribute names in first-seen order.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = Vec::new();
+        for (k, _) in &self.items {
+            if !keys.contains(k) {
+                keys.push(k.clone());
+            }
+        }
+        keys
+    }
+
+    pub fn get(&self, key: &str) -> Vec<&ASFValue> {
+        self.items.iter().filter(|(k, _)| k == key).map(|(_, v)| v).collect()
+    }
+
+    pub fn get_text(&self, key: &str) -> Option<String> {
+        self.get(key).first().and_then(|v| v.as_text())
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.items.iter().any(|(k, _)| k == key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+/// ASF stream information.
+#[derive(Debug, Clone, Default)]
+pub struct ASFInfo {
+    pub length: f64,
+    pub sample_rate: u32,
+    pub channels: u32,
+    pub bitrate: u32,
+    /// Codec family from the WAVEFORMATEX codec ID (e.g. "Windows Media Audio 9 Standard").
+    pub codec_type: String,
+    /// Name and description from the Codec List object, if present.
+    pub codec_name: String,
+    pub codec_description: String,
+}
+
+impl ASFInfo {
+    /// Whether the stream is WMA Lossless.
+    pub fn lossless(&self) -> bool {
+        self.codec_type.contains("Lossless")
+    }
+}
+
+/// Complete ASF (WMA) file: stream info + attributes.
+#[derive(Debug)]
+pub struct ASFFile {
+    pub info: ASFInfo,
+    pub tags: ASFTags,
+    pub path: String,
+}
+
+impl ASFFile {
+    pub fn open(path: &str) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        Self::parse(&data, path)
+    }
+
+    pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        if data.len() < 30 || data[0..16] != HEADER_OBJECT {
+            return Err(MutagenError::ASF("not an ASF file".into()));
+        }
+        let header_size = read_u64(data, 16) as usize;
+        let num_objects = u32::from_le_bytes([data[24], data[25], data[26], data[27]]);
+        let header_end = header_size.min(data.len());
+
+        let mut info = ASFInfo::default();
+        let mut tags = ASFTags::new();
+        let mut have_audio_stream = false;
+
+        let mut pos = 30usize;
+        for _ in 0..num_objects {
+            if pos + 24 > header_end {
+                break;
+            }
+            let size = read_u64(data, pos + 16) as usize;
+            if size < 24 || size > header_end - pos {
+                return Err(MutagenError::ASF("header object extends past header".into()));
+            }
+            let guid = &data[pos..pos + 16];
+            let body = &data[pos + 24..pos + size];
+
+            if guid == FILE_PROPERTIES {
+                parse_file_properties(body, &mut info)?;
+            } else if guid == STREAM_PROPERTIES && !have_audio_stream {
+                have_audio_stream = parse_stream_properties(body, &mut info)?;
+            } else if guid == CONTENT_DESCRIPTION {
+                parse_content_description(body, &mut tags)?;
+            } else if guid == EXTENDED_CONTENT_DESCRIPTION {
+                parse_extended_content_description(body, &mut tags)?;
+            } else if guid == CODEC_LIST {
+                parse_codec_list(body, &mut info);
+            }
+
+            pos += size;
+        }
+
+        Ok(ASFFile {
+            info,
+            tags,
+            path: path.to_string(),
+        })
+    }
+
+    pub fn score(path: &str, data: &[u8]) -> u32 {
+        let mut score = 0u32;
+        let ext = path.rsplit('.').next().unwrap_or("");
+        if ext.eq_ignore_ascii_case("wma") || ext.eq_ignore_ascii_case("asf")
+            || ext.eq_ignore_ascii_case("wmv") {
+            score += 2;
+        }
+        if data.len() >= 16 && data[0..16] == HEADER_OBJECT {
+            score += 3;
+        }
+        score
+    }
+}
+
+#[inline]
+fn read_u64(data: &[u8], pos: usize) -> u64 {
+    let mut buf = [0u8; 8];
+    buf.copy_from_slice(&data[pos..pos + 8]);
+    u64::from_le_bytes(buf)
+}
+
+#[inline]
+fn read_u16(data: &[u8], pos: usize) -> Result<u16> {
+    data.get(pos..pos + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or_else(|| MutagenError::ASF("object data too short".into()))
+}
+
+fn parse_file_properties(body: &[u8], info: &mut ASFInfo) -> Result<()> {
+    if body.len() < 80 {
+        return Err(MutagenError::ASF("File Properties object too short".into()));
+    }
+    // Play duration is in 100ns units and includes the preroll (in ms)
+    let play_duration = read_u64(body, 40);
+    let preroll = read_u64(body, 56);
+    info.length = (play_duration as f64 / 1e7 - preroll as f64 / 1e3).max(0.0);
+    Ok(())
+}
+
+/// Returns whether the stream was an audio stream.
+fn parse_stream_properties(body: &[u8], info: &mut ASFInfo) -> Result<bool> {
+    if body.len() < 54 || body[0..16] != AUDIO_MEDIA {
+        return Ok(false);
+    }
+    // WAVEFORMATEX follows the fixed 54-byte stream properties header
+    let fmt = body.get(54..70)
+        .ok_or_else(|| MutagenError::ASF("audio stream format too short".into()))?;
+    let codec_id = u16::from_le_bytes([fmt[0], fmt[1]]);
+    info.channels = u16::from_le_bytes([fmt[2], fmt[3]]) as u32;
+    info.sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+    info.bitrate = u32::from_le_bytes([fmt[8], fmt[9], fmt[10], fmt[11]]).saturating_mul(8);
+    info.codec_type = codec_type_name(codec_id);
+    Ok(true)
+}
+
+fn codec_type_name(codec_id: u16) -> String {
+    match codec_id {
+        0x0001 => "Microsoft PCM".to_string(),
+        0x000A => "Windows Media Audio 9 Voice".to_string(),
+        0x0055 => "MPEG Layer-3".to_string(),
+        0x0160 => "Windows Media Audio 7".to_string(),
+        0x0161 => "Windows Media Audio 9 Standard".to_string(),
+        0x0162 => "Windows Media Audio 9 Professional".to_string(),
+        0x0163 => "Windows Media Audio 9 Lossless".to_string(),
+        _ => format!("Unknown codec (0x{:04X})", codec_id),
+    }
+}
+
+fn parse_content_description(body: &[u8], tags: &mut ASFTags) -> Result<()> {
+    let mut pos = 10usize;
+    for (i, name) in CONTENT_DESCRIPTION_NAMES.iter().enumerate() {
+        let len = read_u16(body, i * 2)? as usize;
+        let value = body.get(pos..pos + len)
+            .ok_or_else(|| MutagenError::ASF("Content Description value too short".into()))?;
+        pos += len;
+        let text = decode_utf16(value);
+        if !text.is_empty() {
+            tags.items.push((name.to_string(), ASFValue::Unicode(text)));
+        }
+    }
+    Ok(())
+}
+
+fn parse_extended_content_description(body: &[u8], tags: &mut ASFTags) -> Result<()> {
+    let count = read_u16(body, 0)?;
+    let mut pos = 2usize;
+    for _ in 0..count {
+        let name_len = read_u16(body, pos)? as usize;
+        pos += 2;
+        let name = body.get(pos..pos + name_len)
+            .ok_or_else(|| MutagenError::ASF("attribute name too short".into()))?;
+        pos += name_len;
+        let value_type = read_u16(body, pos)?;
+        let value_len = read_u16(body, pos + 2)? as usize;
+        pos += 4;
+        let value = body.get(pos..pos + value_len)
+            .ok_or_else(|| MutagenError::ASF("attribute value too short".into()))?;
+        pos += value_len;
+        tags.items.push((decode_utf16(name), ASFValue::parse(value_type, value)?));
+    }
+    Ok(())
+}
+
+/// Take name/description of the first audio codec entry. Malformed lists are ignored.
+fn parse_codec_list(body: &[u8], info: &mut ASFInfo) {
+    let Some(count) = body.get(16..20).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])) else {
+        return;
+    };
+    let mut pos = 20usize;
+    for _ in 0..count {
+        let Ok(codec_type) = read_u16(body, pos) else { return };
+        pos += 2;
+        let mut strings = [String::new(), String::new()];
+        for s in strings.iter_mut() {
+            let Ok(chars) = read_u16(body, pos) else { return };
+            let len = chars as usize * 2;
+            let Some(raw) = body.get(pos + 2..pos + 2 + len) else { return };
+            *s = decode_utf16(raw).trim().to_string();
+            pos += 2 + len;
+        }
+        let Ok(info_len) = read_u16(body, pos) else { return };
+        pos += 2 + info_len as usize;
+
+        // Codec type 2 is audio
+        if codec_type == 2 {
+            let [name, description] = strings;
+            info.codec_name = name;
+            info.codec_description = description;
+            return;
+        }
+    }
+}
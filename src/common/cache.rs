@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+
+/// Default number of files kept in the cache.
+pub const DEFAULT_CAPACITY: usize = 256;
+
+struct Entry {
+    data: Arc<[u8]>,
+    /// Clock value of the last hit; updated under the read lock.
+    last_used: AtomicU64,
+}
+
+/// Process-wide file data cache shared by every thread, including rayon
+/// workers in batch parsing. Hits only take the read lock; inserting past
+/// capacity evicts the least recently used file.
+struct FileCache {
+    entries: RwLock<HashMap<String, Entry>>,
+    capacity: AtomicUsize,
+    clock: AtomicU64,
+}
+
+static CACHE: OnceLock<FileCache> = OnceLock::new();
+
+fn cache() -> &'static FileCache {
+    CACHE.get_or_init(|| FileCache {
+        entries: RwLock::new(HashMap::with_capacity(DEFAULT_CAPACITY)),
+        capacity: AtomicUsize::new(DEFAULT_CAPACITY),
+        clock: AtomicU64::new(0),
+    })
+}
+
+impl FileCache {
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn evict_to(entries: &mut HashMap<String, Entry>, capacity: usize) {
+        while entries.len() > capacity {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, e)| e.last_used.load(Ordering::Relaxed))
+                .map(|(k, _)| k.clone());
+            match oldest {
+                Some(key) => entries.remove(&key),
+                None => break,
+            };
+        }
+    }
+}
+
+/// Read a file, returning cached data if available.
+pub fn read(path: &str) -> std::io::Result<Arc<[u8]>> {
+    let cache = cache();
+    // Fast path: read lock (concurrent, no blocking)
+    {
+        let guard = cache.entries.read().unwrap();
+        if let Some(entry) = guard.get(path) {
+            entry.last_used.store(cache.tick(), Ordering::Relaxed);
+            return Ok(Arc::clone(&entry.data));
+        }
+    }
+    // Slow path: read file, write lock to insert
+    let data: Arc<[u8]> = std::fs::read(path)?.into();
+    let capacity = cache.capacity.load(Ordering::Relaxed);
+    if capacity == 0 {
+        return Ok(data);
+    }
+    let mut guard = cache.entries.write().unwrap();
+    if let Some(existing) = guard.get(path) {
+        return Ok(Arc::clone(&existing.data));
+    }
+    guard.insert(path.to_string(), Entry {
+        data: Arc::clone(&data),
+        last_used: AtomicU64::new(cache.tick()),
+    });
+    FileCache::evict_to(&mut guard, capacity);
+    Ok(data)
+}
+
+/// Drop a single file from the cache, e.g. after it has been rewritten.
+pub fn invalidate(path: &str) {
+    if let Some(cache) = CACHE.get() {
+        cache.entries.write().unwrap().remove(path);
+    }
+}
+
+/// Empty the cache, forcing subsequent reads to hit the filesystem.
+pub fn clear() {
+    if let Some(cache) = CACHE.get() {
+        cache.entries.write().unwrap().clear();
+    }
+}
+
+/// Set the maximum number of cached files, evicting down to it immediately.
+/// A capacity of 0 disables caching.
+pub fn set_capacity(capacity: usize) {
+    let cache = cache();
+    cache.capacity.store(capacity, Ordering::Relaxed);
+    FileCache::evict_to(&mut cache.entries.write().unwrap(), capacity);
+}
+
+pub fn capacity() -> usize {
+    cache().capacity.load(Ordering::Relaxed)
+}
+
+/// Number of files currently cached.
+pub fn len() -> usize {
+    CACHE.get().map_or(0, |c| c.entries.read().unwrap().len())
+}
+
+/// Whether `path` is currently cached.
+pub fn contains(path: &str) -> bool {
+    CACHE.get().is_some_and(|c| c.entries.read().unwrap().contains_key(path))
+}
@@ -47,6 +47,21 @@ pub enum MutagenError {
     #[error("MP4 stream info error: {0}")]
     MP4StreamInfo(String),
 
+    #[error("APEv2 error: {0}")]
+    APE(String),
+
+    #[error("WavPack error: {0}")]
+    WavPack(String),
+
+    #[error("ASF error: {0}")]
+    ASF(String),
+
+    #[error("Monkey's Audio error: {0}")]
+    MonkeysAudio(String),
+
+    #[error("Truncated data: {0}")]
+    Truncated(String),
+
     #[error("Invalid data: {0}")]
     InvalidData(String),
 
@@ -55,6 +70,9 @@ pub enum MutagenError {
 
     #[error("Value error: {0}")]
     ValueError(String),
+
+    #[error("Verification failed: {0}")]
+    Verify(String),
 }
 
 pub type Result<T> = std::result::Result<T, MutagenError>;
@@ -64,7 +82,10 @@ pub type Result<T> = std::result::Result<T, MutagenError>;
 mod python_errors {
     use super::*;
     use pyo3::create_exception;
-    use pyo3::exceptions::PyException;
+    use pyo3::exceptions::{PyException, PyValueError};
+    use pyo3::prelude::*;
+    use pyo3::sync::PyOnceLock;
+    use pyo3::types::{PyDict, PyType};
 
     create_exception!(mutagen_rs, MutagenPyError, PyException);
     create_exception!(mutagen_rs, ID3Error, MutagenPyError);
@@ -81,6 +102,52 @@ mod python_errors {
     create_exception!(mutagen_rs, OggError, MutagenPyError);
     create_exception!(mutagen_rs, MP4Error, MutagenPyError);
     create_exception!(mutagen_rs, MP4StreamInfoError, MP4Error);
+    create_exception!(mutagen_rs, APEError, MutagenPyError);
+    create_exception!(mutagen_rs, WavPackError, MutagenPyError);
+    create_exception!(mutagen_rs, ASFError, MutagenPyError);
+    create_exception!(mutagen_rs, MonkeysAudioHeaderError, MutagenPyError);
+    create_exception!(mutagen_rs, TruncatedError, MutagenPyError);
+    create_exception!(mutagen_rs, VerificationError, MutagenPyError);
+
+    static INVALID_DATA_ERROR: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+    static ENCODING_ERROR: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+
+    /// `InvalidDataError(MutagenError, ValueError)`: unrecognised or
+    /// malformed file data. Like several mutagen errors it is also a
+    /// `ValueError`, which is what these were raised as before.
+    pub fn invalid_data_error(py: Python<'_>) -> PyResult<Bound<'_, PyType>> {
+        value_error_subclass(py, &INVALID_DATA_ERROR, "InvalidDataError")
+    }
+
+    /// `EncodingError(MutagenError, ValueError)`: text that cannot be
+    /// encoded or decoded as the tag requires.
+    pub fn encoding_error(py: Python<'_>) -> PyResult<Bound<'_, PyType>> {
+        value_error_subclass(py, &ENCODING_ERROR, "EncodingError")
+    }
+
+    /// `create_exception!` takes a single base, so the two-base classes are
+    /// built with `type()` on first use.
+    fn value_error_subclass<'py>(
+        py: Python<'py>,
+        cell: &'static PyOnceLock<Py<PyType>>,
+        name: &str,
+    ) -> PyResult<Bound<'py, PyType>> {
+        let ty = cell.get_or_try_init(py, || -> PyResult<Py<PyType>> {
+            let bases = (py.get_type::<MutagenPyError>(), py.get_type::<PyValueError>());
+            let namespace = PyDict::new(py);
+            namespace.set_item("__module__", "mutagen_rs")?;
+            let ty = py.get_type::<PyType>().call1((name, bases, namespace))?;
+            Ok(ty.cast_into::<PyType>()?.unbind())
+        })?;
+        Ok(ty.bind(py).clone())
+    }
+
+    fn new_err(ty: fn(Python<'_>) -> PyResult<Bound<'_, PyType>>, msg: String) -> pyo3::PyErr {
+        Python::attach(|py| match ty(py) {
+            Ok(ty) => pyo3::PyErr::from_type(ty, msg),
+            Err(e) => e,
+        })
+    }
 
     impl From<MutagenError> for pyo3::PyErr {
         fn from(err: MutagenError) -> pyo3::PyErr {
@@ -106,11 +173,15 @@ mod python_errors {
                 MutagenError::Ogg(msg) => self::OggError::new_err(msg),
                 MutagenError::MP4(msg) => self::MP4Error::new_err(msg),
                 MutagenError::MP4StreamInfo(msg) => self::MP4StreamInfoError::new_err(msg),
-                MutagenError::InvalidData(msg) => pyo3::exceptions::PyValueError::new_err(msg),
-                MutagenError::Encoding(msg) => pyo3::exceptions::PyValueError::new_err(
-                    format!("Encoding error: {}", msg),
-                ),
+                MutagenError::APE(msg) => self::APEError::new_err(msg),
+                MutagenError::WavPack(msg) => self::WavPackError::new_err(msg),
+                MutagenError::ASF(msg) => self::ASFError::new_err(msg),
+                MutagenError::MonkeysAudio(msg) => self::MonkeysAudioHeaderError::new_err(msg),
+                MutagenError::Truncated(msg) => self::TruncatedError::new_err(msg),
+                MutagenError::InvalidData(msg) => new_err(invalid_data_error, msg),
+                MutagenError::Encoding(msg) => new_err(encoding_error, format!("Encoding error: {}", msg)),
                 MutagenError::ValueError(msg) => pyo3::exceptions::PyValueError::new_err(msg),
+                MutagenError::Verify(msg) => self::VerificationError::new_err(msg),
             }
         }
     }
@@ -0,0 +1,66 @@
+//! Grouping values by key in first-seen order.
+
+use std::collections::HashMap;
+
+/// Below this many distinct keys a linear scan beats hashing: typical tags
+/// have 5-15 keys, and comparing a few short strings costs less than
+/// hashing one. Past it (e.g. FLACs with per-track cuesheet comments) the
+/// scan goes quadratic, so a key index is built.
+pub const LINEAR_KEY_LIMIT: usize = 16;
+
+/// Values grouped by key, keeping keys in the order they were first seen.
+/// Lookups scan linearly until `LINEAR_KEY_LIMIT` keys, then go through a
+/// `HashMap` index.
+#[derive(Debug, Clone)]
+pub struct KeyGroups<V> {
+    groups: Vec<(String, Vec<V>)>,
+    index: Option<HashMap<String, usize>>,
+}
+
+impl<V> Default for KeyGroups<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> KeyGroups<V> {
+    pub fn new() -> Self {
+        KeyGroups { groups: Vec::new(), index: None }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        KeyGroups { groups: Vec::with_capacity(capacity), index: None }
+    }
+
+    /// Append `value` to the group for `key`, creating it at the end if new.
+    pub fn push(&mut self, key: String, value: V) {
+        let found = match &self.index {
+            Some(index) => index.get(&key).copied(),
+            None => self.groups.iter().position(|(k, _)| k == &key),
+        };
+        if let Some(i) = found {
+            self.groups[i].1.push(value);
+            return;
+        }
+        if let Some(index) = &mut self.index {
+            index.insert(key.clone(), self.groups.len());
+        }
+        self.groups.push((key, vec![value]));
+        if self.index.is_none() && self.groups.len() > LINEAR_KEY_LIMIT {
+            self.index = Some(self.groups.iter().enumerate().map(|(i, (k, _))| (k.clone(), i)).collect());
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// The groups in first-seen key order.
+    pub fn into_vec(self) -> Vec<(String, Vec<V>)> {
+        self.groups
+    }
+}
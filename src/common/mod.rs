@@ -1,2 +1,6 @@
 pub mod error;
 pub mod util;
+pub mod probe;
+pub mod cache;
+pub mod options;
+pub mod groups;
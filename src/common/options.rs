@@ -0,0 +1,13 @@
+/// Options controlling how tolerant the parsers are of damaged metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Error on truncated frames, blocks and comments instead of keeping
+    /// whatever was read before the damage (the default, like mutagen).
+    pub strict: bool,
+}
+
+impl ParseOptions {
+    pub fn strict() -> Self {
+        ParseOptions { strict: true }
+    }
+}
@@ -0,0 +1,147 @@
+use crate::flac::FLACFile;
+use crate::mp3::MP3File;
+use crate::mp4::MP4File;
+use crate::ogg::OggVorbisFile;
+use crate::oggflac::{self, OggFLACFile};
+use crate::wavpack::WavPackFile;
+use crate::asf::ASFFile;
+use crate::monkeysaudio::MonkeysAudioFile;
+use std::io::Read;
+
+/// File formats that can be detected and opened.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileFormat {
+    MP3,
+    FLAC,
+    OggVorbis,
+    OggFLAC,
+    MP4,
+    WavPack,
+    ASF,
+    MonkeysAudio,
+}
+
+impl FileFormat {
+    /// Short lowercase name, as returned by the Python `detect_format`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FileFormat::MP3 => "mp3",
+            FileFormat::FLAC => "flac",
+            FileFormat::OggVorbis => "ogg",
+            FileFormat::OggFLAC => "oggflac",
+            FileFormat::MP4 => "mp4",
+            FileFormat::WavPack => "wavpack",
+            FileFormat::ASF => "asf",
+            FileFormat::MonkeysAudio => "monkeysaudio",
+        }
+    }
+
+    /// Format for a name returned by `as_str` (case-insensitive).
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            FileFormat::MP3,
+            FileFormat::FLAC,
+            FileFormat::OggVorbis,
+            FileFormat::OggFLAC,
+            FileFormat::MP4,
+            FileFormat::WavPack,
+            FileFormat::ASF,
+            FileFormat::MonkeysAudio,
+        ]
+        .into_iter()
+        .find(|f| f.as_str().eq_ignore_ascii_case(name))
+    }
+
+    /// Format implied by the file extension alone (used as a fast path before scoring).
+    pub fn from_extension(path: &str) -> Option<Self> {
+        let ext = path.rsplit('.').next().unwrap_or("");
+        if ext.eq_ignore_ascii_case("flac") {
+            Some(FileFormat::FLAC)
+        } else if ext.eq_ignore_ascii_case("ogg") {
+            Some(FileFormat::OggVorbis)
+        } else if ext.eq_ignore_ascii_case("mp3") {
+            Some(FileFormat::MP3)
+        } else if ext.eq_ignore_ascii_case("m4a") || ext.eq_ignore_ascii_case("m4b")
+            || ext.eq_ignore_ascii_case("mp4") || ext.eq_ignore_ascii_case("m4v") {
+            Some(FileFormat::MP4)
+        } else if ext.eq_ignore_ascii_case("wv") {
+            Some(FileFormat::WavPack)
+        } else if ext.eq_ignore_ascii_case("wma") || ext.eq_ignore_ascii_case("asf")
+            || ext.eq_ignore_ascii_case("wmv") {
+            Some(FileFormat::ASF)
+        } else if ext.eq_ignore_ascii_case("ape") {
+            Some(FileFormat::MonkeysAudio)
+        } else {
+            None
+        }
+    }
+
+    /// Correct an extension guess using the data: Ogg FLAC streams are
+    /// usually named `.ogg` too.
+    pub fn refine(self, data: &[u8]) -> Self {
+        if self == FileFormat::OggVorbis && oggflac::is_oggflac(data) {
+            FileFormat::OggFLAC
+        } else {
+            self
+        }
+    }
+}
+
+/// Score the data against every format and return the winner, or `None`
+/// if nothing scores above zero.
+///
+/// Tie-breaking, in order:
+/// 1. The format with more evidence from the content itself wins over one
+///    that only matched the file extension (every `score` gives an extension
+///    match 2 points), so `detect` calls a tagged MP3 named `.ogg` an MP3.
+///    Opening by path takes a known extension's format without scoring, so
+///    such a file is only detected this way when scored directly.
+/// 2. Otherwise FLAC, Ogg, Ogg FLAC, MP4, WavPack, ASF, Monkey's Audio, MP3
+///    in that order: all but MP3 are identified by magic bytes at offset 0,
+///    while an MPEG sync can turn up in arbitrary data.
+pub fn detect(path: &str, data: &[u8]) -> Option<FileFormat> {
+    let ext_format = FileFormat::from_extension(path);
+    let candidates = [
+        (FileFormat::FLAC, FLACFile::score(path, data)),
+        (FileFormat::OggVorbis, OggVorbisFile::score(path, data)),
+        (FileFormat::OggFLAC, OggFLACFile::score(path, data)),
+        (FileFormat::MP4, MP4File::score(path, data)),
+        (FileFormat::WavPack, WavPackFile::score(path, data)),
+        (FileFormat::ASF, ASFFile::score(path, data)),
+        (FileFormat::MonkeysAudio, MonkeysAudioFile::score(path, data)),
+        (FileFormat::MP3, MP3File::score(path, data)),
+    ];
+
+    let mut best: Option<(FileFormat, u32, u32)> = None;
+    for (format, score) in candidates {
+        if score == 0 {
+            continue;
+        }
+        let content = if ext_format == Some(format) { score.saturating_sub(2) } else { score };
+        let better = match best {
+            None => true,
+            Some((_, s, c)) => score > s || (score == s && content > c),
+        };
+        if better {
+            best = Some((format, score, content));
+        }
+    }
+    best.map(|(format, _, _)| format)
+}
+
+/// Bytes read from the start of a file for content scoring. Every `score`
+/// looks at the first 512 bytes at most, so this is plenty.
+pub const HEADER_SIZE: usize = 64 * 1024;
+
+/// Like `detect`, but reads at most `HEADER_SIZE` bytes from `reader`.
+pub fn detect_reader<R: Read>(path: &str, reader: R) -> std::io::Result<Option<FileFormat>> {
+    let mut header = Vec::with_capacity(HEADER_SIZE);
+    reader.take(HEADER_SIZE as u64).read_to_end(&mut header)?;
+    Ok(detect(path, &header))
+}
+
+/// Detect a file's format from its header alone, without reading the whole
+/// file into memory.
+pub fn detect_file(path: &str) -> std::io::Result<Option<FileFormat>> {
+    detect_reader(path, std::fs::File::open(path)?)
+}
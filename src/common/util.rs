@@ -68,7 +68,55 @@ pub fn open_rw(path: &str) -> Result<File> {
         .open(path)?)
 }
 
+/// Run `write` and then, with `preserve` set, put the file's modification
+/// time back to what it was before. Saves rewrite files in place, so
+/// hardlinks survive either way.
+pub fn preserving_mtime<T>(path: &str, preserve: bool, write: impl FnOnce() -> Result<T>) -> Result<T> {
+    if !preserve {
+        return write();
+    }
+    let mtime = std::fs::metadata(path)?.modified()?;
+    let result = write()?;
+    OpenOptions::new().write(true).open(path)?.set_modified(mtime)?;
+    Ok(result)
+}
+
 /// Open a file for read-only access.
 pub fn open_ro(path: &str) -> Result<File> {
     Ok(File::open(path)?)
 }
+
+/// Decode standard (RFC 4648) base64, ignoring whitespace. Returns `None`
+/// on characters outside the alphabet or a truncated final quantum.
+pub fn base64_decode(input: &str) -> Option<Vec<u8>> {
+    fn value(c: u8) -> Option<u32> {
+        match c {
+            b'A'..=b'Z' => Some((c - b'A') as u32),
+            b'a'..=b'z' => Some((c - b'a') as u32 + 26),
+            b'0'..=b'9' => Some((c - b'0') as u32 + 52),
+            b'+' => Some(62),
+            b'/' => Some(63),
+            _ => None,
+        }
+    }
+
+    let chars: Vec<u8> = input
+        .bytes()
+        .filter(|c| !c.is_ascii_whitespace())
+        .collect();
+    let chars = chars.strip_suffix(b"==").or_else(|| chars.strip_suffix(b"=")).unwrap_or(&chars);
+    if chars.len() % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Vec::with_capacity(chars.len() * 3 / 4);
+    for chunk in chars.chunks(4) {
+        let mut acc = 0u32;
+        for &c in chunk {
+            acc = (acc << 6) | value(c)?;
+        }
+        acc <<= 6 * (4 - chunk.len()) as u32;
+        out.extend_from_slice(&acc.to_be_bytes()[1..chunk.len()]);
+    }
+    Some(out)
+}
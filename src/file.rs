@@ -0,0 +1,322 @@
+use crate::apev2::{APEValue, APEv2Tags};
+use crate::asf::{ASFFile, ASFTags};
+use crate::common::error::{MutagenError, Result};
+use crate::common::probe::{self, FileFormat};
+use crate::common::util::base64_decode;
+use crate::flac::{FLACFile, FLACPicture};
+use crate::id3::frames::Frame;
+use crate::id3::specs::PictureType;
+use crate::id3::tags::ID3Tags;
+use crate::monkeysaudio::MonkeysAudioFile;
+use crate::mp3::xing::BitrateMode;
+use crate::mp3::MP3File;
+use crate::mp4::{MP4CoverFormat, MP4File, MP4Tags, MP4TagValue};
+use crate::ogg::OggVorbisFile;
+use crate::oggflac::OggFLACFile;
+use crate::vorbis::VorbisComment;
+use crate::wavpack::WavPackFile;
+
+/// A fully parsed audio file of any supported format. Unlike the Python
+/// bindings, this API is available without the `python` feature.
+#[derive(Debug)]
+pub enum AudioFile {
+    MP3(MP3File),
+    FLAC(FLACFile),
+    OggVorbis(OggVorbisFile),
+    OggFLAC(OggFLACFile),
+    MP4(MP4File),
+    WavPack(WavPackFile),
+    ASF(ASFFile),
+    MonkeysAudio(MonkeysAudioFile),
+}
+
+/// Stream properties common to every format.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioInfo {
+    pub length: f64,
+    pub sample_rate: u32,
+    pub channels: u32,
+    pub bitrate: u32,
+    /// Bits per sample, for lossless formats that record it.
+    pub bits_per_sample: Option<u32>,
+    /// Whether the stream decodes to exactly the source samples.
+    pub lossless: bool,
+    pub bitrate_mode: BitrateMode,
+}
+
+/// Borrowed view of a file's native tag container.
+#[derive(Debug, Clone, Copy)]
+pub enum Tags<'a> {
+    ID3(&'a ID3Tags),
+    Vorbis(&'a VorbisComment),
+    MP4(&'a MP4Tags),
+    APEv2(&'a APEv2Tags),
+    ASF(&'a ASFTags),
+}
+
+/// Open and fully parse a file, detecting its format by extension, then content.
+pub fn open(path: &str) -> Result<AudioFile> {
+    let data = std::fs::read(path)?;
+    AudioFile::parse(&data, path)
+}
+
+/// The front cover of a file as `(mime, image bytes)`: the first front-cover
+/// APIC (MP3), the first type-3 Picture block (FLAC), the first `covr` image
+/// (MP4) or the first `METADATA_BLOCK_PICTURE` (Ogg Vorbis). Other formats
+/// have no cover and yield `None`.
+pub fn front_cover(data: &[u8], path: &str) -> Result<Option<(String, Vec<u8>)>> {
+    Ok(match AudioFile::parse(data, path)? {
+        AudioFile::MP3(f) => f.tags.keys().iter().filter(|k| k.starts_with("APIC")).find_map(|key| {
+            match f.tags.decode_first(key)?.into_owned() {
+                Frame::Picture(p) if p.pic_type == PictureType::CoverFront => Some((p.mime, p.data)),
+                _ => None,
+            }
+        }),
+        AudioFile::FLAC(f) => f.lazy_pictures.iter().find_map(|lp| {
+            let block = data.get(lp.block_offset..lp.block_offset + lp.block_size)?;
+            let pic = FLACPicture::parse(block).ok()?;
+            (pic.pic_type == PictureType::CoverFront as u32).then_some((pic.mime, pic.data))
+        }),
+        AudioFile::MP4(f) => match f.tags.get("covr") {
+            Some(MP4TagValue::Cover(covers)) => covers.first().map(|c| {
+                let mime = match c.format {
+                    MP4CoverFormat::JPEG => "image/jpeg",
+                    MP4CoverFormat::PNG => "image/png",
+                    MP4CoverFormat::BMP => "image/bmp",
+                    MP4CoverFormat::Unknown(_) => "application/octet-stream",
+                };
+                (mime.to_string(), c.data.clone())
+            }),
+            _ => None,
+        },
+        AudioFile::OggVorbis(f) => f.tags.get("METADATA_BLOCK_PICTURE").first().and_then(|value| {
+            let pic = FLACPicture::parse(&base64_decode(value)?).ok()?;
+            Some((pic.mime, pic.data))
+        }),
+        AudioFile::OggFLAC(_) | AudioFile::WavPack(_) | AudioFile::ASF(_) | AudioFile::MonkeysAudio(_) => None,
+    })
+}
+
+/// Length in seconds, reading only what each format needs for it: MP4 skips
+/// `ilst` and chapters, and the lazily tagged formats never decode tags.
+pub fn duration(data: &[u8], path: &str) -> Result<f64> {
+    let format = FileFormat::from_extension(path)
+        .map(|f| f.refine(data))
+        .or_else(|| probe::detect(path, data))
+        .ok_or_else(|| MutagenError::InvalidData(format!("unable to detect format for: {}", path)))?;
+
+    Ok(match format {
+        FileFormat::MP3 => MP3File::parse(data, path)?.info.length,
+        FileFormat::FLAC => FLACFile::parse(data, path)?.info.length,
+        FileFormat::OggVorbis => {
+            let mut f = OggVorbisFile::parse(data, path)?;
+            f.ensure_full_parse(data);
+            f.info.length
+        }
+        FileFormat::MP4 => MP4File::info_only(data, path)?.length,
+        _ => AudioFile::parse(data, path)?.info().length,
+    })
+}
+
+/// Chapter or cue markers as `(start_seconds, label)` pairs: ID3 `CHAP`
+/// frames (MP3), CueSheet tracks (FLAC) or MP4 chapters. Formats without
+/// markers yield an empty list.
+pub fn markers(data: &[u8], path: &str) -> Result<Vec<(f64, String)>> {
+    Ok(match AudioFile::parse(data, path)? {
+        AudioFile::MP3(f) => f.tags.chapters(),
+        AudioFile::FLAC(f) => f.cue_markers(),
+        AudioFile::MP4(f) => f.chapters().to_vec(),
+        _ => Vec::new(),
+    })
+}
+
+impl AudioFile {
+    /// Parse file data already in memory; `path` is used as a format hint.
+    pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        let format = FileFormat::from_extension(path)
+            .map(|f| f.refine(data))
+            .or_else(|| probe::detect(path, data))
+            .ok_or_else(|| MutagenError::InvalidData(format!("unable to detect format for: {}", path)))?;
+
+        Ok(match format {
+            FileFormat::MP3 => {
+                let mut f = MP3File::parse(data, path)?;
+                f.ensure_tags_parsed(data);
+                AudioFile::MP3(f)
+            }
+            FileFormat::FLAC => {
+                let mut f = FLACFile::parse(data, path)?;
+                f.ensure_tags();
+                AudioFile::FLAC(f)
+            }
+            FileFormat::OggVorbis => {
+                let mut f = OggVorbisFile::parse(data, path)?;
+                f.ensure_full_parse(data);
+                f.ensure_tags();
+                AudioFile::OggVorbis(f)
+            }
+            FileFormat::OggFLAC => AudioFile::OggFLAC(OggFLACFile::parse(data, path)?),
+            FileFormat::MP4 => {
+                let mut f = MP4File::parse(data, path)?;
+                f.ensure_parsed_with_data(data);
+                AudioFile::MP4(f)
+            }
+            FileFormat::WavPack => AudioFile::WavPack(WavPackFile::parse(data, path)?),
+            FileFormat::ASF => AudioFile::ASF(ASFFile::parse(data, path)?),
+            FileFormat::MonkeysAudio => AudioFile::MonkeysAudio(MonkeysAudioFile::parse(data, path)?),
+        })
+    }
+
+    pub fn format(&self) -> FileFormat {
+        match self {
+            AudioFile::MP3(_) => FileFormat::MP3,
+            AudioFile::FLAC(_) => FileFormat::FLAC,
+            AudioFile::OggVorbis(_) => FileFormat::OggVorbis,
+            AudioFile::OggFLAC(_) => FileFormat::OggFLAC,
+            AudioFile::MP4(_) => FileFormat::MP4,
+            AudioFile::WavPack(_) => FileFormat::WavPack,
+            AudioFile::ASF(_) => FileFormat::ASF,
+            AudioFile::MonkeysAudio(_) => FileFormat::MonkeysAudio,
+        }
+    }
+
+    pub fn info(&self) -> AudioInfo {
+        match self {
+            AudioFile::MP3(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels,
+                bitrate: f.info.bitrate,
+                bits_per_sample: None,
+                lossless: false,
+                bitrate_mode: f.info.bitrate_mode,
+            },
+            AudioFile::FLAC(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels as u32,
+                bitrate: f.info.bits_per_sample as u32 * f.info.sample_rate * f.info.channels as u32,
+                bits_per_sample: Some(f.info.bits_per_sample as u32),
+                lossless: true,
+                bitrate_mode: f.info.bitrate_mode(),
+            },
+            AudioFile::OggVorbis(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels as u32,
+                bitrate: f.info.bitrate,
+                bits_per_sample: None,
+                lossless: false,
+                bitrate_mode: f.info.bitrate_mode,
+            },
+            AudioFile::OggFLAC(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels as u32,
+                bitrate: f.info.bits_per_sample as u32 * f.info.sample_rate * f.info.channels as u32,
+                bits_per_sample: Some(f.info.bits_per_sample as u32),
+                lossless: true,
+                bitrate_mode: f.info.bitrate_mode(),
+            },
+            AudioFile::MP4(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels,
+                bitrate: f.info.bitrate,
+                bits_per_sample: Some(f.info.bits_per_sample).filter(|_| f.info.lossless()),
+                lossless: f.info.lossless(),
+                bitrate_mode: f.info.bitrate_mode,
+            },
+            AudioFile::WavPack(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels,
+                bitrate: f.info.bitrate,
+                bits_per_sample: Some(f.info.bits_per_sample),
+                lossless: f.info.lossless,
+                bitrate_mode: BitrateMode::Unknown,
+            },
+            AudioFile::ASF(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels,
+                bitrate: f.info.bitrate,
+                bits_per_sample: None,
+                lossless: f.info.lossless(),
+                bitrate_mode: BitrateMode::Unknown,
+            },
+            AudioFile::MonkeysAudio(f) => AudioInfo {
+                length: f.info.length,
+                sample_rate: f.info.sample_rate,
+                channels: f.info.channels,
+                bitrate: f.info.bitrate,
+                bits_per_sample: Some(f.info.bits_per_sample).filter(|&b| b > 0),
+                lossless: true,
+                bitrate_mode: BitrateMode::VBR,
+            },
+        }
+    }
+
+    /// The file's tags, or `None` if the format allows a file without any.
+    pub fn tags(&self) -> Option<Tags<'_>> {
+        match self {
+            AudioFile::MP3(f) => Some(Tags::ID3(&f.tags)),
+            AudioFile::FLAC(f) => f.tags.as_ref().map(Tags::Vorbis),
+            AudioFile::OggVorbis(f) => Some(Tags::Vorbis(&f.tags)),
+            AudioFile::OggFLAC(f) => f.tags.as_ref().map(Tags::Vorbis),
+            AudioFile::MP4(f) => Some(Tags::MP4(&f.tags)),
+            AudioFile::WavPack(f) => f.tags.as_ref().map(Tags::APEv2),
+            AudioFile::ASF(f) => Some(Tags::ASF(&f.tags)),
+            AudioFile::MonkeysAudio(f) => f.tags.as_ref().map(Tags::APEv2),
+        }
+    }
+}
+
+impl Tags<'_> {
+    /// First text value of a format-native key (`TIT2`, `title`, `©nam`, `Title`, ...).
+    pub fn get_text(&self, key: &str) -> Option<String> {
+        match self {
+            Tags::ID3(t) => match t.decode_first(key)?.as_ref() {
+                Frame::Text(f) => f.text.first().cloned(),
+                frame => frame.text_values().into_iter().next(),
+            },
+            Tags::Vorbis(t) => t.get(key).first().map(|s| s.to_string()),
+            Tags::MP4(t) => t.get_text(key),
+            Tags::APEv2(t) => match t.get(key)? {
+                APEValue::Text(v) => v.first().cloned(),
+                APEValue::External(s) => Some(s.clone()),
+                APEValue::Binary(_) => None,
+            },
+            Tags::ASF(t) => t.get_text(key),
+        }
+    }
+
+    pub fn title(&self) -> Option<String> {
+        self.get_text(self.common_key("TIT2", "title", "\u{a9}nam", "Title", "Title"))
+    }
+
+    pub fn artist(&self) -> Option<String> {
+        self.get_text(self.common_key("TPE1", "artist", "\u{a9}ART", "Artist", "Author"))
+    }
+
+    pub fn album(&self) -> Option<String> {
+        self.get_text(self.common_key("TALB", "album", "\u{a9}alb", "Album", "WM/AlbumTitle"))
+    }
+
+    fn common_key(
+        &self,
+        id3: &'static str,
+        vorbis: &'static str,
+        mp4: &'static str,
+        ape: &'static str,
+        asf: &'static str,
+    ) -> &'static str {
+        match self {
+            Tags::ID3(_) => id3,
+            Tags::Vorbis(_) => vorbis,
+            Tags::MP4(_) => mp4,
+            Tags::APEv2(_) => ape,
+            Tags::ASF(_) => asf,
+        }
+    }
+}
@@ -1,6 +1,9 @@
 use std::fs::File;
 use std::io::{Write, Seek, SeekFrom, Read};
 use crate::common::error::{MutagenError, Result};
+use crate::common::options::ParseOptions;
+use crate::id3::tags::ID3Tags;
+use crate::mp3::xing::BitrateMode;
 use crate::vorbis::VorbisComment;
 
 /// FLAC metadata block types.
@@ -114,6 +117,70 @@ impl StreamInfo {
             length,
         })
     }
+
+    /// Lossless compression always yields a variable bitrate.
+    pub fn bitrate_mode(&self) -> BitrateMode {
+        BitrateMode::VBR
+    }
+}
+
+/// A SeekTable entry: the first sample of a target frame and its byte
+/// offset from the first audio frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeekPoint {
+    pub sample: u64,
+    pub offset: u64,
+    pub samples: u16,
+}
+
+impl SeekPoint {
+    /// Parse a SeekTable block, dropping placeholder points.
+    pub fn parse_table(block_data: &[u8]) -> Vec<SeekPoint> {
+        block_data
+            .chunks_exact(18)
+            .map(|p| SeekPoint {
+                sample: u64::from_be_bytes(p[0..8].try_into().unwrap()),
+                offset: u64::from_be_bytes(p[8..16].try_into().unwrap()),
+                samples: u16::from_be_bytes([p[16], p[17]]),
+            })
+            .filter(|p| p.sample != u64::MAX)
+            .collect()
+    }
+}
+
+/// A CueSheet track and the sample at which its INDEX 01 begins (the
+/// track offset plus that index's offset, or the track offset alone
+/// without one).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueTrack {
+    pub number: u8,
+    pub isrc: String,
+    pub start: u64,
+}
+
+impl CueTrack {
+    /// Parse a CueSheet block's tracks, dropping the final lead-out track.
+    pub fn parse_cuesheet(block_data: &[u8]) -> Vec<CueTrack> {
+        // Catalog number, lead-in, CD flag and reserved bytes, then the count
+        let Some(&count) = block_data.get(395) else { return Vec::new() };
+        let mut tracks = Vec::with_capacity(count as usize);
+        let mut pos = 396;
+        for _ in 0..count {
+            let Some(t) = block_data.get(pos..pos + 36) else { break };
+            let offset = u64::from_be_bytes(t[0..8].try_into().unwrap());
+            let isrc = String::from_utf8_lossy(&t[9..21]).trim_end_matches('\0').to_string();
+            let indices = t[35] as usize;
+            pos += 36;
+            let index_start = (0..indices)
+                .filter_map(|i| block_data.get(pos + i * 12..pos + i * 12 + 12))
+                .find(|idx| idx[8] == 1)
+                .map_or(0, |idx| u64::from_be_bytes(idx[0..8].try_into().unwrap()));
+            pos += indices * 12;
+            tracks.push(CueTrack { number: t[8], isrc, start: offset + index_start });
+        }
+        tracks.pop();
+        tracks
+    }
 }
 
 /// FLAC Picture block.
@@ -210,6 +277,23 @@ impl FLACPicture {
         })
     }
 
+    /// Build a picture from JPEG or PNG data, reading the MIME type, width,
+    /// height and bit depth from the image header. `colors` is left at 0.
+    pub fn from_image_bytes(data: Vec<u8>, pic_type: u32, desc: &str) -> Result<Self> {
+        let (mime, width, height, depth) = sniff_image(&data)
+            .ok_or_else(|| MutagenError::ValueError("unrecognized image format (expected JPEG or PNG)".into()))?;
+        Ok(FLACPicture {
+            pic_type,
+            mime: mime.to_string(),
+            desc: desc.to_string(),
+            width,
+            height,
+            depth,
+            colors: 0,
+            data,
+        })
+    }
+
     pub fn render(&self) -> Vec<u8> {
         let mut data = Vec::new();
         data.extend_from_slice(&self.pic_type.to_be_bytes());
@@ -245,7 +329,103 @@ pub struct BlockDesc {
     pub data_size: usize,
 }
 
+/// MIME type, width, height and bits per pixel of a JPEG or PNG image.
+fn sniff_image(data: &[u8]) -> Option<(&'static str, u32, u32, u32)> {
+    let be_u16 = |pos: usize| Some(u16::from_be_bytes([*data.get(pos)?, *data.get(pos + 1)?]) as u32);
+    let be_u32 = |pos: usize| Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?));
+
+    if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+        // IHDR is always the first chunk
+        if data.get(12..16)? != b"IHDR" {
+            return None;
+        }
+        let channels = match *data.get(25)? {
+            2 => 3,
+            4 => 2,
+            6 => 4,
+            _ => 1, // greyscale or palette
+        };
+        return Some(("image/png", be_u32(16)?, be_u32(20)?, *data.get(24)? as u32 * channels));
+    }
+
+    if data.starts_with(b"\xff\xd8") {
+        // Walk the segments up to the first start-of-frame marker
+        let mut pos = 2;
+        while *data.get(pos)? == 0xFF {
+            let marker = *data.get(pos + 1)?;
+            let is_sof = (0xC0..=0xCF).contains(&marker) && !matches!(marker, 0xC4 | 0xC8 | 0xCC);
+            if is_sof {
+                let precision = *data.get(pos + 4)? as u32;
+                let components = *data.get(pos + 9)? as u32;
+                return Some(("image/jpeg", be_u16(pos + 7)?, be_u16(pos + 5)?, precision * components));
+            }
+            pos += 2 + be_u16(pos + 2)? as usize;
+        }
+    }
+    None
+}
+
+/// Offset of the `fLaC` marker: 0, or the end of a leading ID3v2 tag.
+fn find_flac_offset(existing: &[u8]) -> Result<usize> {
+    if existing.len() >= 4 && &existing[0..4] == b"fLaC" {
+        Ok(0)
+    } else if existing.len() >= 10 && &existing[0..3] == b"ID3" {
+        let size = crate::id3::header::BitPaddedInt::syncsafe(&existing[6..10]) as usize;
+        Ok(10 + size)
+    } else {
+        Err(MutagenError::FLAC("Cannot find fLaC header".into()))
+    }
+}
+
+/// `fLaC` followed by the blocks with their headers, the last one flagged.
+fn render_metadata(blocks: &[(BlockType, Vec<u8>)]) -> Vec<u8> {
+    let mut metadata = b"fLaC".to_vec();
+    for (i, (block_type, block_data)) in blocks.iter().enumerate() {
+        let is_last = i == blocks.len() - 1;
+        let header_byte = if is_last {
+            block_type.to_byte() | 0x80
+        } else {
+            block_type.to_byte()
+        };
+        metadata.push(header_byte);
+        let size = block_data.len() as u32;
+        metadata.push((size >> 16) as u8);
+        metadata.push((size >> 8) as u8);
+        metadata.push(size as u8);
+        metadata.extend_from_slice(block_data);
+    }
+    metadata
+}
+
+/// Options for `FLACFile::save_with`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SaveOptions {
+    /// Keep blocks that are not rewritten (SeekTable, CueSheet,
+    /// Application, ...) at their original position, replacing the
+    /// VorbisComment and existing pictures in place. Otherwise blocks are
+    /// written as StreamInfo, VorbisComment, pictures, then the rest.
+    pub preserve_order: bool,
+    /// Drop comments repeating an earlier `KEY=value` pair.
+    pub dedupe: bool,
+    /// Re-read the file after writing and fail with `MutagenError::Verify`
+    /// if it doesn't match (see `FLACFile::verify`).
+    pub verify: bool,
+    /// Restore the file's modification time after writing.
+    pub preserve_mtime: bool,
+    /// When the new blocks fit in the space of the old ones (their padding
+    /// included), overwrite just the metadata region and leave the audio
+    /// untouched. Otherwise the file is rewritten as usual.
+    pub padding_reuse: bool,
+}
+
 /// Complete FLAC file handler.
+///
+/// Memory model: blocks are recorded as offsets (`block_descs`,
+/// `lazy_pictures`) rather than copies, and `save_with` re-reads the file
+/// from disk, so the parsed file holds no audio or picture data from the
+/// source. The exceptions are `vc_raw`, the comment bytes kept for lazy
+/// parsing (see `drop_raw`), and `pictures`, which are added but not yet
+/// written.
 #[derive(Debug)]
 pub struct FLACFile {
     pub info: StreamInfo,
@@ -254,9 +434,15 @@ pub struct FLACFile {
     pub pictures: Vec<FLACPicture>,
     pub lazy_pictures: Vec<LazyPicture>,
     pub block_descs: Vec<BlockDesc>,       // Lightweight descriptors (no data copies)
+    pub seek_points: Vec<SeekPoint>,
+    pub cue_tracks: Vec<CueTrack>,
+    /// ID3v2 tag found in front of the `fLaC` marker, if any. `save_with`
+    /// rewrites only from `flac_offset` on, so this region is kept as is.
+    pub id3: Option<ID3Tags>,
     pub path: String,
     pub metadata_length: usize,
     pub flac_offset: usize,
+    pub file_size: usize,
 }
 
 impl FLACFile {
@@ -267,6 +453,12 @@ impl FLACFile {
     }
 
     pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        Self::parse_with(data, path, ParseOptions::default())
+    }
+
+    /// Like `parse`; in strict mode a metadata block that runs past the end
+    /// of the data is an error instead of ending the block list.
+    pub fn parse_with(data: &[u8], path: &str, options: ParseOptions) -> Result<Self> {
         // Check for fLaC magic
         if data.len() < 4 || &data[0..4] != b"fLaC" {
             // Check if there's an ID3v2 header before fLaC
@@ -286,18 +478,20 @@ impl FLACFile {
                 return Err(MutagenError::FLACNoHeader);
             }
 
-            return Self::parse_from_offset(data, offset, path);
+            return Self::parse_from_offset(data, offset, path, options);
         }
 
-        Self::parse_from_offset(data, 0, path)
+        Self::parse_from_offset(data, 0, path, options)
     }
 
-    fn parse_from_offset(data: &[u8], flac_offset: usize, path: &str) -> Result<Self> {
+    fn parse_from_offset(data: &[u8], flac_offset: usize, path: &str, options: ParseOptions) -> Result<Self> {
         let mut pos = flac_offset + 4; // Skip fLaC magic
         let mut block_descs = Vec::new();
         let mut stream_info = None;
         let mut vc_raw = None;
         let mut lazy_pictures = Vec::new();
+        let mut seek_points = Vec::new();
+        let mut cue_tracks = Vec::new();
 
         loop {
             if pos + 4 > data.len() {
@@ -311,6 +505,12 @@ impl FLACFile {
             pos += 4;
 
             if pos + block_size > data.len() {
+                if options.strict {
+                    return Err(MutagenError::Truncated(format!(
+                        "{:?} block truncated: {} bytes declared, {} available",
+                        block_type, block_size, data.len() - pos
+                    )));
+                }
                 break;
             }
 
@@ -330,6 +530,12 @@ impl FLACFile {
                     // Lazy: store raw bytes, don't parse yet
                     vc_raw = Some(data[pos..pos + block_size].to_vec());
                 }
+                BlockType::SeekTable => {
+                    seek_points = SeekPoint::parse_table(&data[pos..pos + block_size]);
+                }
+                BlockType::CueSheet => {
+                    cue_tracks = CueTrack::parse_cuesheet(&data[pos..pos + block_size]);
+                }
                 BlockType::Picture => {
                     lazy_pictures.push(LazyPicture {
                         block_offset: pos,
@@ -347,6 +553,11 @@ impl FLACFile {
         }
 
         let info = stream_info.ok_or_else(|| MutagenError::FLAC("No StreamInfo block found".into()))?;
+        let id3 = if flac_offset > 0 {
+            crate::id3::load_id3_from_data(&data[..flac_offset]).ok().map(|(tags, _)| tags)
+        } else {
+            None
+        };
 
         Ok(FLACFile {
             info,
@@ -355,19 +566,94 @@ impl FLACFile {
             pictures: Vec::new(),
             lazy_pictures,
             block_descs,
+            seek_points,
+            cue_tracks,
+            id3,
             path: path.to_string(),
             metadata_length: pos - flac_offset,
             flac_offset,
+            file_size: data.len(),
         })
     }
 
+    /// CueSheet tracks as `(start_seconds, "Track NN")` pairs.
+    pub fn cue_markers(&self) -> Vec<(f64, String)> {
+        if self.info.sample_rate == 0 {
+            return Vec::new();
+        }
+        self.cue_tracks
+            .iter()
+            .map(|t| (t.start as f64 / self.info.sample_rate as f64, format!("Track {:02}", t.number)))
+            .collect()
+    }
+
+    /// Byte offset of the first audio frame, right after the last metadata block.
+    pub fn audio_offset(&self) -> usize {
+        self.flac_offset + self.metadata_length
+    }
+
+    /// File byte offset of the frame data holding `seconds`, for seeking.
+    ///
+    /// Interpolates between the surrounding SeekTable points, treating the
+    /// start and end of the audio as implicit points; without a SeekTable
+    /// this is a proportional estimate over the audio bytes. Returns `None`
+    /// for times outside the stream or when the sample count is unknown.
+    pub fn byte_offset_for_time(&self, seconds: f64) -> Option<u64> {
+        let total = self.info.total_samples;
+        if total == 0 || self.info.sample_rate == 0 || !(0.0..=self.info.length).contains(&seconds) {
+            return None;
+        }
+        let target = ((seconds * self.info.sample_rate as f64) as u64).min(total);
+        let audio_size = self.file_size.saturating_sub(self.audio_offset()) as u64;
+
+        let mut anchors = vec![(0u64, 0u64)];
+        anchors.extend(
+            self.seek_points
+                .iter()
+                .filter(|p| p.sample > 0 && p.sample < total && p.offset < audio_size)
+                .map(|p| (p.sample, p.offset)),
+        );
+        anchors.push((total, audio_size));
+
+        let i = anchors.iter().rposition(|&(sample, _)| sample <= target)?;
+        let (s0, o0) = anchors[i];
+        let offset = match anchors.get(i + 1) {
+            Some(&(s1, o1)) if s1 > s0 && o1 >= o0 => {
+                o0 + ((target - s0) as f64 / (s1 - s0) as f64 * (o1 - o0) as f64) as u64
+            }
+            _ => o0,
+        };
+        Some(self.audio_offset() as u64 + offset)
+    }
+
     /// Lazily parse VorbisComment from raw bytes if not yet parsed.
     pub fn ensure_tags(&mut self) {
+        let _ = self.ensure_tags_with(ParseOptions::default());
+    }
+
+    /// Like `ensure_tags`, returning the Vorbis comment error in strict mode.
+    pub fn ensure_tags_with(&mut self, options: ParseOptions) -> Result<()> {
         if self.tags.is_none() {
             if let Some(ref raw) = self.vc_raw {
-                self.tags = VorbisComment::parse(raw, false).ok();
+                match VorbisComment::parse_with(raw, false, options) {
+                    Ok(vc) => self.tags = Some(vc),
+                    Err(e) if options.strict => return Err(e),
+                    Err(_) => {}
+                }
             }
         }
+        Ok(())
+    }
+
+    /// Parse the tags if needed, then release `vc_raw`. The raw bytes are
+    /// kept if they fail to parse, since `save` would otherwise drop the
+    /// comment block. Added `pictures` are kept too: they exist nowhere
+    /// else until saved.
+    pub fn drop_raw(&mut self) {
+        self.ensure_tags();
+        if self.tags.is_some() {
+            self.vc_raw = None;
+        }
     }
 
     /// Get tags, parsing lazily if needed.
@@ -378,28 +664,156 @@ impl FLACFile {
 
     /// Save metadata back to the FLAC file.
     pub fn save(&self) -> Result<()> {
+        self.save_with(SaveOptions::default())?;
+        Ok(())
+    }
+
+    /// Like `save`, with the block order and comment rendering set by
+    /// `options`. Returns whether the metadata was written in place (only
+    /// ever with `padding_reuse`).
+    pub fn save_with(&self, options: SaveOptions) -> Result<bool> {
+        let in_place = crate::common::util::preserving_mtime(&self.path, options.preserve_mtime, || self.write_blocks(options))?;
+        if options.verify {
+            self.verify(options.dedupe)?;
+        }
+        Ok(in_place)
+    }
+
+    fn write_blocks(&self, options: SaveOptions) -> Result<bool> {
         let mut file = std::fs::OpenOptions::new().read(true).write(true).open(&self.path)?;
         let mut existing = Vec::new();
-        file.read_to_end(&mut existing)?;
 
-        // Find the fLaC offset
-        let flac_offset = if existing.len() >= 4 && &existing[0..4] == b"fLaC" {
-            0
-        } else if existing.len() >= 3 && &existing[0..3] == b"ID3" {
-            if existing.len() >= 10 {
-                let size = crate::id3::header::BitPaddedInt::syncsafe(&existing[6..10]) as usize;
-                10 + size
-            } else {
-                return Err(MutagenError::FLAC("Cannot find fLaC header".into()));
+        if options.padding_reuse {
+            // The metadata region is all an in-place write needs to read
+            (&mut file).take((self.flac_offset + self.metadata_length) as u64).read_to_end(&mut existing)?;
+            if let Some(new_metadata) = self.metadata_in_place(&existing, options)? {
+                file.seek(SeekFrom::Start(self.flac_offset as u64))?;
+                file.write_all(&new_metadata)?;
+                file.flush()?;
+                crate::common::cache::invalidate(&self.path);
+                return Ok(true);
             }
+            existing.clear();
+            file.seek(SeekFrom::Start(0))?;
+        }
+        file.read_to_end(&mut existing)?;
+        let flac_offset = find_flac_offset(&existing)?;
+
+        // Rebuild metadata blocks
+        let mut blocks_to_write = self.blocks_for_save(&existing, options);
+
+        // Padding
+        blocks_to_write.push((BlockType::Padding, vec![0u8; 1024]));
+        let new_metadata = render_metadata(&blocks_to_write);
+
+        // Audio data starts after original metadata
+        let audio_start = flac_offset + self.metadata_length;
+        let audio_data = &existing[audio_start..];
+
+        file.seek(SeekFrom::Start(flac_offset as u64))?;
+        file.set_len(flac_offset as u64)?;
+        file.write_all(&new_metadata)?;
+        file.write_all(audio_data)?;
+        file.flush()?;
+        crate::common::cache::invalidate(&self.path);
+        Ok(false)
+    }
+
+    /// The blocks to save, padding excluded, in the order `options` asks for.
+    fn blocks_for_save(&self, existing: &[u8], options: SaveOptions) -> Vec<(BlockType, Vec<u8>)> {
+        if options.preserve_order {
+            self.blocks_in_file_order(existing, options.dedupe)
         } else {
-            return Err(MutagenError::FLAC("Cannot find fLaC header".into()));
+            self.blocks_in_canonical_order(existing, options.dedupe)
+        }
+    }
+
+    /// The new metadata rendered to exactly fill the current metadata
+    /// region, with whatever space is left as padding, or `None` if it
+    /// doesn't fit (or the region moved since the file was parsed).
+    fn metadata_in_place(&self, existing: &[u8], options: SaveOptions) -> Result<Option<Vec<u8>>> {
+        if existing.len() != self.flac_offset + self.metadata_length
+            || find_flac_offset(existing).ok() != Some(self.flac_offset)
+        {
+            return Ok(None);
+        }
+        let mut blocks = self.blocks_for_save(existing, options);
+        let used = 4 + blocks.iter().map(|(_, data)| 4 + data.len()).sum::<usize>();
+        let space = self.metadata_length;
+        if used != space {
+            // A padding block needs room for its own 4-byte header
+            match space.checked_sub(used + 4) {
+                Some(padding) if padding < 1 << 24 => blocks.push((BlockType::Padding, vec![0u8; padding])),
+                _ => return Ok(None),
+            }
+        }
+        Ok(Some(render_metadata(&blocks)))
+    }
+
+    /// Re-parse the file on disk and check it holds what `save_with` writes
+    /// for this state: the same StreamInfo, the same comments, every picture,
+    /// and the audio data unchanged in length.
+    pub fn verify(&self, dedupe: bool) -> Result<()> {
+        let fail = |what: &str| Err(MutagenError::Verify(format!("{}: {}", self.path, what)));
+        let data = std::fs::read(&self.path)?;
+        let mut written = match Self::parse_with(&data, &self.path, ParseOptions::strict()) {
+            Ok(written) => written,
+            Err(e) => return fail(&format!("written metadata does not parse: {}", e)),
         };
 
-        // Rebuild metadata blocks
-        let mut new_metadata = Vec::new();
-        new_metadata.extend_from_slice(b"fLaC");
+        let (a, b) = (&self.info, &written.info);
+        if (a.sample_rate, a.channels, a.bits_per_sample, a.total_samples, a.md5)
+            != (b.sample_rate, b.channels, b.bits_per_sample, b.total_samples, b.md5)
+        {
+            return fail("StreamInfo differs");
+        }
+
+        let expected = self.vc_block(dedupe).map(|raw| VorbisComment::parse(&raw, false)).transpose()?;
+        if written.ensure_tags_with(ParseOptions::strict()).is_err() {
+            return fail("written Vorbis comment does not parse");
+        }
+        if expected.as_ref().map(|vc| &vc.comments) != written.tags.as_ref().map(|vc| &vc.comments) {
+            return fail("comments differ");
+        }
+
+        if written.lazy_pictures.len() != self.lazy_pictures.len() + self.pictures.len() {
+            return fail("picture count differs");
+        }
+        for pic in &self.pictures {
+            let rendered = pic.render();
+            let found = written.lazy_pictures.iter().any(|lp| {
+                data.get(lp.block_offset..lp.block_offset + lp.block_size) == Some(&rendered[..])
+            });
+            if !found {
+                return fail("added picture missing");
+            }
+        }
 
+        if written.file_size - written.audio_offset() != self.file_size - self.audio_offset() {
+            return fail("audio data length differs");
+        }
+        Ok(())
+    }
+
+    /// The VorbisComment block body: re-rendered if the tags were loaded,
+    /// otherwise the original bytes. With `dedupe`, repeated `KEY=value`
+    /// comments are dropped (see `VorbisComment::render_deduped`).
+    fn vc_block(&self, dedupe: bool) -> Option<Vec<u8>> {
+        match (&self.tags, &self.vc_raw) {
+            (Some(vc), _) if dedupe => Some(vc.render_deduped(false)),
+            (Some(vc), _) => Some(vc.render(false)),
+            (None, Some(raw)) if dedupe => match VorbisComment::parse(raw, false) {
+                Ok(vc) => Some(vc.render_deduped(false)),
+                Err(_) => Some(raw.clone()),
+            },
+            (None, Some(raw)) => Some(raw.clone()),
+            (None, None) => None,
+        }
+    }
+
+    /// StreamInfo, VorbisComment, pictures, then the remaining blocks
+    /// (padding excluded).
+    fn blocks_in_canonical_order(&self, existing: &[u8], dedupe: bool) -> Vec<(BlockType, Vec<u8>)> {
         let mut blocks_to_write: Vec<(BlockType, Vec<u8>)> = Vec::new();
 
         // StreamInfo (always first) - read from existing file using descriptor
@@ -413,21 +827,19 @@ impl FLACFile {
         }
 
         // Vorbis comment
-        if let Some(ref vc) = self.tags {
-            blocks_to_write.push((BlockType::VorbisComment, vc.render(false)));
-        } else if let Some(ref raw) = self.vc_raw {
-            blocks_to_write.push((BlockType::VorbisComment, raw.clone()));
+        if let Some(vc) = self.vc_block(dedupe) {
+            blocks_to_write.push((BlockType::VorbisComment, vc));
         }
 
-        // Pictures
-        for pic in &self.pictures {
-            blocks_to_write.push((BlockType::Picture, pic.render()));
-        }
+        // Pictures: those already in the file, then added ones
         for lp in &self.lazy_pictures {
             if lp.block_offset + lp.block_size <= existing.len() {
                 blocks_to_write.push((BlockType::Picture, existing[lp.block_offset..lp.block_offset + lp.block_size].to_vec()));
             }
         }
+        for pic in &self.pictures {
+            blocks_to_write.push((BlockType::Picture, pic.render()));
+        }
 
         // Other blocks from descriptors (skip StreamInfo, VC, Picture, Padding)
         for bd in &self.block_descs {
@@ -440,37 +852,48 @@ impl FLACFile {
                 }
             }
         }
+        blocks_to_write
+    }
 
-        // Padding
-        blocks_to_write.push((BlockType::Padding, vec![0u8; 1024]));
+    /// Blocks in their original order (padding excluded). A VorbisComment
+    /// missing from the file goes right after StreamInfo; added pictures
+    /// follow the last existing picture, or the VorbisComment if there is none.
+    fn blocks_in_file_order(&self, existing: &[u8], dedupe: bool) -> Vec<(BlockType, Vec<u8>)> {
+        let mut blocks_to_write: Vec<(BlockType, Vec<u8>)> = Vec::new();
+        let mut vc = self.vc_block(dedupe);
+        let has_vc = self.block_descs.iter().any(|bd| bd.block_type == BlockType::VorbisComment);
+        let last_picture = self.block_descs.iter().rposition(|bd| bd.block_type == BlockType::Picture);
 
-        // Write blocks with proper headers
-        for (i, (block_type, block_data)) in blocks_to_write.iter().enumerate() {
-            let is_last = i == blocks_to_write.len() - 1;
-            let header_byte = if is_last {
-                block_type.to_byte() | 0x80
-            } else {
-                block_type.to_byte()
+        for (i, bd) in self.block_descs.iter().enumerate() {
+            match bd.block_type {
+                BlockType::Padding => continue,
+                BlockType::VorbisComment => {
+                    if let Some(vc) = vc.take() {
+                        blocks_to_write.push((BlockType::VorbisComment, vc));
+                    }
+                }
+                _ => {
+                    if bd.data_offset + bd.data_size <= existing.len() {
+                        blocks_to_write.push((bd.block_type, existing[bd.data_offset..bd.data_offset + bd.data_size].to_vec()));
+                    }
+                }
+            }
+            if bd.block_type == BlockType::StreamInfo && !has_vc {
+                if let Some(vc) = vc.take() {
+                    blocks_to_write.push((BlockType::VorbisComment, vc));
+                }
+            }
+            let is_anchor = match last_picture {
+                Some(last) => last == i,
+                None => bd.block_type == BlockType::VorbisComment || (bd.block_type == BlockType::StreamInfo && !has_vc),
             };
-            new_metadata.push(header_byte);
-            let size = block_data.len() as u32;
-            new_metadata.push((size >> 16) as u8);
-            new_metadata.push((size >> 8) as u8);
-            new_metadata.push(size as u8);
-            new_metadata.extend_from_slice(block_data);
+            if is_anchor {
+                for pic in &self.pictures {
+                    blocks_to_write.push((BlockType::Picture, pic.render()));
+                }
+            }
         }
-
-        // Audio data starts after original metadata
-        let audio_start = flac_offset + self.metadata_length;
-        let audio_data = &existing[audio_start..];
-
-        file.seek(SeekFrom::Start(flac_offset as u64))?;
-        file.set_len(flac_offset as u64)?;
-        file.write_all(&new_metadata)?;
-        file.write_all(audio_data)?;
-        file.flush()?;
-
-        Ok(())
+        blocks_to_write
     }
 
     /// Score for auto-detection.
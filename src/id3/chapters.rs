@@ -0,0 +1,34 @@
+//! ID3v2 chapter frames (`CHAP`, from the ID3v2 Chapter Frame Addendum).
+
+use crate::id3::frames::{self, Frame};
+use crate::id3::header::BitPaddedInt;
+
+/// Parse a `CHAP` frame body into its start time in seconds and a label:
+/// the title from an embedded `TIT2` frame, or the element ID without one.
+/// Embedded frame sizes are syncsafe in v2.4 and plain in v2.3.
+pub fn parse_chap(data: &[u8], version: u8) -> Option<(f64, String)> {
+    let id_end = data.iter().position(|&b| b == 0)?;
+    let element_id = String::from_utf8_lossy(&data[..id_end]).into_owned();
+    // Start and end time (ms), then start and end byte offsets
+    let times = data.get(id_end + 1..id_end + 17)?;
+    let start_ms = u32::from_be_bytes(times[0..4].try_into().ok()?);
+
+    let mut title = None;
+    let mut pos = id_end + 17;
+    while let Some(header) = data.get(pos..pos + 10) {
+        let size = if version == 4 {
+            BitPaddedInt::syncsafe(&header[4..8]) as usize
+        } else {
+            u32::from_be_bytes(header[4..8].try_into().ok()?) as usize
+        };
+        let body = data.get(pos + 10..pos + 10 + size)?;
+        if &header[0..4] == b"TIT2" {
+            if let Ok(Frame::Text(f)) = frames::parse_text_frame("TIT2", body) {
+                title = f.text.into_iter().next();
+            }
+            break;
+        }
+        pos += 10 + size;
+    }
+    Some((start_ms as f64 / 1000.0, title.unwrap_or(element_id)))
+}
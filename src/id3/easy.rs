@@ -0,0 +1,125 @@
+//! Friendly key names over ID3 frames, after mutagen's EasyID3.
+//!
+//! Each key maps either to a text frame or to a TXXX frame with a fixed
+//! description. TXXX descriptions are case-sensitive in hash keys, so they
+//! are stored exactly as other taggers (Picard, fpcalc) write them.
+
+use crate::id3::frames::{Frame, TextFrame, UserTextFrame};
+use crate::id3::specs::Encoding;
+use crate::id3::tags::ID3Tags;
+
+/// Where an easy key's values are stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EasyKey {
+    /// A standard text frame, e.g. `TIT2`.
+    Text(&'static str),
+    /// A `TXXX` frame with the given description.
+    UserText(&'static str),
+}
+
+/// Registered keys, in the order `keys()` reports them.
+pub const KEYS: &[(&str, EasyKey)] = &[
+    ("album", EasyKey::Text("TALB")),
+    ("bpm", EasyKey::Text("TBPM")),
+    ("compilation", EasyKey::Text("TCMP")),
+    ("composer", EasyKey::Text("TCOM")),
+    ("copyright", EasyKey::Text("TCOP")),
+    ("encodedby", EasyKey::Text("TENC")),
+    ("lyricist", EasyKey::Text("TEXT")),
+    ("length", EasyKey::Text("TLEN")),
+    ("media", EasyKey::Text("TMED")),
+    ("mood", EasyKey::Text("TMOO")),
+    ("grouping", EasyKey::Text("TIT1")),
+    ("title", EasyKey::Text("TIT2")),
+    ("version", EasyKey::Text("TIT3")),
+    ("artist", EasyKey::Text("TPE1")),
+    ("albumartist", EasyKey::Text("TPE2")),
+    ("conductor", EasyKey::Text("TPE3")),
+    ("arranger", EasyKey::Text("TPE4")),
+    ("discnumber", EasyKey::Text("TPOS")),
+    ("organization", EasyKey::Text("TPUB")),
+    ("tracknumber", EasyKey::Text("TRCK")),
+    ("author", EasyKey::Text("TOLY")),
+    ("albumartistsort", EasyKey::Text("TSO2")),
+    ("albumsort", EasyKey::Text("TSOA")),
+    ("composersort", EasyKey::Text("TSOC")),
+    ("artistsort", EasyKey::Text("TSOP")),
+    ("titlesort", EasyKey::Text("TSOT")),
+    ("isrc", EasyKey::Text("TSRC")),
+    ("discsubtitle", EasyKey::Text("TSST")),
+    ("language", EasyKey::Text("TLAN")),
+    ("genre", EasyKey::Text("TCON")),
+    ("date", EasyKey::Text("TDRC")),
+    ("originaldate", EasyKey::Text("TDOR")),
+    ("musicbrainz_artistid", EasyKey::UserText("MusicBrainz Artist Id")),
+    ("musicbrainz_albumid", EasyKey::UserText("MusicBrainz Album Id")),
+    ("musicbrainz_albumartistid", EasyKey::UserText("MusicBrainz Album Artist Id")),
+    ("musicbrainz_trackid", EasyKey::UserText("MusicBrainz Release Track Id")),
+    ("musicbrainz_releasegroupid", EasyKey::UserText("MusicBrainz Release Group Id")),
+    ("musicbrainz_workid", EasyKey::UserText("MusicBrainz Work Id")),
+    ("musicbrainz_discid", EasyKey::UserText("MusicBrainz Disc Id")),
+    ("musicbrainz_albumstatus", EasyKey::UserText("MusicBrainz Album Status")),
+    ("musicbrainz_albumtype", EasyKey::UserText("MusicBrainz Album Type")),
+    ("releasecountry", EasyKey::UserText("MusicBrainz Album Release Country")),
+    ("acoustid_id", EasyKey::UserText("Acoustid Id")),
+    ("acoustid_fingerprint", EasyKey::UserText("Acoustid Fingerprint")),
+    ("asin", EasyKey::UserText("ASIN")),
+    ("barcode", EasyKey::UserText("BARCODE")),
+    ("catalognumber", EasyKey::UserText("CATALOGNUMBER")),
+];
+
+impl EasyKey {
+    /// Look up a registered key; names are case-insensitive.
+    pub fn lookup(name: &str) -> Option<EasyKey> {
+        KEYS.iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+            .map(|(_, key)| *key)
+    }
+
+    /// The frame hash key this easy key reads and writes.
+    pub fn frame_key(&self) -> String {
+        match self {
+            EasyKey::Text(id) => id.to_string(),
+            EasyKey::UserText(desc) => format!("TXXX:{}", desc),
+        }
+    }
+
+    /// Values of the backing frame, or `None` if it is absent.
+    pub fn get(&self, tags: &ID3Tags) -> Option<Vec<String>> {
+        match (self, tags.decode_first(&self.frame_key()).as_deref()) {
+            (EasyKey::Text(_), Some(Frame::Text(f))) => Some(f.text.clone()),
+            (EasyKey::UserText(_), Some(Frame::UserText(f))) => Some(f.text.clone()),
+            _ => None,
+        }
+    }
+
+    /// Replace the backing frame with one holding `values` (UTF-8).
+    pub fn set(&self, tags: &mut ID3Tags, values: Vec<String>) {
+        let frame = match self {
+            EasyKey::Text(id) => Frame::Text(TextFrame {
+                id: id.to_string(),
+                encoding: Encoding::Utf8,
+                text: values,
+            }),
+            EasyKey::UserText(desc) => Frame::UserText(UserTextFrame {
+                id: "TXXX".to_string(),
+                encoding: Encoding::Utf8,
+                desc: desc.to_string(),
+                text: values,
+            }),
+        };
+        tags.setall(&self.frame_key(), vec![frame]);
+    }
+
+    pub fn delete(&self, tags: &mut ID3Tags) {
+        tags.delall(&self.frame_key());
+    }
+}
+
+/// Registered keys that have a frame in `tags`.
+pub fn keys(tags: &ID3Tags) -> Vec<&'static str> {
+    KEYS.iter()
+        .filter(|(_, key)| key.get(tags).is_some())
+        .map(|(name, _)| *name)
+        .collect()
+}
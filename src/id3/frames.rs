@@ -79,9 +79,57 @@ pub enum Frame {
     Popularimeter(PopularimeterFrame),
     Binary(BinaryFrame),
     PairedText(PairedTextFrame),
+    Flag(FlagFrame),
+    Seek(SeekFrame),
+    AudioSeekPointIndex(AudioSeekPointIndexFrame),
 }
 
 impl Frame {
+    // Constructors below encode text as UTF-8; v2.3 writes convert it to
+    // UTF-16 (or Latin-1, see `TextEncodingPolicy`).
+
+    /// Text frame `id` (e.g. `"TIT2"`) holding `values`.
+    pub fn text<S: Into<String>>(id: &str, values: impl IntoIterator<Item = S>) -> Frame {
+        Frame::Text(TextFrame {
+            id: id.to_string(),
+            encoding: Encoding::Utf8,
+            text: values.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// TXXX frame with description `desc` holding `values`.
+    pub fn user_text<S: Into<String>>(desc: &str, values: impl IntoIterator<Item = S>) -> Frame {
+        Frame::UserText(UserTextFrame {
+            id: "TXXX".to_string(),
+            encoding: Encoding::Utf8,
+            desc: desc.to_string(),
+            text: values.into_iter().map(Into::into).collect(),
+        })
+    }
+
+    /// COMM frame in language `lang` (e.g. `"eng"`).
+    pub fn comment(lang: &str, desc: &str, text: &str) -> Frame {
+        Frame::Comment(CommentFrame {
+            id: "COMM".to_string(),
+            encoding: Encoding::Utf8,
+            lang: normalize_language(lang),
+            desc: desc.to_string(),
+            text: text.to_string(),
+        })
+    }
+
+    /// APIC frame holding image `data` of MIME type `mime`.
+    pub fn picture(mime: &str, pic_type: PictureType, desc: &str, data: Vec<u8>) -> Frame {
+        Frame::Picture(PictureFrame {
+            id: "APIC".to_string(),
+            encoding: Encoding::Utf8,
+            mime: mime.to_string(),
+            pic_type,
+            desc: desc.to_string(),
+            data,
+        })
+    }
+
     /// Get the frame ID (4-char string like "TIT2").
     pub fn frame_id(&self) -> &str {
         match self {
@@ -95,6 +143,45 @@ impl Frame {
             Frame::Popularimeter(f) => &f.id,
             Frame::Binary(f) => &f.id,
             Frame::PairedText(f) => &f.id,
+            Frame::Flag(f) => &f.id,
+            Frame::Seek(f) => &f.id,
+            Frame::AudioSeekPointIndex(f) => &f.id,
+        }
+    }
+
+    /// Text encoding of frames that store one.
+    pub fn encoding(&self) -> Option<Encoding> {
+        match self {
+            Frame::Text(f) => Some(f.encoding),
+            Frame::UserText(f) => Some(f.encoding),
+            Frame::UserUrl(f) => Some(f.encoding),
+            Frame::Comment(f) => Some(f.encoding),
+            Frame::Lyrics(f) => Some(f.encoding),
+            Frame::Picture(f) => Some(f.encoding),
+            Frame::PairedText(f) => Some(f.encoding),
+            Frame::Url(_) | Frame::Popularimeter(_) | Frame::Binary(_) | Frame::Flag(_)
+            | Frame::Seek(_) | Frame::AudioSeekPointIndex(_) => None,
+        }
+    }
+
+    /// Language code of COMM and USLT frames.
+    pub fn language(&self) -> Option<&str> {
+        match self {
+            Frame::Comment(f) => Some(&f.lang),
+            Frame::Lyrics(f) => Some(&f.lang),
+            _ => None,
+        }
+    }
+
+    /// Description of TXXX, WXXX, COMM, USLT and APIC frames.
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Frame::UserText(f) => Some(&f.desc),
+            Frame::UserUrl(f) => Some(&f.desc),
+            Frame::Comment(f) => Some(&f.desc),
+            Frame::Lyrics(f) => Some(&f.desc),
+            Frame::Picture(f) => Some(&f.desc),
+            _ => None,
         }
     }
 
@@ -103,14 +190,20 @@ impl Frame {
         match self {
             Frame::Text(f) => HashKey::new(&f.id),
             Frame::UserText(f) => HashKey::from_string(format!("TXXX:{}", f.desc)),
+            Frame::Url(f) if is_repeatable_url_frame(&f.id) => {
+                HashKey::from_string(format!("{}:{}", f.id, f.url))
+            }
             Frame::Url(f) => HashKey::new(&f.id),
             Frame::UserUrl(f) => HashKey::from_string(format!("WXXX:{}", f.desc)),
             Frame::Comment(f) => HashKey::from_string(format!("COMM:{}:{}", f.desc, f.lang)),
             Frame::Lyrics(f) => HashKey::from_string(format!("USLT:{}:{}", f.desc, f.lang)),
-            Frame::Picture(f) => HashKey::from_string(format!("APIC:{}", f.desc)),
+            Frame::Picture(f) => HashKey::from_string(picture_key(&f.desc, f.pic_type as u8)),
             Frame::Popularimeter(f) => HashKey::from_string(format!("POPM:{}", f.email)),
             Frame::Binary(f) => HashKey::new(&f.id),
             Frame::PairedText(f) => HashKey::new(&f.id),
+            Frame::Flag(f) => HashKey::new(&f.id),
+            Frame::Seek(f) => HashKey::new(&f.id),
+            Frame::AudioSeekPointIndex(f) => HashKey::new(&f.id),
         }
     }
 
@@ -133,6 +226,11 @@ impl Frame {
                     .collect::<Vec<_>>()
                     .join("/")
             }
+            Frame::Flag(f) => f.value.to_string(),
+            Frame::Seek(f) => f.offset.to_string(),
+            Frame::AudioSeekPointIndex(f) => {
+                format!("start={}, length={}, {} points", f.data_start, f.data_length, f.fractions.len())
+            }
         }
     }
 
@@ -149,9 +247,15 @@ impl Frame {
 
     /// Serialize frame data back to bytes (without frame header).
     pub fn write_data(&self, version: u8) -> Result<Vec<u8>> {
+        self.write_data_with(version, TextEncodingPolicy::default())
+    }
+
+    /// Like `write_data`, choosing the v2.3 encoding of UTF-8 text frames
+    /// according to `policy`.
+    pub fn write_data_with(&self, version: u8, policy: TextEncodingPolicy) -> Result<Vec<u8>> {
         match self {
-            Frame::Text(f) => write_text_frame(f, version),
-            Frame::UserText(f) => write_user_text_frame(f, version),
+            Frame::Text(f) => write_text_frame(f, version, policy),
+            Frame::UserText(f) => write_user_text_frame(f, version, policy),
             Frame::Url(f) => write_url_frame(f),
             Frame::UserUrl(f) => write_user_url_frame(f, version),
             Frame::Comment(f) => write_comment_frame(f, version),
@@ -160,8 +264,77 @@ impl Frame {
             Frame::Popularimeter(f) => write_popm_frame(f),
             Frame::Binary(f) => Ok(f.data.clone()),
             Frame::PairedText(f) => write_paired_text_frame(f, version),
+            Frame::Flag(f) => Ok(f.value.to_be_bytes().to_vec()),
+            Frame::Seek(f) => Ok(f.offset.to_be_bytes().to_vec()),
+            Frame::AudioSeekPointIndex(f) => write_aspi_frame(f),
         }
     }
+
+    /// Check for non-fatal problems: malformed language codes, non-numeric
+    /// values in numeric text frames, and implausible MIME types.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+        let mut warn = |message: String| warnings.push(ValidationWarning {
+            key: self.hash_key().as_str().to_string(),
+            message,
+        });
+
+        match self {
+            Frame::Comment(CommentFrame { lang, .. }) | Frame::Lyrics(LyricsFrame { lang, .. })
+                if lang.len() != 3 || !lang.bytes().all(|b| b.is_ascii_alphabetic()) =>
+            {
+                warn(format!("invalid language code {:?}", lang));
+            }
+            Frame::Text(f) => {
+                for value in &f.text {
+                    let valid = match f.id.as_str() {
+                        "TRCK" | "TPOS" => {
+                            let mut parts = value.trim().splitn(2, '/');
+                            parts.all(|p| !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()))
+                        }
+                        "TBPM" | "TLEN" | "TDLY" | "TYER" | "TORY" | "TSIZ" | "TDAT" | "TIME" => {
+                            !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit())
+                        }
+                        _ => true,
+                    };
+                    if !valid {
+                        warn(format!("non-numeric value {:?}", value));
+                    }
+                }
+            }
+            // "-->" marks a linked image (ID3v2.3/2.4 section 4.14)
+            Frame::Picture(f) if f.mime != "-->" && !is_valid_mime(&f.mime) => {
+                warn(format!("invalid MIME type {:?}", f.mime));
+            }
+            _ => {}
+        }
+
+        warnings
+    }
+}
+
+/// A non-fatal problem found by `Frame::validate`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationWarning {
+    /// Hash key of the offending frame (e.g. `"COMM::eng"`).
+    pub key: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// `type/subtype` made of RFC 2045 token characters.
+fn is_valid_mime(mime: &str) -> bool {
+    let is_token = |s: &str| !s.is_empty()
+        && s.bytes().all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?=".contains(&b));
+    match mime.split_once('/') {
+        Some((kind, sub)) => is_token(kind) && is_token(sub),
+        None => false,
+    }
 }
 
 /// Standard text frame (TIT2, TPE1, TALB, TRCK, TCON, TDRC, etc.)
@@ -172,6 +345,40 @@ pub struct TextFrame {
     pub text: Vec<String>,
 }
 
+/// Frames whose values are lists of names or genres, which pre-v2.4 taggers
+/// commonly join with a separator instead of nulls.
+const MULTI_VALUE_FRAMES: &[&str] = &[
+    "TPE1", "TPE2", "TPE3", "TPE4", "TCOM", "TEXT", "TOLY", "TOPE", "TCON",
+];
+
+/// How a text frame's content is split into values.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum SeparatorPolicy {
+    /// Split on the null separator only, as ID3v2.4 specifies.
+    #[default]
+    NullOnly,
+    /// Additionally split known multi-valued frames (TPE1, TCON, ...) on `/`
+    /// and `;`, the joins used by many ID3v2.3 taggers.
+    Legacy,
+}
+
+impl TextFrame {
+    /// Values resolved under `policy`. Other frames, such as a title
+    /// containing a slash, are never split on `/` or `;`.
+    pub fn values(&self, policy: SeparatorPolicy) -> Vec<String> {
+        if policy == SeparatorPolicy::NullOnly || !MULTI_VALUE_FRAMES.contains(&self.id.as_str()) {
+            return self.text.clone();
+        }
+        self.text
+            .iter()
+            .flat_map(|v| v.split(['/', ';']))
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
 /// User-defined text frame (TXXX).
 #[derive(Debug, Clone)]
 pub struct UserTextFrame {
@@ -244,6 +451,40 @@ pub struct BinaryFrame {
     pub data: Vec<u8>,
 }
 
+/// iTunes podcast flag frame (PCST): a 32-bit integer, non-zero when set.
+#[derive(Debug, Clone)]
+pub struct FlagFrame {
+    pub id: String,
+    pub value: u32,
+}
+
+impl FlagFrame {
+    pub fn is_set(&self) -> bool {
+        self.value != 0
+    }
+}
+
+/// Seek frame (SEEK, v2.4): the minimum number of bytes from the end of
+/// this tag to the start of the next one.
+#[derive(Debug, Clone)]
+pub struct SeekFrame {
+    pub id: String,
+    pub offset: u32,
+}
+
+/// Audio seek point index (ASPI, v2.4): evenly spaced points in the indexed
+/// audio, each the byte position as a fraction of `data_length` stored in
+/// `bits_per_point` (8 or 16) bits.
+#[derive(Debug, Clone)]
+pub struct AudioSeekPointIndexFrame {
+    pub id: String,
+    /// Start of the indexed audio, relative to the start of the file.
+    pub data_start: u32,
+    pub data_length: u32,
+    pub bits_per_point: u8,
+    pub fractions: Vec<u16>,
+}
+
 /// Paired text frame (TIPL, TMCL, IPLS).
 #[derive(Debug, Clone)]
 pub struct PairedTextFrame {
@@ -265,13 +506,15 @@ pub fn parse_text_frame(id: &str, data: &[u8]) -> Result<Frame> {
     }
 
     let encoding = Encoding::from_byte(data[0])?;
-    let text_data = &data[1..];
+    let text_data = specs::trim_trailing_nulls(&data[1..], encoding);
     let full_text = specs::decode_text(text_data, encoding)?;
 
-    // Split by null characters for multiple values
+    // Split by null characters for multiple values; in UTF-16 every value
+    // may carry its own BOM, which only the first decode strips
     let text: Vec<String> = if full_text.contains('\0') {
         full_text
             .split('\0')
+            .map(|s| s.strip_prefix('\u{feff}').unwrap_or(s))
             .filter(|s| !s.is_empty())
             .map(|s| s.to_string())
             .collect()
@@ -351,6 +594,40 @@ pub fn parse_user_url_frame(id: &str, data: &[u8]) -> Result<Frame> {
     }))
 }
 
+/// Language code for a COMM/USLT frame. Empty, blank or NUL-filled codes,
+/// as some taggers write, become `"XXX"` (unknown), both when read and
+/// when written.
+pub fn normalize_language(lang: &str) -> String {
+    if lang.trim_matches(|c: char| c == '\0' || c.is_whitespace()).is_empty() {
+        "XXX".to_string()
+    } else {
+        lang.to_string()
+    }
+}
+
+/// Language code from the 3 bytes after the encoding byte of COMM/USLT.
+pub(crate) fn read_language(bytes: &[u8]) -> String {
+    normalize_language(std::str::from_utf8(bytes).unwrap_or("XXX"))
+}
+
+/// The 3 bytes written for a language code; codes too short to fill them
+/// are written as `"XXX"`.
+fn language_bytes(lang: &str) -> [u8; 3] {
+    match normalize_language(lang).as_bytes() {
+        [a, b, c, ..] => [*a, *b, *c],
+        _ => *b"XXX",
+    }
+}
+
+/// Normalize the language code in the undecoded body of a COMM/USLT frame
+/// that is written back as-is.
+pub(crate) fn normalize_raw_language(id: &str, data: &mut [u8]) {
+    if matches!(id, "COMM" | "USLT") && data.len() >= 4 {
+        let lang = language_bytes(std::str::from_utf8(&data[1..4]).unwrap_or(""));
+        data[1..4].copy_from_slice(&lang);
+    }
+}
+
 /// Parse a COMM (comment) frame.
 pub fn parse_comment_frame(id: &str, data: &[u8]) -> Result<Frame> {
     if data.len() < 4 {
@@ -358,9 +635,7 @@ pub fn parse_comment_frame(id: &str, data: &[u8]) -> Result<Frame> {
     }
 
     let encoding = Encoding::from_byte(data[0])?;
-    let lang = std::str::from_utf8(&data[1..4])
-        .unwrap_or("XXX")
-        .to_string();
+    let lang = read_language(&data[1..4]);
     let rest = &data[4..];
 
     let (desc, consumed) = specs::read_encoded_text(rest, encoding)?;
@@ -383,9 +658,7 @@ pub fn parse_lyrics_frame(id: &str, data: &[u8]) -> Result<Frame> {
     }
 
     let encoding = Encoding::from_byte(data[0])?;
-    let lang = std::str::from_utf8(&data[1..4])
-        .unwrap_or("XXX")
-        .to_string();
+    let lang = read_language(&data[1..4]);
     let rest = &data[4..];
 
     let (desc, consumed) = specs::read_encoded_text(rest, encoding)?;
@@ -489,9 +762,114 @@ pub fn parse_paired_text_frame(id: &str, data: &[u8]) -> Result<Frame> {
     }))
 }
 
+/// Non-standard iTunes frames laid out as text frames (encoding byte +
+/// text) even though their IDs don't start with `T`.
+pub fn is_itunes_text_frame(id: &str) -> bool {
+    matches!(id, "WFED" | "GRP1" | "MVNM" | "MVIN")
+}
+
+/// v2.4 ID of a sort order frame under the ID iTunes used before v2.4
+/// defined them (`XSOA`, `XSOP`, `XSOT`); they are laid out as text frames.
+pub fn convert_itunes_sort_frame_id(id: &[u8]) -> Option<&'static [u8]> {
+    match id {
+        b"XSOA" => Some(b"TSOA"),
+        b"XSOP" => Some(b"TSOP"),
+        b"XSOT" => Some(b"TSOT"),
+        _ => None,
+    }
+}
+
+/// URL frames that may appear more than once (commercial information and
+/// artist webpage); they are keyed by their URL as well, e.g. `WOAR:url`.
+pub fn is_repeatable_url_frame(id: &str) -> bool {
+    matches!(id, "WCOM" | "WOAR")
+}
+
+/// Hash key of a picture: `APIC:desc` for front covers, `APIC:desc:type`
+/// otherwise, so pictures sharing a (often empty) description but not a
+/// type get separate keys.
+pub fn picture_key(desc: &str, pic_type: u8) -> String {
+    if pic_type == PictureType::CoverFront as u8 {
+        format!("APIC:{}", desc)
+    } else {
+        format!("APIC:{}:{}", desc, pic_type)
+    }
+}
+
+/// Split the part of a picture key after `APIC:` into description and
+/// picture type, the inverse of `picture_key`. A trailing `:N` is only read
+/// as the type when N is a picture type other than the front cover (which
+/// `picture_key` never appends); otherwise the whole suffix is the
+/// description of a front cover. Use `picture_key_desc` when the type is
+/// known, since e.g. `Disc:2` may also be a front cover's description.
+pub fn split_picture_key(rest: &str) -> (&str, u8) {
+    rest.rsplit_once(':')
+        .and_then(|(desc, ty)| {
+            let ty = ty.parse::<u8>().ok()?;
+            let appended = ty != PictureType::CoverFront as u8 && ty <= PictureType::PublisherLogo as u8;
+            appended.then_some((desc, ty))
+        })
+        .unwrap_or((rest, PictureType::CoverFront as u8))
+}
+
+/// Description of a picture of type `pic_type` keyed `APIC:<rest>`, or
+/// `None` if `rest` can't be `picture_key` output for that type.
+pub fn picture_key_desc(rest: &str, pic_type: u8) -> Option<&str> {
+    if pic_type == PictureType::CoverFront as u8 {
+        Some(rest)
+    } else {
+        rest.strip_suffix(&format!(":{}", pic_type))
+    }
+}
+
+/// Parse a PCST frame; short frames are read as unset.
+pub fn parse_flag_frame(id: &str, data: &[u8]) -> Result<Frame> {
+    let value = match data.get(..4) {
+        Some(b) => u32::from_be_bytes([b[0], b[1], b[2], b[3]]),
+        None => data.iter().fold(0, |v, &b| (v << 8) | b as u32),
+    };
+    Ok(Frame::Flag(FlagFrame { id: id.to_string(), value }))
+}
+
+/// Parse a SEEK frame.
+pub fn parse_seek_frame(id: &str, data: &[u8]) -> Result<Frame> {
+    let b = data.get(..4).ok_or_else(|| MutagenError::ID3("SEEK frame too short".into()))?;
+    let offset = u32::from_be_bytes([b[0], b[1], b[2], b[3]]);
+    Ok(Frame::Seek(SeekFrame { id: id.to_string(), offset }))
+}
+
+/// Parse an ASPI frame; index points missing from a truncated frame are
+/// dropped.
+pub fn parse_aspi_frame(id: &str, data: &[u8]) -> Result<Frame> {
+    if data.len() < 11 {
+        return Err(MutagenError::ID3("ASPI frame too short".into()));
+    }
+    let data_start = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+    let data_length = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+    let count = u16::from_be_bytes([data[8], data[9]]) as usize;
+    let bits_per_point = data[10];
+    let points = &data[11..];
+    let fractions = match bits_per_point {
+        8 => points.iter().take(count).map(|&b| b as u16).collect(),
+        16 => points.chunks_exact(2).take(count).map(|c| u16::from_be_bytes([c[0], c[1]])).collect(),
+        _ => return Err(MutagenError::ID3(format!("unsupported ASPI bits per index point {}", bits_per_point))),
+    };
+    Ok(Frame::AudioSeekPointIndex(AudioSeekPointIndexFrame {
+        id: id.to_string(),
+        data_start,
+        data_length,
+        bits_per_point,
+        fractions,
+    }))
+}
+
 /// Parse a frame from its ID and raw data.
 pub fn parse_frame(id: &str, data: &[u8]) -> Result<Frame> {
     match id {
+        // iTunes podcast frames (TGID/TDES are covered by the T*** rule)
+        s if is_itunes_text_frame(s) => parse_text_frame(id, data),
+        "PCST" => parse_flag_frame(id, data),
+
         // Text frames (T*** except TXXX)
         s if s.starts_with('T') && s != "TXXX" => parse_text_frame(id, data),
         "TXXX" => parse_user_text_frame(id, data),
@@ -513,6 +891,10 @@ pub fn parse_frame(id: &str, data: &[u8]) -> Result<Frame> {
         // Paired text
         "TIPL" | "TMCL" | "IPLS" => parse_paired_text_frame(id, data),
 
+        // v2.4 seek frames
+        "SEEK" => parse_seek_frame(id, data),
+        "ASPI" => parse_aspi_frame(id, data),
+
         // Everything else → binary
         _ => Ok(Frame::Binary(BinaryFrame {
             id: id.to_string(),
@@ -536,6 +918,10 @@ pub fn convert_v22_frame_id(id: &str) -> Option<&'static str> {
         "LNK" => Some("LINK"),
         "MCI" => Some("MCDI"),
         "MLL" => Some("MLLT"),
+        "GP1" => Some("GRP1"),
+        "MVI" => Some("MVIN"),
+        "MVN" => Some("MVNM"),
+        "PCS" => Some("PCST"),
         "PIC" => Some("APIC"),
         "POP" => Some("POPM"),
         "REV" => Some("RVRB"),
@@ -547,9 +933,11 @@ pub fn convert_v22_frame_id(id: &str) -> Option<&'static str> {
         "TCO" => Some("TCON"),
         "TCR" => Some("TCOP"),
         "TDA" => Some("TDAT"),
+        "TDS" => Some("TDES"),
         "TDY" => Some("TDLY"),
         "TEN" => Some("TENC"),
         "TFT" => Some("TFLT"),
+        "TID" => Some("TGID"),
         "TIM" => Some("TIME"),
         "TKE" => Some("TKEY"),
         "TLA" => Some("TLAN"),
@@ -584,6 +972,7 @@ pub fn convert_v22_frame_id(id: &str) -> Option<&'static str> {
         "WAS" => Some("WOAS"),
         "WCM" => Some("WCOM"),
         "WCP" => Some("WCOP"),
+        "WFD" => Some("WFED"),
         "WPB" => Some("WPUB"),
         "WXX" => Some("WXXX"),
         _ => None,
@@ -624,14 +1013,34 @@ pub fn parse_v22_picture_frame(data: &[u8]) -> Result<Frame> {
 
 // ---- Write functions ----
 
-fn write_text_frame(f: &TextFrame, version: u8) -> Result<Vec<u8>> {
-    let encoding = if version >= 4 {
-        f.encoding
-    } else if f.encoding == Encoding::Utf8 {
-        Encoding::Utf16
+/// How UTF-8 text frames are re-encoded for ID3v2.3, which has no UTF-8.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TextEncodingPolicy {
+    /// Always UTF-16 with a BOM.
+    #[default]
+    Utf16,
+    /// Latin-1 when every value fits, UTF-16 otherwise.
+    PreferLatin1,
+}
+
+/// The encoding a text frame is written with for `version`.
+fn text_write_encoding<'a>(
+    encoding: Encoding,
+    version: u8,
+    policy: TextEncodingPolicy,
+    mut texts: impl Iterator<Item = &'a str>,
+) -> Encoding {
+    if version >= 4 || encoding != Encoding::Utf8 {
+        encoding
+    } else if policy == TextEncodingPolicy::PreferLatin1 && texts.all(specs::is_latin1) {
+        Encoding::Latin1
     } else {
-        f.encoding
-    };
+        Encoding::Utf16
+    }
+}
+
+fn write_text_frame(f: &TextFrame, version: u8, policy: TextEncodingPolicy) -> Result<Vec<u8>> {
+    let encoding = text_write_encoding(f.encoding, version, policy, f.text.iter().map(String::as_str));
 
     let mut data = vec![encoding as u8];
     let joined = f.text.join("\0");
@@ -639,14 +1048,9 @@ fn write_text_frame(f: &TextFrame, version: u8) -> Result<Vec<u8>> {
     Ok(data)
 }
 
-fn write_user_text_frame(f: &UserTextFrame, version: u8) -> Result<Vec<u8>> {
-    let encoding = if version >= 4 {
-        f.encoding
-    } else if f.encoding == Encoding::Utf8 {
-        Encoding::Utf16
-    } else {
-        f.encoding
-    };
+fn write_user_text_frame(f: &UserTextFrame, version: u8, policy: TextEncodingPolicy) -> Result<Vec<u8>> {
+    let texts = std::iter::once(f.desc.as_str()).chain(f.text.iter().map(String::as_str));
+    let encoding = text_write_encoding(f.encoding, version, policy, texts);
 
     let mut data = vec![encoding as u8];
     data.extend_from_slice(&specs::encode_text(&f.desc, encoding));
@@ -688,13 +1092,7 @@ fn write_comment_frame(f: &CommentFrame, version: u8) -> Result<Vec<u8>> {
     };
 
     let mut data = vec![encoding as u8];
-    let lang_bytes = f.lang.as_bytes();
-    let lang = if lang_bytes.len() >= 3 {
-        &lang_bytes[..3]
-    } else {
-        b"XXX"
-    };
-    data.extend_from_slice(lang);
+    data.extend_from_slice(&language_bytes(&f.lang));
     data.extend_from_slice(&specs::encode_text(&f.desc, encoding));
     let term = specs::null_terminator_size(encoding);
     data.extend_from_slice(&vec![0u8; term]);
@@ -712,13 +1110,7 @@ fn write_lyrics_frame(f: &LyricsFrame, version: u8) -> Result<Vec<u8>> {
     };
 
     let mut data = vec![encoding as u8];
-    let lang_bytes = f.lang.as_bytes();
-    let lang = if lang_bytes.len() >= 3 {
-        &lang_bytes[..3]
-    } else {
-        b"XXX"
-    };
-    data.extend_from_slice(lang);
+    data.extend_from_slice(&language_bytes(&f.lang));
     data.extend_from_slice(&specs::encode_text(&f.desc, encoding));
     let term = specs::null_terminator_size(encoding);
     data.extend_from_slice(&vec![0u8; term]);
@@ -767,6 +1159,24 @@ fn write_popm_frame(f: &PopularimeterFrame) -> Result<Vec<u8>> {
     Ok(data)
 }
 
+fn write_aspi_frame(f: &AudioSeekPointIndexFrame) -> Result<Vec<u8>> {
+    let count = u16::try_from(f.fractions.len())
+        .map_err(|_| MutagenError::ID3("too many ASPI index points".into()))?;
+    let mut data = Vec::with_capacity(11 + f.fractions.len() * 2);
+    data.extend_from_slice(&f.data_start.to_be_bytes());
+    data.extend_from_slice(&f.data_length.to_be_bytes());
+    data.extend_from_slice(&count.to_be_bytes());
+    data.push(f.bits_per_point);
+    for &fraction in &f.fractions {
+        match f.bits_per_point {
+            8 => data.push(fraction.min(0xFF) as u8),
+            16 => data.extend_from_slice(&fraction.to_be_bytes()),
+            bits => return Err(MutagenError::ID3(format!("unsupported ASPI bits per index point {}", bits))),
+        }
+    }
+    Ok(data)
+}
+
 fn write_paired_text_frame(f: &PairedTextFrame, version: u8) -> Result<Vec<u8>> {
     let encoding = if version >= 4 {
         f.encoding
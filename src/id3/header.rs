@@ -104,6 +104,21 @@ impl ID3Header {
         })
     }
 
+    /// Parse the ID3v2 header at the start of `data`, or the one
+    /// `find_id3v2_header` finds after leading junk. `offset` is set to
+    /// where it was found.
+    pub fn locate(data: &[u8]) -> Result<Self> {
+        match find_id3v2_header(data) {
+            Some(offset) => Self::parse(&data[offset as usize..], offset),
+            None => Err(MutagenError::ID3NoHeader),
+        }
+    }
+
+    /// Offset of the first byte after the 10-byte header.
+    pub fn data_offset(&self) -> usize {
+        self.offset as usize + 10
+    }
+
     /// Full tag size including 10-byte header (and optional 10-byte footer).
     pub fn full_size(&self) -> u32 {
         let mut s = self.size + 10;
@@ -114,66 +129,201 @@ impl ID3Header {
     }
 }
 
-/// Determine BPI (Bytes Per Integer) for frame sizes in ID3v2.4.
-/// Some encoders (notably iTunes) incorrectly use normal integers instead of syncsafe.
-/// This function heuristically determines which encoding is used.
-pub fn determine_bpi(data: &[u8], frames_end: usize) -> u8 {
-    // Try both interpretations and see which one gives valid frame boundaries
-    let mut pos_syncsafe = 0usize;
-    let mut pos_normal = 0usize;
-    let mut syncsafe_valid = 0u32;
-    let mut normal_valid = 0u32;
+/// Parsed ID3v2 extended header.
+///
+/// `size` is the value of the on-disk size field: for v2.3 it excludes the
+/// 4-byte size field itself, for v2.4 it covers the whole extended header.
+/// `padding` is only carried by v2.3 (v2.4 has no padding size field).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ID3ExtHeader {
+    pub size: u32,
+    pub flags: u16,
+    pub padding: u32,
+    pub crc: Option<u32>,
+}
 
-    let end = frames_end.min(data.len());
+impl ID3ExtHeader {
+    /// v2.3 flag: CRC data present.
+    pub const V23_CRC: u16 = 0x8000;
+    /// v2.4 flag: CRC data present.
+    pub const V24_CRC: u16 = 0x20;
 
-    while pos_syncsafe + 10 <= end && pos_syncsafe < end.saturating_sub(10) {
-        if data[pos_syncsafe] == 0 {
-            break;
+    /// Parse an extended header from the start of the (de-unsynchronised) tag data.
+    pub fn parse(data: &[u8], version: u8) -> Result<Self> {
+        if version == 4 {
+            Self::parse_v24(data)
+        } else {
+            Self::parse_v23(data)
         }
-        // Check if frame ID is valid (uppercase ASCII or digits)
-        let id = &data[pos_syncsafe..pos_syncsafe + 4];
-        if !id.iter().all(|&b| b.is_ascii_uppercase() || b.is_ascii_digit()) {
-            break;
+    }
+
+    /// Parse a v2.3 extended header: size, 2 flag bytes, padding size, optional CRC.
+    pub fn parse_v23(data: &[u8]) -> Result<Self> {
+        if data.len() < 10 {
+            return Err(MutagenError::ID3("extended header too short".into()));
         }
-        let size = BitPaddedInt::syncsafe(&data[pos_syncsafe + 4..pos_syncsafe + 8]) as usize;
-        if size == 0 || pos_syncsafe + 10 + size > end {
-            break;
+        let size = BitPaddedInt::normal(&data[0..4]);
+        let flags = u16::from_be_bytes([data[4], data[5]]);
+        let padding = BitPaddedInt::normal(&data[6..10]);
+        let crc = if flags & Self::V23_CRC != 0 {
+            if size < 10 || data.len() < 14 {
+                return Err(MutagenError::ID3("extended header CRC truncated".into()));
+            }
+            Some(BitPaddedInt::normal(&data[10..14]))
+        } else {
+            None
+        };
+        Ok(ID3ExtHeader { size, flags, padding, crc })
+    }
+
+    /// Parse a v2.4 extended header: syncsafe size, flag byte count, flags, flag data.
+    pub fn parse_v24(data: &[u8]) -> Result<Self> {
+        if data.len() < 6 {
+            return Err(MutagenError::ID3("extended header too short".into()));
+        }
+        let size = BitPaddedInt::syncsafe(&data[0..4]);
+        let flags = data[5] as u16;
+        let end = (size as usize).min(data.len());
+
+        // Flag data follows in flag-bit order: update (0x40), CRC (0x20), restrictions (0x10).
+        let mut pos = 6usize;
+        if flags & 0x40 != 0 {
+            pos += 1;
+        }
+        let mut crc = None;
+        if flags & Self::V24_CRC != 0 {
+            if pos + 6 > end || data[pos] != 5 {
+                return Err(MutagenError::ID3("extended header CRC truncated".into()));
+            }
+            // 35-bit CRC stored as a 5-byte syncsafe integer
+            crc = Some(BitPaddedInt::decode(&data[pos + 1..pos + 6], 7));
+        }
+        Ok(ID3ExtHeader { size, flags, padding: 0, crc })
+    }
+
+    /// Number of bytes the extended header occupies in the tag data.
+    pub fn byte_len(&self, version: u8) -> usize {
+        if version == 4 {
+            self.size as usize
+        } else {
+            self.size as usize + 4
         }
-        syncsafe_valid += 1;
-        pos_syncsafe += 10 + size;
     }
 
-    while pos_normal + 10 <= end && pos_normal < end.saturating_sub(10) {
-        if data[pos_normal] == 0 {
+    /// Serialize the extended header for the given version.
+    pub fn render(&self, version: u8) -> Vec<u8> {
+        if version == 4 {
+            let mut out = Vec::with_capacity(12);
+            let has_crc = self.crc.is_some();
+            let size = if has_crc { 12 } else { 6 };
+            out.extend_from_slice(&BitPaddedInt::encode(size, 4, 7));
+            out.push(1);
+            out.push(if has_crc { Self::V24_CRC as u8 } else { 0 });
+            if let Some(crc) = self.crc {
+                out.push(5);
+                out.extend_from_slice(&BitPaddedInt::encode(crc, 5, 7));
+            }
+            out
+        } else {
+            let mut out = Vec::with_capacity(14);
+            let has_crc = self.crc.is_some();
+            out.extend_from_slice(&(if has_crc { 10u32 } else { 6 }).to_be_bytes());
+            out.extend_from_slice(&(if has_crc { Self::V23_CRC } else { 0 }).to_be_bytes());
+            out.extend_from_slice(&self.padding.to_be_bytes());
+            if let Some(crc) = self.crc {
+                out.extend_from_slice(&crc.to_be_bytes());
+            }
+            out
+        }
+    }
+}
+
+/// CRC-32 (ISO 3309) as used by the ID3v2 extended header.
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = flate2::Crc::new();
+    crc.update(data);
+    crc.sum()
+}
+
+/// Number of consecutive well-formed frame headers at the start of `data`
+/// when frame sizes are read as syncsafe (7 bits per byte) or normal integers.
+fn count_valid_frames(data: &[u8], frames_end: usize, syncsafe: bool) -> u32 {
+    let end = frames_end.min(data.len());
+    let mut pos = 0usize;
+    let mut valid = 0u32;
+
+    while pos + 10 <= end && pos < end.saturating_sub(10) {
+        if data[pos] == 0 {
             break;
         }
-        let id = &data[pos_normal..pos_normal + 4];
+        // Check if frame ID is valid (uppercase ASCII or digits)
+        let id = &data[pos..pos + 4];
         if !id.iter().all(|&b| b.is_ascii_uppercase() || b.is_ascii_digit()) {
             break;
         }
-        let size = BitPaddedInt::normal(&data[pos_normal + 4..pos_normal + 8]) as usize;
-        if size == 0 || pos_normal + 10 + size > end {
+        let size_bytes = &data[pos + 4..pos + 8];
+        let size = if syncsafe {
+            BitPaddedInt::syncsafe(size_bytes)
+        } else {
+            BitPaddedInt::normal(size_bytes)
+        } as usize;
+        if size == 0 || pos + 10 + size > end {
             break;
         }
-        normal_valid += 1;
-        pos_normal += 10 + size;
+        valid += 1;
+        pos += 10 + size;
+    }
+    valid
+}
+
+/// Determine BPI (Bytes Per Integer) for frame sizes in ID3v2.4.
+/// Some encoders (notably iTunes) incorrectly use normal integers instead of syncsafe.
+/// This function heuristically determines which encoding is used.
+pub fn determine_bpi(data: &[u8], frames_end: usize) -> u8 {
+    // Try both interpretations and see which one gives valid frame boundaries.
+    // If syncsafe parsed at least as many frames, use syncsafe (7)
+    if count_valid_frames(data, frames_end, true) >= count_valid_frames(data, frames_end, false) {
+        7
+    } else {
+        8
     }
+}
 
-    // If syncsafe parsed more frames successfully, use syncsafe (7)
-    // Otherwise use normal (8)
-    if syncsafe_valid >= normal_valid {
+/// Determine BPI for frame sizes in ID3v2.3, the inverse of `determine_bpi`:
+/// sizes are normal integers (8) unless reading them as syncsafe, as some
+/// iTunes versions wrote them, yields strictly more valid frames.
+pub fn determine_bpi_v23(data: &[u8], frames_end: usize) -> u8 {
+    if count_valid_frames(data, frames_end, true) > count_valid_frames(data, frames_end, false) {
         7
     } else {
         8
     }
 }
 
-/// Search for an ID3v2 tag in the file data.
+/// How far into a file `find_id3v2_header` looks for a tag preceded by junk.
+pub const ID3V2_SEARCH_LIMIT: usize = 128 * 1024;
+
+/// Search for an ID3v2 tag in the file data: at the start, or after leading
+/// junk within the first `ID3V2_SEARCH_LIMIT` bytes. Past offset 0 only a
+/// plausible header (supported version, syncsafe size) counts, so stray
+/// `ID3` bytes are not taken for a tag, and only if no MPEG frame sync
+/// comes before it: a tag after the audio (as in short files with a
+/// trailing ID3v2 tag) is not a tag behind junk.
 /// Returns the offset where the tag starts, or None.
 pub fn find_id3v2_header(data: &[u8]) -> Option<u64> {
     if data.len() >= 10 && &data[0..3] == b"ID3" {
-        Some(0)
-    } else {
-        None
+        return Some(0);
+    }
+    let limit = data.len().min(ID3V2_SEARCH_LIMIT + 2);
+    let pos = memchr::memmem::find_iter(&data[..limit], b"ID3").find(|&pos| {
+        data.get(pos..pos + 10).is_some_and(|h| {
+            (2..=4).contains(&h[3])
+                && h[4] != 0xFF
+                && h[6..10].iter().all(|b| b & 0x80 == 0)
+        })
+    })?;
+    match crate::mp3::header::find_sync(data, 0) {
+        Some((sync, _)) if sync < pos => None,
+        _ => Some(pos as u64),
     }
 }
@@ -1,6 +1,6 @@
 use crate::common::error::Result;
 use crate::id3::frames::{Frame, TextFrame};
-use crate::id3::specs::{self, Encoding, GENRES};
+use crate::id3::specs::{self, Encoding};
 
 /// Check if file data ends with an ID3v1 tag.
 /// Returns the offset of the TAG if found.
@@ -105,12 +105,11 @@ pub fn parse_id3v1(data: &[u8]) -> Result<Vec<Frame>> {
     }
 
     // Genre: byte 127
-    let genre_id = tag_data[127] as usize;
-    if genre_id < GENRES.len() {
+    if let Some(genre) = specs::genre_name(tag_data[127] as usize) {
         frames.push(Frame::Text(TextFrame {
             id: "TCON".to_string(),
             encoding: Encoding::Latin1,
-            text: vec![GENRES[genre_id].to_string()],
+            text: vec![genre.to_string()],
         }));
     }
 
@@ -131,6 +130,7 @@ pub fn make_id3v1(frames: &[Frame]) -> Vec<u8> {
     tag[0] = b'T';
     tag[1] = b'A';
     tag[2] = b'G';
+    tag[127] = 255; // No genre unless TCON names a known one
 
     for frame in frames {
         match frame {
@@ -149,14 +149,7 @@ pub fn make_id3v1(frames: &[Frame]) -> Vec<u8> {
                     }
                     "TCON" => {
                         let genres = specs::parse_genre(text);
-                        if let Some(genre_name) = genres.first() {
-                            if let Some(idx) = GENRES.iter().position(|&g| g == genre_name.as_str())
-                            {
-                                tag[127] = idx as u8;
-                            } else {
-                                tag[127] = 255; // Unknown
-                            }
-                        }
+                        tag[127] = genres.first().and_then(|g| specs::genre_index(g)).unwrap_or(255);
                     }
                     _ => {}
                 }
@@ -0,0 +1,55 @@
+//! iTunes data kept in COMM frames as space-separated hex fields:
+//! `iTunSMPB` (gapless playback) and `iTunNORM` (Sound Check).
+
+/// Gapless playback info from an `iTunSMPB` comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GaplessInfo {
+    /// Priming samples the encoder added before the audio.
+    pub encoder_delay: u32,
+    /// Samples of padding after the audio.
+    pub padding: u32,
+    /// Sample count of the original, unpadded audio.
+    pub original_samples: u64,
+}
+
+impl GaplessInfo {
+    /// Parse `" 00000000 00000840 000001C4 0000000000A30D7C ..."`; the first
+    /// field is reserved, the rest after the sample count are ignored.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut fields = text.split_whitespace().skip(1);
+        Some(GaplessInfo {
+            encoder_delay: u32::from_str_radix(fields.next()?, 16).ok()?,
+            padding: u32::from_str_radix(fields.next()?, 16).ok()?,
+            original_samples: u64::from_str_radix(fields.next()?, 16).ok()?,
+        })
+    }
+}
+
+/// Sound Check values from an `iTunNORM` comment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Normalization {
+    /// The ten fields as stored: left/right adjustment in 1/1000 W, the same
+    /// in 1/2500 W, an unknown pair, left/right peaks, another unknown pair.
+    pub values: [u32; 10],
+}
+
+impl Normalization {
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut values = [0u32; 10];
+        let mut fields = text.split_whitespace();
+        for v in values.iter_mut() {
+            *v = u32::from_str_radix(fields.next()?, 16).ok()?;
+        }
+        Some(Normalization { values })
+    }
+
+    /// Left and right volume adjustment, in milliwatts (1/1000 W).
+    pub fn volume_adjustment(&self) -> (u32, u32) {
+        (self.values[0], self.values[1])
+    }
+
+    /// Left and right peak sample values.
+    pub fn peak(&self) -> (u32, u32) {
+        (self.values[6], self.values[7])
+    }
+}
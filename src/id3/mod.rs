@@ -5,11 +5,14 @@ pub mod frames;
 pub mod tags;
 pub mod id3v1;
 pub mod writer;
+pub mod easy;
+pub mod itunes;
+pub mod chapters;
 
 use std::fs::File;
 use std::io::{Read, Write, Seek, SeekFrom};
 use crate::common::error::{MutagenError, Result};
-use crate::id3::header::ID3Header;
+use crate::id3::header::{ID3Header, ID3V2_SEARCH_LIMIT};
 use crate::id3::tags::ID3Tags;
 
 /// Load ID3v2 tags from a file path using direct read (faster than mmap for small data).
@@ -26,12 +29,27 @@ pub fn load_id3(path: &str) -> Result<(ID3Tags, Option<ID3Header>)> {
         return load_id3_from_data(&data);
     }
 
-    match ID3Header::parse(&header_buf, 0) {
+    let header = match ID3Header::parse(&header_buf, 0) {
+        Err(MutagenError::ID3NoHeader) => {
+            // Look for a tag behind leading junk, then continue reading after its header
+            let mut head = header_buf.to_vec();
+            (&mut file).take(ID3V2_SEARCH_LIMIT as u64).read_to_end(&mut head)?;
+            let h = ID3Header::locate(&head);
+            if let Ok(ref h) = h {
+                file.seek(SeekFrom::Start(h.data_offset() as u64))?;
+            }
+            h
+        }
+        result => result,
+    };
+
+    match header {
         Ok(h) => {
             // Read just the tag data (not the entire file!)
+            // A corrupt size field may claim more than the file holds; take what's there
             let tag_size = h.size as usize;
-            let mut tag_data = vec![0u8; tag_size];
-            file.read_exact(&mut tag_data)?;
+            let mut tag_data = Vec::with_capacity(tag_size.min(1 << 20));
+            (&mut file).take(tag_size as u64).read_to_end(&mut tag_data)?;
 
             let mut tags = ID3Tags::new();
 
@@ -84,7 +102,7 @@ pub fn load_id3(path: &str) -> Result<(ID3Tags, Option<ID3Header>)> {
 pub fn load_id3_from_data(data: &[u8]) -> Result<(ID3Tags, Option<ID3Header>)> {
     let mut tags = ID3Tags::new();
 
-    let header = match ID3Header::parse(data, 0) {
+    let header = match ID3Header::locate(data) {
         Ok(h) => h,
         Err(MutagenError::ID3NoHeader) => {
             if let Some(_offset) = id3v1::find_id3v1(data) {
@@ -98,8 +116,8 @@ pub fn load_id3_from_data(data: &[u8]) -> Result<(ID3Tags, Option<ID3Header>)> {
         Err(e) => return Err(e),
     };
 
-    let tag_start = 10;
-    let tag_end = (10 + header.size as usize).min(data.len());
+    let tag_start = header.data_offset();
+    let tag_end = (tag_start + header.size as usize).min(data.len());
     let mut tag_data = data[tag_start..tag_end].to_vec();
 
     if header.flags.unsynchronisation && header.version.0 < 4 {
@@ -121,8 +139,226 @@ pub fn load_id3_from_data(data: &[u8]) -> Result<(ID3Tags, Option<ID3Header>)> {
     Ok((tags, Some(header)))
 }
 
+/// Load ID3v2 tags, failing if the extended header CRC doesn't match the frame data.
+pub fn load_id3_strict(path: &str) -> Result<(ID3Tags, Option<ID3Header>)> {
+    let (tags, header) = load_id3(path)?;
+    tags.check_crc()?;
+    Ok((tags, header))
+}
+
+/// Options for `save_id3_file`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SaveOptions {
+    /// CRC, frame order, padding and text encoding of the rendered tag.
+    pub render: writer::RenderOptions,
+    /// Re-read the tag after writing and fail with `MutagenError::Verify`
+    /// if it doesn't match (see `verify_id3`).
+    pub verify: bool,
+    /// Restore the file's modification time after writing.
+    pub preserve_mtime: bool,
+    /// Write over the existing tag when the new one fits in its space (see
+    /// `save_id3_in_place`); `render.padding` then only applies if the file
+    /// has to be rewritten.
+    pub padding_reuse: bool,
+}
+
+/// Save ID3v2 tags to a file as set by `options`. Returns whether the tag
+/// was written in place (only ever with `padding_reuse`).
+pub fn save_id3_file(path: &str, tags: &ID3Tags, v2_version: u8, options: SaveOptions) -> Result<bool> {
+    let in_place = crate::common::util::preserving_mtime(path, options.preserve_mtime, || {
+        if options.padding_reuse {
+            save_id3_in_place(path, tags, v2_version, options.render)
+        } else {
+            save_id3_with(path, tags, v2_version, options.render).map(|_| false)
+        }
+    })?;
+    if options.verify {
+        verify_id3(path, tags, v2_version, options.render)?;
+    }
+    Ok(in_place)
+}
+
 /// Save ID3v2 tags to a file.
 pub fn save_id3(path: &str, tags: &ID3Tags, v2_version: u8) -> Result<()> {
+    let new_tag = writer::render_tag(tags, v2_version)?;
+    write_tag(path, &new_tag)
+}
+
+/// Save ID3v2 tags to a file, emitting an extended header with a CRC.
+pub fn save_id3_with_crc(path: &str, tags: &ID3Tags, v2_version: u8) -> Result<()> {
+    let new_tag = writer::render_tag_with_crc(tags, v2_version)?;
+    write_tag(path, &new_tag)
+}
+
+/// Save ID3v2 tags to a file using explicit render options.
+pub fn save_id3_with(path: &str, tags: &ID3Tags, v2_version: u8, options: writer::RenderOptions) -> Result<()> {
+    let new_tag = writer::render_tag_with(tags, v2_version, options)?;
+    write_tag(path, &new_tag)
+}
+
+/// Save ID3v2 tags over the file's existing tag when they fit in its space
+/// (padding included): only the tag region is written and the audio is
+/// left untouched, with the leftover space as padding. Otherwise the file
+/// is rewritten as by `save_id3_with`. Returns whether the tag was written
+/// in place.
+pub fn save_id3_in_place(path: &str, tags: &ID3Tags, v2_version: u8, options: writer::RenderOptions) -> Result<bool> {
+    let mut file = std::fs::OpenOptions::new().read(true).write(true).open(path)?;
+    let mut head = Vec::new();
+    (&mut file).take(ID3V2_SEARCH_LIMIT as u64 + 10).read_to_end(&mut head)?;
+    let file_len = file.metadata()?.len();
+
+    match in_place_tag(&head, file_len, tags, v2_version, options)? {
+        Some((offset, new_tag)) => {
+            file.seek(SeekFrom::Start(offset))?;
+            file.write_all(&new_tag)?;
+            file.flush()?;
+            crate::common::cache::invalidate(path);
+            Ok(true)
+        }
+        None => {
+            drop(file);
+            write_tag(path, &writer::render_tag_with(tags, v2_version, options)?)?;
+            Ok(false)
+        }
+    }
+}
+
+/// The tag to write over the existing one at its offset, padded to fill
+/// its space exactly, or `None` if the frames don't fit (or there is no
+/// complete tag). `head` is the start of the file, `file_len` its length.
+fn in_place_tag(head: &[u8], file_len: u64, tags: &ID3Tags, v2_version: u8, options: writer::RenderOptions) -> Result<Option<(u64, Vec<u8>)>> {
+    let space = ID3Header::locate(head).ok()
+        .map(|h| (h.offset, h.full_size() as usize))
+        .filter(|&(offset, size)| offset + size as u64 <= file_len);
+    let Some((offset, size)) = space else { return Ok(None) };
+
+    let unpadded = writer::render_tag_with(tags, v2_version, writer::RenderOptions {
+        padding: writer::PaddingStrategy::None,
+        ..options
+    })?;
+    if unpadded.len() > size {
+        return Ok(None);
+    }
+    let new_tag = writer::render_tag_with(tags, v2_version, writer::RenderOptions {
+        padding: writer::PaddingStrategy::Fixed(size - unpadded.len()),
+        ..options
+    })?;
+    Ok(Some((offset, new_tag)))
+}
+
+/// Re-read the ID3v2 tag of `path` and check that its frames
+/// render the same as `tags` did when saved with `v2_version` and `options`.
+/// Any ID3v1 tag is ignored. Returns `MutagenError::Verify` on a mismatch.
+pub fn verify_id3(path: &str, tags: &ID3Tags, v2_version: u8, options: writer::RenderOptions) -> Result<()> {
+    let data = std::fs::read(path)?;
+    let header = ID3Header::locate(&data)
+        .map_err(|e| MutagenError::Verify(format!("written tag does not parse: {}", e)))?;
+    let tag_end = header.data_offset() + header.size as usize;
+    if tag_end > data.len() {
+        return Err(MutagenError::Verify("written tag is cut short".into()));
+    }
+    let mut tag_data = data[header.data_offset()..tag_end].to_vec();
+    if header.flags.unsynchronisation && header.version.0 < 4 {
+        tag_data = unsynch::decode(&tag_data)?;
+    }
+    let mut written = ID3Tags::new();
+    written.read_frames(&tag_data, &header)
+        .map_err(|e| MutagenError::Verify(format!("written frames do not parse: {}", e)))?;
+
+    let expected = tags.render_ordered(v2_version, options.preserve_order, options.text_encoding)?;
+    let actual = written.render_ordered(v2_version, true, options.text_encoding)?;
+    if expected != actual {
+        let (want, got) = (tags.keys(), written.keys());
+        let detail = match want.iter().find(|k| !got.contains(k)) {
+            Some(key) => format!("{} is missing", key),
+            None => "frame data differs".to_string(),
+        };
+        return Err(MutagenError::Verify(format!("re-read tag does not match: {}", detail)));
+    }
+    Ok(())
+}
+
+/// What saving a tag would do to a file, computed without modifying it.
+#[derive(Debug, Clone)]
+pub struct SavePlan {
+    /// The complete rendered tag, header and padding included.
+    pub new_tag: Vec<u8>,
+    /// Offset of the existing ID3v2 tag, i.e. how many leading junk bytes
+    /// are kept in front of the new one (0 if there is no tag).
+    pub tag_offset: usize,
+    /// Size of the ID3v2 tag currently in the file (0 if there is none).
+    pub old_tag_size: usize,
+    /// Offset of the first byte of audio data kept after the tag.
+    pub audio_start: usize,
+    /// Whether the audio data has to be moved, i.e. the new tag doesn't
+    /// exactly fill the space of the old one.
+    pub full_rewrite: bool,
+}
+
+impl SavePlan {
+    fn new(existing: &[u8], new_tag: Vec<u8>) -> Self {
+        let (tag_offset, old_tag_size, audio_start) = existing_tag_bounds(existing);
+        let full_rewrite = new_tag.len() != audio_start - tag_offset;
+        SavePlan { new_tag, tag_offset, old_tag_size, audio_start, full_rewrite }
+    }
+}
+
+/// Offset and size of the existing ID3v2 tag, and the offset where the
+/// audio after it starts.
+fn existing_tag_bounds(existing: &[u8]) -> (usize, usize, usize) {
+    match ID3Header::locate(existing) {
+        Ok(h) => {
+            let tag_offset = h.offset as usize;
+            let old_tag_size = h.full_size() as usize;
+            if tag_offset + old_tag_size <= existing.len() {
+                (tag_offset, old_tag_size, tag_offset + old_tag_size)
+            } else {
+                // Implausible size field: the audio starts at the first MPEG sync
+                // after the header, or there is no audio left to keep
+                let audio_start = crate::mp3::header::find_sync(existing, h.data_offset())
+                    .map(|(offset, _)| offset)
+                    .unwrap_or(existing.len());
+                (tag_offset, old_tag_size, audio_start)
+            }
+        }
+        Err(_) => (0, 0, 0),
+    }
+}
+
+/// Compute what `save_id3` would write, without touching the file.
+pub fn save_id3_preview(path: &str, tags: &ID3Tags, v2_version: u8) -> Result<SavePlan> {
+    save_id3_preview_with(path, tags, v2_version, writer::RenderOptions::default())
+}
+
+/// Compute what `save_id3_with` would write, without touching the file.
+pub fn save_id3_preview_with(path: &str, tags: &ID3Tags, v2_version: u8, options: writer::RenderOptions) -> Result<SavePlan> {
+    let new_tag = writer::render_tag_with(tags, v2_version, options)?;
+    let existing = std::fs::read(path)?;
+    Ok(SavePlan::new(&existing, new_tag))
+}
+
+/// Compute what `save_id3_file` would write, without touching the file.
+pub fn save_id3_file_preview(path: &str, tags: &ID3Tags, v2_version: u8, options: SaveOptions) -> Result<SavePlan> {
+    if options.padding_reuse {
+        save_id3_in_place_preview(path, tags, v2_version, options.render)
+    } else {
+        save_id3_preview_with(path, tags, v2_version, options.render)
+    }
+}
+
+/// Compute what `save_id3_in_place` would write, without touching the file.
+pub fn save_id3_in_place_preview(path: &str, tags: &ID3Tags, v2_version: u8, options: writer::RenderOptions) -> Result<SavePlan> {
+    let existing = std::fs::read(path)?;
+    let new_tag = match in_place_tag(&existing, existing.len() as u64, tags, v2_version, options)? {
+        Some((_, new_tag)) => new_tag,
+        None => writer::render_tag_with(tags, v2_version, options)?,
+    };
+    Ok(SavePlan::new(&existing, new_tag))
+}
+
+/// Replace the file's existing ID3v2 tag (if any) with `new_tag`, keeping
+/// any junk bytes in front of it.
+fn write_tag(path: &str, new_tag: &[u8]) -> Result<()> {
     let mut file = std::fs::OpenOptions::new()
         .read(true)
         .write(true)
@@ -131,27 +367,27 @@ pub fn save_id3(path: &str, tags: &ID3Tags, v2_version: u8) -> Result<()> {
     let mut existing = Vec::new();
     file.read_to_end(&mut existing)?;
 
-    let old_tag_size = match ID3Header::parse(&existing, 0) {
-        Ok(h) => h.full_size() as usize,
-        Err(_) => 0,
-    };
-
-    let new_tag = writer::render_tag(tags, v2_version)?;
-
-    let audio_start = old_tag_size;
+    let (tag_offset, _, audio_start) = existing_tag_bounds(&existing);
     let audio_data = &existing[audio_start..];
 
-    file.seek(SeekFrom::Start(0))?;
-    file.set_len(0)?;
-    file.write_all(&new_tag)?;
+    file.seek(SeekFrom::Start(tag_offset as u64))?;
+    file.set_len(tag_offset as u64)?;
+    file.write_all(new_tag)?;
     file.write_all(audio_data)?;
     file.flush()?;
+    crate::common::cache::invalidate(path);
 
     Ok(())
 }
 
-/// Delete ID3v2 tags from a file.
+/// Delete ID3 tags from a file: the ID3v2 tag at the start and any ID3v1 tag at the end.
 pub fn delete_id3(path: &str) -> Result<()> {
+    delete_id3v2(path)?;
+    delete_id3v1(path)
+}
+
+/// Delete only the ID3v2 tag, leaving a trailing ID3v1 tag intact.
+pub fn delete_id3v2(path: &str) -> Result<()> {
     let mut file = std::fs::OpenOptions::new()
         .read(true)
         .write(true)
@@ -160,17 +396,26 @@ pub fn delete_id3(path: &str) -> Result<()> {
     let mut existing = Vec::new();
     file.read_to_end(&mut existing)?;
 
-    let old_tag_size = match ID3Header::parse(&existing, 0) {
-        Ok(h) => h.full_size() as usize,
+    let (tag_offset, tag_end) = match ID3Header::locate(&existing) {
+        Ok(h) => (h.offset as usize, (h.offset as usize + h.full_size() as usize).min(existing.len())),
         Err(_) => return Ok(()),
     };
 
-    let audio_data = existing[old_tag_size..].to_vec();
-
-    file.seek(SeekFrom::Start(0))?;
-    file.set_len(0)?;
-    file.write_all(&audio_data)?;
+    file.seek(SeekFrom::Start(tag_offset as u64))?;
+    file.set_len(tag_offset as u64)?;
+    file.write_all(&existing[tag_end..])?;
     file.flush()?;
+    crate::common::cache::invalidate(path);
+
+    Ok(())
+}
+
+/// Delete only the trailing ID3v1 tag, leaving an ID3v2 tag intact.
+pub fn delete_id3v1(path: &str) -> Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)?;
 
     let file_len = file.metadata()?.len();
     if file_len >= 128 {
@@ -179,6 +424,7 @@ pub fn delete_id3(path: &str) -> Result<()> {
         file.read_exact(&mut tag_check)?;
         if &tag_check == b"TAG" {
             file.set_len(file_len - 128)?;
+            crate::common::cache::invalidate(path);
         }
     }
 
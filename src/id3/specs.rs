@@ -31,6 +31,11 @@ impl Encoding {
     }
 }
 
+/// Whether every character of `text` fits in Latin-1.
+pub fn is_latin1(text: &str) -> bool {
+    text.chars().all(|c| c as u32 <= 0xFF)
+}
+
 /// Decode text from bytes using the specified encoding.
 pub fn decode_text(data: &[u8], encoding: Encoding) -> Result<String> {
     match encoding {
@@ -43,29 +48,8 @@ pub fn decode_text(data: &[u8], encoding: Encoding) -> Result<String> {
                 Ok(data.iter().map(|&b| b as char).collect())
             }
         }
-        Encoding::Utf16 => {
-            if data.len() < 2 {
-                return Ok(String::new());
-            }
-            // Check BOM
-            let (decoder, start) = if data[0] == 0xFF && data[1] == 0xFE {
-                (encoding_rs::UTF_16LE, 2)
-            } else if data[0] == 0xFE && data[1] == 0xFF {
-                (encoding_rs::UTF_16BE, 2)
-            } else {
-                // Default to LE if no BOM
-                (encoding_rs::UTF_16LE, 0)
-            };
-            let (result, _, had_errors) = decoder.decode(&data[start..]);
-            if had_errors {
-                // Still return what we got - mutagen is lenient
-            }
-            Ok(result.into_owned())
-        }
-        Encoding::Utf16Be => {
-            let (result, _, _) = encoding_rs::UTF_16BE.decode(data);
-            Ok(result.into_owned())
-        }
+        Encoding::Utf16 => Ok(decode_utf16(data, encoding_rs::UTF_16LE)),
+        Encoding::Utf16Be => Ok(decode_utf16(data, encoding_rs::UTF_16BE)),
         Encoding::Utf8 => {
             // Try strict first, fall back to lossy
             match std::str::from_utf8(data) {
@@ -76,6 +60,41 @@ pub fn decode_text(data: &[u8], encoding: Encoding) -> Result<String> {
     }
 }
 
+/// Decode UTF-16 in the byte order its BOM names, or `default` without one.
+/// A BOM wins over the declared encoding (some taggers prefix `FE FF` or
+/// even `FF FE` to Utf16Be text), a lone BOM yields an empty string, and an
+/// odd trailing byte is dropped rather than decoded as U+FFFD.
+fn decode_utf16(data: &[u8], default: &'static encoding_rs::Encoding) -> String {
+    let data = &data[..data.len() & !1];
+    let (decoder, start) = match data {
+        [0xFF, 0xFE, ..] => (encoding_rs::UTF_16LE, 2),
+        [0xFE, 0xFF, ..] => (encoding_rs::UTF_16BE, 2),
+        _ => (default, 0),
+    };
+    // Unpaired surrogates become U+FFFD - mutagen is lenient
+    let (result, _) = decoder.decode_without_bom_handling(&data[start..]);
+    result.into_owned()
+}
+
+/// Trim trailing null padding from encoded text, respecting the code unit
+/// size: whole zero bytes for Latin-1/UTF-8, aligned zero pairs for UTF-16
+/// (a dangling odd byte is dropped too, since it can't form a code unit).
+pub fn trim_trailing_nulls(data: &[u8], encoding: Encoding) -> &[u8] {
+    match encoding {
+        Encoding::Latin1 | Encoding::Utf8 => {
+            let end = data.iter().rposition(|&b| b != 0).map_or(0, |i| i + 1);
+            &data[..end]
+        }
+        Encoding::Utf16 | Encoding::Utf16Be => {
+            let mut end = data.len() & !1;
+            while end >= 2 && data[end - 2] == 0 && data[end - 1] == 0 {
+                end -= 2;
+            }
+            &data[..end]
+        }
+    }
+}
+
 /// Encode text to bytes using the specified encoding.
 pub fn encode_text(text: &str, encoding: Encoding) -> Vec<u8> {
     match encoding {
@@ -256,6 +275,76 @@ pub const GENRES: &[&str] = &[
     "G-Funk", "Dubstep", "Garage Rock", "Psybient",
 ];
 
+/// Genre name for a 0-based ID3v1 genre byte, the same index ID3v2 `(n)`
+/// references use. 255 and other out-of-range bytes mean no genre.
+pub fn genre_name(index: usize) -> Option<&'static str> {
+    GENRES.get(index).copied()
+}
+
+/// The 0-based ID3v1 genre byte for a genre name.
+pub fn genre_index(name: &str) -> Option<u8> {
+    GENRES.iter().position(|&g| g == name).and_then(|i| u8::try_from(i).ok())
+}
+
+/// Genre name for an MP4 `gnre` value, which is the ID3v1 index plus one.
+pub fn mp4_genre_name(gnre: u16) -> Option<&'static str> {
+    genre_name((gnre as usize).checked_sub(1)?)
+}
+
+/// Parse a TRCK/TPOS style `"n/m"` value into `(number, total)`.
+/// Either part may be missing or non-numeric, e.g. `"3"` yields `(Some(3), None)`.
+pub fn parse_number_pair(text: &str) -> (Option<u32>, Option<u32>) {
+    let mut parts = text.trim().splitn(2, '/');
+    let number = parts.next().and_then(|n| n.trim().parse().ok());
+    let total = parts.next().and_then(|t| t.trim().parse().ok());
+    (number, total)
+}
+
+/// An ID3v2.4 timestamp, `yyyy[-MM[-dd[THH[:mm[:ss]]]]]`, as stored in
+/// TDRC, TDEN, TDOR and TDRL. Fields after the first missing one are `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ID3TimeStamp {
+    pub year: u16,
+    pub month: Option<u8>,
+    pub day: Option<u8>,
+    pub hour: Option<u8>,
+    pub minute: Option<u8>,
+    pub second: Option<u8>,
+}
+
+impl ID3TimeStamp {
+    /// Parse a timestamp, stopping at the first field that doesn't fit the
+    /// format. A space is accepted in place of the `T` separator.
+    pub fn parse(text: &str) -> Option<Self> {
+        let digits = |s: &str| s.len() == 2 && s.bytes().all(|b| b.is_ascii_digit());
+        let text = text.trim();
+        let year = text.get(0..4).filter(|y| y.bytes().all(|b| b.is_ascii_digit()))?.parse().ok()?;
+
+        let mut fields = [None; 5];
+        let mut rest = &text[4..];
+        for (field, seps) in fields.iter_mut().zip(["-", "-", "T ", ":", ":"]) {
+            let Some(sep) = rest.chars().next().filter(|c| seps.contains(*c)) else { break };
+            let Some(value) = rest.get(sep.len_utf8()..sep.len_utf8() + 2).filter(|v| digits(v)) else { break };
+            *field = value.parse().ok();
+            rest = &rest[sep.len_utf8() + 2..];
+        }
+        let [month, day, hour, minute, second] = fields;
+        Some(ID3TimeStamp { year, month, day, hour, minute, second })
+    }
+}
+
+impl std::fmt::Display for ID3TimeStamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.year)?;
+        let parts = [("-", self.month), ("-", self.day), ("T", self.hour), (":", self.minute), (":", self.second)];
+        for (sep, value) in parts {
+            let Some(value) = value else { break };
+            write!(f, "{}{:02}", sep, value)?;
+        }
+        Ok(())
+    }
+}
+
 /// Parse TCON (content type / genre) value.
 /// Handles formats like: "Rock", "(17)", "(17)Rock", "17", "(RX)", "(CR)"
 pub fn parse_genre(text: &str) -> Vec<String> {
@@ -281,8 +370,8 @@ pub fn parse_genre(text: &str) -> Vec<String> {
                 } else if inner == "CR" {
                     genres.push("Cover".to_string());
                 } else if let Ok(num) = inner.parse::<usize>() {
-                    if num < GENRES.len() {
-                        genres.push(GENRES[num].to_string());
+                    if let Some(name) = genre_name(num) {
+                        genres.push(name.to_string());
                     } else {
                         genres.push(format!("Unknown({})", num));
                     }
@@ -297,8 +386,8 @@ pub fn parse_genre(text: &str) -> Vec<String> {
         } else {
             // No parens - try numeric, otherwise take as text
             if let Ok(num) = remaining.parse::<usize>() {
-                if num < GENRES.len() {
-                    genres.push(GENRES[num].to_string());
+                if let Some(name) = genre_name(num) {
+                    genres.push(name.to_string());
                 } else {
                     genres.push(remaining.to_string());
                 }
@@ -308,8 +397,8 @@ pub fn parse_genre(text: &str) -> Vec<String> {
                     let part = part.trim();
                     if !part.is_empty() {
                         if let Ok(num) = part.parse::<usize>() {
-                            if num < GENRES.len() {
-                                genres.push(GENRES[num].to_string());
+                            if let Some(name) = genre_name(num) {
+                                genres.push(name.to_string());
                             } else {
                                 genres.push(part.to_string());
                             }
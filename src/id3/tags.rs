@@ -1,6 +1,10 @@
+use std::borrow::Cow;
 use crate::common::error::{MutagenError, Result};
-use crate::id3::header::{ID3Header, BitPaddedInt, determine_bpi};
-use crate::id3::frames::{self, Frame, HashKey, convert_v22_frame_id, parse_v22_picture_frame};
+use crate::common::options::ParseOptions;
+use crate::id3::header::{ID3Header, ID3ExtHeader, BitPaddedInt, crc32, determine_bpi, determine_bpi_v23};
+use crate::id3::frames::{self, Frame, HashKey, TextEncodingPolicy, convert_v22_frame_id, parse_v22_picture_frame};
+use crate::id3::chapters;
+use crate::id3::itunes::{GaplessInfo, Normalization};
 use crate::id3::specs;
 use crate::id3::unsynch;
 
@@ -92,6 +96,11 @@ pub struct ID3Tags {
     pub frames: Vec<(HashKey, Vec<LazyFrame>)>,
     pub version: (u8, u8),
     pub unknown_frames: Vec<(String, Vec<u8>)>,
+    /// Extended header of the tag this container was read from, if any.
+    pub ext_header: Option<ID3ExtHeader>,
+    /// Group identifier bytes of frames read with the grouping flag, by
+    /// hash key; `render` writes those frames grouped again.
+    pub groups: Vec<(HashKey, u8)>,
     pub(crate) raw_buf: Vec<u8>,
 }
 
@@ -101,6 +110,8 @@ impl ID3Tags {
             frames: Vec::with_capacity(16),
             version: (4, 0),
             unknown_frames: Vec::new(),
+            ext_header: None,
+            groups: Vec::new(),
             raw_buf: Vec::new(),
         }
     }
@@ -126,31 +137,83 @@ impl ID3Tags {
         }
     }
 
-    /// Get all frames with the given key (forces decode).
-    pub fn getall(&self, key: &str) -> Vec<&Frame> {
+    /// Slots matching `key`: the exact key if present, otherwise every
+    /// `key:...` slot, so `getall("WOAR")` returns all artist webpages.
+    fn slot_indices(&self, key: &str) -> Vec<usize> {
         let hash_key = HashKey::new(key);
-        match self.frames.iter().find(|(k, _)| k == &hash_key) {
-            Some((_, frames)) => {
-                frames.iter().filter_map(|lf| lf.get_decoded()).collect()
-            }
-            None => vec![],
+        if let Some(i) = self.frames.iter().position(|(k, _)| k == &hash_key) {
+            return vec![i];
         }
+        let prefix = format!("{}:", key);
+        (0..self.frames.len())
+            .filter(|&i| self.frames[i].0.as_str().starts_with(&prefix))
+            .collect()
+    }
+
+    /// Get all frames with the given key (forces decode).
+    pub fn getall(&self, key: &str) -> Vec<&Frame> {
+        self.slot_indices(key)
+            .into_iter()
+            .flat_map(|i| self.frames[i].1.iter().filter_map(|lf| lf.get_decoded()))
+            .collect()
     }
 
     /// Get all frames with given key, decoding if needed (mutable version).
     pub fn getall_mut(&mut self, key: &str) -> Vec<&Frame> {
-        let hash_key = HashKey::new(key);
-        if let Some((_, frames)) = self.frames.iter_mut().find(|(k, _)| k == &hash_key) {
-            for lf in frames.iter_mut() {
+        for i in self.slot_indices(key) {
+            for lf in self.frames[i].1.iter_mut() {
                 let _ = lf.decode_with_buf(&self.raw_buf);
             }
         }
         self.getall(key)
     }
 
+    /// Slots whose hash key names the frame ID `id`, whatever its description
+    /// or language parts.
+    fn id_slot_indices(&self, id: &str) -> Vec<usize> {
+        (0..self.frames.len())
+            .filter(|&i| self.frames[i].0.as_str().split(':').next() == Some(id))
+            .collect()
+    }
+
+    /// Get every frame with the given frame ID across all hash keys, so
+    /// `getall_by_id("COMM")` returns each comment (forces decode).
+    pub fn getall_by_id(&self, frame_id: &str) -> Vec<&Frame> {
+        self.id_slot_indices(frame_id)
+            .into_iter()
+            .flat_map(|i| self.frames[i].1.iter().filter_map(|lf| lf.get_decoded()))
+            .collect()
+    }
+
+    /// Get every frame with the given frame ID, decoding if needed.
+    pub fn getall_by_id_mut(&mut self, frame_id: &str) -> Vec<&Frame> {
+        for i in self.id_slot_indices(frame_id) {
+            for lf in self.frames[i].1.iter_mut() {
+                let _ = lf.decode_with_buf(&self.raw_buf);
+            }
+        }
+        self.getall_by_id(frame_id)
+    }
+
+    /// `CHAP` frames as `(start_seconds, title)` pairs, in start order.
+    pub fn chapters(&self) -> Vec<(f64, String)> {
+        let mut chapters: Vec<(f64, String)> = self.id_slot_indices("CHAP")
+            .into_iter()
+            .flat_map(|i| self.frames[i].1.iter())
+            .filter_map(|lf| match self.decode_lazy(lf)?.as_ref() {
+                Frame::Binary(b) => chapters::parse_chap(&b.data, self.version.0),
+                _ => None,
+            })
+            .collect();
+        chapters.sort_by(|a, b| a.0.total_cmp(&b.0));
+        chapters
+    }
+
     /// Get the first frame with the given key (forces decode).
     pub fn get(&self, key: &str) -> Option<&Frame> {
-        self.getall(key).into_iter().next()
+        let hash_key = HashKey::new(key);
+        let (_, frames) = self.frames.iter().find(|(k, _)| k == &hash_key)?;
+        frames.iter().find_map(|lf| lf.get_decoded())
     }
 
     /// Get first frame, decoding if needed.
@@ -164,21 +227,243 @@ impl ID3Tags {
         self.get(key)
     }
 
-    /// Set all frames for a given key (replaces existing).
-    pub fn setall(&mut self, key: &str, frames_list: Vec<Frame>) {
+    /// Decode the first frame for `key` without mutating the container.
+    /// Lazy frames are parsed into an owned copy; decoded frames are borrowed.
+    pub fn decode_first(&self, key: &str) -> Option<Cow<'_, Frame>> {
         let hash_key = HashKey::new(key);
-        let new_frames: Vec<LazyFrame> = frames_list.into_iter().map(LazyFrame::Decoded).collect();
-        if let Some((_, frames)) = self.frames.iter_mut().find(|(k, _)| k == &hash_key) {
-            *frames = new_frames;
-        } else {
-            self.frames.push((hash_key, new_frames));
+        let (_, frames) = self.frames.iter().find(|(k, _)| k == &hash_key)?;
+        self.decode_lazy(frames.first()?)
+    }
+
+    /// Validate every frame, collecting non-fatal warnings.
+    /// Frames that fail to decode at all are reported as warnings too.
+    pub fn validate(&self) -> Vec<frames::ValidationWarning> {
+        let mut warnings = Vec::new();
+        for (key, frames_list) in &self.frames {
+            for lf in frames_list {
+                match self.decode_lazy(lf) {
+                    Some(frame) => warnings.extend(frame.validate()),
+                    None => warnings.push(frames::ValidationWarning {
+                        key: key.as_str().to_string(),
+                        message: "frame could not be decoded".into(),
+                    }),
+                }
+            }
         }
+        warnings
     }
 
-    /// Delete all frames with the given key.
+    fn decode_lazy<'a>(&'a self, lf: &'a LazyFrame) -> Option<Cow<'a, Frame>> {
+        match lf {
+            LazyFrame::Decoded(f) => Some(Cow::Borrowed(f)),
+            LazyFrame::Raw { id, data } => frames::parse_frame(id, data).ok().map(Cow::Owned),
+            LazyFrame::Slice { id, offset, len } => {
+                let id_str = std::str::from_utf8(&id[..]).ok()?;
+                let data = self.raw_buf.get(*offset as usize..(*offset + *len) as usize)?;
+                frames::parse_frame(id_str, data).ok().map(Cow::Owned)
+            }
+        }
+    }
+
+    /// Track number and total from TRCK (`"3/12"` -> `(Some(3), Some(12))`).
+    pub fn track_number(&self) -> (Option<u32>, Option<u32>) {
+        self.number_pair("TRCK")
+    }
+
+    /// Disc number and total from TPOS.
+    pub fn disc_number(&self) -> (Option<u32>, Option<u32>) {
+        self.number_pair("TPOS")
+    }
+
+    /// Values of a text frame, split according to `policy`. Empty if the
+    /// frame is missing or not a text frame.
+    pub fn text_values(&self, key: &str, policy: frames::SeparatorPolicy) -> Vec<String> {
+        match self.decode_first(key).as_deref() {
+            Some(Frame::Text(f)) => f.values(policy),
+            _ => Vec::new(),
+        }
+    }
+
+    fn number_pair(&self, key: &str) -> (Option<u32>, Option<u32>) {
+        match self.decode_first(key).as_deref() {
+            Some(Frame::Text(f)) => f.text.first()
+                .map(|t| specs::parse_number_pair(t))
+                .unwrap_or((None, None)),
+            _ => (None, None),
+        }
+    }
+
+    /// Timestamp held by one of the `TIMESTAMP_FRAMES` (TDRC, TDEN, TDOR or
+    /// TDRL). `None` for other IDs or if the frame is missing or unparsable.
+    pub fn timestamp(&self, id: &str) -> Option<specs::ID3TimeStamp> {
+        if !TIMESTAMP_FRAMES.contains(&id) {
+            return None;
+        }
+        match self.decode_first(id)?.as_ref() {
+            Frame::Text(f) => specs::ID3TimeStamp::parse(f.text.first()?),
+            _ => None,
+        }
+    }
+
+    /// Convert ID3v2.4-only timestamp frames before writing ID3v2.3: TDOR
+    /// becomes a TORY year (unless TORY is already set), while TDEN and
+    /// TDRL, which have no v2.3 counterpart, are dropped with a warning.
+    pub fn update_to_v23(&mut self) -> Vec<frames::ValidationWarning> {
+        let mut warnings = Vec::new();
+        if let Some(year) = self.timestamp("TDOR").map(|ts| ts.year) {
+            if !self.contains_key(&HashKey::new("TORY")) {
+                self.add(Frame::Text(frames::TextFrame {
+                    id: "TORY".to_string(),
+                    encoding: specs::Encoding::Latin1,
+                    text: vec![format!("{:04}", year)],
+                }));
+            }
+        }
+        self.delall("TDOR");
+        for id in ["TDEN", "TDRL"] {
+            if self.contains_key(&HashKey::new(id)) {
+                self.delall(id);
+                warnings.push(frames::ValidationWarning {
+                    key: id.to_string(),
+                    message: "no ID3v2.3 equivalent, frame dropped".to_string(),
+                });
+            }
+        }
+        warnings
+    }
+
+    /// Set all frames for a given key, replacing every slot `delall` would
+    /// remove. Frames are stored under their own hash keys, at the position
+    /// of the first replaced slot.
+    pub fn setall(&mut self, key: &str, frames_list: Vec<Frame>) {
+        let at = self.slot_indices(key).first().copied().unwrap_or(self.frames.len());
+        self.delall(key);
+        let mut slots: Vec<(HashKey, Vec<LazyFrame>)> = Vec::new();
+        for frame in frames_list {
+            let hash_key = frame.hash_key();
+            match slots.iter_mut().find(|(k, _)| k == &hash_key) {
+                Some((_, frames)) => frames.push(LazyFrame::Decoded(frame)),
+                None => slots.push((hash_key, vec![LazyFrame::Decoded(frame)])),
+            }
+        }
+        // Drop any other slot already holding one of the new keys
+        self.frames.retain(|(k, _)| !slots.iter().any(|(new, _)| new == k));
+        let at = at.min(self.frames.len());
+        self.frames.splice(at..at, slots);
+    }
+
+    /// Remove the frames `getall(key)` would return and return them
+    /// decoded. Frames that fail to decode are dropped.
+    pub fn pop(&mut self, key: &str) -> Vec<Frame> {
+        let indices = self.slot_indices(key);
+        let mut removed = Vec::new();
+        for &i in indices.iter().rev() {
+            removed.push(self.frames.remove(i).1);
+        }
+        removed
+            .into_iter()
+            .rev()
+            .flatten()
+            .filter_map(|mut lf| {
+                lf.decode_with_buf(&self.raw_buf).ok()?;
+                match lf {
+                    LazyFrame::Decoded(frame) => Some(frame),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Delete all frames with the given key, or every `key:...` slot when
+    /// there is no exact match (so `delall("WOAR")` removes all of them).
     pub fn delall(&mut self, key: &str) {
-        let hash_key = HashKey::new(key);
-        self.frames.retain(|(k, _)| k != &hash_key);
+        for i in self.slot_indices(key).into_iter().rev() {
+            self.frames.remove(i);
+        }
+    }
+
+    /// Re-key the frames stored under `old` to `new` in place, keeping their
+    /// position. Works for `TXXX:desc`, `APIC:desc[:type]`, `COMM:desc:lang`
+    /// and `USLT:desc:lang`; the frame ID and picture type cannot change and
+    /// `new` must be free.
+    pub fn rename(&mut self, old: &str, new: &str) -> Result<()> {
+        let old_key = HashKey::new(old);
+        let new_key = HashKey::new(new);
+        let idx = self.frames.iter().position(|(k, _)| k == &old_key)
+            .ok_or_else(|| MutagenError::ValueError(format!("no frame for key {}", old)))?;
+        if old_key == new_key {
+            return Ok(());
+        }
+        if self.contains_key(&new_key) {
+            return Err(MutagenError::ValueError(format!("key {} already exists", new)));
+        }
+
+        let (id, rest) = new.split_once(':')
+            .ok_or_else(|| MutagenError::ValueError(format!("{} has no description", new)))?;
+        if old.split(':').next() != Some(id) {
+            return Err(MutagenError::ValueError(format!("cannot rename {} to another frame ID", old)));
+        }
+        let (desc, lang) = match id {
+            "TXXX" => (rest, None),
+            // The description depends on each picture's type, see below
+            "APIC" => (rest, None),
+            "COMM" | "USLT" => match rest.rsplit_once(':') {
+                Some((desc, lang)) if lang.len() == 3 && lang.is_ascii() => (desc, Some(lang)),
+                _ => return Err(MutagenError::ValueError(format!("{} needs a 3-letter language", new))),
+            },
+            _ => return Err(MutagenError::ValueError(format!("{} frames cannot be renamed", id))),
+        };
+
+        // Build the renamed frames first so a failure leaves the tags untouched
+        let mut renamed = Vec::with_capacity(self.frames[idx].1.len());
+        for lf in self.frames[idx].1.iter_mut() {
+            let mut frame = lf.decode_with_buf(&self.raw_buf)?.clone();
+            match (&mut frame, lang) {
+                (Frame::UserText(f), None) => f.desc = desc.to_string(),
+                (Frame::Picture(f), None) => {
+                    f.desc = frames::picture_key_desc(desc, f.pic_type as u8)
+                        .ok_or_else(|| MutagenError::ValueError(format!("{} does not match the frame's picture type", new)))?
+                        .to_string();
+                }
+                (Frame::Comment(f), Some(lang)) => {
+                    f.desc = desc.to_string();
+                    f.lang = lang.to_string();
+                }
+                (Frame::Lyrics(f), Some(lang)) => {
+                    f.desc = desc.to_string();
+                    f.lang = lang.to_string();
+                }
+                _ => return Err(MutagenError::ValueError(format!("{} frames cannot be renamed", id))),
+            }
+            // The picture type is part of an APIC key and cannot be renamed
+            if frame.hash_key() != new_key {
+                return Err(MutagenError::ValueError(format!("{} does not match the frame's picture type", new)));
+            }
+            renamed.push(LazyFrame::Decoded(frame));
+        }
+        self.frames[idx] = (new_key, renamed);
+        Ok(())
+    }
+
+    /// Text of the first COMM frame with the given description, any language.
+    fn comment_text(&self, desc: &str) -> Option<String> {
+        let prefix = format!("COMM:{}:", desc);
+        self.frames.iter().map(|(k, _)| k.as_str()).filter(|k| k.starts_with(&prefix)).find_map(|key| {
+            match self.decode_first(key)?.into_owned() {
+                Frame::Comment(c) => Some(c.text),
+                _ => None,
+            }
+        })
+    }
+
+    /// Encoder delay and padding from an iTunes `iTunSMPB` comment.
+    pub fn gapless_info(&self) -> Option<GaplessInfo> {
+        GaplessInfo::parse(&self.comment_text("iTunSMPB")?)
+    }
+
+    /// Sound Check values from an iTunes `iTunNORM` comment.
+    pub fn normalization(&self) -> Option<Normalization> {
+        Normalization::parse(&self.comment_text("iTunNORM")?)
     }
 
     /// Get all keys.
@@ -186,6 +471,66 @@ impl ID3Tags {
         self.frames.iter().map(|(k, _)| k.as_str().to_string()).collect()
     }
 
+    /// Frame IDs and bodies exactly as read, without decoding. Frames that were
+    /// set or decoded in place are re-serialized for the tag's version.
+    pub fn raw_frames(&self) -> Vec<(String, Vec<u8>)> {
+        let version = self.version.0.max(3);
+        let mut out = Vec::new();
+        for (_, frames) in &self.frames {
+            for lf in frames {
+                match lf {
+                    LazyFrame::Decoded(frame) => {
+                        if let Ok(data) = frame.write_data(version) {
+                            out.push((frame.frame_id().to_string(), data));
+                        }
+                    }
+                    LazyFrame::Raw { id, data } => out.push((id.clone(), data.clone())),
+                    LazyFrame::Slice { offset, len, .. } => {
+                        let start = *offset as usize;
+                        if let Some(data) = self.raw_buf.get(start..start + *len as usize) {
+                            out.push((lf.frame_id().to_string(), data.to_vec()));
+                        }
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// The frames rendered in stored order without a tag header, for copying
+    /// to another tag with `import_frames`. Written as v2.3 for a v2.3 tag
+    /// and as v2.4 otherwise.
+    pub fn export_frames(&self) -> Result<Vec<u8>> {
+        let version = if self.version.0 == 3 { 3 } else { 4 };
+        self.render_ordered(version, true, TextEncodingPolicy::default())
+    }
+
+    /// Read a headerless frame stream of the given major version, as
+    /// returned by `export_frames`. The frames are stored undecoded and
+    /// replace any frames already held under the same keys.
+    pub fn import_frames(&mut self, data: &[u8], version: u8) -> Result<()> {
+        let mut imported = ID3Tags::new();
+        let options = ParseOptions::default();
+        match version {
+            2 => imported.read_v22_frames(data, 0, options)?,
+            3 => imported.read_v23_v24_frames(data, 0, 3, determine_bpi_v23(data, data.len()), options)?,
+            4 => imported.read_v23_v24_frames(data, 0, 4, determine_bpi(data, data.len()), options)?,
+            _ => return Err(MutagenError::ValueError(format!("unsupported ID3v2 version {}", version))),
+        }
+        imported.raw_buf = data.to_vec();
+
+        for (key, _) in &imported.frames {
+            self.delall(key.as_str());
+        }
+        self.groups.retain(|(key, _)| !imported.contains_key(key));
+        for (id, data) in imported.raw_frames() {
+            self.add_raw(id, data);
+        }
+        self.groups.append(&mut imported.groups);
+        self.unknown_frames.append(&mut imported.unknown_frames);
+        Ok(())
+    }
+
     /// Get all decoded frames as a flat list.
     pub fn values(&self) -> Vec<&Frame> {
         self.frames.iter().flat_map(|(_, v)| {
@@ -219,6 +564,13 @@ impl ID3Tags {
 
     /// Parse frames from raw tag data.
     pub fn read_frames(&mut self, data: &[u8], header: &ID3Header) -> Result<()> {
+        self.read_frames_with(data, header, ParseOptions::default())
+    }
+
+    /// Parse frames from raw tag data. In strict mode a frame whose size
+    /// runs past the end of the tag is an error rather than the end of
+    /// the frame list.
+    pub fn read_frames_with(&mut self, data: &[u8], header: &ID3Header, options: ParseOptions) -> Result<()> {
         let version = header.version.0;
         let mut offset = 0usize;
 
@@ -237,16 +589,17 @@ impl ID3Tags {
             } else {
                 ext_size + 4
             };
+            self.ext_header = ID3ExtHeader::parse(data, version).ok();
             if offset >= data.len() {
                 return Ok(());
             }
         }
 
-        // Determine BPI for v2.4
-        let bpi = if version == 4 {
-            determine_bpi(&data[offset..], data.len())
-        } else {
-            8
+        // Determine BPI; both versions may carry the other's size encoding
+        let bpi = match version {
+            4 => determine_bpi(&data[offset..], data.len()),
+            3 => determine_bpi_v23(&data[offset..], data.len()),
+            _ => 8,
         };
 
         self.version = header.version;
@@ -255,16 +608,41 @@ impl ID3Tags {
         self.raw_buf = data.to_vec();
 
         if version == 2 {
-            self.read_v22_frames(data, offset)?;
+            self.read_v22_frames(data, offset, options)?;
         } else {
-            self.read_v23_v24_frames(data, offset, version, bpi)?;
+            self.read_v23_v24_frames(data, offset, version, bpi, options)?;
         }
 
         Ok(())
     }
 
+    /// Check the extended header CRC against the frame data.
+    /// Returns `None` when the tag carries no CRC.
+    ///
+    /// For v2.3 the CRC covers the frames only (padding excluded),
+    /// for v2.4 it covers frames and padding.
+    pub fn crc_valid(&self) -> Option<bool> {
+        let ext = self.ext_header.as_ref()?;
+        let expected = ext.crc?;
+        let start = ext.byte_len(self.version.0).min(self.raw_buf.len());
+        let end = if self.version.0 == 4 {
+            self.raw_buf.len()
+        } else {
+            self.raw_buf.len().saturating_sub(ext.padding as usize).max(start)
+        };
+        Some(crc32(&self.raw_buf[start..end]) == expected)
+    }
+
+    /// Strict-mode CRC check: error if the extended header CRC doesn't match.
+    pub fn check_crc(&self) -> Result<()> {
+        match self.crc_valid() {
+            Some(false) => Err(MutagenError::ID3("extended header CRC mismatch".into())),
+            _ => Ok(()),
+        }
+    }
+
     /// Read v2.2 frames (6-byte headers).
-    fn read_v22_frames(&mut self, data: &[u8], mut offset: usize) -> Result<()> {
+    fn read_v22_frames(&mut self, data: &[u8], mut offset: usize, options: ParseOptions) -> Result<()> {
         while offset + 6 <= data.len() {
             if data[offset] == 0 {
                 break;
@@ -281,6 +659,9 @@ impl ID3Tags {
 
             offset += 6;
 
+            if offset + size > data.len() && options.strict {
+                return Err(truncated_frame(id_bytes, size, data.len() - offset));
+            }
             if size == 0 || offset + size > data.len() {
                 break;
             }
@@ -307,7 +688,9 @@ impl ID3Tags {
                 }
             };
 
-            // Store as lazy (raw) frame
+            // Store as lazy (raw) frame. COM/ULT bodies share the COMM/USLT
+            // layout (encoding, language, description), so `quick_hash_key`
+            // yields the same `COMM:desc:lang` keys as for v2.3/v2.4.
             self.add_raw(v24_id, frame_data.to_vec());
         }
 
@@ -321,6 +704,7 @@ impl ID3Tags {
         mut offset: usize,
         version: u8,
         bpi: u8,
+        options: ParseOptions,
     ) -> Result<()> {
         while offset + 10 <= data.len() {
             if data[offset] == 0 {
@@ -334,23 +718,28 @@ impl ID3Tags {
             {
                 break;
             }
+            let id_bytes = frames::convert_itunes_sort_frame_id(id_bytes).unwrap_or(id_bytes);
 
             let size = BitPaddedInt::decode(&data[offset + 4..offset + 8], bpi) as usize;
             let flags = u16::from_be_bytes([data[offset + 8], data[offset + 9]]);
 
             offset += 10;
 
+            if offset + size > data.len() && options.strict {
+                return Err(truncated_frame(id_bytes, size, data.len() - offset));
+            }
             if size == 0 || offset + size > data.len() {
                 break;
             }
 
             // Handle frame-level flags
-            let (compressed, encrypted, unsynchronised, has_data_length) = if version == 4 {
+            let (compressed, encrypted, unsynchronised, has_data_length, grouped) = if version == 4 {
                 (
                     flags & 0x0008 != 0,
                     flags & 0x0004 != 0,
                     flags & 0x0002 != 0,
                     flags & 0x0001 != 0,
+                    flags & 0x0040 != 0,
                 )
             } else {
                 (
@@ -358,6 +747,7 @@ impl ID3Tags {
                     flags & 0x0040 != 0,
                     false,
                     flags & 0x0080 != 0,
+                    flags & 0x0020 != 0,
                 )
             };
 
@@ -366,7 +756,7 @@ impl ID3Tags {
 
             // Fast path: no flags that require data mutation (common case)
             // Use Slice frames: zero allocation (no String for ID, no Vec for data)
-            if !encrypted && !compressed && !unsynchronised && !has_data_length {
+            if !encrypted && !compressed && !unsynchronised && !has_data_length && !grouped {
                 let id_arr: [u8; 4] = [id_bytes[0], id_bytes[1], id_bytes[2], id_bytes[3]];
                 let frame_offset = offset as u32;
                 let frame_len = size as u32;
@@ -391,10 +781,21 @@ impl ID3Tags {
                 continue;
             }
 
+            // The group byte leads the v2.4 header extras (group, data
+            // length) but trails the v2.3 ones (decompressed size, group)
+            let mut group = None;
+            if grouped && version == 4 && !frame_data.is_empty() {
+                group = Some(frame_data.remove(0));
+            }
+
             if has_data_length && frame_data.len() >= 4 {
                 frame_data = frame_data[4..].to_vec();
             }
 
+            if grouped && version != 4 && !frame_data.is_empty() {
+                group = Some(frame_data.remove(0));
+            }
+
             if unsynchronised {
                 frame_data = unsynch::decode(&frame_data)?;
             }
@@ -409,6 +810,13 @@ impl ID3Tags {
                 }
             }
 
+            if let Some(group) = group {
+                let key = quick_hash_key(&id, &frame_data);
+                if !self.groups.iter().any(|(k, _)| k == &key) {
+                    self.groups.push((key, group));
+                }
+            }
+
             // Store as lazy (raw) frame - don't decode until accessed
             self.add_raw(id, frame_data);
         }
@@ -416,42 +824,63 @@ impl ID3Tags {
         Ok(())
     }
 
-    /// Serialize all frames to bytes for writing.
+    /// Serialize all frames to bytes for writing, in canonical order
+    /// (see `frame_priority`).
     pub fn render(&self, version: u8) -> Result<Vec<u8>> {
+        self.render_ordered(version, false, TextEncodingPolicy::default())
+    }
+
+    /// Serialize all frames to bytes for writing. With `preserve_order`,
+    /// frames are written in stored order; otherwise they are stably sorted
+    /// by `frame_priority`. `encoding` picks the v2.3 encoding of UTF-8
+    /// text frames.
+    pub fn render_ordered(&self, version: u8, preserve_order: bool, encoding: TextEncodingPolicy) -> Result<Vec<u8>> {
         let mut data = Vec::with_capacity(4096);
 
-        for (_, frames_list) in self.frames.iter() {
-            for lf in frames_list {
-                let (id, frame_data) = match lf {
-                    LazyFrame::Decoded(frame) => {
-                        (frame.frame_id().to_string(), frame.write_data(version)?)
-                    }
-                    LazyFrame::Raw { id, data } => {
-                        // Re-serialize raw data as-is
-                        (id.clone(), data.clone())
-                    }
-                    LazyFrame::Slice { id, offset, len } => {
-                        let id_str = std::str::from_utf8(&id[..]).unwrap_or("XXXX").to_string();
-                        let slice_data = self.raw_buf[*offset as usize..(*offset as usize + *len as usize)].to_vec();
-                        (id_str, slice_data)
-                    }
-                };
-
-                if version == 4 {
-                    data.extend_from_slice(id.as_bytes());
-                    data.extend_from_slice(&BitPaddedInt::encode(
-                        frame_data.len() as u32,
-                        4,
-                        7,
-                    ));
-                    data.extend_from_slice(&[0u8; 2]);
-                    data.extend_from_slice(&frame_data);
-                } else {
-                    data.extend_from_slice(id.as_bytes());
-                    data.extend_from_slice(&(frame_data.len() as u32).to_be_bytes());
-                    data.extend_from_slice(&[0u8; 2]);
-                    data.extend_from_slice(&frame_data);
+        let mut ordered: Vec<(&HashKey, &LazyFrame)> = self.frames.iter()
+            .flat_map(|(key, frames_list)| frames_list.iter().map(move |lf| (key, lf)))
+            .collect();
+        if !preserve_order {
+            ordered.sort_by_key(|(_, lf)| frame_priority(lf.frame_id()));
+        }
+
+        for (key, lf) in ordered {
+            let (id, mut frame_data) = match lf {
+                LazyFrame::Decoded(frame) => {
+                    (frame.frame_id().to_string(), frame.write_data_with(version, encoding)?)
                 }
+                LazyFrame::Raw { id, data } => {
+                    // Re-serialize raw data as-is
+                    (id.clone(), data.clone())
+                }
+                LazyFrame::Slice { id, offset, len } => {
+                    let id_str = std::str::from_utf8(&id[..]).unwrap_or("XXXX").to_string();
+                    let slice_data = self.raw_buf[*offset as usize..(*offset as usize + *len as usize)].to_vec();
+                    (id_str, slice_data)
+                }
+            };
+            frames::normalize_raw_language(&id, &mut frame_data);
+
+            let mut flags = [0u8; 2];
+            if let Some(&(_, group)) = self.groups.iter().find(|(k, _)| k == key) {
+                flags[1] = if version == 4 { 0x40 } else { 0x20 };
+                frame_data.insert(0, group);
+            }
+
+            if version == 4 {
+                data.extend_from_slice(id.as_bytes());
+                data.extend_from_slice(&BitPaddedInt::encode(
+                    frame_data.len() as u32,
+                    4,
+                    7,
+                ));
+                data.extend_from_slice(&flags);
+                data.extend_from_slice(&frame_data);
+            } else {
+                data.extend_from_slice(id.as_bytes());
+                data.extend_from_slice(&(frame_data.len() as u32).to_be_bytes());
+                data.extend_from_slice(&flags);
+                data.extend_from_slice(&frame_data);
             }
         }
 
@@ -459,6 +888,35 @@ impl ID3Tags {
     }
 }
 
+/// Text frames holding an ID3v2.4 timestamp.
+pub const TIMESTAMP_FRAMES: [&str; 4] = ["TDRC", "TDEN", "TDOR", "TDRL"];
+
+/// Frames players commonly read first, in the order they are written.
+const LEADING_FRAMES: [&str; 7] = ["TIT2", "TPE1", "TRCK", "TALB", "TPOS", "TDRC", "TCON"];
+
+/// Write priority of a frame ID (lower is written first): the core text
+/// frames in `LEADING_FRAMES` order, then other text and URL frames, then
+/// everything else, with large binary frames (pictures, objects) last.
+pub fn frame_priority(id: &str) -> usize {
+    if let Some(i) = LEADING_FRAMES.iter().position(|&f| f == id) {
+        return i;
+    }
+    let base = LEADING_FRAMES.len();
+    match id {
+        "APIC" | "GEOB" | "PRIV" | "SYLT" | "ENCR" => base + 2,
+        _ if id.starts_with('T') || id.starts_with('W') => base,
+        _ => base + 1,
+    }
+}
+
+/// Error for a frame whose declared size runs past the tag data.
+fn truncated_frame(id: &[u8], size: usize, available: usize) -> MutagenError {
+    MutagenError::ID3(format!(
+        "frame {} truncated: {} bytes declared, {} available",
+        String::from_utf8_lossy(id), size, available
+    ))
+}
+
 /// Extract hash key from raw frame bytes without full frame parsing.
 /// For special frames (TXXX, WXXX, COMM, USLT, APIC, POPM), reads only
 /// the description/email header bytes to build the key. Avoids copying
@@ -478,7 +936,7 @@ fn quick_hash_key(id: &str, data: &[u8]) -> HashKey {
         "COMM" | "USLT" => {
             if data.len() < 4 { return HashKey::new(id); }
             if let Ok(enc) = specs::Encoding::from_byte(data[0]) {
-                let lang = std::str::from_utf8(&data[1..4]).unwrap_or("XXX");
+                let lang = frames::read_language(&data[1..4]);
                 if let Ok((desc, _)) = specs::read_encoded_text(&data[4..], enc) {
                     return HashKey::from_string(format!("{}:{}:{}", id, desc, lang));
                 }
@@ -491,17 +949,22 @@ fn quick_hash_key(id: &str, data: &[u8]) -> HashKey {
                 // Skip MIME (null-term Latin1)
                 if let Ok((_, mime_consumed)) = specs::read_latin1_text(&data[1..]) {
                     let after_mime = 1 + mime_consumed;
-                    // Skip pic_type (1 byte)
                     let after_type = after_mime + 1;
                     if after_type < data.len() {
                         if let Ok((desc, _)) = specs::read_encoded_text(&data[after_type..], enc) {
-                            return HashKey::from_string(format!("APIC:{}", desc));
+                            return HashKey::from_string(frames::picture_key(&desc, data[after_mime]));
                         }
                     }
                 }
             }
             HashKey::new("APIC")
         }
+        "WCOM" | "WOAR" => {
+            if let Ok((url, _)) = specs::read_latin1_text(data) {
+                return HashKey::from_string(format!("{}:{}", id, url));
+            }
+            HashKey::new(id)
+        }
         "POPM" => {
             if let Ok((email, _)) = specs::read_latin1_text(data) {
                 return HashKey::from_string(format!("POPM:{}", email));
@@ -3,6 +3,10 @@ use crate::common::error::Result;
 /// Decode unsynchronised data.
 /// Removes 0x00 bytes that follow 0xFF bytes.
 /// In ID3v2, 0xFF 0x00 sequences are used to avoid false sync signals.
+/// Every such 0x00 was inserted by the encoder, so exactly one is dropped
+/// per 0xFF (0xFF 0x00 0x00 decodes to 0xFF 0x00); a 0xFF at the very end
+/// of the buffer, with no zero after it, is kept as-is.
+/// `decode(&encode(x))` always returns `x`.
 pub fn decode(data: &[u8]) -> Result<Vec<u8>> {
     if data.is_empty() {
         return Ok(Vec::new());
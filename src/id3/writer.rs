@@ -1,15 +1,90 @@
 use crate::common::error::Result;
-use crate::id3::header::BitPaddedInt;
+use crate::id3::header::{BitPaddedInt, ID3ExtHeader, crc32};
+use crate::id3::frames::TextEncodingPolicy;
 use crate::id3::tags::ID3Tags;
 
+/// How much zero padding to reserve after the frames, leaving room for
+/// later in-place edits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaddingStrategy {
+    /// Exactly this many bytes.
+    Fixed(usize),
+    /// This percentage of the rendered frame data size.
+    Percent(u32),
+    /// No padding at all.
+    None,
+}
+
+impl Default for PaddingStrategy {
+    /// 1024 bytes, like mutagen.
+    fn default() -> Self {
+        PaddingStrategy::Fixed(1024)
+    }
+}
+
+impl PaddingStrategy {
+    /// Padding size for a tag whose frames render to `frame_len` bytes.
+    pub fn size(&self, frame_len: usize) -> usize {
+        match *self {
+            PaddingStrategy::Fixed(n) => n,
+            PaddingStrategy::Percent(pct) => frame_len * pct as usize / 100,
+            PaddingStrategy::None => 0,
+        }
+    }
+}
+
+/// Options controlling how a tag is rendered.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RenderOptions {
+    /// Emit an extended header carrying a CRC-32 of the frame data.
+    pub crc: bool,
+    /// Write frames in stored order instead of the canonical priority order.
+    pub preserve_order: bool,
+    /// Padding reserved after the frames.
+    pub padding: PaddingStrategy,
+    /// Encoding of UTF-8 text frames in a v2.3 tag.
+    pub text_encoding: TextEncodingPolicy,
+}
+
 /// Build a complete ID3v2 tag from frames, ready to write to file.
 /// Returns the full tag data including header.
 pub fn render_tag(tags: &ID3Tags, version: u8) -> Result<Vec<u8>> {
-    let frame_data = tags.render(version)?;
+    render_tag_with(tags, version, RenderOptions::default())
+}
+
+/// Like `render_tag`, but also emits an extended header carrying a CRC-32
+/// recomputed over the rendered frame data.
+pub fn render_tag_with_crc(tags: &ID3Tags, version: u8) -> Result<Vec<u8>> {
+    render_tag_with(tags, version, RenderOptions { crc: true, ..Default::default() })
+}
+
+/// Build a complete ID3v2 tag using the given options.
+pub fn render_tag_with(tags: &ID3Tags, version: u8, options: RenderOptions) -> Result<Vec<u8>> {
+    let frame_data = tags.render_ordered(version, options.preserve_order, options.text_encoding)?;
 
-    // Add padding (1024 bytes default, like mutagen)
-    let padding = 1024usize;
-    let total_size = frame_data.len() + padding;
+    let padding = options.padding.size(frame_data.len());
+
+    let ext = if options.crc {
+        // v2.3 CRC covers the frames only, v2.4 covers frames + padding
+        let crc = if version == 4 {
+            let mut crc = flate2::Crc::new();
+            crc.update(&frame_data);
+            crc.update(&vec![0u8; padding]);
+            crc.sum()
+        } else {
+            crc32(&frame_data)
+        };
+        let ext = ID3ExtHeader {
+            padding: if version == 4 { 0 } else { padding as u32 },
+            crc: Some(crc),
+            ..Default::default()
+        };
+        ext.render(version)
+    } else {
+        Vec::new()
+    };
+
+    let total_size = ext.len() + frame_data.len() + padding;
 
     let mut tag = Vec::with_capacity(10 + total_size);
 
@@ -18,12 +93,15 @@ pub fn render_tag(tags: &ID3Tags, version: u8) -> Result<Vec<u8>> {
     tag.push(version); // major version
     tag.push(0);       // revision
 
-    // Flags (none set)
-    tag.push(0);
+    // Flags (extended header only)
+    tag.push(if ext.is_empty() { 0 } else { 0x40 });
 
     // Size (syncsafe)
     tag.extend_from_slice(&BitPaddedInt::encode(total_size as u32, 4, 7));
 
+    // Extended header
+    tag.extend_from_slice(&ext);
+
     // Frame data
     tag.extend_from_slice(&frame_data);
 
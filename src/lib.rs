@@ -3,45 +3,27 @@ pub mod id3;
 pub mod mp3;
 pub mod flac;
 pub mod ogg;
+pub mod oggflac;
 pub mod mp4;
 pub mod vorbis;
+pub mod apev2;
+pub mod wavpack;
+pub mod asf;
+pub mod monkeysaudio;
+pub mod file;
 
+pub use file::{open, AudioFile, AudioInfo, Tags};
+
+#[cfg(feature = "mimalloc")]
 #[global_allocator]
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
-use std::sync::{Arc, RwLock, OnceLock};
-use std::collections::HashMap;
-
-/// Global file data cache — avoids repeated syscalls for the same file.
-/// Uses RwLock for concurrent reads in batch parallel parsing.
-static FILE_CACHE: OnceLock<RwLock<HashMap<String, Arc<[u8]>>>> = OnceLock::new();
-
-fn get_file_cache() -> &'static RwLock<HashMap<String, Arc<[u8]>>> {
-    FILE_CACHE.get_or_init(|| RwLock::new(HashMap::with_capacity(256)))
-}
+use std::sync::Arc;
 
-/// Read a file, returning cached data if available.
-/// Cache hit with RwLock: concurrent across all threads.
+/// Read a file through the shared bounded cache (see `common::cache`).
 #[inline]
 fn read_cached(path: &str) -> std::io::Result<Arc<[u8]>> {
-    let cache = get_file_cache();
-    // Fast path: read lock (concurrent, no blocking)
-    {
-        let guard = cache.read().unwrap();
-        if let Some(data) = guard.get(path) {
-            return Ok(Arc::clone(data));
-        }
-    }
-    // Slow path: read file, write lock to insert
-    let data: Arc<[u8]> = std::fs::read(path)?.into();
-    {
-        let mut guard = cache.write().unwrap();
-        if let Some(existing) = guard.get(path) {
-            return Ok(Arc::clone(existing));
-        }
-        guard.insert(path.to_string(), Arc::clone(&data));
-    }
-    Ok(data)
+    common::cache::read(path)
 }
 
 
@@ -49,8 +31,24 @@ fn read_cached(path: &str) -> std::io::Result<Arc<[u8]>> {
 mod python_bindings {
 use super::*;
 use pyo3::prelude::*;
+use pyo3::buffer::PyBuffer;
 use pyo3::types::{PyDict, PyList, PyBytes, PyTuple};
 use pyo3::exceptions::{PyValueError, PyKeyError, PyIOError};
+use common::options::ParseOptions;
+
+/// Run `f` over the bytes of a buffer-protocol object, without copying
+/// when the buffer is contiguous.
+fn with_buffer<R>(obj: &Bound<'_, PyAny>, f: impl FnOnce(&[u8]) -> PyResult<R>) -> PyResult<R> {
+    let buf = PyBuffer::<u8>::get(obj)?;
+    if buf.is_c_contiguous() {
+        // SAFETY: the buffer is contiguous and holds `len_bytes()` u8 items; it
+        // stays exported until `buf` drops, and the GIL is held throughout.
+        let bytes = unsafe { std::slice::from_raw_parts(buf.buf_ptr() as *const u8, buf.len_bytes()) };
+        f(bytes)
+    } else {
+        f(&buf.to_vec(obj.py())?)
+    }
+}
 
 // ---- Python Classes ----
 
@@ -71,6 +69,14 @@ struct PyMPEGInfo {
     layer: u8,
     #[pyo3(get)]
     mode: u32,
+    mode_label: &'static str,
+    /// Raw mode extension bits of the first frame.
+    #[pyo3(get)]
+    mode_extension: u8,
+    #[pyo3(get)]
+    intensity_stereo: bool,
+    #[pyo3(get)]
+    ms_stereo: bool,
     #[pyo3(get)]
     protected: bool,
     #[pyo3(get)]
@@ -85,10 +91,31 @@ struct PyMPEGInfo {
     track_peak: Option<f32>,
     #[pyo3(get)]
     album_gain: Option<f32>,
+    #[pyo3(get)]
+    decoded_length: Option<f64>,
+    /// False for a tag-only file with no MPEG frames.
+    #[pyo3(get)]
+    has_audio: bool,
 }
 
 #[pymethods]
 impl PyMPEGInfo {
+    /// Always None: lossy streams have no meaningful sample depth.
+    #[getter]
+    fn bits_per_sample(&self) -> Option<u32> {
+        None
+    }
+
+    #[getter]
+    fn lossless(&self) -> bool {
+        false
+    }
+
+    /// Channel mode label, e.g. "Joint Stereo".
+    fn mode_str(&self) -> &'static str {
+        self.mode_label
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "MPEGInfo(length={:.2}, bitrate={}, sample_rate={}, channels={}, version={}, layer={})",
@@ -116,11 +143,15 @@ struct PyID3 {
 #[pymethods]
 impl PyID3 {
     #[new]
-    #[pyo3(signature = (filename=None))]
-    fn new(filename: Option<&str>) -> PyResult<Self> {
+    #[pyo3(signature = (filename=None, strict=false))]
+    fn new(filename: Option<&str>, strict: bool) -> PyResult<Self> {
         match filename {
             Some(path) => {
-                let (tags, header) = id3::load_id3(path)?;
+                let (tags, header) = if strict {
+                    id3::load_id3_strict(path)?
+                } else {
+                    id3::load_id3(path)?
+                };
                 let version = header.as_ref().map(|h| h.version).unwrap_or((4, 0));
                 Ok(PyID3 {
                     tags,
@@ -136,11 +167,68 @@ impl PyID3 {
         }
     }
 
-    fn getall(&self, key: &str) -> PyResult<Vec<PyObject>> {
-        Python::with_gil(|py| {
-            let frames = self.tags.getall(key);
-            Ok(frames.iter().map(|f| frame_to_py(py, f)).collect())
-        })
+    fn getall(&mut self, py: Python, key: &str) -> Vec<Py<PyAny>> {
+        self.tags.getall_mut(key).iter().map(|f| frame_to_py(py, f)).collect()
+    }
+
+    /// Every frame with the given 4-character ID regardless of description
+    /// or language, e.g. `getall_by_id("COMM")` for all comments.
+    fn getall_by_id(&mut self, py: Python, frame_id: &str) -> Vec<Py<PyAny>> {
+        self.tags.getall_by_id_mut(frame_id).iter().map(|f| frame_to_py(py, f)).collect()
+    }
+
+    /// Move the frames under `old` to the key `new` without changing their
+    /// position, e.g. `rename("COMM::eng", "COMM:note:eng")`.
+    fn rename(&mut self, old: &str, new: &str) -> PyResult<()> {
+        if !self.tags.contains_key(&id3::frames::HashKey::new(old)) {
+            return Err(PyKeyError::new_err(old.to_string()));
+        }
+        Ok(self.tags.rename(old, new)?)
+    }
+
+    /// iTunes gapless info from the `iTunSMPB` comment, as a dict with
+    /// `encoder_delay`, `padding` and `original_samples`, or None.
+    fn gapless_info(&self, py: Python) -> PyResult<Option<Py<PyDict>>> {
+        let Some(info) = self.tags.gapless_info() else { return Ok(None) };
+        let dict = PyDict::new(py);
+        dict.set_item("encoder_delay", info.encoder_delay)?;
+        dict.set_item("padding", info.padding)?;
+        dict.set_item("original_samples", info.original_samples)?;
+        Ok(Some(dict.unbind()))
+    }
+
+    /// Timestamp of TDRC, TDEN, TDOR or TDRL as a dict with `year`, `month`,
+    /// `day`, `hour`, `minute`, `second` (None past the stored precision) and
+    /// `text`, or None if the frame is missing or not a timestamp.
+    fn timestamp(&self, py: Python, key: &str) -> PyResult<Option<Py<PyDict>>> {
+        let Some(ts) = self.tags.timestamp(key) else { return Ok(None) };
+        let dict = PyDict::new(py);
+        dict.set_item("year", ts.year)?;
+        dict.set_item("month", ts.month)?;
+        dict.set_item("day", ts.day)?;
+        dict.set_item("hour", ts.hour)?;
+        dict.set_item("minute", ts.minute)?;
+        dict.set_item("second", ts.second)?;
+        dict.set_item("text", ts.to_string())?;
+        Ok(Some(dict.unbind()))
+    }
+
+    /// Metadata of the first frame under `key` without its value: a dict
+    /// with `encoding` (0 Latin-1, 1 UTF-16, 2 UTF-16BE, 3 UTF-8),
+    /// `language` and `description`, each None where the frame has none.
+    /// None if the key is missing.
+    fn frame_info(&mut self, py: Python, key: &str) -> PyResult<Option<Py<PyDict>>> {
+        let Some(frame) = self.tags.get_mut(key) else { return Ok(None) };
+        let dict = PyDict::new(py);
+        dict.set_item("encoding", frame.encoding().map(|e| e as u8))?;
+        dict.set_item("language", frame.language())?;
+        dict.set_item("description", frame.description())?;
+        Ok(Some(dict.unbind()))
+    }
+
+    /// The ten iTunes Sound Check values from the `iTunNORM` comment, or None.
+    fn normalization(&self) -> Option<Vec<u32>> {
+        self.tags.normalization().map(|n| n.values.to_vec())
     }
 
     fn keys(&self) -> Vec<String> {
@@ -151,6 +239,26 @@ impl PyID3 {
         self.tags.values().iter().map(|f| frame_to_py(py, f)).collect()
     }
 
+    /// One `(key, value)` row per individual value, e.g. for CSV export:
+    /// text frames yield a row per string, other frames a row per frame.
+    fn flat_items(&mut self, py: Python) -> PyResult<Vec<(String, Py<PyAny>)>> {
+        let mut rows = Vec::new();
+        for key in self.tags.keys() {
+            for frame in self.tags.getall_mut(&key) {
+                match frame {
+                    id3::frames::Frame::Text(id3::frames::TextFrame { text, .. })
+                    | id3::frames::Frame::UserText(id3::frames::UserTextFrame { text, .. }) => {
+                        for value in text {
+                            rows.push((key.clone(), value.into_pyobject(py)?.into_any().unbind()));
+                        }
+                    }
+                    _ => rows.push((key.clone(), frame_to_py(py, frame))),
+                }
+            }
+        }
+        Ok(rows)
+    }
+
     fn __getitem__(&mut self, py: Python, key: &str) -> PyResult<PyObject> {
         match self.tags.get_mut(key) {
             Some(frame) => Ok(frame_to_py(py, frame)),
@@ -158,16 +266,33 @@ impl PyID3 {
         }
     }
 
+    /// Like `tags[key]`, but returns `default` for a missing key.
+    #[pyo3(signature = (key, default=None))]
+    fn get(&mut self, py: Python, key: &str, default: Option<Py<PyAny>>) -> Py<PyAny> {
+        match self.tags.get_mut(key) {
+            Some(frame) => frame_to_py(py, frame),
+            None => default.unwrap_or_else(|| py.None()),
+        }
+    }
+
+    /// Remove `key` and return its value as `tags[key]` would, or a list
+    /// when several frames share the key; `default` if it is missing.
+    #[pyo3(signature = (key, default=None))]
+    fn pop(&mut self, py: Python, key: &str, default: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        let frames = self.tags.pop(key);
+        Ok(match frames.as_slice() {
+            [] => default.unwrap_or_else(|| py.None()),
+            [frame] => frame_to_py(py, frame),
+            frames => PyList::new(py, frames.iter().map(|f| frame_to_py(py, f)))?.into_any().unbind(),
+        })
+    }
+
     fn __setitem__(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
         let text = value.extract::<Vec<String>>().or_else(|_| {
             value.extract::<String>().map(|s| vec![s])
         })?;
 
-        let frame = id3::frames::Frame::Text(id3::frames::TextFrame {
-            id: key.to_string(),
-            encoding: id3::specs::Encoding::Utf8,
-            text,
-        });
+        let frame = id3::frames::Frame::text(key, text);
 
         let hash_key = frame.hash_key();
         // Replace existing or push new (Vec-based tag storage)
@@ -179,6 +304,80 @@ impl PyID3 {
         Ok(())
     }
 
+    /// Replace all frames under `key`. `APIC` keys take picture dicts
+    /// (`data`, `mime`, `type`, `desc`), `COMM` keys take comment dicts
+    /// (`text`, `desc`, `lang`) or strings, and text keys take strings,
+    /// stored as one multi-value frame. Missing descriptions, languages and
+    /// picture types come from the key, e.g. `"COMM:desc:eng"` or
+    /// `"APIC:desc:4"`.
+    fn setall(&mut self, key: &str, values: Vec<Bound<'_, PyAny>>) -> PyResult<()> {
+        let frames = if key.starts_with("APIC") || key.starts_with("COMM") {
+            values.iter().map(|v| py_to_frame(key, v)).collect::<PyResult<Vec<_>>>()?
+        } else if key.starts_with('T') {
+            let text = values.iter().map(|v| v.extract::<String>()).collect::<PyResult<Vec<_>>>()?;
+            let frame = match key.strip_prefix("TXXX:") {
+                Some(desc) => id3::frames::Frame::user_text(desc, text),
+                None => id3::frames::Frame::text(key, text),
+            };
+            vec![frame]
+        } else {
+            return Err(PyValueError::new_err(format!("setall does not support {:?} frames", key)));
+        };
+        self.tags.setall(key, frames);
+        Ok(())
+    }
+
+    /// Append one frame, keeping the frames already stored under its key.
+    /// `frame` is a dict as `setall` takes for APIC and COMM keys, with the
+    /// frame named by its `FrameID` entry or by `key` (which may carry the
+    /// description, e.g. `"COMM:desc:eng"`). Text frames take `text` as a
+    /// string or list, and TXXX a `desc`.
+    #[pyo3(signature = (frame, key=None))]
+    fn add(&mut self, frame: &Bound<'_, PyDict>, key: Option<String>) -> PyResult<()> {
+        let key = match key {
+            Some(key) => key,
+            None => frame.get_item("FrameID")?
+                .ok_or_else(|| PyValueError::new_err("frame dict needs 'FrameID' unless a key is given"))?
+                .extract::<String>()?,
+        };
+        let frame = if key.starts_with("APIC") || key.starts_with("COMM") {
+            py_to_frame(&key, frame.as_any())?
+        } else if key.starts_with('T') {
+            let value = frame.get_item("text")?
+                .ok_or_else(|| PyValueError::new_err("text frame dict needs 'text'"))?;
+            let text = value.extract::<Vec<String>>().or_else(|_| value.extract::<String>().map(|s| vec![s]))?;
+            if key.starts_with("TXXX") {
+                let desc = match frame.get_item("desc")? {
+                    Some(d) => d.extract::<String>()?,
+                    None => key.strip_prefix("TXXX:").unwrap_or_default().to_string(),
+                };
+                id3::frames::Frame::user_text(&desc, text)
+            } else {
+                id3::frames::Frame::text(&key, text)
+            }
+        } else {
+            return Err(PyValueError::new_err(format!("add does not support {:?} frames", key)));
+        };
+        self.tags.add(frame);
+        Ok(())
+    }
+
+    /// The frames as a headerless byte stream, for copying them verbatim to
+    /// another tag with `import_frames`.
+    fn export_frames<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.tags.export_frames()?))
+    }
+
+    /// Add the frames from `export_frames` output, replacing frames with
+    /// the same keys. `version` is the major version they were written as
+    /// (3 when exported from a v2.3 tag, 4 otherwise), defaulting to this
+    /// tag's.
+    #[pyo3(signature = (data, version=None))]
+    fn import_frames(&mut self, data: &[u8], version: Option<u8>) -> PyResult<()> {
+        let version = version.unwrap_or(self.version.0);
+        Ok(self.tags.import_frames(data, version)?)
+    }
+
     fn __delitem__(&mut self, key: &str) -> PyResult<()> {
         self.tags.delall(key);
         Ok(())
@@ -202,23 +401,61 @@ impl PyID3 {
         Ok(list.call_method0("__iter__")?.into())
     }
 
-    fn save(&self, filename: Option<&str>) -> PyResult<()> {
-        let path = filename
-            .map(|s| s.to_string())
-            .or_else(|| self.path.clone())
-            .ok_or_else(|| PyValueError::new_err("No filename specified"))?;
-
-        id3::save_id3(&path, &self.tags, self.version.0.max(3))?;
-        Ok(())
-    }
-
-    fn delete(&self, filename: Option<&str>) -> PyResult<()> {
+    /// Write the tag. Options are keyword-only.
+    ///
+    /// Frames are written in canonical order (core text frames first,
+    /// pictures last) unless `preserve_order` is set. `padding` is the
+    /// number of zero bytes reserved after the frames (1024 by default).
+    /// With `crc`, an extended header carrying a CRC-32 of the frames is
+    /// written.
+    ///
+    /// With `dry_run`, the file is left untouched and a dict describing the
+    /// planned write is returned instead: `new_tag` (bytes), `tag_offset`,
+    /// `old_tag_size`, `audio_start` and `full_rewrite`.
+    ///
+    /// `v2_version` (3 or 4) defaults to the version the tag was read as.
+    /// Saving as 2.3 converts TDOR to TORY and drops TDEN/TDRL with a warning.
+    /// v2.3 has no UTF-8, so UTF-8 text frames are written as UTF-16, or as
+    /// Latin-1 when `prefer_latin1` is set and the text fits.
+    ///
+    /// With `verify`, the tag is read back after writing and
+    /// `VerificationError` is raised if its frames don't match. With
+    /// `preserve_mtime`, the file keeps its modification time. There is no
+    /// `preserve_links` option: the file is always rewritten in place, so
+    /// hardlinks and symlinks survive every save.
+    ///
+    /// With `padding_reuse`, a tag that fits in the space of the existing
+    /// one (padding included) is written over it without touching the
+    /// audio, and `padding` only applies if the file has to be rewritten.
+    /// The return value then tells whether the write was in place, and a
+    /// `dry_run` plan describes the in-place write when there is one.
+    /// Otherwise `None` is returned.
+    #[pyo3(signature = (filename=None, *, crc=false, preserve_order=false, dry_run=false, padding=None, v2_version=None, prefer_latin1=false, verify=false, preserve_mtime=false, padding_reuse=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn save(&self, py: Python, filename: Option<&str>, crc: bool, preserve_order: bool, dry_run: bool, padding: Option<usize>, v2_version: Option<u8>, prefer_latin1: bool, verify: bool, preserve_mtime: bool, padding_reuse: bool) -> PyResult<Option<Py<PyAny>>> {
+        let args = ID3SaveArgs::new(crc, preserve_order, dry_run, padding, v2_version, prefer_latin1, verify, preserve_mtime, padding_reuse);
+        self.save_with(py, filename, args)
+    }
+
+    /// Remove the ID3v2 tag (`v2`) and/or the trailing ID3v1 tag (`v1`).
+    /// With `apev2`, an APEv2 tag at the end of the file (before any ID3v1
+    /// tag) is removed as well.
+    #[pyo3(signature = (filename=None, v1=true, v2=true, apev2=false))]
+    fn delete(&self, filename: Option<&str>, v1: bool, v2: bool, apev2: bool) -> PyResult<()> {
         let path = filename
             .map(|s| s.to_string())
             .or_else(|| self.path.clone())
             .ok_or_else(|| PyValueError::new_err("No filename specified"))?;
 
-        id3::delete_id3(&path)?;
+        if v2 {
+            id3::delete_id3v2(&path)?;
+        }
+        if apev2 {
+            apev2::delete_apev2(&path)?;
+        }
+        if v1 {
+            id3::delete_id3v1(&path)?;
+        }
         Ok(())
     }
 
@@ -234,6 +471,206 @@ impl PyID3 {
     fn version(&self) -> (u8, u8) {
         self.version
     }
+
+    /// Extended header fields as a dict, or None if the tag has none.
+    #[getter]
+    fn extended_header(&self, py: Python) -> PyResult<Py<PyAny>> {
+        match &self.tags.ext_header {
+            Some(ext) => {
+                let dict = PyDict::new(py);
+                dict.set_item("size", ext.size)?;
+                dict.set_item("flags", ext.flags)?;
+                dict.set_item("padding", ext.padding)?;
+                dict.set_item("crc", ext.crc)?;
+                Ok(dict.into_any().unbind())
+            }
+            None => Ok(py.None()),
+        }
+    }
+
+    /// Whether the extended header CRC matches the frame data (None if no CRC).
+    #[getter]
+    fn crc_valid(&self) -> Option<bool> {
+        self.tags.crc_valid()
+    }
+
+    /// Non-fatal problems in the tag's frames, as `"KEY: message"` strings.
+    fn validate(&self) -> Vec<String> {
+        self.tags.validate().iter().map(|w| w.to_string()).collect()
+    }
+
+    /// `(id, bytes)` for every frame, with the frame body exactly as stored.
+    fn raw_frames(&self, py: Python) -> Vec<(String, Py<PyAny>)> {
+        self.tags
+            .raw_frames()
+            .into_iter()
+            .map(|(id, data)| (id, PyBytes::new(py, &data).into_any().unbind()))
+            .collect()
+    }
+
+    /// The 128-byte ID3v1 block for the current frames: title, artist,
+    /// album, year, track number and genre, truncated to the v1 field sizes.
+    fn render_v1(&mut self, py: Python) -> Py<PyAny> {
+        let frames: Vec<id3::frames::Frame> = self.tags.values_decoded().into_iter().cloned().collect();
+        PyBytes::new(py, &id3::id3v1::make_id3v1(&frames)).into_any().unbind()
+    }
+
+    /// Resolved value list of a text frame. With `legacy_separators`, known
+    /// multi-valued frames (TPE1, TCON, ...) are also split on `/` and `;`.
+    #[pyo3(signature = (key, legacy_separators=false))]
+    fn text_values(&self, key: &str, legacy_separators: bool) -> Vec<String> {
+        self.tags.text_values(key, separator_policy(legacy_separators))
+    }
+}
+
+/// Keyword options of `ID3.save` and `MP3.save`.
+#[derive(Debug, Default)]
+struct ID3SaveArgs {
+    dry_run: bool,
+    v2_version: Option<u8>,
+    options: id3::SaveOptions,
+}
+
+impl ID3SaveArgs {
+    /// Collect the keyword arguments shared by `ID3.save` and `MP3.save`.
+    #[allow(clippy::too_many_arguments)]
+    fn new(crc: bool, preserve_order: bool, dry_run: bool, padding: Option<usize>, v2_version: Option<u8>, prefer_latin1: bool, verify: bool, preserve_mtime: bool, padding_reuse: bool) -> Self {
+        let padding = padding.map_or_else(Default::default, id3::writer::PaddingStrategy::Fixed);
+        let text_encoding = if prefer_latin1 {
+            id3::frames::TextEncodingPolicy::PreferLatin1
+        } else {
+            id3::frames::TextEncodingPolicy::Utf16
+        };
+        let render = id3::writer::RenderOptions { crc, preserve_order, padding, text_encoding };
+        let options = id3::SaveOptions { render, verify, preserve_mtime, padding_reuse };
+        ID3SaveArgs { dry_run, v2_version, options }
+    }
+}
+
+impl PyID3 {
+    /// Save to `filename` (or the file the tag was read from). Returns the
+    /// plan dict for `dry_run`, whether the write was in place for
+    /// `padding_reuse`, and `None` otherwise.
+    fn save_with(&self, py: Python, filename: Option<&str>, args: ID3SaveArgs) -> PyResult<Option<Py<PyAny>>> {
+        let path = filename
+            .map(|s| s.to_string())
+            .or_else(|| self.path.clone())
+            .ok_or_else(|| PyValueError::new_err("No filename specified"))?;
+
+        let version = args.v2_version.unwrap_or(self.version.0).max(3);
+        if version > 4 {
+            return Err(PyValueError::new_err("v2_version must be 3 or 4"));
+        }
+        let mut converted = None;
+        if version == 3 {
+            let mut tags = self.tags.clone();
+            for warning in tags.update_to_v23() {
+                let message = std::ffi::CString::new(warning.to_string()).unwrap_or_default();
+                PyErr::warn(py, &py.get_type::<pyo3::exceptions::PyUserWarning>(), &message, 1)?;
+            }
+            converted = Some(tags);
+        }
+        let tags = converted.as_ref().unwrap_or(&self.tags);
+        if args.dry_run {
+            let plan = id3::save_id3_file_preview(&path, tags, version, args.options)?;
+            let dict = PyDict::new(py);
+            dict.set_item("new_tag", PyBytes::new(py, &plan.new_tag))?;
+            dict.set_item("tag_offset", plan.tag_offset)?;
+            dict.set_item("old_tag_size", plan.old_tag_size)?;
+            dict.set_item("audio_start", plan.audio_start)?;
+            dict.set_item("full_rewrite", plan.full_rewrite)?;
+            return Ok(Some(dict.into_any().unbind()));
+        }
+        let in_place = id3::save_id3_file(&path, tags, version, args.options)?;
+        if args.options.padding_reuse {
+            return Ok(Some(in_place.into_pyobject(py)?.to_owned().into_any().unbind()));
+        }
+        Ok(None)
+    }
+}
+
+/// ID3 tags behind friendly key names (`title`, `musicbrainz_albumid`, ...),
+/// like mutagen's EasyID3. Values are always lists of strings.
+#[pyclass(name = "EasyID3")]
+#[derive(Debug)]
+struct PyEasyID3 {
+    id3: PyID3,
+}
+
+fn easy_key(key: &str) -> PyResult<id3::easy::EasyKey> {
+    id3::easy::EasyKey::lookup(key).ok_or_else(|| PyKeyError::new_err(format!("{:?} is not a valid key", key)))
+}
+
+#[pymethods]
+impl PyEasyID3 {
+    #[new]
+    #[pyo3(signature = (filename=None))]
+    fn new(filename: Option<&str>) -> PyResult<Self> {
+        Ok(PyEasyID3 { id3: PyID3::new(filename, false)? })
+    }
+
+    /// Every key name this class understands.
+    #[staticmethod]
+    fn valid_keys() -> Vec<&'static str> {
+        id3::easy::KEYS.iter().map(|(name, _)| *name).collect()
+    }
+
+    fn keys(&self) -> Vec<&'static str> {
+        id3::easy::keys(&self.id3.tags)
+    }
+
+    fn __getitem__(&self, key: &str) -> PyResult<Vec<String>> {
+        easy_key(key)?.get(&self.id3.tags).ok_or_else(|| PyKeyError::new_err(key.to_string()))
+    }
+
+    #[pyo3(signature = (key, default=None))]
+    fn get(&self, py: Python, key: &str, default: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        match easy_key(key)?.get(&self.id3.tags) {
+            Some(values) => Ok(PyList::new(py, values)?.into_any().unbind()),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    fn __setitem__(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let values = value.extract::<Vec<String>>().or_else(|_| {
+            value.extract::<String>().map(|s| vec![s])
+        })?;
+        easy_key(key)?.set(&mut self.id3.tags, values);
+        Ok(())
+    }
+
+    fn __delitem__(&mut self, key: &str) -> PyResult<()> {
+        let easy = easy_key(key)?;
+        if easy.get(&self.id3.tags).is_none() {
+            return Err(PyKeyError::new_err(key.to_string()));
+        }
+        easy.delete(&mut self.id3.tags);
+        Ok(())
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        id3::easy::EasyKey::lookup(key).is_some_and(|k| k.get(&self.id3.tags).is_some())
+    }
+
+    fn __len__(&self) -> usize {
+        self.keys().len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("EasyID3(keys={})", self.keys().join(", "))
+    }
+
+    /// The underlying `ID3` frame keys, e.g. `TXXX:MusicBrainz Album Id`.
+    fn frame_keys(&self) -> Vec<String> {
+        self.id3.tags.keys()
+    }
+
+    #[pyo3(signature = (filename=None, preserve_mtime=false))]
+    fn save(&self, py: Python, filename: Option<&str>, preserve_mtime: bool) -> PyResult<()> {
+        let options = id3::SaveOptions { preserve_mtime, ..Default::default() };
+        self.id3.save_with(py, filename, ID3SaveArgs { options, ..Default::default() })?;
+        Ok(())
+    }
 }
 
 /// MP3 file (ID3 tags + audio info).
@@ -243,18 +680,33 @@ struct PyMP3 {
     info: PyMPEGInfo,
     #[pyo3(get)]
     filename: String,
+    /// Byte offset where the audio data starts.
+    #[pyo3(get)]
+    audio_offset: usize,
     tag_dict: Py<PyDict>,
     tag_keys: Vec<String>,
     id3: PyID3,
+    has_tags: bool,
 }
 
 impl PyMP3 {
     #[inline(always)]
-    fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
-        let mut mp3_file = mp3::MP3File::parse(data, filename)?;
-        mp3_file.ensure_tags_parsed(data);
+    fn from_data_with(py: Python<'_>, data: &[u8], filename: &str, options: ParseOptions) -> PyResult<Self> {
+        Self::from_data_checked(py, data, filename, false, options)
+    }
+
+    fn from_data_checked(
+        py: Python<'_>,
+        data: &[u8],
+        filename: &str,
+        check_crc: bool,
+        options: ParseOptions,
+    ) -> PyResult<Self> {
+        let mut mp3_file = mp3::MP3File::parse_checked(data, filename, check_crc)?;
+        mp3_file.ensure_tags_parsed_with(data, options)?;
         let info = make_mpeg_info(&mp3_file.info);
         let version = mp3_file.id3_header.as_ref().map(|h| h.version).unwrap_or((4, 0));
+        let has_tags = mp3_file.id3_header.is_some() || !mp3_file.tags.is_empty();
 
         // Pre-build Python dict of all tags during construction
         let tag_dict = PyDict::new(py);
@@ -272,6 +724,7 @@ impl PyMP3 {
         Ok(PyMP3 {
             info,
             filename: filename.to_string(),
+            audio_offset: mp3_file.audio_offset,
             tag_dict: tag_dict.into(),
             tag_keys,
             id3: PyID3 {
@@ -279,6 +732,7 @@ impl PyMP3 {
                 path: Some(filename.to_string()),
                 version,
             },
+            has_tags,
         })
     }
 }
@@ -286,14 +740,26 @@ impl PyMP3 {
 #[pymethods]
 impl PyMP3 {
     #[new]
-    fn new(py: Python<'_>, filename: &str) -> PyResult<Self> {
+    #[pyo3(signature = (filename, check_crc=false))]
+    fn new(py: Python<'_>, filename: &str, check_crc: bool) -> PyResult<Self> {
         let data = read_cached(filename)
             .map_err(|e| PyIOError::new_err(format!("{}", e)))?;
-        Self::from_data(py, &data, filename)
+        Self::from_data_checked(py, &data, filename, check_crc, ParseOptions::default())
+    }
+
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data_with(py, bytes, "", ParseOptions::default()))
     }
 
+    /// The ID3 tag, or None if the file has neither an ID3v2 nor an ID3v1
+    /// tag and `add_tags` hasn't been called.
     #[getter]
     fn tags(&self, py: Python) -> PyResult<PyObject> {
+        if !self.has_tags {
+            return Ok(py.None());
+        }
         let id3 = PyID3 {
             tags: self.id3.tags.clone(),
             path: self.id3.path.clone(),
@@ -302,6 +768,24 @@ impl PyMP3 {
         Ok(id3.into_pyobject(py)?.into_any().unbind())
     }
 
+    /// Whether the file has an ID3 tag (or one was added with `add_tags`).
+    #[getter]
+    fn has_tags(&self) -> bool {
+        self.has_tags
+    }
+
+    /// Start an empty ID3v2.4 tag on a file that has none; raises
+    /// `ID3Error` if the file is already tagged, like mutagen.
+    fn add_tags(&mut self) -> PyResult<()> {
+        if self.has_tags {
+            return Err(common::error::MutagenError::ID3("an ID3 tag already exists".into()).into());
+        }
+        self.id3.tags = id3::tags::ID3Tags::new();
+        self.id3.version = (4, 0);
+        self.has_tags = true;
+        Ok(())
+    }
+
     fn keys(&self) -> Vec<String> {
         self.tag_keys.clone()
     }
@@ -319,17 +803,57 @@ impl PyMP3 {
         self.tag_dict.bind(py).get_item(key).ok().flatten().is_some()
     }
 
+    /// Length, sample rate, channels, bitrate and tags as a JSON object
+    /// string, in the per-file shape of `BatchResult.to_dict`.
+    fn to_json(&self) -> String {
+        file_to_json(self.info.length, self.info.sample_rate, self.info.channels,
+                     Some(self.info.bitrate), id3_to_batch(&self.id3.tags))
+    }
+
     fn __repr__(&self) -> String {
         format!("MP3(filename={:?})", self.filename)
     }
 
-    fn save(&self) -> PyResult<()> {
-        self.id3.save(Some(&self.filename))
+    /// Write the ID3 tag back to the file. Takes the same keyword options
+    /// as `ID3.save`, and returns the same values.
+    #[pyo3(signature = (*, crc=false, preserve_order=false, dry_run=false, padding=None, v2_version=None, prefer_latin1=false, verify=false, preserve_mtime=false, padding_reuse=false))]
+    #[allow(clippy::too_many_arguments)]
+    fn save(&self, py: Python, crc: bool, preserve_order: bool, dry_run: bool, padding: Option<usize>, v2_version: Option<u8>, prefer_latin1: bool, verify: bool, preserve_mtime: bool, padding_reuse: bool) -> PyResult<Option<Py<PyAny>>> {
+        let args = ID3SaveArgs::new(crc, preserve_order, dry_run, padding, v2_version, prefer_latin1, verify, preserve_mtime, padding_reuse);
+        self.id3.save_with(py, Some(&self.filename), args)
+    }
+
+    /// Non-fatal problems in the ID3 frames, as `"KEY: message"` strings.
+    fn validate(&self) -> Vec<String> {
+        self.id3.validate()
+    }
+
+    /// Resolved value list of a text frame; see `ID3.text_values`.
+    #[pyo3(signature = (key, legacy_separators=false))]
+    fn text_values(&self, key: &str, legacy_separators: bool) -> Vec<String> {
+        self.id3.text_values(key, legacy_separators)
+    }
+
+    /// Remove ID3 tags from the file (v1 and/or v2), and with `apev2` a
+    /// trailing APEv2 tag too.
+    #[pyo3(signature = (v1=true, v2=true, apev2=false))]
+    fn delete(&self, v1: bool, v2: bool, apev2: bool) -> PyResult<()> {
+        self.id3.delete(Some(&self.filename), v1, v2, apev2)
     }
 
     fn pprint(&self) -> String {
         format!("{}\n{}", self.info.pprint(), self.id3.pprint())
     }
+
+    /// `(track, total)` from TRCK.
+    fn track_number(&self) -> (Option<u32>, Option<u32>) {
+        self.id3.tags.track_number()
+    }
+
+    /// `(disc, total)` from TPOS.
+    fn disc_number(&self) -> (Option<u32>, Option<u32>) {
+        self.id3.tags.disc_number()
+    }
 }
 
 /// FLAC stream info.
@@ -354,10 +878,17 @@ struct PyStreamInfo {
     min_frame_size: u32,
     #[pyo3(get)]
     max_frame_size: u32,
+    #[pyo3(get)]
+    bitrate_mode: u8,
 }
 
 #[pymethods]
 impl PyStreamInfo {
+    #[getter]
+    fn lossless(&self) -> bool {
+        true
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "StreamInfo(length={:.2}, sample_rate={}, channels={}, bits_per_sample={})",
@@ -401,6 +932,16 @@ impl PyVComment {
         Ok(PyList::new(py, values)?.into_any().unbind())
     }
 
+    /// Like `tags[key]`, but returns `default` for a missing key.
+    #[pyo3(signature = (key, default=None))]
+    fn get(&self, py: Python, key: &str, default: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        let values = self.vc.get(key);
+        if values.is_empty() {
+            return Ok(default.unwrap_or_else(|| py.None()));
+        }
+        Ok(PyList::new(py, values)?.into_any().unbind())
+    }
+
     fn __setitem__(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
         let values = value.extract::<Vec<String>>().or_else(|_| {
             value.extract::<String>().map(|s| vec![s])
@@ -436,6 +977,11 @@ impl PyVComment {
     fn vendor(&self) -> &str {
         &self.vc.vendor
     }
+
+    #[setter]
+    fn set_vendor(&mut self, vendor: &str) {
+        self.vc.set_vendor(vendor);
+    }
 }
 
 /// FLAC file.
@@ -445,6 +991,9 @@ struct PyFLAC {
     info: PyStreamInfo,
     #[pyo3(get)]
     filename: String,
+    /// Byte offset where the audio data starts.
+    #[pyo3(get)]
+    audio_offset: usize,
     flac_file: flac::FLACFile,
     vc_data: vorbis::VorbisComment,
     tag_dict: Py<PyDict>,
@@ -454,7 +1003,11 @@ struct PyFLAC {
 impl PyFLAC {
     #[inline(always)]
     fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
-        let mut flac_file = flac::FLACFile::parse(data, filename)?;
+        Self::from_data_with(py, data, filename, ParseOptions::default())
+    }
+
+    fn from_data_with(py: Python<'_>, data: &[u8], filename: &str, options: ParseOptions) -> PyResult<Self> {
+        let mut flac_file = flac::FLACFile::parse_with(data, filename, options)?;
 
         let info = PyStreamInfo {
             length: flac_file.info.length,
@@ -466,9 +1019,11 @@ impl PyFLAC {
             max_block_size: flac_file.info.max_block_size,
             min_frame_size: flac_file.info.min_frame_size,
             max_frame_size: flac_file.info.max_frame_size,
+            bitrate_mode: flac_file.info.bitrate_mode() as u8,
         };
 
-        flac_file.ensure_tags();
+        flac_file.ensure_tags_with(options)?;
+        flac_file.drop_raw();
         let vc_data = flac_file.tags.clone().unwrap_or_else(|| vorbis::VorbisComment::new());
 
         // Pre-build Python dict of all tags
@@ -484,6 +1039,7 @@ impl PyFLAC {
         Ok(PyFLAC {
             info,
             filename: filename.to_string(),
+            audio_offset: flac_file.audio_offset(),
             flac_file,
             vc_data,
             tag_dict: tag_dict.into(),
@@ -501,6 +1057,12 @@ impl PyFLAC {
         Self::from_data(py, &data, filename)
     }
 
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data(py, bytes, ""))
+    }
+
     #[getter]
     fn tags(&self, py: Python) -> PyResult<PyObject> {
         let vc = self.vc_data.clone();
@@ -508,6 +1070,24 @@ impl PyFLAC {
         Ok(pvc.into_pyobject(py)?.into_any().unbind())
     }
 
+    /// Vendor string of the file's VorbisComment block (the encoder
+    /// identity, e.g. `reference libFLAC 1.2.1 20070917`), or None if the
+    /// file has no such block.
+    #[getter]
+    fn vendor(&self) -> Option<&str> {
+        self.flac_file.tags.as_ref().map(|vc| vc.vendor.as_str())
+    }
+
+    /// The ID3v2 tag in front of the `fLaC` marker, or None if there is
+    /// none. `save` leaves that tag untouched.
+    fn id3_tags(&self) -> Option<PyID3> {
+        self.flac_file.id3.as_ref().map(|tags| PyID3 {
+            tags: tags.clone(),
+            path: Some(self.filename.clone()),
+            version: tags.version,
+        })
+    }
+
     fn keys(&self) -> Vec<String> {
         self.tag_keys.clone()
     }
@@ -525,12 +1105,43 @@ impl PyFLAC {
         self.tag_dict.bind(py).get_item(key).ok().flatten().is_some()
     }
 
+    /// Length, sample rate, channels, bitrate and tags as a JSON object
+    /// string, in the per-file shape of `BatchResult.to_dict`.
+    fn to_json(&self) -> String {
+        file_to_json(self.info.length, self.info.sample_rate, self.info.channels as u32,
+                     None, vc_to_batch(&self.vc_data))
+    }
+
     fn __repr__(&self) -> String {
         format!("FLAC(filename={:?})", self.filename)
     }
 
-    fn save(&self) -> PyResult<()> {
-        self.flac_file.save()?;
+    /// Write the metadata blocks back. With `preserve_order`, blocks that
+    /// are not rewritten keep their original position (e.g. a SeekTable
+    /// right after StreamInfo). With `dedupe`, repeated identical
+    /// `KEY=value` comments are written once. With `verify`, the file is
+    /// re-parsed after writing and `VerificationError` is raised if the
+    /// metadata doesn't read back as written. With `preserve_mtime`, the
+    /// file keeps its modification time.
+    ///
+    /// With `padding_reuse`, metadata that fits in the existing blocks and
+    /// padding is written over them without touching the audio, and the
+    /// return value tells whether that happened (`False` means the file
+    /// was rewritten). Without it, `None` is returned.
+    #[pyo3(signature = (preserve_order=false, dedupe=false, verify=false, preserve_mtime=false, padding_reuse=false))]
+    fn save(&self, preserve_order: bool, dedupe: bool, verify: bool, preserve_mtime: bool, padding_reuse: bool) -> PyResult<Option<bool>> {
+        let options = flac::SaveOptions { preserve_order, dedupe, verify, preserve_mtime, padding_reuse };
+        let in_place = self.flac_file.save_with(options)?;
+        Ok(padding_reuse.then_some(in_place))
+    }
+
+    /// Queue a JPEG or PNG picture for the next `save`; the MIME type and
+    /// dimensions are read from the image itself. `type` defaults to 3
+    /// (front cover).
+    #[pyo3(signature = (data, r#type=3, desc=""))]
+    fn add_picture(&mut self, data: &[u8], r#type: u32, desc: &str) -> PyResult<()> {
+        let picture = flac::FLACPicture::from_image_bytes(data.to_vec(), r#type, desc)?;
+        self.flac_file.pictures.push(picture);
         Ok(())
     }
 }
@@ -545,12 +1156,34 @@ struct PyOggVorbisInfo {
     channels: u8,
     #[pyo3(get)]
     sample_rate: u32,
+    /// Average bitrate: the nominal bitrate, or derived from the file size
+    /// when the header leaves it unset.
     #[pyo3(get)]
     bitrate: u32,
+    /// Identification header bitrate fields, as stored (0 or -1 means unset).
+    #[pyo3(get)]
+    bitrate_nominal: i32,
+    #[pyo3(get)]
+    bitrate_upper: i32,
+    #[pyo3(get)]
+    bitrate_lower: i32,
+    #[pyo3(get)]
+    bitrate_mode: u8,
 }
 
 #[pymethods]
 impl PyOggVorbisInfo {
+    /// Always None: lossy streams have no meaningful sample depth.
+    #[getter]
+    fn bits_per_sample(&self) -> Option<u32> {
+        None
+    }
+
+    #[getter]
+    fn lossless(&self) -> bool {
+        false
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "OggVorbisInfo(length={:.2}, sample_rate={}, channels={})",
@@ -573,6 +1206,9 @@ struct PyOggVorbis {
     info: PyOggVorbisInfo,
     #[pyo3(get)]
     filename: String,
+    /// Byte offset where the audio data starts.
+    #[pyo3(get)]
+    audio_offset: usize,
     vc: PyVComment,
     tag_dict: Py<PyDict>,
     tag_keys: Vec<String>,
@@ -581,15 +1217,23 @@ struct PyOggVorbis {
 impl PyOggVorbis {
     #[inline(always)]
     fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
+        Self::from_data_with(py, data, filename, ParseOptions::default())
+    }
+
+    fn from_data_with(py: Python<'_>, data: &[u8], filename: &str, options: ParseOptions) -> PyResult<Self> {
         let mut ogg_file = ogg::OggVorbisFile::parse(data, filename)?;
-        ogg_file.ensure_full_parse(data);
-        ogg_file.ensure_tags();
+        ogg_file.ensure_full_parse_with(data, options)?;
+        ogg_file.ensure_tags_with(options)?;
 
         let info = PyOggVorbisInfo {
             length: ogg_file.info.length,
             channels: ogg_file.info.channels,
             sample_rate: ogg_file.info.sample_rate,
             bitrate: ogg_file.info.bitrate,
+            bitrate_nominal: ogg_file.info.bitrate_nominal as i32,
+            bitrate_upper: ogg_file.info.bitrate_max as i32,
+            bitrate_lower: ogg_file.info.bitrate_min as i32,
+            bitrate_mode: ogg_file.info.bitrate_mode as u8,
         };
 
         // Pre-build Python dict of all tags
@@ -610,6 +1254,7 @@ impl PyOggVorbis {
         Ok(PyOggVorbis {
             info,
             filename: filename.to_string(),
+            audio_offset: ogg_file.audio_offset,
             vc,
             tag_dict: tag_dict.into(),
             tag_keys,
@@ -626,6 +1271,12 @@ impl PyOggVorbis {
         Self::from_data(py, &data, filename)
     }
 
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data(py, bytes, ""))
+    }
+
     #[getter]
     fn tags(&self, py: Python) -> PyResult<PyObject> {
         let vc = self.vc.clone();
@@ -649,6 +1300,13 @@ impl PyOggVorbis {
         self.tag_dict.bind(py).get_item(key).ok().flatten().is_some()
     }
 
+    /// Length, sample rate, channels, bitrate and tags as a JSON object
+    /// string, in the per-file shape of `BatchResult.to_dict`.
+    fn to_json(&self) -> String {
+        file_to_json(self.info.length, self.info.sample_rate, self.info.channels as u32,
+                     Some(self.info.bitrate), vc_to_batch(&self.vc.vc))
+    }
+
     fn __repr__(&self) -> String {
         format!("OggVorbis(filename={:?})", self.filename)
     }
@@ -658,28 +1316,133 @@ impl PyOggVorbis {
     }
 }
 
-/// MP4 file info.
-#[pyclass(name = "MP4Info")]
-#[derive(Debug, Clone)]
-struct PyMP4Info {
-    #[pyo3(get)]
-    length: f64,
-    #[pyo3(get)]
-    channels: u32,
-    #[pyo3(get)]
-    sample_rate: u32,
+/// Ogg FLAC file.
+#[pyclass(name = "OggFLAC")]
+struct PyOggFLAC {
     #[pyo3(get)]
-    bitrate: u32,
+    info: PyStreamInfo,
     #[pyo3(get)]
-    bits_per_sample: u32,
+    filename: String,
+    vc: Option<PyVComment>,
+    tag_dict: Py<PyDict>,
+    tag_keys: Vec<String>,
+}
+
+impl PyOggFLAC {
+    fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
+        let f = oggflac::OggFLACFile::parse(data, filename)?;
+
+        let info = PyStreamInfo {
+            length: f.info.length,
+            channels: f.info.channels,
+            sample_rate: f.info.sample_rate,
+            bits_per_sample: f.info.bits_per_sample,
+            total_samples: f.info.total_samples,
+            min_block_size: f.info.min_block_size,
+            max_block_size: f.info.max_block_size,
+            min_frame_size: f.info.min_frame_size,
+            max_frame_size: f.info.max_frame_size,
+            bitrate_mode: f.info.bitrate_mode() as u8,
+        };
+
+        // Pre-build Python dict of all tags
+        let tag_dict = PyDict::new(py);
+        let mut tag_keys = Vec::new();
+        if let Some(ref tags) = f.tags {
+            tag_keys = tags.keys();
+            for key in &tag_keys {
+                let values = tags.get(key);
+                if !values.is_empty() {
+                    let _ = tag_dict.set_item(key.as_str(), PyList::new(py, values)?);
+                }
+            }
+        }
+
+        Ok(PyOggFLAC {
+            info,
+            filename: filename.to_string(),
+            vc: f.tags.map(|vc| PyVComment { vc, path: Some(filename.to_string()) }),
+            tag_dict: tag_dict.into(),
+            tag_keys,
+        })
+    }
+}
+
+#[pymethods]
+impl PyOggFLAC {
+    #[new]
+    fn new(py: Python<'_>, filename: &str) -> PyResult<Self> {
+        let data = read_cached(filename)
+            .map_err(|e| PyIOError::new_err(format!("{}", e)))?;
+        Self::from_data(py, &data, filename)
+    }
+
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data(py, bytes, ""))
+    }
+
+    #[getter]
+    fn tags(&self, py: Python) -> PyResult<Py<PyAny>> {
+        match self.vc {
+            Some(ref vc) => Ok(vc.clone().into_pyobject(py)?.into_any().unbind()),
+            None => Ok(py.None()),
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.tag_keys.clone()
+    }
+
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<Py<PyAny>> {
+        let dict = self.tag_dict.bind(py);
+        match dict.get_item(key)? {
+            Some(val) => Ok(val.unbind()),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __contains__(&self, py: Python, key: &str) -> bool {
+        self.tag_dict.bind(py).get_item(key).ok().flatten().is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("OggFLAC(filename={:?})", self.filename)
+    }
+}
+
+/// MP4 file info.
+#[pyclass(name = "MP4Info")]
+#[derive(Debug, Clone)]
+struct PyMP4Info {
+    #[pyo3(get)]
+    length: f64,
+    #[pyo3(get)]
+    channels: u32,
+    #[pyo3(get)]
+    sample_rate: u32,
+    #[pyo3(get)]
+    bitrate: u32,
+    #[pyo3(get)]
+    bits_per_sample: u32,
     #[pyo3(get)]
     codec: String,
     #[pyo3(get)]
     codec_description: String,
+    #[pyo3(get)]
+    bitrate_mode: u8,
 }
 
 #[pymethods]
 impl PyMP4Info {
+    /// True for ALAC (and FLAC in MP4), whose `bits_per_sample` is the real
+    /// sample depth; for AAC that field is the sample entry's nominal 16.
+    #[getter]
+    fn lossless(&self) -> bool {
+        matches!(self.codec.as_str(), "alac" | "fLaC")
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "MP4Info(length={:.2}, codec={}, channels={}, sample_rate={})",
@@ -689,30 +1452,686 @@ impl PyMP4Info {
 
     fn pprint(&self) -> String {
         format!(
-            "MPEG-4 audio ({}), {:.2} seconds, {} bps",
-            self.codec, self.length, self.bitrate
+            "MPEG-4 audio ({}), {:.2} seconds, {} bps",
+            self.codec, self.length, self.bitrate
+        )
+    }
+}
+
+/// MP4 tags.
+#[pyclass(name = "MP4Tags")]
+#[derive(Debug, Clone)]
+struct PyMP4Tags {
+    tags: mp4::MP4Tags,
+}
+
+impl PyMP4Tags {
+    /// Store raw payloads as cover images under `covr`, as freeform data otherwise.
+    fn set_bytes(&mut self, key: &str, values: Vec<Vec<u8>>) -> PyResult<()> {
+        if key == "covr" {
+            self.tags.set_covers(values)?;
+        } else {
+            self.tags.set_freeform(key, values)?;
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl PyMP4Tags {
+    fn keys(&self) -> Vec<String> {
+        self.tags.keys()
+    }
+
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<PyObject> {
+        match self.tags.get(key) {
+            Some(value) => mp4_value_to_py(py, value),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    /// Like `tags[key]`, but returns `default` for a missing key.
+    #[pyo3(signature = (key, default=None))]
+    fn get(&self, py: Python, key: &str, default: Option<Py<PyAny>>) -> PyResult<Py<PyAny>> {
+        match self.tags.get(key) {
+            Some(value) => mp4_value_to_py(py, value),
+            None => Ok(default.unwrap_or_else(|| py.None())),
+        }
+    }
+
+    /// Typed assignment: `bool`, `int`, `(int, int)`, `bytes` (cover or
+    /// freeform), `str`, or a list of any one of these.
+    fn __setitem__(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        if value.is_instance_of::<pyo3::types::PyBool>() {
+            self.tags.set_bool(key, value.extract()?)?;
+        } else if let Ok(n) = value.extract::<i64>() {
+            self.tags.set_int(key, n)?;
+        } else if let Ok(pair) = value.extract::<(i32, i32)>() {
+            self.tags.set_pair(key, pair)?;
+        } else if let Ok(data) = value.cast::<PyBytes>() {
+            self.set_bytes(key, vec![data.as_bytes().to_vec()])?;
+        } else if let Ok(text) = value.extract::<String>() {
+            self.tags.set_text(key, vec![text])?;
+        } else if let Ok(texts) = value.extract::<Vec<String>>() {
+            self.tags.set_text(key, texts)?;
+        } else if let Ok(numbers) = value.extract::<Vec<i64>>() {
+            self.tags.set(key, mp4::MP4TagValue::Integer(numbers))?;
+        } else if let Ok(pairs) = value.extract::<Vec<(i32, i32)>>() {
+            self.tags.set(key, mp4::MP4TagValue::IntPair(pairs))?;
+        } else if let Ok(items) = value.extract::<Vec<Bound<'_, PyBytes>>>() {
+            self.set_bytes(key, items.iter().map(|b| b.as_bytes().to_vec()).collect())?;
+        } else {
+            return Err(PyValueError::new_err(format!("unsupported value for MP4 key {:?}", key)));
+        }
+        Ok(())
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        self.tags.contains_key(key)
+    }
+
+    fn __len__(&self) -> usize {
+        self.tags.items.len()
+    }
+
+    fn __iter__(&self, py: Python) -> PyResult<PyObject> {
+        let keys = self.tags.keys();
+        let list = PyList::new(py, &keys)?;
+        Ok(list.call_method0("__iter__")?.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MP4Tags(keys={})", self.tags.keys().join(", "))
+    }
+
+    /// Release date from `©day` (date part only).
+    fn date(&self) -> Option<String> {
+        self.tags.date()
+    }
+
+    /// ISRC from `ISRC` or the iTunes `----:com.apple.iTunes:ISRC` freeform item.
+    fn isrc(&self) -> Option<String> {
+        self.tags.isrc()
+    }
+}
+
+/// MP4 file.
+#[pyclass(name = "MP4")]
+struct PyMP4 {
+    #[pyo3(get)]
+    info: PyMP4Info,
+    #[pyo3(get)]
+    filename: String,
+    /// Byte offset where the audio data starts.
+    #[pyo3(get)]
+    audio_offset: usize,
+    mp4_tags: PyMP4Tags,
+    chapters: Vec<(f64, String)>,
+    tag_dict: Py<PyDict>,
+    tag_keys: Vec<String>,
+}
+
+impl PyMP4 {
+    #[inline(always)]
+    fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
+        let mut mp4_file = mp4::MP4File::parse(data, filename)?;
+        mp4_file.ensure_parsed_with_data(data);
+        let chapters = mp4_file.chapters().to_vec();
+
+        let info = PyMP4Info {
+            length: mp4_file.info.length,
+            channels: mp4_file.info.channels,
+            sample_rate: mp4_file.info.sample_rate,
+            bitrate: mp4_file.info.bitrate,
+            bits_per_sample: mp4_file.info.bits_per_sample,
+            codec: mp4_file.info.codec,
+            codec_description: mp4_file.info.codec_description,
+            bitrate_mode: mp4_file.info.bitrate_mode as u8,
+        };
+
+        // Pre-build Python dict of all tags
+        let tag_dict = PyDict::new(py);
+        let tag_keys = mp4_file.tags.keys();
+        for key in &tag_keys {
+            if let Some(value) = mp4_file.tags.get(key) {
+                if let Ok(py_val) = mp4_value_to_py(py, value) {
+                    let _ = tag_dict.set_item(key.as_str(), py_val);
+                }
+            }
+        }
+
+        let mp4_tags = PyMP4Tags {
+            tags: mp4_file.tags,
+        };
+
+        Ok(PyMP4 {
+            info,
+            filename: filename.to_string(),
+            audio_offset: mp4_file.audio_offset,
+            mp4_tags,
+            chapters,
+            tag_dict: tag_dict.into(),
+            tag_keys,
+        })
+    }
+}
+
+#[pymethods]
+impl PyMP4 {
+    #[new]
+    fn new(py: Python<'_>, filename: &str) -> PyResult<Self> {
+        let data = read_cached(filename)
+            .map_err(|e| PyIOError::new_err(format!("{}", e)))?;
+        Self::from_data(py, &data, filename)
+    }
+
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data(py, bytes, ""))
+    }
+
+    #[getter]
+    fn tags(&self, py: Python) -> PyResult<PyObject> {
+        let tags = self.mp4_tags.clone();
+        Ok(tags.into_pyobject(py)?.into_any().unbind())
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.tag_keys.clone()
+    }
+
+    #[inline(always)]
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<PyObject> {
+        let dict = self.tag_dict.bind(py);
+        match dict.get_item(key)? {
+            Some(val) => Ok(val.unbind()),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __contains__(&self, py: Python, key: &str) -> bool {
+        self.tag_dict.bind(py).get_item(key).ok().flatten().is_some()
+    }
+
+    /// Length, sample rate, channels, bitrate and tags as a JSON object
+    /// string, in the per-file shape of `BatchResult.to_dict`.
+    fn to_json(&self) -> String {
+        let tags = self.mp4_tags.tags.items.iter()
+            .map(|(key, value)| (key.clone(), mp4_value_to_batch(value)))
+            .collect();
+        file_to_json(self.info.length, self.info.sample_rate, self.info.channels,
+                     Some(self.info.bitrate), tags)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MP4(filename={:?})", self.filename)
+    }
+
+    /// Chapters as `(start_seconds, title)` pairs, from a Nero `chpl`
+    /// atom or a QuickTime chapter track.
+    fn chapters(&self) -> Vec<(f64, String)> {
+        self.chapters.clone()
+    }
+
+    /// `(track, total)` from `trkn`.
+    fn track_number(&self) -> (Option<u32>, Option<u32>) {
+        self.mp4_tags.tags.track_number()
+    }
+
+    /// `(disc, total)` from `disk`.
+    fn disc_number(&self) -> (Option<u32>, Option<u32>) {
+        self.mp4_tags.tags.disc_number()
+    }
+
+    /// ReplayGain from the iTunes freeform `replaygain_*` items, as a dict
+    /// with `track_gain`, `track_peak`, `album_gain` and `album_peak`
+    /// (None where missing or unparseable).
+    fn replaygain<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let rg = self.mp4_tags.tags.replaygain();
+        let dict = PyDict::new(py);
+        dict.set_item("track_gain", rg.track_gain)?;
+        dict.set_item("track_peak", rg.track_peak)?;
+        dict.set_item("album_gain", rg.album_gain)?;
+        dict.set_item("album_peak", rg.album_peak)?;
+        Ok(dict)
+    }
+}
+
+/// APEv2 tags (used by WavPack and Monkey's Audio, and found at the end of MP3s).
+#[pyclass(name = "APEv2")]
+#[derive(Debug, Clone)]
+struct PyAPEv2 {
+    tags: apev2::APEv2Tags,
+    path: Option<String>,
+}
+
+#[pymethods]
+impl PyAPEv2 {
+    /// Load the APEv2 tag from `filename`, or start an empty tag.
+    #[new]
+    #[pyo3(signature = (filename=None))]
+    fn new(filename: Option<&str>) -> PyResult<Self> {
+        let Some(filename) = filename else {
+            return Ok(PyAPEv2 { tags: apev2::APEv2Tags::new(), path: None });
+        };
+        let data = read_cached(filename)
+            .map_err(|e| PyIOError::new_err(format!("{}", e)))?;
+        let tags = apev2::APEv2Tags::from_data(&data)?
+            .ok_or_else(|| common::error::MutagenError::APE(format!("no APE tag found in {}", filename)))?;
+        Ok(PyAPEv2 { tags, path: Some(filename.to_string()) })
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.tags.keys()
+    }
+
+    /// `str` or a list of `str` sets a text item; `bytes` sets a binary one.
+    fn __setitem__(&mut self, key: &str, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let value = if let Ok(b) = value.cast::<PyBytes>() {
+            apev2::APEValue::Binary(b.as_bytes().to_vec())
+        } else if let Ok(s) = value.extract::<String>() {
+            apev2::APEValue::Text(vec![s])
+        } else {
+            apev2::APEValue::Text(value.extract::<Vec<String>>()?)
+        };
+        self.tags.set(key, value)?;
+        Ok(())
+    }
+
+    fn __delitem__(&mut self, key: &str) -> PyResult<()> {
+        if !self.tags.delete(key) {
+            return Err(PyKeyError::new_err(key.to_string()));
+        }
+        Ok(())
+    }
+
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<Py<PyAny>> {
+        match self.tags.get(key) {
+            Some(value) => ape_value_to_py(py, value),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        self.tags.contains_key(key)
+    }
+
+    fn __len__(&self) -> usize {
+        self.tags.len()
+    }
+
+    fn __iter__(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let keys = self.tags.keys();
+        let list = PyList::new(py, &keys)?;
+        Ok(list.call_method0("__iter__")?.into())
+    }
+
+    fn __repr__(&self) -> String {
+        format!("APEv2(keys={})", self.tags.keys().join(", "))
+    }
+
+    /// Write the tag to the end of the file, replacing an existing APEv2 tag
+    /// and keeping any ID3v1 tag last. With `preserve_mtime`, the file keeps
+    /// its modification time.
+    #[pyo3(signature = (filename=None, preserve_mtime=false))]
+    fn save(&self, filename: Option<&str>, preserve_mtime: bool) -> PyResult<()> {
+        let path = filename
+            .map(|s| s.to_string())
+            .or_else(|| self.path.clone())
+            .ok_or_else(|| PyValueError::new_err("No filename specified"))?;
+        common::util::preserving_mtime(&path, preserve_mtime, || apev2::save_apev2(&path, &self.tags))?;
+        Ok(())
+    }
+
+    /// Remove the APEv2 tag from the file.
+    #[pyo3(signature = (filename=None))]
+    fn delete(&self, filename: Option<&str>) -> PyResult<()> {
+        let path = filename
+            .map(|s| s.to_string())
+            .or_else(|| self.path.clone())
+            .ok_or_else(|| PyValueError::new_err("No filename specified"))?;
+        apev2::delete_apev2(&path)?;
+        Ok(())
+    }
+}
+
+/// WavPack stream info.
+#[pyclass(name = "WavPackInfo")]
+#[derive(Debug, Clone)]
+struct PyWavPackInfo {
+    #[pyo3(get)]
+    length: f64,
+    #[pyo3(get)]
+    channels: u32,
+    #[pyo3(get)]
+    sample_rate: u32,
+    #[pyo3(get)]
+    bits_per_sample: u32,
+    #[pyo3(get)]
+    bitrate: u32,
+    #[pyo3(get)]
+    version: u16,
+    /// False for hybrid (lossy) streams.
+    #[pyo3(get)]
+    lossless: bool,
+}
+
+#[pymethods]
+impl PyWavPackInfo {
+    fn __repr__(&self) -> String {
+        format!(
+            "WavPackInfo(length={:.2}, sample_rate={}, channels={})",
+            self.length, self.sample_rate, self.channels
+        )
+    }
+
+    fn pprint(&self) -> String {
+        format!(
+            "WavPack, {:.2} seconds, {} Hz",
+            self.length, self.sample_rate
+        )
+    }
+}
+
+/// WavPack file.
+#[pyclass(name = "WavPack")]
+struct PyWavPack {
+    #[pyo3(get)]
+    info: PyWavPackInfo,
+    #[pyo3(get)]
+    filename: String,
+    ape_tags: Option<PyAPEv2>,
+    tag_dict: Py<PyDict>,
+    tag_keys: Vec<String>,
+}
+
+impl PyWavPack {
+    #[inline(always)]
+    fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
+        let wv_file = wavpack::WavPackFile::parse(data, filename)?;
+
+        let info = PyWavPackInfo {
+            length: wv_file.info.length,
+            channels: wv_file.info.channels,
+            sample_rate: wv_file.info.sample_rate,
+            bits_per_sample: wv_file.info.bits_per_sample,
+            bitrate: wv_file.info.bitrate,
+            version: wv_file.info.version,
+            lossless: wv_file.info.lossless,
+        };
+
+        // Pre-build Python dict of all tags
+        let tag_dict = PyDict::new(py);
+        let mut tag_keys = Vec::new();
+        if let Some(ref tags) = wv_file.tags {
+            for (key, value) in &tags.items {
+                if let Ok(py_val) = ape_value_to_py(py, value) {
+                    let _ = tag_dict.set_item(key.as_str(), py_val);
+                }
+                tag_keys.push(key.clone());
+            }
+        }
+
+        Ok(PyWavPack {
+            info,
+            filename: filename.to_string(),
+            ape_tags: wv_file.tags.map(|tags| PyAPEv2 { tags, path: Some(filename.to_string()) }),
+            tag_dict: tag_dict.into(),
+            tag_keys,
+        })
+    }
+}
+
+#[pymethods]
+impl PyWavPack {
+    #[new]
+    fn new(py: Python<'_>, filename: &str) -> PyResult<Self> {
+        let data = read_cached(filename)
+            .map_err(|e| PyIOError::new_err(format!("{}", e)))?;
+        Self::from_data(py, &data, filename)
+    }
+
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data(py, bytes, ""))
+    }
+
+    #[getter]
+    fn tags(&self, py: Python) -> PyResult<Py<PyAny>> {
+        match self.ape_tags {
+            Some(ref tags) => Ok(tags.clone().into_pyobject(py)?.into_any().unbind()),
+            None => Ok(py.None()),
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.tag_keys.clone()
+    }
+
+    #[inline(always)]
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<Py<PyAny>> {
+        let dict = self.tag_dict.bind(py);
+        match dict.get_item(key)? {
+            Some(val) => Ok(val.unbind()),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __contains__(&self, py: Python, key: &str) -> bool {
+        self.tag_dict.bind(py).get_item(key).ok().flatten().is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("WavPack(filename={:?})", self.filename)
+    }
+}
+
+/// Monkey's Audio stream info.
+#[pyclass(name = "MonkeysAudioInfo")]
+#[derive(Debug, Clone)]
+struct PyMonkeysAudioInfo {
+    #[pyo3(get)]
+    length: f64,
+    #[pyo3(get)]
+    channels: u32,
+    #[pyo3(get)]
+    sample_rate: u32,
+    #[pyo3(get)]
+    bits_per_sample: u32,
+    #[pyo3(get)]
+    bitrate: u32,
+    #[pyo3(get)]
+    version: f64,
+    #[pyo3(get)]
+    compression_level: u16,
+}
+
+#[pymethods]
+impl PyMonkeysAudioInfo {
+    #[getter]
+    fn lossless(&self) -> bool {
+        true
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MonkeysAudioInfo(length={:.2}, sample_rate={}, channels={})",
+            self.length, self.sample_rate, self.channels
+        )
+    }
+
+    fn pprint(&self) -> String {
+        format!(
+            "Monkey's Audio {:.2}, {:.2} seconds, {} Hz",
+            self.version, self.length, self.sample_rate
+        )
+    }
+}
+
+/// Monkey's Audio (APE) file.
+#[pyclass(name = "MonkeysAudio")]
+struct PyMonkeysAudio {
+    #[pyo3(get)]
+    info: PyMonkeysAudioInfo,
+    #[pyo3(get)]
+    filename: String,
+    ape_tags: Option<PyAPEv2>,
+    tag_dict: Py<PyDict>,
+    tag_keys: Vec<String>,
+}
+
+impl PyMonkeysAudio {
+    #[inline(always)]
+    fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
+        let ma_file = monkeysaudio::MonkeysAudioFile::parse(data, filename)?;
+
+        let info = PyMonkeysAudioInfo {
+            length: ma_file.info.length,
+            channels: ma_file.info.channels,
+            sample_rate: ma_file.info.sample_rate,
+            bits_per_sample: ma_file.info.bits_per_sample,
+            bitrate: ma_file.info.bitrate,
+            version: ma_file.info.version,
+            compression_level: ma_file.info.compression_level,
+        };
+
+        // Pre-build Python dict of all tags
+        let tag_dict = PyDict::new(py);
+        let mut tag_keys = Vec::new();
+        if let Some(ref tags) = ma_file.tags {
+            for (key, value) in &tags.items {
+                if let Ok(py_val) = ape_value_to_py(py, value) {
+                    let _ = tag_dict.set_item(key.as_str(), py_val);
+                }
+                tag_keys.push(key.clone());
+            }
+        }
+
+        Ok(PyMonkeysAudio {
+            info,
+            filename: filename.to_string(),
+            ape_tags: ma_file.tags.map(|tags| PyAPEv2 { tags, path: Some(filename.to_string()) }),
+            tag_dict: tag_dict.into(),
+            tag_keys,
+        })
+    }
+}
+
+#[pymethods]
+impl PyMonkeysAudio {
+    #[new]
+    fn new(py: Python<'_>, filename: &str) -> PyResult<Self> {
+        let data = read_cached(filename)
+            .map_err(|e| PyIOError::new_err(format!("{}", e)))?;
+        Self::from_data(py, &data, filename)
+    }
+
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data(py, bytes, ""))
+    }
+
+    #[getter]
+    fn tags(&self, py: Python) -> PyResult<Py<PyAny>> {
+        match self.ape_tags {
+            Some(ref tags) => Ok(tags.clone().into_pyobject(py)?.into_any().unbind()),
+            None => Ok(py.None()),
+        }
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.tag_keys.clone()
+    }
+
+    #[inline(always)]
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<Py<PyAny>> {
+        let dict = self.tag_dict.bind(py);
+        match dict.get_item(key)? {
+            Some(val) => Ok(val.unbind()),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    fn __contains__(&self, py: Python, key: &str) -> bool {
+        self.tag_dict.bind(py).get_item(key).ok().flatten().is_some()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("MonkeysAudio(filename={:?})", self.filename)
+    }
+}
+
+/// ASF stream info.
+#[pyclass(name = "ASFInfo")]
+#[derive(Debug, Clone)]
+struct PyASFInfo {
+    #[pyo3(get)]
+    length: f64,
+    #[pyo3(get)]
+    channels: u32,
+    #[pyo3(get)]
+    sample_rate: u32,
+    #[pyo3(get)]
+    bitrate: u32,
+    #[pyo3(get)]
+    codec_type: String,
+    #[pyo3(get)]
+    codec_name: String,
+    #[pyo3(get)]
+    codec_description: String,
+}
+
+#[pymethods]
+impl PyASFInfo {
+    #[getter]
+    fn bits_per_sample(&self) -> Option<u32> {
+        None
+    }
+
+    /// True for WMA Lossless.
+    #[getter]
+    fn lossless(&self) -> bool {
+        self.codec_type.contains("Lossless")
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ASFInfo(length={:.2}, sample_rate={}, channels={})",
+            self.length, self.sample_rate, self.channels
+        )
+    }
+
+    fn pprint(&self) -> String {
+        format!(
+            "{}, {} bps, {} Hz, {} channels, {:.2} seconds",
+            if self.codec_type.is_empty() { "ASF" } else { &self.codec_type },
+            self.bitrate, self.sample_rate, self.channels, self.length
         )
     }
 }
 
-/// MP4 tags.
-#[pyclass(name = "MP4Tags")]
+/// ASF attributes. Every key maps to a list of values.
+#[pyclass(name = "ASFTags")]
 #[derive(Debug, Clone)]
-struct PyMP4Tags {
-    tags: mp4::MP4Tags,
+struct PyASFTags {
+    tags: asf::ASFTags,
 }
 
 #[pymethods]
-impl PyMP4Tags {
+impl PyASFTags {
     fn keys(&self) -> Vec<String> {
         self.tags.keys()
     }
 
-    fn __getitem__(&self, py: Python, key: &str) -> PyResult<PyObject> {
-        match self.tags.get(key) {
-            Some(value) => mp4_value_to_py(py, value),
-            None => Err(PyKeyError::new_err(key.to_string())),
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<Py<PyAny>> {
+        let values = self.tags.get(key);
+        if values.is_empty() {
+            return Err(PyKeyError::new_err(key.to_string()));
         }
+        asf_values_to_py(py, &values)
     }
 
     fn __contains__(&self, key: &str) -> bool {
@@ -720,67 +2139,61 @@ impl PyMP4Tags {
     }
 
     fn __len__(&self) -> usize {
-        self.tags.items.len()
+        self.tags.len()
     }
 
-    fn __iter__(&self, py: Python) -> PyResult<PyObject> {
+    fn __iter__(&self, py: Python) -> PyResult<Py<PyAny>> {
         let keys = self.tags.keys();
         let list = PyList::new(py, &keys)?;
         Ok(list.call_method0("__iter__")?.into())
     }
 
     fn __repr__(&self) -> String {
-        format!("MP4Tags(keys={})", self.tags.keys().join(", "))
+        format!("ASFTags(keys={})", self.tags.keys().join(", "))
     }
 }
 
-/// MP4 file.
-#[pyclass(name = "MP4")]
-struct PyMP4 {
+/// ASF (WMA) file.
+#[pyclass(name = "ASF")]
+struct PyASF {
     #[pyo3(get)]
-    info: PyMP4Info,
+    info: PyASFInfo,
     #[pyo3(get)]
     filename: String,
-    mp4_tags: PyMP4Tags,
+    asf_tags: PyASFTags,
     tag_dict: Py<PyDict>,
     tag_keys: Vec<String>,
 }
 
-impl PyMP4 {
+impl PyASF {
     #[inline(always)]
     fn from_data(py: Python<'_>, data: &[u8], filename: &str) -> PyResult<Self> {
-        let mut mp4_file = mp4::MP4File::parse(data, filename)?;
-        mp4_file.ensure_parsed_with_data(data);
-
-        let info = PyMP4Info {
-            length: mp4_file.info.length,
-            channels: mp4_file.info.channels,
-            sample_rate: mp4_file.info.sample_rate,
-            bitrate: mp4_file.info.bitrate,
-            bits_per_sample: mp4_file.info.bits_per_sample,
-            codec: mp4_file.info.codec,
-            codec_description: mp4_file.info.codec_description,
+        let asf_file = asf::ASFFile::parse(data, filename)?;
+
+        let info = PyASFInfo {
+            length: asf_file.info.length,
+            channels: asf_file.info.channels,
+            sample_rate: asf_file.info.sample_rate,
+            bitrate: asf_file.info.bitrate,
+            codec_type: asf_file.info.codec_type,
+            codec_name: asf_file.info.codec_name,
+            codec_description: asf_file.info.codec_description,
         };
 
         // Pre-build Python dict of all tags
         let tag_dict = PyDict::new(py);
-        let tag_keys = mp4_file.tags.keys();
+        let tag_keys = asf_file.tags.keys();
         for key in &tag_keys {
-            if let Some(value) = mp4_file.tags.get(key) {
-                if let Ok(py_val) = mp4_value_to_py(py, value) {
-                    let _ = tag_dict.set_item(key.as_str(), py_val);
-                }
+            let values = asf_file.tags.get(key);
+            if let Ok(py_val) = asf_values_to_py(py, &values) {
+                let _ = tag_dict.set_item(key.as_str(), py_val);
             }
         }
 
-        let mp4_tags = PyMP4Tags {
-            tags: mp4_file.tags,
-        };
-
-        Ok(PyMP4 {
+        Ok(PyASF {
             info,
             filename: filename.to_string(),
-            mp4_tags,
+            asf_tags: PyASFTags { tags: asf_file.tags },
             tag_dict: tag_dict.into(),
             tag_keys,
         })
@@ -788,7 +2201,7 @@ impl PyMP4 {
 }
 
 #[pymethods]
-impl PyMP4 {
+impl PyASF {
     #[new]
     fn new(py: Python<'_>, filename: &str) -> PyResult<Self> {
         let data = read_cached(filename)
@@ -796,9 +2209,15 @@ impl PyMP4 {
         Self::from_data(py, &data, filename)
     }
 
+    /// Parse an in-memory buffer (`bytes`, `bytearray` or `memoryview`).
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &Bound<'_, PyAny>) -> PyResult<Self> {
+        with_buffer(data, |bytes| Self::from_data(py, bytes, ""))
+    }
+
     #[getter]
-    fn tags(&self, py: Python) -> PyResult<PyObject> {
-        let tags = self.mp4_tags.clone();
+    fn tags(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let tags = self.asf_tags.clone();
         Ok(tags.into_pyobject(py)?.into_any().unbind())
     }
 
@@ -807,7 +2226,7 @@ impl PyMP4 {
     }
 
     #[inline(always)]
-    fn __getitem__(&self, py: Python, key: &str) -> PyResult<PyObject> {
+    fn __getitem__(&self, py: Python, key: &str) -> PyResult<Py<PyAny>> {
         let dict = self.tag_dict.bind(py);
         match dict.get_item(key)? {
             Some(val) => Ok(val.unbind()),
@@ -820,12 +2239,20 @@ impl PyMP4 {
     }
 
     fn __repr__(&self) -> String {
-        format!("MP4(filename={:?})", self.filename)
+        format!("ASF(filename={:?})", self.filename)
     }
 }
 
 // ---- Helper functions ----
 
+fn separator_policy(legacy: bool) -> id3::frames::SeparatorPolicy {
+    if legacy {
+        id3::frames::SeparatorPolicy::Legacy
+    } else {
+        id3::frames::SeparatorPolicy::NullOnly
+    }
+}
+
 #[inline(always)]
 fn make_mpeg_info(info: &mp3::MPEGInfo) -> PyMPEGInfo {
     PyMPEGInfo {
@@ -836,18 +2263,19 @@ fn make_mpeg_info(info: &mp3::MPEGInfo) -> PyMPEGInfo {
         version: info.version,
         layer: info.layer,
         mode: info.mode,
+        mode_label: info.mode_str(),
+        mode_extension: info.mode_extension,
+        intensity_stereo: info.intensity_stereo(),
+        ms_stereo: info.ms_stereo(),
         protected: info.protected,
-        bitrate_mode: match info.bitrate_mode {
-            mp3::xing::BitrateMode::Unknown => 0,
-            mp3::xing::BitrateMode::CBR => 1,
-            mp3::xing::BitrateMode::VBR => 2,
-            mp3::xing::BitrateMode::ABR => 3,
-        },
+        bitrate_mode: info.bitrate_mode as u8,
         encoder_info: info.encoder_info.clone(),
         encoder_settings: info.encoder_settings.clone(),
         track_gain: info.track_gain,
         track_peak: info.track_peak,
         album_gain: info.album_gain,
+        decoded_length: info.decoded_length,
+        has_audio: info.has_audio,
     }
 }
 
@@ -905,9 +2333,77 @@ fn frame_to_py(py: Python, frame: &id3::frames::Frame) -> PyObject {
             let list = PyList::new(py, &pairs).unwrap();
             list.into_any().unbind()
         }
+        id3::frames::Frame::Flag(f) => {
+            pyo3::types::PyBool::new(py, f.is_set()).to_owned().into_any().unbind()
+        }
+        id3::frames::Frame::Seek(f) => {
+            f.offset.into_pyobject(py).unwrap().into_any().unbind()
+        }
+        id3::frames::Frame::AudioSeekPointIndex(f) => {
+            let dict = PyDict::new(py);
+            dict.set_item("data_start", f.data_start).unwrap();
+            dict.set_item("data_length", f.data_length).unwrap();
+            dict.set_item("bits_per_point", f.bits_per_point).unwrap();
+            dict.set_item("fractions", &f.fractions).unwrap();
+            dict.into_any().unbind()
+        }
     }
 }
 
+/// Build an APIC or COMM frame for `ID3.setall` from a dict (or a plain
+/// string for comments).
+fn py_to_frame(key: &str, value: &Bound<'_, PyAny>) -> PyResult<id3::frames::Frame> {
+    let mut parts = key.splitn(3, ':');
+    let id = parts.next().unwrap_or_default();
+    let key_desc = parts.next().unwrap_or_default();
+    let key_lang = parts.next().unwrap_or("eng");
+
+    if id == "COMM" {
+        if let Ok(text) = value.extract::<String>() {
+            return Ok(id3::frames::Frame::comment(key_lang, key_desc, &text));
+        }
+    }
+    let dict = value.cast::<PyDict>()?;
+    let field = |name: &str| dict.get_item(name);
+
+    if id == "APIC" {
+        let rest = key.strip_prefix("APIC:").unwrap_or_default();
+        let data = field("data")?
+            .ok_or_else(|| PyValueError::new_err("picture dict needs 'data'"))?
+            .extract::<Vec<u8>>()?;
+        let mime = match field("mime")? {
+            Some(m) => m.extract::<String>()?,
+            None => "image/jpeg".to_string(),
+        };
+        // An explicit type says whether the key ends in an appended `:type`
+        let (key_desc, pic_type) = match field("type")? {
+            Some(t) => {
+                let pic_type = t.extract::<u8>()?;
+                (id3::frames::picture_key_desc(rest, pic_type).unwrap_or(rest), pic_type)
+            }
+            None => id3::frames::split_picture_key(rest),
+        };
+        let desc = match field("desc")? {
+            Some(d) => d.extract::<String>()?,
+            None => key_desc.to_string(),
+        };
+        return Ok(id3::frames::Frame::picture(&mime, id3::specs::PictureType::from_byte(pic_type), &desc, data));
+    }
+
+    let text = field("text")?
+        .ok_or_else(|| PyValueError::new_err("comment dict needs 'text'"))?
+        .extract::<String>()?;
+    let desc = match field("desc")? {
+        Some(d) => d.extract::<String>()?,
+        None => key_desc.to_string(),
+    };
+    let lang = match field("lang")? {
+        Some(l) => l.extract::<String>()?,
+        None => key_lang.to_string(),
+    };
+    Ok(id3::frames::Frame::comment(&lang, &desc, &text))
+}
+
 #[inline(always)]
 fn mp4_value_to_py(py: Python, value: &mp4::MP4TagValue) -> PyResult<PyObject> {
     match value {
@@ -945,7 +2441,7 @@ fn mp4_value_to_py(py: Python, value: &mp4::MP4TagValue) -> PyResult<PyObject> {
             for cover in covers {
                 let dict = PyDict::new(py);
                 dict.set_item("data", PyBytes::new(py, &cover.data))?;
-                dict.set_item("format", cover.format as u8)?;
+                dict.set_item("format", cover.format.type_indicator())?;
                 list.append(dict)?;
             }
             Ok(list.into_any().unbind())
@@ -963,6 +2459,38 @@ fn mp4_value_to_py(py: Python, value: &mp4::MP4TagValue) -> PyResult<PyObject> {
     }
 }
 
+#[inline(always)]
+fn ape_value_to_py(py: Python, value: &apev2::APEValue) -> PyResult<Py<PyAny>> {
+    match value {
+        apev2::APEValue::Text(v) => {
+            if v.len() == 1 {
+                Ok(v[0].as_str().into_pyobject(py)?.into_any().unbind())
+            } else {
+                Ok(PyList::new(py, v)?.into_any().unbind())
+            }
+        }
+        apev2::APEValue::Binary(d) => Ok(PyBytes::new(py, d).into_any().unbind()),
+        apev2::APEValue::External(s) => Ok(s.as_str().into_pyobject(py)?.into_any().unbind()),
+    }
+}
+
+#[inline(always)]
+fn asf_values_to_py(py: Python, values: &[&asf::ASFValue]) -> PyResult<Py<PyAny>> {
+    let list = PyList::empty(py);
+    for value in values {
+        match value {
+            asf::ASFValue::Unicode(s) => list.append(s.as_str())?,
+            asf::ASFValue::Bytes(d) => list.append(PyBytes::new(py, d))?,
+            asf::ASFValue::Guid(g) => list.append(PyBytes::new(py, g))?,
+            asf::ASFValue::Bool(b) => list.append(*b)?,
+            asf::ASFValue::DWord(n) => list.append(*n)?,
+            asf::ASFValue::QWord(n) => list.append(*n)?,
+            asf::ASFValue::Word(n) => list.append(*n)?,
+        }
+    }
+    Ok(list.into_any().unbind())
+}
+
 // ---- Batch API ----
 
 /// Pre-serialized tag value — all decoding done in parallel phase.
@@ -1031,6 +2559,11 @@ fn frame_to_batch_value(frame: &id3::frames::Frame) -> BatchTagValue {
         },
         id3::frames::Frame::Binary(f) => BatchTagValue::Bytes(f.data.clone()),
         id3::frames::Frame::PairedText(f) => BatchTagValue::PairedText(f.people.clone()),
+        id3::frames::Frame::Flag(f) => BatchTagValue::Bool(f.is_set()),
+        id3::frames::Frame::Seek(f) => BatchTagValue::Int(f.offset as i64),
+        id3::frames::Frame::AudioSeekPointIndex(_) => {
+            BatchTagValue::Bytes(frame.write_data(4).unwrap_or_default())
+        }
     }
 }
 
@@ -1051,13 +2584,18 @@ fn parse_vc_to_batch_tags(data: &[u8]) -> Vec<(String, BatchTagValue)> {
     let count = u32::from_le_bytes([data[pos], data[pos+1], data[pos+2], data[pos+3]]) as usize;
     pos += 4;
 
-    let mut tags: Vec<(String, BatchTagValue)> = Vec::with_capacity(count.min(64));
+    let mut groups = common::groups::KeyGroups::with_capacity(count.min(64));
 
     for _ in 0..count {
         if pos + 4 > data.len() { break; }
         let comment_len = u32::from_le_bytes([data[pos], data[pos+1], data[pos+2], data[pos+3]]) as usize;
         pos += 4;
-        if pos + comment_len > data.len() { break; }
+        if pos + comment_len > data.len() {
+            match vorbis::next_entry(data, pos) {
+                Some(next) => { pos = next; continue; }
+                None => break,
+            }
+        }
 
         let raw = &data[pos..pos + comment_len];
         pos += comment_len;
@@ -1092,17 +2630,11 @@ fn parse_vc_to_batch_tags(data: &[u8]) -> Vec<(String, BatchTagValue)> {
             Err(_) => String::from_utf8_lossy(value_bytes).into_owned(),
         };
 
-        // Group by key (linear scan — fast for typical 5-15 unique keys)
-        if let Some(entry) = tags.iter_mut().find(|(k, _)| k == &key) {
-            if let BatchTagValue::TextList(ref mut v) = entry.1 {
-                v.push(value);
-            }
-        } else {
-            tags.push((key, BatchTagValue::TextList(vec![value])));
-        }
+        // Group by key (linear scan for typical tags, indexed past 16 keys)
+        groups.push(key, value);
     }
 
-    tags
+    groups.into_vec().into_iter().map(|(k, v)| (k, BatchTagValue::TextList(v))).collect()
 }
 
 /// Batch-optimized FLAC parser: skips pictures, direct VC parsing.
@@ -1217,7 +2749,7 @@ fn parse_ogg_batch(data: &[u8], data_arc: Option<&Arc<[u8]>>) -> Option<PreSeria
     let vc_offset = comment_start + 7;
     let vc_size = first_packet_size - 7;
 
-    let length = ogg::find_last_granule(data, serial)
+    let length = ogg::vorbis_sample_count(data, serial)
         .map(|g| if g > 0 && sample_rate > 0 { g as f64 / sample_rate as f64 } else { 0.0 })
         .unwrap_or(0.0);
 
@@ -1257,7 +2789,7 @@ fn mp4_value_to_batch(value: &mp4::MP4TagValue) -> BatchTagValue {
         }
         mp4::MP4TagValue::Bool(v) => BatchTagValue::Bool(*v),
         mp4::MP4TagValue::Cover(covers) => {
-            BatchTagValue::CoverList(covers.iter().map(|c| (c.data.clone(), c.format as u8)).collect())
+            BatchTagValue::CoverList(covers.iter().map(|c| (c.data.clone(), c.format.type_indicator())).collect())
         }
         mp4::MP4TagValue::FreeForm(forms) => {
             BatchTagValue::FreeFormList(forms.iter().map(|f| f.data.clone()).collect())
@@ -1285,12 +2817,7 @@ fn parse_mp3_batch(data: &[u8], path: &str) -> Option<PreSerializedFile> {
         ("layer", BatchTagValue::Int(f.info.layer as i64)),
         ("mode", BatchTagValue::Int(f.info.mode as i64)),
         ("protected", BatchTagValue::Bool(f.info.protected)),
-        ("bitrate_mode", BatchTagValue::Int(match f.info.bitrate_mode {
-            mp3::xing::BitrateMode::Unknown => 0,
-            mp3::xing::BitrateMode::CBR => 1,
-            mp3::xing::BitrateMode::VBR => 2,
-            mp3::xing::BitrateMode::ABR => 3,
-        })),
+        ("bitrate_mode", BatchTagValue::Int(f.info.bitrate_mode as i64)),
     ];
     Some(PreSerializedFile {
         length: f.info.length,
@@ -1314,6 +2841,7 @@ fn parse_mp4_batch(data: &[u8], path: &str) -> Option<PreSerializedFile> {
     }
     let extra = vec![
         ("codec", BatchTagValue::Text(f.info.codec.clone())),
+        ("codec_description", BatchTagValue::Text(f.info.codec_description.clone())),
         ("bits_per_sample", BatchTagValue::Int(f.info.bits_per_sample as i64)),
     ];
     Some(PreSerializedFile {
@@ -1327,43 +2855,125 @@ fn parse_mp4_batch(data: &[u8], path: &str) -> Option<PreSerializedFile> {
     })
 }
 
-/// Parse + fully decode a single file from data (runs in parallel phase).
-/// Uses extension-based fast dispatch to skip unnecessary scoring.
+/// Convert APEv2 items to batch tag values.
+fn ape_tags_to_batch(ape: &apev2::APEv2Tags) -> Vec<(String, BatchTagValue)> {
+    ape.items
+        .iter()
+        .map(|(key, value)| {
+            let bv = match value {
+                apev2::APEValue::Text(v) if v.len() == 1 => BatchTagValue::Text(v[0].clone()),
+                apev2::APEValue::Text(v) => BatchTagValue::TextList(v.clone()),
+                apev2::APEValue::Binary(d) => BatchTagValue::Bytes(d.clone()),
+                apev2::APEValue::External(s) => BatchTagValue::Text(s.clone()),
+            };
+            (key.clone(), bv)
+        })
+        .collect()
+}
+
+/// Parse WavPack data into batch result.
 #[inline(always)]
-fn parse_and_serialize(data: &[u8], path: &str, data_arc: Option<&Arc<[u8]>>) -> Option<PreSerializedFile> {
-    let ext = path.rsplit('.').next().unwrap_or("");
-    if ext.eq_ignore_ascii_case("flac") {
-        return parse_flac_batch(data, data_arc);
-    }
-    if ext.eq_ignore_ascii_case("ogg") {
-        return parse_ogg_batch(data, data_arc);
-    }
-    if ext.eq_ignore_ascii_case("mp3") {
-        return parse_mp3_batch(data, path);
-    }
-    if ext.eq_ignore_ascii_case("m4a") || ext.eq_ignore_ascii_case("m4b")
-        || ext.eq_ignore_ascii_case("mp4") || ext.eq_ignore_ascii_case("m4v") {
-        return parse_mp4_batch(data, path);
-    }
+fn parse_wavpack_batch(data: &[u8], path: &str) -> Option<PreSerializedFile> {
+    let f = wavpack::WavPackFile::parse(data, path).ok()?;
+    let tags = f.tags.as_ref().map(ape_tags_to_batch).unwrap_or_default();
+    let extra = vec![
+        ("bits_per_sample", BatchTagValue::Int(f.info.bits_per_sample as i64)),
+    ];
+    Some(PreSerializedFile {
+        length: f.info.length,
+        sample_rate: f.info.sample_rate,
+        channels: f.info.channels,
+        bitrate: Some(f.info.bitrate),
+        tags,
+        extra,
+        lazy_vc: None,
+    })
+}
+
+/// Parse Monkey's Audio data into batch result.
+#[inline(always)]
+fn parse_monkeysaudio_batch(data: &[u8], path: &str) -> Option<PreSerializedFile> {
+    let f = monkeysaudio::MonkeysAudioFile::parse(data, path).ok()?;
+    let tags = f.tags.as_ref().map(ape_tags_to_batch).unwrap_or_default();
+    let extra = vec![
+        ("bits_per_sample", BatchTagValue::Int(f.info.bits_per_sample as i64)),
+    ];
+    Some(PreSerializedFile {
+        length: f.info.length,
+        sample_rate: f.info.sample_rate,
+        channels: f.info.channels,
+        bitrate: Some(f.info.bitrate),
+        tags,
+        extra,
+        lazy_vc: None,
+    })
+}
 
-    let mp3_score = mp3::MP3File::score(path, data);
-    let flac_score = flac::FLACFile::score(path, data);
-    let ogg_score = ogg::OggVorbisFile::score(path, data);
-    let mp4_score = mp4::MP4File::score(path, data);
-    let max_score = mp3_score.max(flac_score).max(ogg_score).max(mp4_score);
+/// Parse Ogg FLAC data into batch result.
+#[inline(always)]
+fn parse_oggflac_batch(data: &[u8], path: &str) -> Option<PreSerializedFile> {
+    let f = oggflac::OggFLACFile::parse(data, path).ok()?;
+    let tags = f.tags.as_ref().map(vc_to_batch).unwrap_or_default();
+    let extra = vec![
+        ("bits_per_sample", BatchTagValue::Int(f.info.bits_per_sample as i64)),
+    ];
+    Some(PreSerializedFile {
+        length: f.info.length,
+        sample_rate: f.info.sample_rate,
+        channels: f.info.channels as u32,
+        bitrate: None,
+        tags,
+        extra,
+        lazy_vc: None,
+    })
+}
 
-    if max_score == 0 {
-        return None;
+/// Parse ASF data into batch result.
+#[inline(always)]
+fn parse_asf_batch(data: &[u8], path: &str) -> Option<PreSerializedFile> {
+    let f = asf::ASFFile::parse(data, path).ok()?;
+    let mut tags = Vec::new();
+    for key in f.tags.keys() {
+        let values: Vec<String> = f.tags.get(&key).iter().filter_map(|v| v.as_text()).collect();
+        match values.len() {
+            0 => {}
+            1 => tags.push((key, BatchTagValue::Text(values.into_iter().next().unwrap()))),
+            _ => tags.push((key, BatchTagValue::TextList(values))),
+        }
     }
+    let extra = vec![
+        ("codec", BatchTagValue::Text(f.info.codec_type.clone())),
+    ];
+    Some(PreSerializedFile {
+        length: f.info.length,
+        sample_rate: f.info.sample_rate,
+        channels: f.info.channels,
+        bitrate: Some(f.info.bitrate),
+        tags,
+        extra,
+        lazy_vc: None,
+    })
+}
 
-    if max_score == flac_score {
-        parse_flac_batch(data, data_arc)
-    } else if max_score == ogg_score {
-        parse_ogg_batch(data, data_arc)
-    } else if max_score == mp4_score {
-        parse_mp4_batch(data, path)
-    } else {
-        parse_mp3_batch(data, path)
+/// Parse + fully decode a single file from data (runs in parallel phase).
+/// Uses extension-based fast dispatch to skip unnecessary scoring.
+#[inline(always)]
+fn parse_and_serialize(data: &[u8], path: &str, data_arc: Option<&Arc<[u8]>>) -> Option<PreSerializedFile> {
+    use common::probe::{self, FileFormat};
+
+    // Extension fast path, then score-based detection
+    let format = FileFormat::from_extension(path)
+        .map(|f| f.refine(data))
+        .or_else(|| probe::detect(path, data))?;
+    match format {
+        FileFormat::FLAC => parse_flac_batch(data, data_arc),
+        FileFormat::OggVorbis => parse_ogg_batch(data, data_arc),
+        FileFormat::OggFLAC => parse_oggflac_batch(data, path),
+        FileFormat::MP4 => parse_mp4_batch(data, path),
+        FileFormat::MP3 => parse_mp3_batch(data, path),
+        FileFormat::WavPack => parse_wavpack_batch(data, path),
+        FileFormat::ASF => parse_asf_batch(data, path),
+        FileFormat::MonkeysAudio => parse_monkeysaudio_batch(data, path),
     }
 }
 
@@ -1576,6 +3186,43 @@ fn preserialized_to_json(pf: &PreSerializedFile, out: &mut String) {
     out.push_str("}}");
 }
 
+/// JSON for a single opened file, in the same shape `BatchResult.to_dict`
+/// uses per file: `{length, sample_rate, channels, bitrate, tags}`.
+fn file_to_json(length: f64, sample_rate: u32, channels: u32, bitrate: Option<u32>,
+                tags: Vec<(String, BatchTagValue)>) -> String {
+    let pf = PreSerializedFile {
+        length, sample_rate, channels, bitrate, tags,
+        extra: Vec::new(),
+        lazy_vc: None,
+    };
+    let mut out = String::with_capacity(256);
+    preserialized_to_json(&pf, &mut out);
+    out
+}
+
+fn id3_to_batch(tags: &id3::tags::ID3Tags) -> Vec<(String, BatchTagValue)> {
+    tags.keys().into_iter()
+        .filter_map(|key| {
+            let frame = tags.decode_first(&key)?;
+            let value = frame_to_batch_value(&frame);
+            Some((key, value))
+        })
+        .collect()
+}
+
+fn vc_to_batch(vc: &vorbis::VorbisComment) -> Vec<(String, BatchTagValue)> {
+    vc.keys().into_iter()
+        .map(|key| {
+            let values = vc.get(&key);
+            let value = match values.as_slice() {
+                [one] => BatchTagValue::Text(one.to_string()),
+                _ => BatchTagValue::TextList(values.iter().map(|v| v.to_string()).collect()),
+            };
+            (key, value)
+        })
+        .collect()
+}
+
 /// Lazy batch result — stores parsed Rust data, creates Python objects on demand.
 #[pyclass(name = "BatchResult")]
 struct PyBatchResult {
@@ -1718,70 +3365,137 @@ fn batch_diag(py: Python<'_>, filenames: Vec<String>) -> PyResult<String> {
 }
 
 /// Auto-detect file format and open.
+///
+/// With `strict=True`, truncated ID3 frames, FLAC metadata blocks and Vorbis
+/// comments raise instead of yielding the tags read before the damage.
+/// Other formats ignore the flag.
+///
+/// `format` (a `detect_format` name such as "flac") skips detection and
+/// parses the file as that format, raising its error if it does not parse.
 #[pyfunction]
-#[pyo3(signature = (filename, easy=false))]
-fn file_open(py: Python<'_>, filename: &str, easy: bool) -> PyResult<PyObject> {
+#[pyo3(signature = (filename, easy=false, strict=false, format=None))]
+fn file_open(py: Python<'_>, filename: &str, easy: bool, strict: bool, format: Option<&str>) -> PyResult<Py<PyAny>> {
+    use common::probe::FileFormat;
     let _ = easy;
+    let options = ParseOptions { strict };
+
+    let forced = format.is_some();
+    let format = match format {
+        Some(name) => Some(FileFormat::from_name(name)
+            .ok_or_else(|| PyValueError::new_err(format!("Unknown format: {}", name)))?),
+        // Fast path: extension-based detection (avoids scoring overhead)
+        None => match FileFormat::from_extension(filename) {
+            Some(format) => Some(format),
+            None => detect_path(filename)
+                .map_err(|e| PyIOError::new_err(format!("Cannot open file: {}", e)))?,
+        },
+    };
+    let format = format.ok_or_else(|| common::error::MutagenError::InvalidData(format!(
+        "Unable to detect format for: {}",
+        filename
+    )))?;
 
     let data = read_cached(filename)
         .map_err(|e| PyIOError::new_err(format!("Cannot open file: {}", e)))?;
+    let format = if forced { format } else { format.refine(&data) };
 
-    // Fast path: extension-based detection (avoids scoring overhead)
-    let ext = filename.rsplit('.').next().unwrap_or("");
-    if ext.eq_ignore_ascii_case("flac") {
-        let f = PyFLAC::from_data(py, &data, filename)?;
-        return Ok(f.into_pyobject(py)?.into_any().unbind());
-    }
-    if ext.eq_ignore_ascii_case("ogg") {
-        let f = PyOggVorbis::from_data(py, &data, filename)?;
-        return Ok(f.into_pyobject(py)?.into_any().unbind());
-    }
-    if ext.eq_ignore_ascii_case("mp3") {
-        let f = PyMP3::from_data(py, &data, filename)?;
-        return Ok(f.into_pyobject(py)?.into_any().unbind());
-    }
-    if ext.eq_ignore_ascii_case("m4a") || ext.eq_ignore_ascii_case("m4b")
-        || ext.eq_ignore_ascii_case("mp4") || ext.eq_ignore_ascii_case("m4v") {
-        let f = PyMP4::from_data(py, &data, filename)?;
-        return Ok(f.into_pyobject(py)?.into_any().unbind());
-    }
-
-    // Fallback: score-based detection
-    let mp3_score = mp3::MP3File::score(filename, &data);
-    let flac_score = flac::FLACFile::score(filename, &data);
-    let ogg_score = ogg::OggVorbisFile::score(filename, &data);
-    let mp4_score = mp4::MP4File::score(filename, &data);
-
-    let max_score = mp3_score.max(flac_score).max(ogg_score).max(mp4_score);
-
-    if max_score == 0 {
-        return Err(PyValueError::new_err(format!(
-            "Unable to detect format for: {}",
-            filename
-        )));
-    }
-
-    if max_score == flac_score {
-        let f = PyFLAC::from_data(py, &data, filename)?;
-        Ok(f.into_pyobject(py)?.into_any().unbind())
-    } else if max_score == ogg_score {
-        let f = PyOggVorbis::from_data(py, &data, filename)?;
-        Ok(f.into_pyobject(py)?.into_any().unbind())
-    } else if max_score == mp4_score {
-        let f = PyMP4::from_data(py, &data, filename)?;
-        Ok(f.into_pyobject(py)?.into_any().unbind())
-    } else {
-        let f = PyMP3::from_data(py, &data, filename)?;
-        Ok(f.into_pyobject(py)?.into_any().unbind())
+    match format {
+        FileFormat::FLAC => {
+            let f = PyFLAC::from_data_with(py, &data, filename, options)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+        FileFormat::OggVorbis => {
+            let f = PyOggVorbis::from_data_with(py, &data, filename, options)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+        FileFormat::OggFLAC => {
+            let f = PyOggFLAC::from_data(py, &data, filename)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+        FileFormat::MP4 => {
+            let f = PyMP4::from_data(py, &data, filename)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+        FileFormat::MP3 => {
+            let f = PyMP3::from_data_with(py, &data, filename, options)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+        FileFormat::WavPack => {
+            let f = PyWavPack::from_data(py, &data, filename)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+        FileFormat::ASF => {
+            let f = PyASF::from_data(py, &data, filename)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+        FileFormat::MonkeysAudio => {
+            let f = PyMonkeysAudio::from_data(py, &data, filename)?;
+            Ok(f.into_pyobject(py)?.into_any().unbind())
+        }
+    }
+}
+
+/// Detect a file's format by content scoring (extension counts as a hint only).
+/// Returns "mp3", "flac", "ogg", "oggflac", "mp4", "wavpack", "asf", "monkeysaudio", or None if unrecognized.
+#[pyfunction]
+fn detect_format(filename: &str) -> PyResult<Option<&'static str>> {
+    let data = read_cached(filename)
+        .map_err(|e| PyIOError::new_err(format!("Cannot open file: {}", e)))?;
+    Ok(common::probe::detect(filename, &data).map(|f| f.as_str()))
+}
+
+/// Score a file's cached data if present; otherwise read only its header,
+/// so unrecognized or not-yet-opened files are never loaded whole.
+fn detect_path(path: &str) -> std::io::Result<Option<common::probe::FileFormat>> {
+    if common::cache::contains(path) {
+        let data = read_cached(path)?;
+        return Ok(common::probe::detect(path, &data));
     }
+    common::probe::detect_file(path)
+}
+
+/// Front cover art as `(mime, data)`, or None if the file has none.
+#[pyfunction]
+fn front_cover(py: Python<'_>, filename: &str) -> PyResult<Option<(String, Py<PyBytes>)>> {
+    let data = read_cached(filename)
+        .map_err(|e| PyIOError::new_err(format!("Cannot open file: {}", e)))?;
+    let cover = file::front_cover(&data, filename)?;
+    Ok(cover.map(|(mime, bytes)| (mime, PyBytes::new(py, &bytes).unbind())))
+}
+
+/// Length in seconds without parsing tags, for duration-only scans.
+#[pyfunction]
+fn duration(filename: &str) -> PyResult<f64> {
+    let data = read_cached(filename)
+        .map_err(|e| PyIOError::new_err(format!("Cannot open file: {}", e)))?;
+    Ok(file::duration(&data, filename)?)
+}
+
+/// Chapter and cue markers as `(start_seconds, label)` pairs, from ID3
+/// `CHAP` frames, FLAC CueSheet tracks or MP4 chapters.
+#[pyfunction]
+fn markers(filename: &str) -> PyResult<Vec<(f64, String)>> {
+    let data = read_cached(filename)
+        .map_err(|e| PyIOError::new_err(format!("Cannot open file: {}", e)))?;
+    Ok(file::markers(&data, filename)?)
 }
 
 /// Clear the in-memory file data cache, forcing subsequent reads to hit the filesystem.
 #[pyfunction]
 fn clear_cache() {
-    let cache = get_file_cache();
-    let mut guard = cache.write().unwrap();
-    guard.clear();
+    common::cache::clear();
+}
+
+/// Set the maximum number of files kept in the file data cache (0 disables it).
+#[pyfunction]
+fn set_file_cache_capacity(capacity: usize) {
+    common::cache::set_capacity(capacity);
+}
+
+/// `(cached files, capacity)` of the file data cache.
+#[pyfunction]
+fn file_cache_info() -> (usize, usize) {
+    (common::cache::len(), common::cache::capacity())
 }
 
 /// Alias for batch_open (used by benchmark scripts).
@@ -1874,7 +3588,12 @@ fn parse_vc_grouped<'a>(data: &'a [u8]) -> Vec<(&'a [u8], Vec<&'a str>)> {
         if pos + 4 > data.len() { break; }
         let clen = u32::from_le_bytes([data[pos], data[pos+1], data[pos+2], data[pos+3]]) as usize;
         pos += 4;
-        if pos + clen > data.len() { break; }
+        if pos + clen > data.len() {
+            match vorbis::next_entry(data, pos) {
+                Some(next) => { pos = next; continue; }
+                None => break,
+            }
+        }
         let raw = &data[pos..pos + clen];
         pos += clen;
 
@@ -2141,20 +3860,21 @@ fn fast_walk_v2x_frames(
         if tag_bytes[*offset] == 0 { break; }
         let id_bytes = &tag_bytes[*offset..*offset+4];
         if !id_bytes.iter().all(|&b| b.is_ascii_uppercase() || b.is_ascii_digit()) { break; }
+        let id_bytes = id3::frames::convert_itunes_sort_frame_id(id_bytes).unwrap_or(id_bytes);
         let size = id3::header::BitPaddedInt::decode(&tag_bytes[*offset+4..*offset+8], bpi) as usize;
         let flags = u16::from_be_bytes([tag_bytes[*offset+8], tag_bytes[*offset+9]]);
         *offset += 10;
         if size == 0 || *offset + size > tag_bytes.len() { break; }
 
-        let (compressed, encrypted, unsynchronised, has_data_length) = if version == 4 {
-            (flags & 0x0008 != 0, flags & 0x0004 != 0, flags & 0x0002 != 0, flags & 0x0001 != 0)
+        let (compressed, encrypted, unsynchronised, has_data_length, grouped) = if version == 4 {
+            (flags & 0x0008 != 0, flags & 0x0004 != 0, flags & 0x0002 != 0, flags & 0x0001 != 0, flags & 0x0040 != 0)
         } else {
-            (flags & 0x0080 != 0, flags & 0x0040 != 0, false, flags & 0x0080 != 0)
+            (flags & 0x0080 != 0, flags & 0x0040 != 0, false, flags & 0x0080 != 0, flags & 0x0020 != 0)
         };
 
         let id_str = std::str::from_utf8(id_bytes).unwrap_or("XXXX");
 
-        if !encrypted && !compressed && !unsynchronised && !has_data_length {
+        if !encrypted && !compressed && !unsynchronised && !has_data_length && !grouped {
             // Fast path: no frame flags
             let frame_data = &tag_bytes[*offset..*offset+size];
             *offset += size;
@@ -2178,7 +3898,9 @@ fn fast_walk_v2x_frames(
             }
 
             // URL frames: raw Latin-1, no encoding byte
-            if id_bytes[0] == b'W' && id_str != "WXXX" {
+            if id_bytes[0] == b'W' && id_str != "WXXX" && !id3::frames::is_itunes_text_frame(id_str)
+                && !id3::frames::is_repeatable_url_frame(id_str)
+            {
                 let mut flen = frame_data.len();
                 while flen > 0 && frame_data[flen-1] == 0 { flen -= 1; }
                 if flen > 0 && frame_data[..flen].iter().all(|&b| b < 128) {
@@ -2222,9 +3944,16 @@ fn fast_walk_v2x_frames(
             let mut frame_data = tag_bytes[*offset..*offset+size].to_vec();
             *offset += size;
             if encrypted { continue; }
+            // Group byte: before the data length in v2.4, after it in v2.3
+            if grouped && version == 4 && !frame_data.is_empty() {
+                frame_data.remove(0);
+            }
             if has_data_length && frame_data.len() >= 4 {
                 frame_data = frame_data[4..].to_vec();
             }
+            if grouped && version != 4 && !frame_data.is_empty() {
+                frame_data.remove(0);
+            }
             if unsynchronised {
                 frame_data = match id3::unsynch::decode(&frame_data) {
                     Ok(d) => d,
@@ -2277,7 +4006,12 @@ fn parse_vc_to_dict_direct<'py>(
         if pos + 4 > data.len() { break; }
         let clen = u32::from_le_bytes([data[pos], data[pos+1], data[pos+2], data[pos+3]]) as usize;
         pos += 4;
-        if pos + clen > data.len() { break; }
+        if pos + clen > data.len() {
+            match vorbis::next_entry(data, pos) {
+                Some(next) => { pos = next; continue; }
+                None => break,
+            }
+        }
         let raw = &data[pos..pos + clen];
         pos += clen;
 
@@ -2373,6 +4107,7 @@ fn fast_read_flac_direct<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, Py
                         set_dict_f64(dict_ptr, pyo3::intern!(py, "length").as_ptr(), si.length);
                         set_dict_u32(dict_ptr, pyo3::intern!(py, "sample_rate").as_ptr(), si.sample_rate);
                         set_dict_u32(dict_ptr, pyo3::intern!(py, "channels").as_ptr(), si.channels as u32);
+                        set_dict_i64(dict_ptr, pyo3::intern!(py, "bitrate_mode").as_ptr(), si.bitrate_mode() as i64);
                     }
                     has_streaminfo = true;
                 }
@@ -2418,6 +4153,11 @@ fn fast_read_ogg_direct<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyD
 
     let channels = id_data[11];
     let sample_rate = u32::from_le_bytes([id_data[12], id_data[13], id_data[14], id_data[15]]);
+    let bitrate_mode = ogg::vorbis_bitrate_mode(
+        u32::from_le_bytes([id_data[20], id_data[21], id_data[22], id_data[23]]),
+        u32::from_le_bytes([id_data[16], id_data[17], id_data[18], id_data[19]]),
+        u32::from_le_bytes([id_data[24], id_data[25], id_data[26], id_data[27]]),
+    );
 
     if first_page_end + 27 > data.len() { return Ok(false); }
     if &data[first_page_end..first_page_end+4] != b"OggS" { return Ok(false); }
@@ -2441,7 +4181,7 @@ fn fast_read_ogg_direct<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyD
 
     let vc_data = &data[comment_start + 7..comment_start + first_packet_size];
 
-    let length = ogg::find_last_granule(data, serial)
+    let length = ogg::vorbis_sample_count(data, serial)
         .map(|g| if g > 0 && sample_rate > 0 { g as f64 / sample_rate as f64 } else { 0.0 })
         .unwrap_or(0.0);
 
@@ -2450,6 +4190,7 @@ fn fast_read_ogg_direct<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyD
         set_dict_f64(dict_ptr_ogg, pyo3::intern!(py, "length").as_ptr(), length);
         set_dict_u32(dict_ptr_ogg, pyo3::intern!(py, "sample_rate").as_ptr(), sample_rate);
         set_dict_u32(dict_ptr_ogg, pyo3::intern!(py, "channels").as_ptr(), channels as u32);
+        set_dict_i64(dict_ptr_ogg, pyo3::intern!(py, "bitrate_mode").as_ptr(), bitrate_mode as i64);
     }
 
     let mut keys_out: Vec<*mut pyo3::ffi::PyObject> = Vec::with_capacity(16);
@@ -2464,25 +4205,24 @@ fn fast_read_ogg_direct<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyD
 fn fast_read_mp3_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &Bound<'py, PyDict>) -> PyResult<bool> {
     let file_size = data.len() as u64;
 
-    // 1. Parse ID3v2 header (10 bytes only)
-    let (id3_header, audio_start) = if data.len() >= 10 {
-        match id3::header::ID3Header::parse(&data[0..10], 0) {
-            Ok(h) => {
-                let tag_size = h.size as usize;
-                if 10 + tag_size <= data.len() {
-                    let astart = h.full_size() as usize;
-                    (Some(h), astart)
-                } else { (None, 0) }
-            }
-            Err(_) => (None, 0),
+    // 1. Parse ID3v2 header (10 bytes only), possibly behind leading junk
+    let (id3_header, audio_start) = match id3::header::ID3Header::locate(data) {
+        Ok(h) => {
+            let tag_size = h.size as usize;
+            if h.data_offset() + tag_size <= data.len() {
+                let astart = h.offset as usize + h.full_size() as usize;
+                (Some(h), astart)
+            } else { (None, 0) }
         }
-    } else { (None, 0) };
+        Err(_) => (None, 0),
+    };
 
     // 2. Parse MPEG audio info
     let audio_end = data.len().min(audio_start + 8192);
     let audio_data = if audio_start < data.len() { &data[audio_start..audio_end] } else { &[] };
     let info = match mp3::MPEGInfo::parse(audio_data, 0, file_size.saturating_sub(audio_start as u64)) {
         Ok(i) => i,
+        Err(common::error::MutagenError::HeaderNotFoundError(_)) if id3_header.is_some() => mp3::MPEGInfo::without_audio(),
         Err(_) => return Ok(false),
     };
 
@@ -2497,12 +4237,7 @@ fn fast_read_mp3_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &B
         set_dict_i64(dict_ptr, pyo3::intern!(py, "layer").as_ptr(), info.layer as i64);
         set_dict_i64(dict_ptr, pyo3::intern!(py, "mode").as_ptr(), info.mode as i64);
         set_dict_bool(dict_ptr, pyo3::intern!(py, "protected").as_ptr(), info.protected);
-        set_dict_i64(dict_ptr, pyo3::intern!(py, "bitrate_mode").as_ptr(), match info.bitrate_mode {
-            mp3::xing::BitrateMode::Unknown => 0,
-            mp3::xing::BitrateMode::CBR => 1,
-            mp3::xing::BitrateMode::VBR => 2,
-            mp3::xing::BitrateMode::ABR => 3,
-        });
+        set_dict_i64(dict_ptr, pyo3::intern!(py, "bitrate_mode").as_ptr(), info.bitrate_mode as i64);
     }
 
     // 4. Walk ID3v2 frames directly (no LazyFrame/ID3Tags intermediary)
@@ -2515,10 +4250,10 @@ fn fast_read_mp3_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &B
         // Handle whole-tag unsynchronisation (v2.3 and below)
         let decoded_buf;
         let tag_bytes: &[u8] = if h.flags.unsynchronisation && version < 4 {
-            decoded_buf = id3::unsynch::decode(&data[10..10 + tag_size]).unwrap_or_default();
+            decoded_buf = id3::unsynch::decode(&data[h.data_offset()..h.data_offset() + tag_size]).unwrap_or_default();
             &decoded_buf[..]
         } else {
-            &data[10..10 + tag_size]
+            &data[h.data_offset()..h.data_offset() + tag_size]
         };
 
         let mut offset = 0usize;
@@ -2533,9 +4268,11 @@ fn fast_read_mp3_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &B
             offset = if version == 4 { ext_size } else { ext_size + 4 };
         }
 
-        let bpi = if version == 4 {
-            id3::header::determine_bpi(&tag_bytes[offset..], tag_bytes.len())
-        } else { 8 };
+        let bpi = match version {
+            4 => id3::header::determine_bpi(&tag_bytes[offset..], tag_bytes.len()),
+            3 => id3::header::determine_bpi_v23(&tag_bytes[offset..], tag_bytes.len()),
+            _ => 8,
+        };
 
         if version == 2 {
             fast_walk_v22_frames(py, tag_bytes, &mut offset, dict_ptr, &mut key_ptrs);
@@ -2610,9 +4347,11 @@ fn fast_read_mp4_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &B
     let mut sample_rate = 44100u32;
     let mut bits_per_sample = 16u32;
     let mut codec_bytes: [u8; 4] = *b"mp4a";
+    let mut object_type = None;
+    let mut bitrate_mode = mp3::xing::BitrateMode::Unknown;
+    let mut audio_size = None;
 
-    'trak_loop: for trak in AtomIter::new(data, moov_s, moov_e) {
-        if trak.name != *b"trak" { continue; }
+    'trak_loop: for trak in AtomIter::new(data, moov_s, moov_e).filter_name(b"trak") {
         let trak_s = trak.data_offset;
         let trak_e = trak_s + trak.data_size;
         let mdia = match AtomIter::new(data, trak_s, trak_e).find_name(b"mdia") {
@@ -2634,6 +4373,7 @@ fn fast_read_mp4_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &B
         let stbl = match AtomIter::new(data, minf.data_offset, minf.data_offset + minf.data_size).find_name(b"stbl") {
             Some(a) => a, None => continue,
         };
+        audio_size = mp4::stsz_total(data, &stbl);
         let stsd = match AtomIter::new(data, stbl.data_offset, stbl.data_offset + stbl.data_size).find_name(b"stsd") {
             Some(a) => a, None => continue,
         };
@@ -2647,15 +4387,24 @@ fn fast_read_mp4_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &B
                     channels = u16::from_be_bytes([audio_entry[16], audio_entry[17]]) as u32;
                     bits_per_sample = u16::from_be_bytes([audio_entry[18], audio_entry[19]]) as u32;
                     if audio_entry.len() >= 28 {
-                        sample_rate = u16::from_be_bytes([audio_entry[24], audio_entry[25]]) as u32;
+                        sample_rate = mp4::fixed_sample_rate(&audio_entry[24..28]);
                     }
                 }
+                bitrate_mode = mp4::esds_bitrate_mode(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size);
+                if let Some(n) = mp4::esds_channels(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size) {
+                    channels = n;
+                }
+                if let Some(rate) = mp4::esds_sample_rate(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size) {
+                    sample_rate = rate;
+                }
+                object_type = mp4::esds_audio_object_type(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size);
             }
         }
         break 'trak_loop;
     }
 
-    let bitrate = if length > 0.0 { (data.len() as f64 * 8.0 / length) as u32 } else { 0 };
+    let audio_size = audio_size.or_else(|| mp4::mdat_size(data)).unwrap_or(data.len() as u64);
+    let bitrate = if length > 0.0 { (audio_size as f64 * 8.0 / length) as u32 } else { 0 };
 
     // 4. Set info fields via raw FFI (no Rust String for codec)
     let dict_ptr = dict.as_ptr();
@@ -2664,53 +4413,49 @@ fn fast_read_mp4_direct<'py>(py: Python<'py>, data: &[u8], _path: &str, dict: &B
         set_dict_u32(dict_ptr, pyo3::intern!(py, "sample_rate").as_ptr(), sample_rate);
         set_dict_u32(dict_ptr, pyo3::intern!(py, "channels").as_ptr(), channels);
         set_dict_u32(dict_ptr, pyo3::intern!(py, "bits_per_sample").as_ptr(), bits_per_sample);
+        set_dict_u32(dict_ptr, pyo3::intern!(py, "bitrate").as_ptr(), bitrate);
+        set_dict_i64(dict_ptr, pyo3::intern!(py, "bitrate_mode").as_ptr(), bitrate_mode as i64);
         // Codec: create Python string directly from 4 bytes (no Rust String)
         let codec_ptr = pyo3::ffi::PyUnicode_FromStringAndSize(
             codec_bytes.as_ptr() as *const std::ffi::c_char, 4);
         pyo3::ffi::PyDict_SetItem(dict_ptr, pyo3::intern!(py, "codec").as_ptr(), codec_ptr);
         pyo3::ffi::Py_DECREF(codec_ptr);
     }
+    let codec_description = mp4::describe_codec(std::str::from_utf8(&codec_bytes).unwrap_or(""), object_type);
+    dict.set_item(pyo3::intern!(py, "codec_description"), codec_description)?;
 
     // 5. Walk ilst and convert tags directly to Python (no MP4Tags intermediate)
     let mut key_ptrs: Vec<*mut pyo3::ffi::PyObject> = Vec::with_capacity(16);
 
-    if let Some(udta) = AtomIter::new(data, moov_s, moov_e).find_name(b"udta") {
-        if let Some(meta) = AtomIter::new(data, udta.data_offset, udta.data_offset + udta.data_size).find_name(b"meta") {
-            let meta_off = meta.data_offset + 4;
-            let meta_end = meta.data_offset + meta.data_size;
-            if meta_off < meta_end {
-                if let Some(ilst) = AtomIter::new(data, meta_off, meta_end).find_name(b"ilst") {
-                    for item in AtomIter::new(data, ilst.data_offset, ilst.data_offset + ilst.data_size) {
-                        // Create Python key directly from atom name bytes (no Rust String)
-                        let key_ptr = unsafe { mp4_atom_name_to_py_key(&item.name) };
-                        if key_ptr.is_null() { continue; }
-
-                        // Find first "data" atom and convert value directly to Python
-                        for da in AtomIter::new(data, item.data_offset, item.data_offset + item.data_size) {
-                            if da.name != *b"data" { continue; }
-                            let ad = &data[da.data_offset..da.data_offset + da.data_size];
-                            if ad.len() < 8 { continue; }
-                            let type_ind = u32::from_be_bytes([ad[0], ad[1], ad[2], ad[3]]);
-                            let vd = &ad[8..];
-
-                            let py_val = unsafe { mp4_data_to_py_raw(py, &item.name, type_ind, vd) };
-                            if !py_val.is_null() {
-                                unsafe {
-                                    if pyo3::ffi::PyDict_Contains(dict_ptr, key_ptr) == 0 {
-                                        pyo3::ffi::PyDict_SetItem(dict_ptr, key_ptr, py_val);
-                                        key_ptrs.push(key_ptr);
-                                    } else {
-                                        pyo3::ffi::Py_DECREF(key_ptr);
-                                    }
-                                    pyo3::ffi::Py_DECREF(py_val);
-                                }
-                            } else {
-                                unsafe { pyo3::ffi::Py_DECREF(key_ptr); }
-                            }
-                            break; // Only first data atom per item
+    // Earlier ilst atoms win: a key already in the dict is not replaced
+    for ilst in mp4::ilst_atoms(data, moov_s, moov_e) {
+        for item in AtomIter::new(data, ilst.data_offset, ilst.data_offset + ilst.data_size) {
+            // Create Python key directly from atom name bytes (no Rust String)
+            let key_ptr = unsafe { mp4_atom_name_to_py_key(&item.name) };
+            if key_ptr.is_null() { continue; }
+
+            // Find first "data" atom and convert value directly to Python
+            for da in AtomIter::new(data, item.data_offset, item.data_offset + item.data_size).filter_name(b"data") {
+                let ad = &data[da.data_offset..da.data_offset + da.data_size];
+                if ad.len() < 8 { continue; }
+                let type_ind = u32::from_be_bytes([ad[0], ad[1], ad[2], ad[3]]);
+                let vd = &ad[8..];
+
+                let py_val = unsafe { mp4_data_to_py_raw(py, &item.name, type_ind, vd) };
+                if !py_val.is_null() {
+                    unsafe {
+                        if pyo3::ffi::PyDict_Contains(dict_ptr, key_ptr) == 0 {
+                            pyo3::ffi::PyDict_SetItem(dict_ptr, key_ptr, py_val);
+                            key_ptrs.push(key_ptr);
+                        } else {
+                            pyo3::ffi::Py_DECREF(key_ptr);
                         }
+                        pyo3::ffi::Py_DECREF(py_val);
                     }
+                } else {
+                    unsafe { pyo3::ffi::Py_DECREF(key_ptr); }
                 }
+                break; // Only first data atom per item
             }
         }
     }
@@ -2742,11 +4487,20 @@ unsafe fn mp4_atom_name_to_py_key(name: &[u8; 4]) -> *mut pyo3::ffi::PyObject {
 #[inline(always)]
 unsafe fn mp4_data_to_py_raw(py: Python<'_>, atom_name: &[u8; 4], type_ind: u32, vd: &[u8]) -> *mut pyo3::ffi::PyObject {
     match type_ind {
+        _ if atom_name == b"covr" => {
+            // Cover art of any type indicator → Python bytes
+            pyo3::ffi::PyBytes_FromStringAndSize(
+                vd.as_ptr() as *const std::ffi::c_char, vd.len() as pyo3::ffi::Py_ssize_t)
+        }
         1 => {
             // UTF-8 text → Python string directly
             pyo3::ffi::PyUnicode_FromStringAndSize(
                 vd.as_ptr() as *const std::ffi::c_char, vd.len() as pyo3::ffi::Py_ssize_t)
         }
+        21 if vd.len() == 1 && mp4::is_bool_atom(atom_name) => {
+            // Single-byte flag (cpil, pgap, pcst) → Python bool
+            pyo3::ffi::PyBool_FromLong((vd[0] != 0) as std::ffi::c_long)
+        }
         21 => {
             // Signed integer
             let val: i64 = match vd.len() {
@@ -2770,9 +4524,8 @@ unsafe fn mp4_data_to_py_raw(py: Python<'_>, atom_name: &[u8; 4], type_ind: u32,
                 pyo3::ffi::PyTuple_SET_ITEM(tup, 1, pb);
                 tup
             } else if atom_name == b"gnre" && vd.len() >= 2 {
-                let genre_id = u16::from_be_bytes([vd[0], vd[1]]) as usize;
-                if genre_id > 0 && genre_id <= crate::id3::specs::GENRES.len() {
-                    let g = crate::id3::specs::GENRES[genre_id - 1];
+                let genre_id = u16::from_be_bytes([vd[0], vd[1]]);
+                if let Some(g) = crate::id3::specs::mp4_genre_name(genre_id) {
                     pyo3::ffi::PyUnicode_FromStringAndSize(
                         g.as_ptr() as *const std::ffi::c_char, g.len() as pyo3::ffi::Py_ssize_t)
                 } else {
@@ -2844,7 +4597,7 @@ fn fast_info_ogg<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyDict>) -
     if id_data.len() < 30 || &id_data[0..7] != b"\x01vorbis" { return Ok(false); }
     let channels = id_data[11];
     let sample_rate = u32::from_le_bytes([id_data[12], id_data[13], id_data[14], id_data[15]]);
-    let length = ogg::find_last_granule(data, serial)
+    let length = ogg::vorbis_sample_count(data, serial)
         .map(|g| if g > 0 && sample_rate > 0 { g as f64 / sample_rate as f64 } else { 0.0 })
         .unwrap_or(0.0);
     let dict_ptr = dict.as_ptr();
@@ -2860,19 +4613,18 @@ fn fast_info_ogg<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyDict>) -
 #[inline(always)]
 fn fast_info_mp3<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyDict>) -> PyResult<bool> {
     let file_size = data.len() as u64;
-    let audio_start = if data.len() >= 10 {
-        match id3::header::ID3Header::parse(&data[0..10], 0) {
-            Ok(h) => {
-                let tag_size = h.size as usize;
-                if 10 + tag_size <= data.len() { h.full_size() as usize } else { 0 }
-            }
-            Err(_) => 0,
+    let audio_start = match id3::header::ID3Header::locate(data) {
+        Ok(h) => {
+            let tag_size = h.size as usize;
+            if h.data_offset() + tag_size <= data.len() { h.offset as usize + h.full_size() as usize } else { 0 }
         }
-    } else { 0 };
+        Err(_) => 0,
+    };
     let audio_end = data.len().min(audio_start + 8192);
     let audio_data = if audio_start < data.len() { &data[audio_start..audio_end] } else { &[] };
     let info = match mp3::MPEGInfo::parse(audio_data, 0, file_size.saturating_sub(audio_start as u64)) {
         Ok(i) => i,
+        Err(common::error::MutagenError::HeaderNotFoundError(_)) if audio_start > 0 => mp3::MPEGInfo::without_audio(),
         Err(_) => return Ok(false),
     };
     let dict_ptr = dict.as_ptr();
@@ -2913,8 +4665,7 @@ fn fast_info_mp4<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyDict>) -
     let length = if timescale > 0 { duration as f64 / timescale as f64 } else { 0.0 };
     let mut channels = 2u32;
     let mut sample_rate = 44100u32;
-    'trak: for trak in AtomIter::new(data, moov_s, moov_e) {
-        if trak.name != *b"trak" { continue; }
+    'trak: for trak in AtomIter::new(data, moov_s, moov_e).filter_name(b"trak") {
         let ts = trak.data_offset;
         let te = ts + trak.data_size;
         let mdia = match AtomIter::new(data, ts, te).find_name(b"mdia") { Some(a) => a, None => continue };
@@ -2937,7 +4688,13 @@ fn fast_info_mp4<'py>(py: Python<'py>, data: &[u8], dict: &Bound<'py, PyDict>) -
                 let audio = &entry[8..];
                 if audio.len() >= 20 {
                     channels = u16::from_be_bytes([audio[16], audio[17]]) as u32;
-                    if audio.len() >= 28 { sample_rate = u16::from_be_bytes([audio[24], audio[25]]) as u32; }
+                    if audio.len() >= 28 { sample_rate = mp4::fixed_sample_rate(&audio[24..28]); }
+                }
+                if let Some(n) = mp4::esds_channels(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size) {
+                    channels = n;
+                }
+                if let Some(rate) = mp4::esds_sample_rate(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size) {
+                    sample_rate = rate;
                 }
             }
         }
@@ -2973,7 +4730,7 @@ fn _fast_info(py: Python<'_>, filename: &str) -> PyResult<PyObject> {
         false
     };
     if !ok {
-        return Err(PyValueError::new_err(format!("Unable to parse: {}", filename)));
+        return Err(common::error::MutagenError::InvalidData(format!("Unable to parse: {}", filename)).into());
     }
     Ok(dict.into_any().unbind())
 }
@@ -2990,7 +4747,7 @@ fn _fast_read(py: Python<'_>, filename: &str) -> PyResult<PyObject> {
 
     let ok = if ext.eq_ignore_ascii_case("flac") {
         fast_read_flac_direct(py, &data, &dict)?
-    } else if ext.eq_ignore_ascii_case("ogg") {
+    } else if ext.eq_ignore_ascii_case("ogg") && !oggflac::is_oggflac(&data) {
         fast_read_ogg_direct(py, &data, &dict)?
     } else if ext.eq_ignore_ascii_case("mp3") {
         fast_read_mp3_direct(py, &data, filename, &dict)?
@@ -3008,7 +4765,7 @@ fn _fast_read(py: Python<'_>, filename: &str) -> PyResult<PyObject> {
     };
 
     if !ok {
-        return Err(PyValueError::new_err(format!("Unable to parse: {}", filename)));
+        return Err(common::error::MutagenError::InvalidData(format!("Unable to parse: {}", filename)).into());
     }
 
     Ok(dict.into_any().unbind())
@@ -3036,7 +4793,7 @@ fn _fast_read_seq(py: Python<'_>, filenames: Vec<String>) -> PyResult<PyObject>
 
             let ok = if ext.eq_ignore_ascii_case("flac") {
                 fast_read_flac_direct(py, &data, &dict).unwrap_or(false)
-            } else if ext.eq_ignore_ascii_case("ogg") {
+            } else if ext.eq_ignore_ascii_case("ogg") && !oggflac::is_oggflac(&data) {
                 fast_read_ogg_direct(py, &data, &dict).unwrap_or(false)
             } else if ext.eq_ignore_ascii_case("mp3") {
                 fast_read_mp3_direct(py, &data, filename, &dict).unwrap_or(false)
@@ -3068,24 +4825,40 @@ fn mutagen_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyMP3>()?;
     m.add_class::<PyMPEGInfo>()?;
     m.add_class::<PyID3>()?;
+    m.add_class::<PyEasyID3>()?;
     m.add_class::<PyFLAC>()?;
     m.add_class::<PyStreamInfo>()?;
     m.add_class::<PyVComment>()?;
     m.add_class::<PyOggVorbis>()?;
     m.add_class::<PyOggVorbisInfo>()?;
+    m.add_class::<PyOggFLAC>()?;
     m.add_class::<PyMP4>()?;
     m.add_class::<PyMP4Info>()?;
     m.add_class::<PyMP4Tags>()?;
+    m.add_class::<PyWavPack>()?;
+    m.add_class::<PyWavPackInfo>()?;
+    m.add_class::<PyAPEv2>()?;
+    m.add_class::<PyASF>()?;
+    m.add_class::<PyASFInfo>()?;
+    m.add_class::<PyASFTags>()?;
+    m.add_class::<PyMonkeysAudio>()?;
+    m.add_class::<PyMonkeysAudioInfo>()?;
     m.add_class::<PyBatchResult>()?;
 
     m.add_function(wrap_pyfunction!(file_open, m)?)?;
     m.add_function(wrap_pyfunction!(batch_open, m)?)?;
     m.add_function(wrap_pyfunction!(batch_diag, m)?)?;
     m.add_function(wrap_pyfunction!(clear_cache, m)?)?;
+    m.add_function(wrap_pyfunction!(set_file_cache_capacity, m)?)?;
+    m.add_function(wrap_pyfunction!(file_cache_info, m)?)?;
     m.add_function(wrap_pyfunction!(_rust_batch_open, m)?)?;
     m.add_function(wrap_pyfunction!(_fast_read, m)?)?;
     m.add_function(wrap_pyfunction!(_fast_info, m)?)?;
     m.add_function(wrap_pyfunction!(_fast_read_seq, m)?)?;
+    m.add_function(wrap_pyfunction!(detect_format, m)?)?;
+    m.add_function(wrap_pyfunction!(front_cover, m)?)?;
+    m.add_function(wrap_pyfunction!(duration, m)?)?;
+    m.add_function(wrap_pyfunction!(markers, m)?)?;
 
     m.add("MutagenError", m.py().get_type::<common::error::MutagenPyError>())?;
     m.add("ID3Error", m.py().get_type::<common::error::ID3Error>())?;
@@ -3096,6 +4869,14 @@ fn mutagen_rs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("FLACNoHeaderError", m.py().get_type::<common::error::FLACNoHeaderError>())?;
     m.add("OggError", m.py().get_type::<common::error::OggError>())?;
     m.add("MP4Error", m.py().get_type::<common::error::MP4Error>())?;
+    m.add("APEError", m.py().get_type::<common::error::APEError>())?;
+    m.add("WavPackError", m.py().get_type::<common::error::WavPackError>())?;
+    m.add("ASFError", m.py().get_type::<common::error::ASFError>())?;
+    m.add("MonkeysAudioHeaderError", m.py().get_type::<common::error::MonkeysAudioHeaderError>())?;
+    m.add("TruncatedError", m.py().get_type::<common::error::TruncatedError>())?;
+    m.add("VerificationError", m.py().get_type::<common::error::VerificationError>())?;
+    m.add("InvalidDataError", common::error::invalid_data_error(m.py())?)?;
+    m.add("EncodingError", common::error::encoding_error(m.py())?)?;
 
     m.add("File", wrap_pyfunction!(file_open, m)?)?;
 
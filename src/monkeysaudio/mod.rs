@@ -0,0 +1,108 @@
+use crate::apev2::{self, APEv2Tags};
+use crate::common::error::{MutagenError, Result};
+
+/// Monkey's Audio stream information.
+#[derive(Debug, Clone)]
+pub struct MonkeysAudioInfo {
+    pub length: f64,
+    pub channels: u32,
+    pub sample_rate: u32,
+    /// 0 when an old-format file carries no WAV header to read it from.
+    pub bits_per_sample: u32,
+    pub bitrate: u32,
+    /// Encoder version, e.g. 3.99 (stored on disk as 3990).
+    pub version: f64,
+    /// Compression level: 1000 (fast) to 5000 (insane).
+    pub compression_level: u16,
+}
+
+impl MonkeysAudioInfo {
+    /// Parse the `MAC ` descriptor and header. Files from 3.98 on start with a
+    /// 52-byte descriptor followed by the header; older files have a single
+    /// combined header.
+    pub fn parse(data: &[u8], audio_end: usize) -> Result<Self> {
+        if data.len() < 76 || &data[0..4] != b"MAC " {
+            return Err(MutagenError::MonkeysAudio("not a Monkey's Audio file".into()));
+        }
+        let u16_at = |i: usize| u16::from_le_bytes([data[i], data[i + 1]]);
+        let u32_at = |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+
+        let version = u16_at(4);
+        let (compression_level, blocks_per_frame, final_frame_blocks, total_frames,
+             bits_per_sample, channels, sample_rate) = if version >= 3980 {
+            (u16_at(52), u32_at(56), u32_at(60), u32_at(64), u16_at(68) as u32, u16_at(70) as u32, u32_at(72))
+        } else {
+            let compression_level = u16_at(6);
+            let blocks_per_frame = if version >= 3950 {
+                73728 * 4
+            } else if version >= 3900 || (version >= 3800 && compression_level == 4000) {
+                73728
+            } else {
+                9216
+            };
+            let bits_per_sample = if &data[48..55] == b"WAVEfmt" { u16_at(74) as u32 } else { 0 };
+            (compression_level, blocks_per_frame, u32_at(28), u32_at(24), bits_per_sample, u16_at(10) as u32, u32_at(12))
+        };
+
+        let length = if sample_rate > 0 && total_frames > 0 {
+            let total_blocks = (total_frames as u64 - 1) * blocks_per_frame as u64 + final_frame_blocks as u64;
+            total_blocks as f64 / sample_rate as f64
+        } else {
+            0.0
+        };
+        let bitrate = if length > 0.0 { (audio_end as f64 * 8.0 / length) as u32 } else { 0 };
+
+        Ok(MonkeysAudioInfo {
+            length,
+            channels,
+            sample_rate,
+            bits_per_sample,
+            bitrate,
+            version: version as f64 / 1000.0,
+            compression_level,
+        })
+    }
+}
+
+/// Complete Monkey's Audio file: stream info + APEv2 tags.
+#[derive(Debug)]
+pub struct MonkeysAudioFile {
+    pub info: MonkeysAudioInfo,
+    pub tags: Option<APEv2Tags>,
+    pub path: String,
+}
+
+impl MonkeysAudioFile {
+    pub fn open(path: &str) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        Self::parse(&data, path)
+    }
+
+    pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        let audio_end = match apev2::find_apev2(data) {
+            Some(loc) => loc.start,
+            None if crate::id3::id3v1::find_id3v1(data).is_some() => data.len() - 128,
+            None => data.len(),
+        };
+        let info = MonkeysAudioInfo::parse(data, audio_end)?;
+        let tags = APEv2Tags::from_data(data)?;
+
+        Ok(MonkeysAudioFile {
+            info,
+            tags,
+            path: path.to_string(),
+        })
+    }
+
+    pub fn score(path: &str, data: &[u8]) -> u32 {
+        let mut score = 0u32;
+        let ext = path.rsplit('.').next().unwrap_or("");
+        if ext.eq_ignore_ascii_case("ape") {
+            score += 2;
+        }
+        if data.len() >= 4 && &data[0..4] == b"MAC " {
+            score += 3;
+        }
+        score
+    }
+}
@@ -106,6 +106,8 @@ pub struct MPEGFrame {
     pub sample_rate: u32,  // Hz
     pub padding: bool,
     pub channel_mode: ChannelMode,
+    /// Mode extension bits; only meaningful for joint stereo.
+    pub mode_extension: u8,
     pub channels: u32,
     pub frame_length: u32, // bytes
     pub samples_per_frame: u32,
@@ -191,6 +193,9 @@ impl MPEGFrame {
             _ => unreachable!(),
         };
 
+        // Mode extension: bits 4-5
+        let mode_extension = ((h >> 4) & 0x03) as u8;
+
         let channels = channel_mode.num_channels();
         let spf = SAMPLES_PER_FRAME[version_idx][layer_idx];
 
@@ -213,17 +218,67 @@ impl MPEGFrame {
             sample_rate,
             padding,
             channel_mode,
+            mode_extension,
             channels,
             frame_length,
             samples_per_frame: spf,
         })
     }
+
+    /// Size of the Layer III side info that the frame CRC covers.
+    /// Layer I/II CRCs depend on the bit allocation and aren't handled.
+    pub fn side_info_len(&self) -> Option<usize> {
+        if self.layer != MPEGLayer::Layer3 {
+            return None;
+        }
+        let mono = self.channel_mode == ChannelMode::Mono;
+        Some(match (self.version, mono) {
+            (MPEGVersion::V1, true) => 17,
+            (MPEGVersion::V1, false) => 32,
+            (_, true) => 9,
+            (_, false) => 17,
+        })
+    }
+
+    /// Validate the 16-bit CRC that follows the header of a protected frame.
+    /// `frame` must start at the sync word. Returns `None` when the frame isn't
+    /// protected, the layer isn't supported, or `frame` is too short.
+    pub fn check_crc(&self, frame: &[u8]) -> Option<bool> {
+        if !self.protected {
+            return None;
+        }
+        let side = self.side_info_len()?;
+        if frame.len() < 6 + side {
+            return None;
+        }
+        let stored = u16::from_be_bytes([frame[4], frame[5]]);
+        let crc = crc16_update(crc16_update(0xFFFF, &frame[2..4]), &frame[6..6 + side]);
+        Some(crc == stored)
+    }
+}
+
+/// CRC-16 (polynomial 0x8005, MSB first) as used for MPEG audio frame protection.
+fn crc16_update(mut crc: u16, data: &[u8]) -> u16 {
+    for &b in data {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 { (crc << 1) ^ 0x8005 } else { crc << 1 };
+        }
+    }
+    crc
 }
 
 /// Scan for the first valid MPEG sync frame in data.
 /// Returns the offset and parsed frame if found.
 #[inline(always)]
 pub fn find_sync(data: &[u8], start: usize) -> Option<(usize, MPEGFrame)> {
+    find_sync_checked(data, start, false)
+}
+
+/// Like `find_sync`, but when `check_crc` is set, frames that claim CRC
+/// protection are only accepted if their CRC matches.
+#[inline(always)]
+pub fn find_sync_checked(data: &[u8], start: usize, check_crc: bool) -> Option<(usize, MPEGFrame)> {
     use memchr::memchr;
 
     let mut pos = start;
@@ -238,6 +293,10 @@ pub fn find_sync(data: &[u8], start: usize) -> Option<(usize, MPEGFrame)> {
                 // Check if this is a valid frame header
                 if data[pos + 1] & 0xE0 == 0xE0 {
                     if let Ok(frame) = MPEGFrame::parse(&data[pos..pos + 4]) {
+                        if check_crc && frame.check_crc(&data[pos..]) == Some(false) {
+                            pos += 1;
+                            continue;
+                        }
                         // Validate: check that the next frame also has valid sync
                         let next_pos = pos + frame.frame_length as usize;
                         if next_pos + 4 <= data.len() {
@@ -2,10 +2,11 @@ pub mod header;
 pub mod xing;
 
 use crate::common::error::{MutagenError, Result};
+use crate::common::options::ParseOptions;
 use crate::id3;
 use crate::id3::header::ID3Header;
 use crate::id3::tags::ID3Tags;
-use crate::mp3::header::{MPEGFrame, find_sync, ChannelMode};
+use crate::mp3::header::{MPEGFrame, find_sync, find_sync_checked, ChannelMode};
 use crate::mp3::xing::{XingHeader, VBRIHeader, BitrateMode};
 
 /// Parsed MP3 file information.
@@ -18,19 +19,82 @@ pub struct MPEGInfo {
     pub version: f64,
     pub layer: u8,
     pub mode: u32,
+    /// Mode extension bits of the first frame; only meaningful for joint stereo.
+    pub mode_extension: u8,
     pub protected: bool,
     pub bitrate_mode: BitrateMode,
     pub encoder_info: String,
     pub encoder_settings: String,
+    /// ReplayGain from the LAME header.
     pub track_gain: Option<f32>,
+    /// LAME stores a single peak amplitude, measured over the track, so
+    /// there is no album peak to report.
     pub track_peak: Option<f32>,
     pub album_gain: Option<f32>,
+    /// Length after trimming the LAME encoder delay and padding, for
+    /// gapless playback; `length` is the full container duration.
+    pub decoded_length: Option<f64>,
+    /// Offset of the first MPEG frame within the data passed to `parse`.
+    pub sync_offset: usize,
+    /// False for a tag-only file with no MPEG frames; the stream fields
+    /// are then all zero.
+    pub has_audio: bool,
 }
 
 impl MPEGInfo {
+    /// Channel mode label: "Stereo", "Joint Stereo", "Dual Channel" or "Mono".
+    pub fn mode_str(&self) -> &'static str {
+        match self.mode {
+            0 => "Stereo",
+            1 => "Joint Stereo",
+            2 => "Dual Channel",
+            _ => "Mono",
+        }
+    }
+
+    /// Whether joint stereo uses intensity stereo. Layer I/II joint stereo
+    /// is always intensity stereo; Layer III signals it in mode extension bit 0.
+    pub fn intensity_stereo(&self) -> bool {
+        self.mode == 1 && (self.layer != 3 || self.mode_extension & 0x01 != 0)
+    }
+
+    /// Whether joint stereo uses mid/side stereo (Layer III mode extension bit 1).
+    pub fn ms_stereo(&self) -> bool {
+        self.mode == 1 && self.layer == 3 && self.mode_extension & 0x02 != 0
+    }
+
+    /// Info for a file without any MPEG audio.
+    pub fn without_audio() -> Self {
+        MPEGInfo {
+            length: 0.0,
+            channels: 0,
+            bitrate: 0,
+            sample_rate: 0,
+            version: 0.0,
+            layer: 0,
+            mode: 0,
+            mode_extension: 0,
+            protected: false,
+            bitrate_mode: BitrateMode::Unknown,
+            encoder_info: String::new(),
+            encoder_settings: String::new(),
+            track_gain: None,
+            track_peak: None,
+            album_gain: None,
+            decoded_length: None,
+            sync_offset: 0,
+            has_audio: false,
+        }
+    }
+
     /// Parse MPEG audio info from data starting at offset.
     pub fn parse(data: &[u8], offset: usize, file_size: u64) -> Result<Self> {
-        let (sync_offset, first_frame) = find_sync(data, offset)
+        Self::parse_checked(data, offset, file_size, false)
+    }
+
+    /// Parse MPEG audio info, optionally rejecting sync candidates whose frame CRC fails.
+    pub fn parse_checked(data: &[u8], offset: usize, file_size: u64, check_crc: bool) -> Result<Self> {
+        let (sync_offset, first_frame) = find_sync_checked(data, offset, check_crc)
             .ok_or_else(|| MutagenError::HeaderNotFoundError(
                 "can't sync to MPEG frame".into(),
             ))?;
@@ -58,6 +122,7 @@ impl MPEGInfo {
         let mut track_gain = None;
         let mut track_peak = None;
         let mut album_gain = None;
+        let mut decoded_length = None;
 
         if let Some(xing) = XingHeader::parse(frame_data, version, channel_mode) {
             bitrate_mode = if xing.is_info { BitrateMode::CBR } else { BitrateMode::VBR };
@@ -66,15 +131,25 @@ impl MPEGInfo {
                 let spf = first_frame.samples_per_frame as f64;
                 length = (frames as f64 * spf) / sample_rate as f64;
                 if length > 0.0 {
-                    bitrate = (bytes as f64 * 8.0 / length) as u32;
+                    // `bytes` counts the Xing frame, `frames` does not
+                    let audio_bytes = bytes.saturating_sub(first_frame.frame_length);
+                    bitrate = (audio_bytes as f64 * 8.0 / length) as u32;
                 }
             }
 
             if let Some(ref lame) = xing.lame_header {
                 encoder_info = lame.encoder_version.clone();
+                encoder_settings = lame.guess_settings();
                 track_gain = lame.track_gain;
                 track_peak = if lame.replay_gain_peak > 0.0 { Some(lame.replay_gain_peak) } else { None };
                 album_gain = lame.album_gain;
+                if let Some(frames) = xing.frames {
+                    let total = frames as u64 * first_frame.samples_per_frame as u64;
+                    let trim = lame.encoder_delay as u64 + lame.encoder_padding as u64;
+                    if trim > 0 && trim < total {
+                        decoded_length = Some((total - trim) as f64 / sample_rate as f64);
+                    }
+                }
                 bitrate_mode = match lame.vbr_method {
                     1 | 8 => BitrateMode::CBR,
                     2 | 9 => BitrateMode::ABR,
@@ -104,9 +179,12 @@ impl MPEGInfo {
         Ok(MPEGInfo {
             length, channels, bitrate, sample_rate,
             version: version.as_f64(), layer: layer.as_u8(),
-            mode, protected, bitrate_mode,
+            mode, mode_extension: first_frame.mode_extension, protected, bitrate_mode,
             encoder_info, encoder_settings,
             track_gain, track_peak, album_gain,
+            decoded_length,
+            sync_offset,
+            has_audio: true,
         })
     }
 }
@@ -118,12 +196,14 @@ pub struct MP3File {
     pub info: MPEGInfo,
     pub path: String,
     pub id3_header: Option<ID3Header>,
+    /// Byte offset of the first MPEG frame in the file.
+    pub audio_offset: usize,
 }
 
 impl MP3File {
     /// Open and parse an MP3 file using cached file reads.
     pub fn open(path: &str) -> Result<Self> {
-        let data = std::fs::read(path)?;
+        let data = crate::common::cache::read(path)?;
         let mut f = Self::parse(&data, path)?;
         f.ensure_tags_parsed(&data);
         Ok(f)
@@ -132,24 +212,25 @@ impl MP3File {
     /// Parse an MP3 file: validates format + parses MPEG info.
     /// ID3 frame parsing is deferred to ensure_tags_parsed().
     pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        Self::parse_checked(data, path, false)
+    }
+
+    /// Like `parse`, with optional CRC validation of protected frames during sync.
+    pub fn parse_checked(data: &[u8], path: &str, check_crc: bool) -> Result<Self> {
         let file_size = data.len() as u64;
 
-        // Parse ID3v2 header (but NOT frames)
-        let (id3_header, audio_start) = if data.len() >= 10 {
-            match ID3Header::parse(&data[0..10], 0) {
-                Ok(h) => {
-                    let tag_size = h.size as usize;
-                    if 10 + tag_size <= data.len() {
-                        let audio_start = h.full_size() as usize;
-                        (Some(h), audio_start)
-                    } else {
-                        (None, 0)
-                    }
+        // Parse ID3v2 header (but NOT frames), possibly behind leading junk
+        let (id3_header, audio_start) = match ID3Header::locate(data) {
+            Ok(h) => {
+                let tag_size = h.size as usize;
+                if h.data_offset() + tag_size <= data.len() {
+                    let audio_start = h.offset as usize + h.full_size() as usize;
+                    (Some(h), audio_start)
+                } else {
+                    (None, 0)
                 }
-                Err(_) => (None, 0),
             }
-        } else {
-            (None, 0)
+            Err(_) => (None, 0),
         };
 
         // Parse MPEG audio info from audio data
@@ -160,29 +241,43 @@ impl MP3File {
             &[]
         };
 
-        let info = MPEGInfo::parse(audio_data, 0, file_size.saturating_sub(audio_start as u64))?;
+        // A file holding only an ID3 tag (e.g. a playlist stub) is still
+        // readable; it just has no audio.
+        let info = match MPEGInfo::parse_checked(audio_data, 0, file_size.saturating_sub(audio_start as u64), check_crc) {
+            Err(MutagenError::HeaderNotFoundError(_)) if id3_header.is_some() => MPEGInfo::without_audio(),
+            result => result?,
+        };
 
+        let audio_offset = audio_start + info.sync_offset;
         Ok(MP3File {
             tags: ID3Tags::new(),
             info,
             path: path.to_string(),
             id3_header,
+            audio_offset,
         })
     }
 
     /// Parse ID3 frames from the original file data.
     /// Call this after parse() when you need tag access.
     pub fn ensure_tags_parsed(&mut self, data: &[u8]) {
+        let _ = self.ensure_tags_parsed_with(data, ParseOptions::default());
+    }
+
+    /// Like `ensure_tags_parsed`, but in strict mode a truncated ID3 frame
+    /// is returned as an error. Frames read before the error are kept.
+    pub fn ensure_tags_parsed_with(&mut self, data: &[u8], options: ParseOptions) -> Result<()> {
+        let mut result = Ok(());
         if let Some(ref h) = self.id3_header {
-            let tag_size = h.size as usize;
-            if 10 + tag_size <= data.len() {
+            let (start, end) = (h.data_offset(), h.data_offset() + h.size as usize);
+            if end <= data.len() {
                 let mut tags = ID3Tags::new();
                 if h.flags.unsynchronisation && h.version.0 < 4 {
-                    if let Ok(tag_data) = id3::unsynch::decode(&data[10..10 + tag_size]) {
-                        let _ = tags.read_frames(&tag_data, h);
+                    if let Ok(tag_data) = id3::unsynch::decode(&data[start..end]) {
+                        result = tags.read_frames_with(&tag_data, h, options);
                     }
                 } else {
-                    let _ = tags.read_frames(&data[10..10 + tag_size], h);
+                    result = tags.read_frames_with(&data[start..end], h, options);
                 }
                 self.tags = tags;
             }
@@ -202,6 +297,7 @@ impl MP3File {
                 }
             }
         }
+        result
     }
 
     pub fn save(&self) -> Result<()> {
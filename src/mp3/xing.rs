@@ -1,13 +1,14 @@
 use crate::common::error::Result;
 use crate::mp3::header::{MPEGVersion, ChannelMode};
 
-/// Bitrate mode for VBR detection.
+/// Bitrate mode for VBR detection. Shared by every format that reports one;
+/// the discriminants are the integers exposed to Python.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum BitrateMode {
-    Unknown,
-    CBR,
-    VBR,
-    ABR,
+    Unknown = 0,
+    CBR = 1,
+    VBR = 2,
+    ABR = 3,
 }
 
 /// Parsed Xing/Info VBR header.
@@ -27,11 +28,106 @@ pub struct LAMEHeader {
     pub encoder_version: String,
     pub vbr_method: u8,
     pub lowpass_freq: u32,
+    /// Peak signal amplitude of the track; the tag has no album peak field.
     pub replay_gain_peak: f32,
     pub track_gain: Option<f32>,
     pub album_gain: Option<f32>,
     pub encoder_delay: u16,
     pub encoder_padding: u16,
+    pub encoding_flags: u8,
+    pub ath_type: u8,
+    /// ABR target bitrate, or minimal bitrate otherwise (kbps, 255 means 255+).
+    pub bitrate: u8,
+    pub stereo_mode: u8,
+    /// LAME preset id (e.g. 1001 for `--preset standard`), 0 if none.
+    pub preset_used: u16,
+    /// `-V` level and `-q` quality derived from the Xing quality field.
+    pub vbr_quality: Option<u32>,
+    pub quality: Option<u32>,
+}
+
+impl LAMEHeader {
+    /// `(major, minor)` from the encoder string: `LAME3.99r` -> `(3, 99)`, `LAME3.100` -> `(3, 100)`.
+    pub fn version(&self) -> Option<(u32, u32)> {
+        let s = self.encoder_version.strip_prefix("LAME")
+            .or_else(|| self.encoder_version.strip_prefix('L'))?;
+        let (major, rest) = s.split_once('.')?;
+        let minor: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        Some((major.parse().ok()?, minor.parse().ok()?))
+    }
+
+    /// Best guess at the command line options used to encode, e.g. `-V 2`
+    /// or `--preset standard`. Empty when nothing can be derived.
+    pub fn guess_settings(&self) -> String {
+        let version = match self.version() {
+            Some(v) => v,
+            None => return String::new(),
+        };
+        let alt_preset_era = matches!(version, (3, 90..=92));
+        let vbr_quality = self.vbr_quality.map(|q| q.to_string()).unwrap_or_else(|| "-1".into());
+        let bitrate = self.bitrate as u32;
+
+        match self.vbr_method {
+            // ABR
+            2 => {
+                if alt_preset_era && self.encoding_flags != 0 {
+                    if bitrate < 255 {
+                        format!("--alt-preset {}", bitrate)
+                    } else {
+                        format!("--alt-preset {}+", bitrate)
+                    }
+                } else if self.preset_used != 0 {
+                    format!("--preset {}", self.preset_used)
+                } else if bitrate < 255 {
+                    format!("--abr {}", bitrate)
+                } else {
+                    format!("--abr {}+", bitrate)
+                }
+            }
+            // CBR
+            1 => match self.preset_used {
+                0 if bitrate < 255 => format!("-b {}", bitrate),
+                0 => "-b 255+".into(),
+                1003 => "--preset insane".into(),
+                preset => format!("-b {}", preset),
+            },
+            method if alt_preset_era => {
+                let preset_key = (self.vbr_quality, self.quality, method, self.lowpass_freq, self.ath_type);
+                match preset_key {
+                    (Some(1), Some(2), 4, 19500, 3) => "--preset r3mix".into(),
+                    (Some(2), Some(2), 3, 19000, 4) => "--alt-preset standard".into(),
+                    (Some(2), Some(2), 3, 19500, 2) => "--alt-preset extreme".into(),
+                    _ if method == 3 => format!("-V {}", vbr_quality),
+                    _ if method == 4 || method == 5 => format!("-V {} --vbr-new", vbr_quality),
+                    _ => String::new(),
+                }
+            }
+            method if matches!(version, (3, 93..=97)) => match self.preset_used {
+                1001 => "--preset standard".into(),
+                1002 => "--preset extreme".into(),
+                1004 => "--preset fast standard".into(),
+                1005 => "--preset fast extreme".into(),
+                1006 => "--preset medium".into(),
+                1007 => "--preset fast medium".into(),
+                _ if method == 3 => format!("-V {}", vbr_quality),
+                _ if method == 4 || method == 5 => format!("-V {} --vbr-new", vbr_quality),
+                _ => String::new(),
+            },
+            3 if version >= (3, 98) => format!("-V {} --vbr-old", vbr_quality),
+            4 | 5 if version == (3, 98) => format!("-V {}", vbr_quality),
+            4 | 5 if version >= (3, 99) => {
+                // Low -V levels are stored wrongly by LAME 3.99 (LAME bug #455)
+                let level = match (self.vbr_quality, bitrate, self.lowpass_freq) {
+                    (Some(5), 32, 0) => "7".into(),
+                    (Some(5), 8, 0) => "8".into(),
+                    (Some(6), 8, 0) => "9".into(),
+                    _ => vbr_quality,
+                };
+                format!("-V {}", level)
+            }
+            _ => String::new(),
+        }
+    }
 }
 
 /// Parsed VBRI header.
@@ -46,17 +142,15 @@ impl XingHeader {
     /// Try to parse a Xing/Info header from the MPEG frame data.
     /// `data` should start at the beginning of the MPEG frame (after sync).
     pub fn parse(data: &[u8], version: MPEGVersion, channel_mode: ChannelMode) -> Option<Self> {
-        // Xing header offset depends on MPEG version and channel mode
-        let offset = match (version, channel_mode) {
+        // Xing header follows the 4-byte frame header and the side info,
+        // whose size depends on MPEG version and channel mode
+        let xing_offset = match (version, channel_mode) {
             (MPEGVersion::V1, ChannelMode::Mono) => 21,
             (MPEGVersion::V1, _) => 36,
             (_, ChannelMode::Mono) => 13,
             (_, _) => 21,
         };
 
-        // Add 4 bytes for the frame header itself
-        let xing_offset = offset + 4;
-
         if data.len() < xing_offset + 4 {
             return None;
         }
@@ -128,7 +222,7 @@ impl XingHeader {
 
         // Try to parse LAME header
         let lame_header = if pos + 9 <= data.len() {
-            parse_lame_header(data, pos)
+            parse_lame_header(data, pos, quality)
         } else {
             None
         };
@@ -145,7 +239,7 @@ impl XingHeader {
 }
 
 /// Parse LAME encoder info from Xing header extension.
-fn parse_lame_header(data: &[u8], offset: usize) -> Option<LAMEHeader> {
+fn parse_lame_header(data: &[u8], offset: usize, xing_quality: Option<u32>) -> Option<LAMEHeader> {
     if offset + 36 > data.len() {
         return None;
     }
@@ -188,20 +282,22 @@ fn parse_lame_header(data: &[u8], offset: usize) -> Option<LAMEHeader> {
         None
     };
 
-    // Encoder delay and padding at pos+21
-    let delay_padding_pos = pos + 21;
-    let (encoder_delay, encoder_padding) = if delay_padding_pos + 3 <= data.len() {
-        let dp = u32::from_be_bytes([
-            0,
-            data[delay_padding_pos],
-            data[delay_padding_pos + 1],
-            data[delay_padding_pos + 2],
-        ]);
-        let delay = ((dp >> 12) & 0xFFF) as u16;
-        let padding = (dp & 0xFFF) as u16;
-        (delay, padding)
-    } else {
-        (0, 0)
+    // Encoding flags + ATH type, then the ABR/minimal bitrate
+    let encoding_flags = data[pos + 10] >> 4;
+    let ath_type = data[pos + 10] & 0x0F;
+    let bitrate = data[pos + 11];
+    // Encoder delay and padding: two 12-bit values in the 3 bytes at pos+12
+    let dp = u32::from_be_bytes([0, data[pos + 12], data[pos + 13], data[pos + 14]]);
+    let encoder_delay = ((dp >> 12) & 0xFFF) as u16;
+    let encoder_padding = (dp & 0xFFF) as u16;
+    // Misc byte after the 3 delay/padding bytes: stereo mode in bits 2-4
+    let stereo_mode = (data[pos + 15] >> 2) & 0x07;
+    // 2 unused bits, 3 bits surround info, 11 bits preset
+    let preset_used = u16::from_be_bytes([data[pos + 17], data[pos + 18]]) & 0x07FF;
+
+    let (vbr_quality, quality) = match xing_quality {
+        Some(scale) if scale <= 100 => (Some((100 - scale) / 10), Some((100 - scale) % 10)),
+        _ => (None, None),
     };
 
     Some(LAMEHeader {
@@ -213,6 +309,13 @@ fn parse_lame_header(data: &[u8], offset: usize) -> Option<LAMEHeader> {
         album_gain,
         encoder_delay,
         encoder_padding,
+        encoding_flags,
+        ath_type,
+        bitrate,
+        stereo_mode,
+        preset_used,
+        vbr_quality,
+        quality,
     })
 }
 
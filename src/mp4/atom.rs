@@ -47,6 +47,13 @@ impl<'a> AtomIter<'a> {
     pub fn find_name(mut self, name: &[u8; 4]) -> Option<Atom> {
         self.find(|a| &a.name == name)
     }
+
+    /// All atoms with the given name, e.g. every `trak` in `moov`.
+    #[inline]
+    pub fn filter_name(self, name: &[u8; 4]) -> impl Iterator<Item = Atom> + 'a {
+        let name = *name;
+        self.filter(move |a| a.name == name)
+    }
 }
 
 impl<'a> Iterator for AtomIter<'a> {
@@ -132,6 +139,38 @@ pub fn find_atom_path_in(data: &[u8], start: usize, end: usize, path: &[&[u8; 4]
     find_atom_path_in(data, found.data_offset, found.data_offset + found.data_size, &path[1..])
 }
 
+/// Find every atom matching a path, following all repeated atoms at each
+/// level, e.g. `["moov", "trak"]` yields each track.
+pub fn find_all_path(data: &[u8], path: &[&[u8; 4]]) -> Vec<Atom> {
+    let mut found = Vec::new();
+    find_all_path_in(data, 0, data.len(), path, &mut found);
+    found
+}
+
+fn find_all_path_in(data: &[u8], start: usize, end: usize, path: &[&[u8; 4]], found: &mut Vec<Atom>) {
+    let Some((first, rest)) = path.split_first() else { return };
+    for atom in AtomIter::new(data, start, end).filter_name(first) {
+        if rest.is_empty() {
+            found.push(atom);
+        } else {
+            find_all_path_in(data, atom.data_offset, atom.data_offset + atom.data_size, rest, found);
+        }
+    }
+}
+
+/// Offset of the first child of a `meta` atom. iTunes writes `meta` as a
+/// full box with 4 bytes of version/flags before the children; some other
+/// muxers write a plain container. The prefix is assumed absent when the
+/// data already starts with a plausible child header (size, printable name).
+pub fn meta_children_offset(data: &[u8], meta: &Atom) -> usize {
+    let start = meta.data_offset;
+    let plain = data.get(start..start + 8).is_some_and(|h| {
+        let size = u32::from_be_bytes([h[0], h[1], h[2], h[3]]) as usize;
+        (8..=meta.data_size).contains(&size) && h[4..8].iter().all(|b| b.is_ascii_graphic() || *b == b' ')
+    });
+    if plain { start } else { start + 4 }
+}
+
 // Keep the old signature for backward compatibility
 #[allow(dead_code)]
 pub fn find_atom_path_legacy<'a>(data: &[u8], atoms: &'a [Atom], path: &[&[u8; 4]]) -> Option<Atom> {
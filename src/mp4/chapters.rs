@@ -0,0 +1,141 @@
+//! Chapter markers: Nero `chpl` atoms and QuickTime chapter text tracks.
+
+use crate::mp4::atom::{find_atom_path_in, Atom, AtomIter};
+
+/// A chapter start time in seconds and its title.
+pub type Chapter = (f64, String);
+
+/// Read chapters from `moov`, preferring a Nero `udta/chpl` list and falling
+/// back to a text track referenced by an audio track's `tref/chap`.
+pub fn parse_chapters(data: &[u8], moov_start: usize, moov_end: usize) -> Vec<Chapter> {
+    let nero = find_atom_path_in(data, moov_start, moov_end, &[b"udta", b"chpl"])
+        .map(|chpl| parse_chpl(body(data, &chpl)))
+        .unwrap_or_default();
+    if !nero.is_empty() {
+        return nero;
+    }
+    parse_text_track(data, moov_start, moov_end).unwrap_or_default()
+}
+
+fn body<'a>(data: &'a [u8], atom: &Atom) -> &'a [u8] {
+    &data[atom.data_offset..atom.data_offset + atom.data_size]
+}
+
+fn be_u32(d: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(d.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+fn be_u64(d: &[u8], pos: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(d.get(pos..pos + 8)?.try_into().ok()?))
+}
+
+/// Nero chapter list: version/flags, 4 reserved bytes (version 1 only), an
+/// 8-bit count, then per chapter a start time in 100 ns units and a
+/// length-prefixed UTF-8 title.
+fn parse_chpl(d: &[u8]) -> Vec<Chapter> {
+    let mut chapters = Vec::new();
+    let Some(&version) = d.first() else { return chapters };
+    let mut pos = if version == 1 { 8 } else { 4 };
+    let Some(&count) = d.get(pos) else { return chapters };
+    pos += 1;
+    for _ in 0..count {
+        let Some(start) = be_u64(d, pos) else { break };
+        let Some(&len) = d.get(pos + 8) else { break };
+        let Some(title) = d.get(pos + 9..pos + 9 + len as usize) else { break };
+        chapters.push((start as f64 / 10_000_000.0, String::from_utf8_lossy(title).into_owned()));
+        pos += 9 + len as usize;
+    }
+    chapters
+}
+
+fn track_id(data: &[u8], trak: &Atom) -> Option<u32> {
+    let tkhd = AtomIter::new(data, trak.data_offset, trak.data_offset + trak.data_size).find_name(b"tkhd")?;
+    let d = body(data, &tkhd);
+    be_u32(d, if d.first() == Some(&1) { 20 } else { 12 })
+}
+
+/// QuickTime chapters: the track named by `tref/chap` holds one text sample
+/// per chapter (16-bit length + text), timed by its `stts`.
+fn parse_text_track(data: &[u8], moov_start: usize, moov_end: usize) -> Option<Vec<Chapter>> {
+    let traks: Vec<Atom> = AtomIter::new(data, moov_start, moov_end).filter_name(b"trak").collect();
+    let chap_id = traks.iter().find_map(|trak| {
+        let chap = find_atom_path_in(data, trak.data_offset, trak.data_offset + trak.data_size, &[b"tref", b"chap"])?;
+        be_u32(body(data, &chap), 0)
+    })?;
+    let trak = traks.iter().find(|t| track_id(data, t) == Some(chap_id))?;
+    let (trak_s, trak_e) = (trak.data_offset, trak.data_offset + trak.data_size);
+
+    let mdhd = find_atom_path_in(data, trak_s, trak_e, &[b"mdia", b"mdhd"])?;
+    let mdhd = body(data, &mdhd);
+    let timescale = be_u32(mdhd, if mdhd.first() == Some(&1) { 20 } else { 12 })?;
+    if timescale == 0 {
+        return None;
+    }
+
+    let stbl = find_atom_path_in(data, trak_s, trak_e, &[b"mdia", b"minf", b"stbl"])?;
+    let (stbl_s, stbl_e) = (stbl.data_offset, stbl.data_offset + stbl.data_size);
+    let find = |name: &[u8; 4]| AtomIter::new(data, stbl_s, stbl_e).find_name(name).map(|a| body(data, &a));
+
+    // Sample sizes; the count is capped by the entries (or, for a fixed
+    // size, the bytes) actually present, so a bogus count can't allocate
+    let stsz = find(b"stsz")?;
+    let fixed = be_u32(stsz, 4)?;
+    let available = if fixed != 0 { data.len() / fixed as usize } else { stsz.len().saturating_sub(12) / 4 };
+    let sample_count = (be_u32(stsz, 8)? as usize).min(available);
+    let sizes: Vec<u32> = (0..sample_count)
+        .map(|i| if fixed != 0 { Some(fixed) } else { be_u32(stsz, 12 + i * 4) })
+        .collect::<Option<_>>()?;
+
+    // Sample start times, one per chapter
+    let stts = find(b"stts")?;
+    let mut times = Vec::with_capacity(sample_count);
+    let mut t = 0u64;
+    'stts: for i in 0..be_u32(stts, 4)? as usize {
+        let count = be_u32(stts, 8 + i * 8)?;
+        let delta = be_u32(stts, 12 + i * 8)? as u64;
+        for _ in 0..count {
+            if times.len() >= sample_count {
+                break 'stts;
+            }
+            times.push(t);
+            t = t.saturating_add(delta);
+        }
+    }
+
+    // Chunk offsets and the sample-to-chunk runs
+    let chunk_offsets: Vec<u64> = if let Some(stco) = find(b"stco") {
+        let count = (be_u32(stco, 4)? as usize).min(stco.len().saturating_sub(8) / 4);
+        (0..count).map(|i| be_u32(stco, 8 + i * 4).map(u64::from)).collect::<Option<_>>()?
+    } else {
+        let co64 = find(b"co64")?;
+        let count = (be_u32(co64, 4)? as usize).min(co64.len().saturating_sub(8) / 8);
+        (0..count).map(|i| be_u64(co64, 8 + i * 8)).collect::<Option<_>>()?
+    };
+    let stsc = find(b"stsc")?;
+    let run_count = (be_u32(stsc, 4)? as usize).min(stsc.len().saturating_sub(8) / 12);
+    let runs: Vec<(u32, u32)> = (0..run_count)
+        .map(|i| Some((be_u32(stsc, 8 + i * 12)?, be_u32(stsc, 12 + i * 12)?)))
+        .collect::<Option<_>>()?;
+
+    let mut chapters = Vec::with_capacity(sample_count);
+    let mut sample = 0usize;
+    for (chunk_idx, &chunk_offset) in chunk_offsets.iter().enumerate() {
+        let chunk_no = chunk_idx as u32 + 1;
+        let per_chunk = runs.iter().rev().find(|(first, _)| *first <= chunk_no).map_or(1, |r| r.1);
+        let mut offset = chunk_offset as usize;
+        for _ in 0..per_chunk {
+            if sample >= sample_count {
+                break;
+            }
+            let text = data.get(offset..offset.saturating_add(sizes[sample] as usize)).and_then(|s| {
+                let len = u16::from_be_bytes([*s.first()?, *s.get(1)?]) as usize;
+                s.get(2..2 + len)
+            })?;
+            let start = times.get(sample).copied().unwrap_or(0) as f64 / timescale as f64;
+            chapters.push((start, String::from_utf8_lossy(text).into_owned()));
+            offset = offset.saturating_add(sizes[sample] as usize);
+            sample += 1;
+        }
+    }
+    Some(chapters)
+}
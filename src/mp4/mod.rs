@@ -1,7 +1,9 @@
 pub mod atom;
+pub mod chapters;
 
 use crate::common::error::{MutagenError, Result};
-use crate::mp4::atom::{Atom, AtomIter, parse_atoms};
+use crate::mp4::atom::{meta_children_offset, Atom, AtomIter, parse_atoms};
+use crate::mp3::xing::BitrateMode;
 
 /// MP4 audio information.
 #[derive(Debug, Clone)]
@@ -13,6 +15,8 @@ pub struct MP4Info {
     pub bits_per_sample: u32,
     pub codec: String,
     pub codec_description: String,
+    /// From the `esds` decoder config; `Unknown` for codecs without one.
+    pub bitrate_mode: BitrateMode,
 }
 
 impl Default for MP4Info {
@@ -25,15 +29,54 @@ impl Default for MP4Info {
             bits_per_sample: 16,
             codec: String::new(),
             codec_description: String::new(),
+            bitrate_mode: BitrateMode::Unknown,
         }
     }
 }
 
+impl MP4Info {
+    /// Whether the codec is lossless (ALAC or FLAC in MP4).
+    pub fn lossless(&self) -> bool {
+        matches!(self.codec.as_str(), "alac" | "fLaC")
+    }
+}
+
 /// MP4 cover art format.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MP4CoverFormat {
-    JPEG = 13,
-    PNG = 14,
+    JPEG,
+    PNG,
+    BMP,
+    /// A type indicator that names no image format, with bytes that don't
+    /// carry a known image magic either.
+    Unknown(u8),
+}
+
+impl MP4CoverFormat {
+    /// Format of a `covr` data atom: its type indicator when that names an
+    /// image (13, 14, 27), otherwise sniffed from the image's magic bytes,
+    /// since some taggers write covers as implicit (type 0) data.
+    pub fn detect(type_indicator: u32, data: &[u8]) -> Self {
+        match type_indicator {
+            13 => MP4CoverFormat::JPEG,
+            14 => MP4CoverFormat::PNG,
+            27 => MP4CoverFormat::BMP,
+            _ if data.starts_with(b"\xFF\xD8\xFF") => MP4CoverFormat::JPEG,
+            _ if data.starts_with(b"\x89PNG\r\n\x1a\n") => MP4CoverFormat::PNG,
+            _ if data.starts_with(b"BM") => MP4CoverFormat::BMP,
+            other => MP4CoverFormat::Unknown(other as u8),
+        }
+    }
+
+    /// The `data` atom type indicator for this format.
+    pub fn type_indicator(self) -> u8 {
+        match self {
+            MP4CoverFormat::JPEG => 13,
+            MP4CoverFormat::PNG => 14,
+            MP4CoverFormat::BMP => 27,
+            MP4CoverFormat::Unknown(t) => t,
+        }
+    }
 }
 
 /// MP4 cover art.
@@ -62,6 +105,15 @@ pub enum MP4TagValue {
     Data(Vec<u8>),
 }
 
+/// ReplayGain values: gains in dB, peaks as linear amplitude.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ReplayGain {
+    pub track_gain: Option<f32>,
+    pub track_peak: Option<f32>,
+    pub album_gain: Option<f32>,
+    pub album_peak: Option<f32>,
+}
+
 /// Complete MP4 tag container (Vec-based for cache locality and low allocation).
 #[derive(Debug, Clone)]
 pub struct MP4Tags {
@@ -95,6 +147,158 @@ impl MP4Tags {
     pub fn contains_key(&self, key: &str) -> bool {
         self.items.iter().any(|(k, _)| k == key)
     }
+
+    /// Check that `key` is a valid item name: four Latin-1 characters
+    /// (e.g. `©nam`, `trkn`) or a freeform `----:mean:name`.
+    pub fn validate_key(key: &str) -> Result<()> {
+        if let Some(rest) = key.strip_prefix("----:") {
+            match rest.split_once(':') {
+                Some((mean, name)) if !mean.is_empty() && !name.is_empty() => return Ok(()),
+                _ => {}
+            }
+        } else if key.chars().count() == 4 && key.chars().all(|c| (c as u32) <= 0xFF) {
+            return Ok(());
+        }
+        Err(MutagenError::ValueError(format!("invalid MP4 key: {:?}", key)))
+    }
+
+    /// Insert or replace the value for `key`, keeping its position if present.
+    pub fn set(&mut self, key: &str, value: MP4TagValue) -> Result<()> {
+        Self::validate_key(key)?;
+        match self.get_mut(key) {
+            Some(existing) => *existing = value,
+            None => self.items.push((key.to_string(), value)),
+        }
+        Ok(())
+    }
+
+    /// Set a text item. Freeform keys store the values as UTF-8 freeform data.
+    pub fn set_text(&mut self, key: &str, values: Vec<String>) -> Result<()> {
+        if key.starts_with("----:") {
+            let forms = values.into_iter()
+                .map(|v| MP4FreeForm { data: v.into_bytes(), dataformat: 1 })
+                .collect();
+            return self.set(key, MP4TagValue::FreeForm(forms));
+        }
+        self.set(key, MP4TagValue::Text(values))
+    }
+
+    /// Set an integer item (e.g. `tmpo`, `cpil` stored as a number).
+    pub fn set_int(&mut self, key: &str, value: i64) -> Result<()> {
+        self.set(key, MP4TagValue::Integer(vec![value]))
+    }
+
+    /// Set a number/total pair item (`trkn`, `disk`).
+    pub fn set_pair(&mut self, key: &str, value: (i32, i32)) -> Result<()> {
+        self.set(key, MP4TagValue::IntPair(vec![value]))
+    }
+
+    /// Set a boolean item (e.g. `cpil`, `pgap`).
+    pub fn set_bool(&mut self, key: &str, value: bool) -> Result<()> {
+        self.set(key, MP4TagValue::Bool(value))
+    }
+
+    /// Set a freeform item from raw payloads (stored as implicit data).
+    pub fn set_freeform(&mut self, key: &str, values: Vec<Vec<u8>>) -> Result<()> {
+        if !key.starts_with("----:") {
+            return Err(MutagenError::ValueError(format!("not a freeform key: {:?}", key)));
+        }
+        let forms = values.into_iter()
+            .map(|data| MP4FreeForm { data, dataformat: 0 })
+            .collect();
+        self.set(key, MP4TagValue::FreeForm(forms))
+    }
+
+    /// Set `covr` images, taking each format from the image's magic bytes.
+    pub fn set_covers(&mut self, values: Vec<Vec<u8>>) -> Result<()> {
+        let covers = values.into_iter()
+            .map(|data| MP4Cover { format: MP4CoverFormat::detect(0, &data), data })
+            .collect();
+        self.set("covr", MP4TagValue::Cover(covers))
+    }
+
+    /// First value of a text item, or the UTF-8 payload of a freeform item.
+    pub fn get_text(&self, key: &str) -> Option<String> {
+        match self.get(key)? {
+            MP4TagValue::Text(v) => v.first().cloned(),
+            MP4TagValue::FreeForm(v) => v.first()
+                .map(|f| String::from_utf8_lossy(&f.data).trim_end_matches('\0').to_string()),
+            _ => None,
+        }
+    }
+
+    /// Release date from `©day`, trimmed to its date part
+    /// (`"2018-05-01T07:00:00Z"` -> `"2018-05-01"`, `"2018"` stays `"2018"`).
+    pub fn date(&self) -> Option<String> {
+        let day = self.get_text("\u{00a9}day")?;
+        let day = day.trim();
+        let date = day.split('T').next().unwrap_or(day);
+        if date.is_empty() { None } else { Some(date.to_string()) }
+    }
+
+    /// Four-digit year from `©day`.
+    pub fn year(&self) -> Option<u32> {
+        let date = self.date()?;
+        date.get(..4)?.parse().ok()
+    }
+
+    /// ISRC from a top-level `ISRC` item or the iTunes freeform
+    /// `----:com.apple.iTunes:ISRC` item.
+    pub fn isrc(&self) -> Option<String> {
+        self.get_text("ISRC")
+            .or_else(|| self.get_text("----:com.apple.iTunes:ISRC"))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Track number and total from `trkn`; zero means "not set".
+    pub fn track_number(&self) -> (Option<u32>, Option<u32>) {
+        self.number_pair("trkn")
+    }
+
+    /// Disc number and total from `disk`; zero means "not set".
+    pub fn disc_number(&self) -> (Option<u32>, Option<u32>) {
+        self.number_pair("disk")
+    }
+
+    /// ReplayGain from the iTunes freeform `replaygain_*` items that
+    /// foobar2000 and others write (`"-7.30 dB"`, `"0.988525"`). The item
+    /// names are matched case-insensitively.
+    pub fn replaygain(&self) -> ReplayGain {
+        let value = |name: &str| {
+            let text = self.items.iter()
+                .find(|(k, _)| k.strip_prefix("----:com.apple.iTunes:").is_some_and(|n| n.eq_ignore_ascii_case(name)))
+                .and_then(|(k, _)| self.get_text(k))?;
+            let text = text.trim();
+            let number = text.strip_suffix("dB").or_else(|| text.strip_suffix("db")).unwrap_or(text);
+            number.trim().parse::<f32>().ok()
+        };
+        ReplayGain {
+            track_gain: value("replaygain_track_gain"),
+            track_peak: value("replaygain_track_peak"),
+            album_gain: value("replaygain_album_gain"),
+            album_peak: value("replaygain_album_peak"),
+        }
+    }
+
+    /// Render every item into a complete `ilst` atom, in stored order.
+    pub fn render_ilst(&self) -> Result<Vec<u8>> {
+        let mut body = Vec::new();
+        for (key, value) in &self.items {
+            body.extend_from_slice(&render_item(key, value)?);
+        }
+        Ok(make_atom(b"ilst", &body))
+    }
+
+    fn number_pair(&self, key: &str) -> (Option<u32>, Option<u32>) {
+        let nonzero = |n: i32| if n > 0 { Some(n as u32) } else { None };
+        match self.get(key) {
+            Some(MP4TagValue::IntPair(pairs)) => pairs.first()
+                .map(|&(n, total)| (nonzero(n), nonzero(total)))
+                .unwrap_or((None, None)),
+            _ => (None, None),
+        }
+    }
 }
 
 /// Complete MP4 file handler.
@@ -103,10 +307,12 @@ pub struct MP4File {
     pub info: MP4Info,
     pub tags: MP4Tags,
     pub path: String,
+    /// Byte offset of the `mdat` payload (0 if there is no top-level `mdat`).
+    pub audio_offset: usize,
     moov_offset: usize,
     moov_size: usize,
-    file_size: usize,
     parsed: bool,
+    chapters: Vec<chapters::Chapter>,
 }
 
 impl MP4File {
@@ -124,14 +330,19 @@ impl MP4File {
             .find_name(b"moov")
             .ok_or_else(|| MutagenError::MP4("No moov atom".into()))?;
 
+        let audio_offset = AtomIter::new(data, 0, data.len())
+            .find_name(b"mdat")
+            .map_or(0, |mdat| mdat.data_offset);
+
         Ok(MP4File {
             info: MP4Info::default(),
             tags: MP4Tags::new(),
             path: path.to_string(),
+            audio_offset,
             moov_offset: moov.data_offset,
             moov_size: moov.data_size,
-            file_size: data.len(),
             parsed: false,
+            chapters: Vec::new(),
         })
     }
 
@@ -142,15 +353,29 @@ impl MP4File {
         }
         self.parsed = true;
         let moov_end = self.moov_offset + self.moov_size;
-        if let Ok(mut info) = parse_mp4_info_iter(data, self.moov_offset, moov_end) {
-            if info.length > 0.0 {
-                info.bitrate = (self.file_size as f64 * 8.0 / info.length) as u32;
-            }
+        if let Ok(info) = self.parse_info(data) {
             self.info = info;
         }
         if let Ok(tags) = parse_mp4_tags_iter(data, self.moov_offset, moov_end) {
             self.tags = tags;
         }
+        self.chapters = chapters::parse_chapters(data, self.moov_offset, moov_end);
+    }
+
+    /// Stream info alone, from `mvhd` and the audio track's `stbl`, without
+    /// walking `ilst` or the chapter tracks; for duration-only scans.
+    pub fn info_only(data: &[u8], path: &str) -> Result<MP4Info> {
+        Self::parse(data, path)?.parse_info(data)
+    }
+
+    fn parse_info(&self, data: &[u8]) -> Result<MP4Info> {
+        parse_mp4_info_iter(data, self.moov_offset, self.moov_offset + self.moov_size)
+    }
+
+    /// Chapter start times (seconds) and titles, from a Nero `chpl` atom or
+    /// a QuickTime chapter text track. Empty until parsed with data.
+    pub fn chapters(&self) -> &[chapters::Chapter] {
+        &self.chapters
     }
 
     pub fn save(&self) -> Result<()> {
@@ -209,12 +434,13 @@ fn parse_mp4_info_iter(data: &[u8], moov_start: usize, moov_end: usize) -> Resul
     let mut sample_rate = 44100u32;
     let mut bits_per_sample = 16u32;
     let mut codec = String::from("mp4a");
-    let codec_description = String::new();
+    let mut codec_description = String::new();
     let mut bitrate = 0u32;
+    let mut bitrate_mode = BitrateMode::Unknown;
+    let mut audio_size = None;
 
     // Walk trak atoms using iterator
-    for trak in AtomIter::new(data, moov_start, moov_end) {
-        if trak.name != *b"trak" { continue; }
+    for trak in AtomIter::new(data, moov_start, moov_end).filter_name(b"trak") {
         let trak_s = trak.data_offset;
         let trak_e = trak.data_offset + trak.data_size;
 
@@ -245,6 +471,9 @@ fn parse_mp4_info_iter(data: &[u8], moov_start: usize, moov_end: usize) -> Resul
             Some(a) => a,
             None => continue,
         };
+        if audio_size.is_none() {
+            audio_size = stsz_total(data, &stbl);
+        }
         let stsd = match AtomIter::new(data, stbl.data_offset, stbl.data_offset + stbl.data_size).find_name(b"stsd") {
             Some(a) => a,
             None => continue,
@@ -262,15 +491,28 @@ fn parse_mp4_info_iter(data: &[u8], moov_start: usize, moov_end: usize) -> Resul
                     channels = u16::from_be_bytes([audio_entry[16], audio_entry[17]]) as u32;
                     bits_per_sample = u16::from_be_bytes([audio_entry[18], audio_entry[19]]) as u32;
                     if audio_entry.len() >= 28 {
-                        sample_rate = u16::from_be_bytes([audio_entry[24], audio_entry[25]]) as u32;
+                        sample_rate = fixed_sample_rate(&audio_entry[24..28]);
                     }
                 }
+                bitrate_mode = esds_bitrate_mode(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size);
+                if let Some(n) = esds_channels(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size) {
+                    channels = n;
+                }
+                if let Some(rate) = esds_sample_rate(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size) {
+                    sample_rate = rate;
+                }
+                let object_type = esds_audio_object_type(data, stsd.data_offset + 8, stsd.data_offset + stsd.data_size);
+                codec_description = describe_codec(&codec, object_type);
             }
         }
     }
 
     if length > 0.0 {
-        bitrate = (data.len() as f64 * 8.0 / length) as u32;
+        // Audio bytes only: the moov atom and cover art don't count
+        let audio_size = audio_size
+            .or_else(|| mdat_size(data))
+            .unwrap_or(data.len() as u64);
+        bitrate = (audio_size as f64 * 8.0 / length) as u32;
     }
 
     Ok(MP4Info {
@@ -281,39 +523,287 @@ fn parse_mp4_info_iter(data: &[u8], moov_start: usize, moov_end: usize) -> Resul
         bits_per_sample,
         codec,
         codec_description,
+        bitrate_mode,
     })
 }
 
-/// Parse MP4 tags using iterators (no intermediate Vec allocations).
-fn parse_mp4_tags_iter(data: &[u8], moov_start: usize, moov_end: usize) -> Result<MP4Tags> {
-    let mut tags = MP4Tags::new();
+/// Total size in bytes of the samples listed in the `stsz` atom of `stbl`,
+/// or `None` if there is no usable table.
+pub fn stsz_total(data: &[u8], stbl: &Atom) -> Option<u64> {
+    let stsz = AtomIter::new(data, stbl.data_offset, stbl.data_offset + stbl.data_size).find_name(b"stsz")?;
+    let d = &data[stsz.data_offset..stsz.data_offset + stsz.data_size];
+    if d.len() < 12 {
+        return None;
+    }
+    let sample_size = u32::from_be_bytes([d[4], d[5], d[6], d[7]]) as u64;
+    let count = u32::from_be_bytes([d[8], d[9], d[10], d[11]]) as u64;
+    if sample_size != 0 {
+        return Some(sample_size * count);
+    }
+    let total = d[12..]
+        .chunks_exact(4)
+        .take(count as usize)
+        .map(|c| u32::from_be_bytes([c[0], c[1], c[2], c[3]]) as u64)
+        .sum::<u64>();
+    (total > 0).then_some(total)
+}
 
-    // Navigate: udta/meta/ilst within moov using iterators
-    let udta = match AtomIter::new(data, moov_start, moov_end).find_name(b"udta") {
-        Some(a) => a,
-        None => return Ok(tags),
-    };
+/// Combined payload size of the top-level `mdat` atoms, as declared in
+/// their headers (the data itself may be cut short).
+pub fn mdat_size(data: &[u8]) -> Option<u64> {
+    let total = AtomIter::new(data, 0, data.len())
+        .filter_name(b"mdat")
+        .map(|a| (a.size - a.header_size as usize) as u64)
+        .sum::<u64>();
+    (total > 0).then_some(total)
+}
 
-    let meta = match AtomIter::new(data, udta.data_offset, udta.data_offset + udta.data_size).find_name(b"meta") {
-        Some(a) => a,
-        None => return Ok(tags),
+/// Bitrate mode of the first sample entry in `stsd`, from the max/average
+/// bitrates in its `esds` DecoderConfigDescriptor. Equal values mean CBR; an
+/// average of 0 (allowed for VBR streams) or one below the max means VBR.
+pub fn esds_bitrate_mode(data: &[u8], entry_start: usize, stsd_end: usize) -> BitrateMode {
+    let d = match decoder_config(data, entry_start, stsd_end) {
+        Some(d) => d,
+        None => return BitrateMode::Unknown,
     };
+    let max = u32::from_be_bytes([d[5], d[6], d[7], d[8]]);
+    let avg = u32::from_be_bytes([d[9], d[10], d[11], d[12]]);
+    match (max, avg) {
+        (0, 0) => BitrateMode::Unknown,
+        (max, avg) if avg == max => BitrateMode::CBR,
+        _ => BitrateMode::VBR,
+    }
+}
 
-    // meta atom has 4 bytes of version/flags before children
-    let meta_offset = meta.data_offset + 4;
-    let meta_end = meta.data_offset + meta.data_size;
+/// MPEG-4 audio object type (2 = AAC LC, 5 = HE-AAC, 29 = HE-AAC v2, ...)
+/// from the AudioSpecificConfig in the first sample entry's `esds`.
+pub fn esds_audio_object_type(data: &[u8], entry_start: usize, stsd_end: usize) -> Option<u8> {
+    let asc = audio_specific_config(data, entry_start, stsd_end)?;
+    read_object_type(&mut BitReader::new(asc))
+}
+
+/// Output channel count from the AudioSpecificConfig channel configuration
+/// in the first sample entry's `esds`. The sample entry's own channel field
+/// is often a placeholder 2, so this is the authoritative value when set.
+/// Parametric stereo (object type 29) decodes a mono core to 2 channels.
+/// `None` when the configuration is 0 (layout in a PCE) or reserved.
+pub fn esds_channels(data: &[u8], entry_start: usize, stsd_end: usize) -> Option<u32> {
+    let mut bits = BitReader::new(audio_specific_config(data, entry_start, stsd_end)?);
+    let object_type = read_object_type(&mut bits)?;
+    read_sampling_frequency(&mut bits)?;
+    match bits.read(4)? {
+        1 if object_type == 29 => Some(2),
+        conf @ 1..=6 => Some(conf),
+        7 => Some(8),
+        _ => None,
+    }
+}
+
+/// Sample rate from an audio sample entry's 16.16 fixed-point field,
+/// rounded to the nearest integer.
+pub fn fixed_sample_rate(field: &[u8]) -> u32 {
+    let fixed = u32::from_be_bytes([field[0], field[1], field[2], field[3]]) as u64;
+    ((fixed + 0x8000) >> 16) as u32
+}
+
+/// Output sample rate from the AudioSpecificConfig in the first sample
+/// entry's `esds`. For explicitly signalled SBR (object type 5 or 29) this
+/// is the extension rate, usually twice the core rate that the sample entry
+/// carries. `None` for a reserved frequency index.
+pub fn esds_sample_rate(data: &[u8], entry_start: usize, stsd_end: usize) -> Option<u32> {
+    let mut bits = BitReader::new(audio_specific_config(data, entry_start, stsd_end)?);
+    let object_type = read_object_type(&mut bits)?;
+    let core = read_sampling_frequency(&mut bits)?;
+    if object_type == 5 || object_type == 29 {
+        bits.read(4)?; // channel configuration
+        return read_sampling_frequency(&mut bits);
+    }
+    Some(core)
+}
+
+/// 4-bit sampling frequency index, with 15 escaping to a 24-bit rate.
+fn read_sampling_frequency(bits: &mut BitReader) -> Option<u32> {
+    const RATES: [u32; 13] = [96000, 88200, 64000, 48000, 44100, 32000, 24000, 22050, 16000, 12000, 11025, 8000, 7350];
+    match bits.read(4)? {
+        15 => bits.read(24),
+        index => RATES.get(index as usize).copied(),
+    }
+}
+
+/// DecoderSpecificInfo (tag 5) body, i.e. the AudioSpecificConfig, which
+/// follows the 13 fixed DecoderConfigDescriptor bytes.
+fn audio_specific_config(data: &[u8], entry_start: usize, stsd_end: usize) -> Option<&[u8]> {
+    let d = decoder_config(data, entry_start, stsd_end)?;
+    let (tag, pos, len) = read_descriptor(d, 13)?;
+    if tag != 0x05 || pos >= d.len() {
+        return None;
+    }
+    Some(&d[pos..(pos + len).min(d.len())])
+}
+
+/// 5-bit audio object type, with 31 escaping to 32 + a 6-bit value.
+fn read_object_type(bits: &mut BitReader) -> Option<u8> {
+    match bits.read(5)? {
+        31 => Some(32 + bits.read(6)? as u8),
+        aot => Some(aot as u8),
+    }
+}
 
+/// MSB-first bit reader over a byte slice.
+struct BitReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        BitReader { data, pos: 0 }
+    }
+
+    fn read(&mut self, count: usize) -> Option<u32> {
+        let mut value = 0u32;
+        for _ in 0..count {
+            let byte = *self.data.get(self.pos / 8)?;
+            value = (value << 1) | ((byte >> (7 - self.pos % 8)) & 1) as u32;
+            self.pos += 1;
+        }
+        Some(value)
+    }
+}
+
+/// Human-readable codec name for a sample entry fourCC; for AAC the audio
+/// object type picks the profile.
+pub fn describe_codec(codec: &str, object_type: Option<u8>) -> String {
+    match codec {
+        "mp4a" => match object_type {
+            Some(1) => "MPEG-4 AAC Main".into(),
+            Some(2) => "MPEG-4 AAC LC".into(),
+            Some(3) => "MPEG-4 AAC SSR".into(),
+            Some(4) => "MPEG-4 AAC LTP".into(),
+            Some(5) => "MPEG-4 AAC HE".into(),
+            Some(29) => "MPEG-4 AAC HEv2".into(),
+            _ => "MPEG-4 AAC".into(),
+        },
+        "alac" => "Apple Lossless".into(),
+        "ac-3" => "Dolby AC-3".into(),
+        "ec-3" => "Dolby E-AC-3".into(),
+        "Opus" => "Opus".into(),
+        "fLaC" => "FLAC".into(),
+        _ => String::new(),
+    }
+}
+
+/// Body of the DecoderConfigDescriptor (tag 4) inside the `esds` of the first
+/// sample entry in `stsd`; at least the 13 fixed bytes long.
+fn decoder_config(data: &[u8], entry_start: usize, stsd_end: usize) -> Option<&[u8]> {
+    let entry = AtomIter::new(data, entry_start, stsd_end).next()?;
+    // Audio sample entry header is 28 bytes; child atoms follow it
+    let children = entry.data_offset + 28;
+    let entry_end = entry.data_offset + entry.data_size;
+    let esds = AtomIter::new(data, children, entry_end).find_name(b"esds")?;
+    let d = &data[esds.data_offset..esds.data_offset + esds.data_size];
+
+    // Skip version/flags, then ES_Descriptor (tag 3)
+    let (tag, mut pos, _) = read_descriptor(d, 4)?;
+    if tag != 0x03 || pos + 3 > d.len() {
+        return None;
+    }
+    let flags = d[pos + 2];
+    pos += 3;
+    if flags & 0x80 != 0 {
+        pos += 2;
+    }
+    if flags & 0x40 != 0 {
+        pos += 1 + *d.get(pos).unwrap_or(&0) as usize;
+    }
+    if flags & 0x20 != 0 {
+        pos += 2;
+    }
+
+    // DecoderConfigDescriptor (tag 4)
+    let (tag, pos, len) = read_descriptor(d, pos)?;
+    if tag != 0x04 || len < 13 || pos + 13 > d.len() {
+        return None;
+    }
+    Some(&d[pos..(pos + len).min(d.len())])
+}
+
+/// Read an MPEG-4 descriptor header at `pos`: returns (tag, body offset, body length).
+/// The length is stored in up to four 7-bit groups, high bit set on all but the last.
+fn read_descriptor(d: &[u8], pos: usize) -> Option<(u8, usize, usize)> {
+    let tag = *d.get(pos)?;
+    let mut len = 0usize;
+    let mut p = pos + 1;
+    for _ in 0..4 {
+        let b = *d.get(p)?;
+        p += 1;
+        len = (len << 7) | (b & 0x7F) as usize;
+        if b & 0x80 == 0 {
+            break;
+        }
+    }
+    Some((tag, p, len))
+}
+
+/// `ilst` atoms holding tags, in priority order: `moov/udta`, then a `udta`
+/// inside each `moov/trak`, then a top-level `udta` (as some cameras write).
+/// Each is reached through `udta/meta/ilst`.
+pub fn ilst_atoms(data: &[u8], moov_start: usize, moov_end: usize) -> Vec<Atom> {
+    let moov_udta = AtomIter::new(data, moov_start, moov_end).find_name(b"udta");
+    let trak_udta = AtomIter::new(data, moov_start, moov_end)
+        .filter_name(b"trak")
+        .filter_map(|trak| AtomIter::new(data, trak.data_offset, trak.data_offset + trak.data_size).find_name(b"udta"));
+    let top_udta = AtomIter::new(data, 0, data.len()).filter_name(b"udta");
+    moov_udta.into_iter().chain(trak_udta).chain(top_udta)
+        .filter_map(|udta| udta_ilst(data, &udta))
+        .collect()
+}
+
+fn udta_ilst(data: &[u8], udta: &Atom) -> Option<Atom> {
+    let meta = AtomIter::new(data, udta.data_offset, udta.data_offset + udta.data_size).find_name(b"meta")?;
+    let meta_offset = meta_children_offset(data, &meta);
+    let meta_end = meta.data_offset + meta.data_size;
     if meta_offset >= meta_end {
-        return Ok(tags);
+        return None;
     }
+    AtomIter::new(data, meta_offset, meta_end).find_name(b"ilst")
+}
 
-    let ilst = match AtomIter::new(data, meta_offset, meta_end).find_name(b"ilst") {
-        Some(a) => a,
-        None => return Ok(tags),
-    };
+/// Parse MP4 tags from every `ilst` found by `ilst_atoms`. A key already
+/// read from an earlier (higher priority) `ilst` is not taken from later ones.
+fn parse_mp4_tags_iter(data: &[u8], moov_start: usize, moov_end: usize) -> Result<MP4Tags> {
+    let mut tags = MP4Tags::new();
+    for ilst in ilst_atoms(data, moov_start, moov_end) {
+        let found = parse_ilst(data, &ilst);
+        if tags.items.is_empty() {
+            tags = found;
+            continue;
+        }
+        for (key, value) in found.items {
+            if tags.get(&key).is_none() {
+                tags.items.push((key, value));
+            }
+        }
+    }
+    Ok(tags)
+}
+
+/// Parse the items of one `ilst` atom (no intermediate Vec allocations).
+fn parse_ilst(data: &[u8], ilst: &Atom) -> MP4Tags {
+    let mut tags = MP4Tags::new();
 
     // Iterate ilst children
     for item_atom in AtomIter::new(data, ilst.data_offset, ilst.data_offset + ilst.data_size) {
+        if item_atom.name == *b"----" {
+            if let Some((key, v)) = parse_freeform_item(data, &item_atom) {
+                match tags.get_mut(&key) {
+                    Some(existing) => merge_mp4_values(existing, v),
+                    None => tags.items.push((key, v)),
+                }
+            }
+            continue;
+        }
+
         let key = atom_name_to_key(&item_atom.name);
 
         // Iterate data atoms within each item
@@ -338,7 +828,33 @@ fn parse_mp4_tags_iter(data: &[u8], moov_start: usize, moov_end: usize) -> Resul
         }
     }
 
-    Ok(tags)
+    tags
+}
+
+/// Parse a `----` freeform item (`mean` + `name` + `data` children) into a
+/// `----:mean:name` key and its values.
+fn parse_freeform_item(data: &[u8], item: &Atom) -> Option<(String, MP4TagValue)> {
+    let mut mean = None;
+    let mut name = None;
+    let mut forms = Vec::new();
+    for child in AtomIter::new(data, item.data_offset, item.data_offset + item.data_size) {
+        let body = &data[child.data_offset..child.data_offset + child.data_size];
+        // mean/name/data all start with 4 bytes of version/flags (data: type indicator)
+        if body.len() < 4 {
+            continue;
+        }
+        match &child.name {
+            b"mean" => mean = Some(String::from_utf8_lossy(&body[4..]).into_owned()),
+            b"name" => name = Some(String::from_utf8_lossy(&body[4..]).into_owned()),
+            b"data" if body.len() >= 8 => {
+                let dataformat = u32::from_be_bytes([body[0], body[1], body[2], body[3]]);
+                forms.push(MP4FreeForm { data: body[8..].to_vec(), dataformat });
+            }
+            _ => {}
+        }
+    }
+    let key = format!("----:{}:{}", mean?, name?);
+    Some((key, MP4TagValue::FreeForm(forms)))
 }
 
 fn atom_name_to_key(name: &[u8; 4]) -> String {
@@ -351,6 +867,12 @@ fn atom_name_to_key(name: &[u8; 4]) -> String {
 
 fn parse_mp4_data_value(key: &str, type_indicator: u32, data: &[u8]) -> Option<MP4TagValue> {
     match type_indicator {
+        _ if key == "covr" => {
+            Some(MP4TagValue::Cover(vec![MP4Cover {
+                data: data.to_vec(),
+                format: MP4CoverFormat::detect(type_indicator, data),
+            }]))
+        }
         1 => {
             let text = String::from_utf8_lossy(data).to_string();
             Some(MP4TagValue::Text(vec![text]))
@@ -359,17 +881,14 @@ fn parse_mp4_data_value(key: &str, type_indicator: u32, data: &[u8]) -> Option<M
             let (result, _, _) = encoding_rs::UTF_16BE.decode(data);
             Some(MP4TagValue::Text(vec![result.into_owned()]))
         }
-        13 => {
+        13 | 14 | 27 => {
             Some(MP4TagValue::Cover(vec![MP4Cover {
                 data: data.to_vec(),
-                format: MP4CoverFormat::JPEG,
+                format: MP4CoverFormat::detect(type_indicator, data),
             }]))
         }
-        14 => {
-            Some(MP4TagValue::Cover(vec![MP4Cover {
-                data: data.to_vec(),
-                format: MP4CoverFormat::PNG,
-            }]))
+        21 if data.len() == 1 && is_bool_atom(key.as_bytes()) => {
+            Some(MP4TagValue::Bool(data[0] != 0))
         }
         21 => {
             let val = match data.len() {
@@ -404,13 +923,10 @@ fn parse_mp4_data_value(key: &str, type_indicator: u32, data: &[u8]) -> Option<M
                 }
                 "gnre" => {
                     if data.len() >= 2 {
-                        let genre_id = u16::from_be_bytes([data[0], data[1]]) as usize;
-                        if genre_id > 0 && genre_id <= crate::id3::specs::GENRES.len() {
-                            Some(MP4TagValue::Text(vec![
-                                crate::id3::specs::GENRES[genre_id - 1].to_string()
-                            ]))
-                        } else {
-                            Some(MP4TagValue::Integer(vec![genre_id as i64]))
+                        let genre_id = u16::from_be_bytes([data[0], data[1]]);
+                        match crate::id3::specs::mp4_genre_name(genre_id) {
+                            Some(genre) => Some(MP4TagValue::Text(vec![genre.to_string()])),
+                            None => Some(MP4TagValue::Integer(vec![genre_id as i64])),
                         }
                     } else {
                         None
@@ -427,6 +943,146 @@ fn parse_mp4_data_value(key: &str, type_indicator: u32, data: &[u8]) -> Option<M
     }
 }
 
+/// Items stored as a single-byte type 21 flag (iTunes compilation, gapless
+/// playback, podcast).
+pub fn is_bool_atom(name: &[u8]) -> bool {
+    matches!(name, b"cpil" | b"pgap" | b"pcst")
+}
+
+/// Smallest integer width iTunes expects for `key`; narrower values are
+/// widened to it.
+fn integer_min_width(key: &str) -> usize {
+    match key {
+        "plID" => 8,
+        "cnID" | "geID" | "atID" | "sfID" | "cmID" | "tvsn" | "tves" => 4,
+        "tmpo" | "\u{00a9}mvi" | "\u{00a9}mvc" => 2,
+        _ => 1,
+    }
+}
+
+fn make_atom(name: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(8 + body.len());
+    out.extend_from_slice(&(8 + body.len() as u32).to_be_bytes());
+    out.extend_from_slice(name);
+    out.extend_from_slice(body);
+    out
+}
+
+/// A `data` atom: type indicator, 4-byte locale, payload.
+fn make_data_atom(type_indicator: u32, payload: &[u8]) -> Vec<u8> {
+    let mut body = Vec::with_capacity(8 + payload.len());
+    body.extend_from_slice(&type_indicator.to_be_bytes());
+    body.extend_from_slice(&[0; 4]);
+    body.extend_from_slice(payload);
+    make_atom(b"data", &body)
+}
+
+/// Big-endian signed bytes of `value` in the narrowest of 1/2/4/8 bytes
+/// that holds it and is at least `min_width` wide.
+fn render_integer(value: i64, min_width: usize) -> Vec<u8> {
+    let width = [1, 2, 4, 8].into_iter()
+        .filter(|&w| w >= min_width)
+        .find(|&w| w == 8 || (-(1i64 << (w * 8 - 1))..(1i64 << (w * 8 - 1))).contains(&value))
+        .unwrap_or(8);
+    value.to_be_bytes()[8 - width..].to_vec()
+}
+
+/// Render one item atom (`©nam`, `trkn`, `----`, ...) with its `data` children.
+pub fn render_item(key: &str, value: &MP4TagValue) -> Result<Vec<u8>> {
+    MP4Tags::validate_key(key)?;
+    if let Some(rest) = key.strip_prefix("----:") {
+        let (mean, name) = rest.split_once(':').unwrap_or_default();
+        let mut body = Vec::new();
+        let mut text_atom = |atom: &[u8; 4], text: &str| {
+            let mut b = vec![0u8; 4];
+            b.extend_from_slice(text.as_bytes());
+            body.extend_from_slice(&make_atom(atom, &b));
+        };
+        text_atom(b"mean", mean);
+        text_atom(b"name", name);
+        match value {
+            MP4TagValue::FreeForm(forms) => {
+                for form in forms {
+                    body.extend_from_slice(&make_data_atom(form.dataformat, &form.data));
+                }
+            }
+            MP4TagValue::Text(values) => {
+                for v in values {
+                    body.extend_from_slice(&make_data_atom(1, v.as_bytes()));
+                }
+            }
+            MP4TagValue::Data(data) => body.extend_from_slice(&make_data_atom(0, data)),
+            _ => return Err(MutagenError::ValueError(format!("unsupported value for freeform key {:?}", key))),
+        }
+        return Ok(make_atom(b"----", &body));
+    }
+
+    let mut name = [0u8; 4];
+    for (b, c) in name.iter_mut().zip(key.chars()) {
+        *b = c as u8;
+    }
+    let mut body = Vec::new();
+    match value {
+        MP4TagValue::Text(values) if key == "gnre" => {
+            // A parsed `gnre` holds genre names; store them back as ID3v1
+            // index + 1, or as free text under `©gen` if any isn't a standard genre
+            let ids: Option<Vec<u16>> = values.iter()
+                .map(|v| crate::id3::specs::genre_index(v).map(|i| i as u16 + 1))
+                .collect();
+            match ids {
+                Some(ids) => {
+                    for id in ids {
+                        body.extend_from_slice(&make_data_atom(0, &id.to_be_bytes()));
+                    }
+                }
+                None => return render_item("\u{00a9}gen", value),
+            }
+        }
+        MP4TagValue::Text(values) => {
+            for v in values {
+                body.extend_from_slice(&make_data_atom(1, v.as_bytes()));
+            }
+        }
+        MP4TagValue::Integer(values) if key == "gnre" => {
+            for &v in values {
+                let id = u16::try_from(v)
+                    .map_err(|_| MutagenError::ValueError(format!("genre id out of range: {}", v)))?;
+                body.extend_from_slice(&make_data_atom(0, &id.to_be_bytes()));
+            }
+        }
+        MP4TagValue::Integer(values) => {
+            let min_width = integer_min_width(key);
+            for &v in values {
+                body.extend_from_slice(&make_data_atom(21, &render_integer(v, min_width)));
+            }
+        }
+        MP4TagValue::IntPair(pairs) => {
+            for &(n, total) in pairs {
+                let half = |x: i32| u16::try_from(x)
+                    .map_err(|_| MutagenError::ValueError(format!("{} value out of range: {}", key, x)));
+                let mut payload = vec![0u8; 2];
+                payload.extend_from_slice(&half(n)?.to_be_bytes());
+                payload.extend_from_slice(&half(total)?.to_be_bytes());
+                payload.extend_from_slice(&[0; 2]);
+                body.extend_from_slice(&make_data_atom(0, &payload));
+            }
+        }
+        MP4TagValue::Bool(v) => body.extend_from_slice(&make_data_atom(21, &[*v as u8])),
+        MP4TagValue::Cover(covers) => {
+            for cover in covers {
+                body.extend_from_slice(&make_data_atom(cover.format.type_indicator() as u32, &cover.data));
+            }
+        }
+        MP4TagValue::FreeForm(forms) => {
+            for form in forms {
+                body.extend_from_slice(&make_data_atom(form.dataformat, &form.data));
+            }
+        }
+        MP4TagValue::Data(data) => body.extend_from_slice(&make_data_atom(0, data)),
+    }
+    Ok(make_atom(&name, &body))
+}
+
 fn merge_mp4_values(existing: &mut MP4TagValue, new: MP4TagValue) {
     match (existing, new) {
         (MP4TagValue::Text(ref mut v), MP4TagValue::Text(new_v)) => v.extend(new_v),
@@ -1,6 +1,9 @@
+use std::borrow::Cow;
 use std::fs::File;
 use std::io::{Read, Write, Seek, SeekFrom};
 use crate::common::error::{MutagenError, Result};
+use crate::common::options::ParseOptions;
+use crate::mp3::xing::BitrateMode;
 use crate::vorbis::VorbisComment;
 
 /// A single OGG page.
@@ -23,7 +26,7 @@ impl OggPage {
     #[inline]
     pub fn parse(data: &[u8], offset: usize) -> Result<Self> {
         if offset + 27 > data.len() {
-            return Err(MutagenError::Ogg("Page header too short".into()));
+            return Err(MutagenError::Truncated("Page header too short".into()));
         }
 
         let d = &data[offset..];
@@ -44,7 +47,7 @@ impl OggPage {
         let num_segments = d[26] as usize;
 
         if offset + 27 + num_segments > data.len() {
-            return Err(MutagenError::Ogg("Segment table extends past data".into()));
+            return Err(MutagenError::Truncated("Segment table extends past data".into()));
         }
 
         let segments = d[27..27 + num_segments].to_vec();
@@ -52,7 +55,7 @@ impl OggPage {
         let header_size = 27 + num_segments;
 
         if offset + header_size + total_data_size > data.len() {
-            return Err(MutagenError::Ogg("Page data extends past file".into()));
+            return Err(MutagenError::Truncated("Page data extends past file".into()));
         }
 
         // Reassemble packets from segments
@@ -127,44 +130,276 @@ impl OggPage {
     }
 }
 
+/// Default number of trailing bytes searched first by `find_last_granule`.
+pub const LAST_GRANULE_WINDOW: usize = 65536;
+
 /// Find the granule position of the last OGG page with the given serial number.
 /// Zero-allocation: only reads the 27-byte page header, no packet reassembly.
 #[inline]
 pub fn find_last_granule(data: &[u8], serial: u32) -> Option<i64> {
-    use memchr::memmem;
+    find_last_granule_in(data, serial, LAST_GRANULE_WINDOW)
+}
 
-    let search_start = data.len().saturating_sub(65536);
-    let search_data = &data[search_start..];
+/// Like `find_last_granule`, searching the last `window` bytes first. If no
+/// page with `serial` starts there (e.g. another multiplexed stream owns the
+/// tail, or the final page is huge), the window doubles backward until the
+/// whole file has been searched. Each byte is scanned only once.
+pub fn find_last_granule_in(data: &[u8], serial: u32, window: usize) -> Option<i64> {
+    use memchr::memmem;
 
-    // Use SIMD-accelerated reverse search for "OggS" magic
-    for pos in memmem::rfind_iter(search_data, b"OggS") {
-        let abs_pos = search_start + pos;
-        if abs_pos + 27 > data.len() {
-            continue;
+    let mut window = window.max(27);
+    // Magic at or after `scanned` has already been checked
+    let mut scanned = data.len();
+
+    while scanned > 0 {
+        let search_start = data.len().saturating_sub(window);
+        // Overlap by 3 bytes so a magic straddling the boundary is found once
+        let search_end = (scanned + 3).min(data.len());
+        let search_data = &data[search_start..search_end];
+
+        // Use SIMD-accelerated reverse search for "OggS" magic
+        for pos in memmem::rfind_iter(search_data, b"OggS") {
+            let abs_pos = search_start + pos;
+            if abs_pos >= scanned || abs_pos + 27 > data.len() {
+                continue;
+            }
+            let d = &data[abs_pos..];
+            // serial_number at offset 14-17
+            let page_serial = u32::from_le_bytes([d[14], d[15], d[16], d[17]]);
+            if page_serial == serial {
+                // granule_position at offset 6-13
+                let granule = i64::from_le_bytes([
+                    d[6], d[7], d[8], d[9], d[10], d[11], d[12], d[13],
+                ]);
+                return Some(granule);
+            }
         }
-        let d = &data[abs_pos..];
-        // serial_number at offset 14-17
-        let page_serial = u32::from_le_bytes([d[14], d[15], d[16], d[17]]);
-        if page_serial == serial {
-            // granule_position at offset 6-13
-            let granule = i64::from_le_bytes([
-                d[6], d[7], d[8], d[9], d[10], d[11], d[12], d[13],
-            ]);
-            return Some(granule);
+
+        scanned = search_start;
+        window = window.saturating_mul(2);
+    }
+    None
+}
+
+/// Samples in Vorbis stream `serial`: the last granule position less the
+/// stream's starting granule (see `vorbis_start_granule`).
+pub fn vorbis_sample_count(data: &[u8], serial: u32) -> Option<i64> {
+    let last = find_last_granule(data, serial)?;
+    Some(last - vorbis_start_granule(data, serial).unwrap_or(0))
+}
+
+/// Granule position of the first sample of Vorbis stream `serial`: 0 for a
+/// stream encoded from the start, more for one cut from a longer stream.
+/// This is the granule position of the first audio page less the samples
+/// decoded from the packets ending on it, each packet's block size coming
+/// from its mode (Vorbis I spec, section A.2). A first page holding fewer
+/// samples than its packets decode to is end-trimmed, not a late start.
+pub fn vorbis_start_granule(data: &[u8], serial: u32) -> Option<i64> {
+    let mut blocksizes = (0u32, 0u32);
+    let mut mode_flags = Vec::new();
+    let mut previous: Option<u32> = None;
+    let mut samples = 0i64;
+    for (index, packet) in Packets::new(data, serial, 0).enumerate() {
+        match index {
+            0 => {
+                if packet.data.len() < 30 || &packet.data[0..7] != b"\x01vorbis" {
+                    return None;
+                }
+                blocksizes = (1 << (packet.data[28] & 0x0F), 1 << (packet.data[28] >> 4));
+            }
+            1 => {}
+            2 => mode_flags = vorbis_mode_blockflags(&packet.data)?,
+            _ => {
+                if let Some(size) = vorbis_packet_blocksize(&packet.data, &mode_flags, blocksizes) {
+                    if let Some(prev) = previous {
+                        samples += (prev / 4 + size / 4) as i64;
+                    }
+                    previous = Some(size);
+                }
+                if let Some(granule) = packet.granule.filter(|&g| g >= 0) {
+                    return Some((granule - samples).max(0));
+                }
+            }
         }
     }
     None
 }
 
+/// A packet of one logical stream, reassembled across pages.
+#[derive(Debug)]
+pub(crate) struct OggPacket<'a> {
+    /// Borrowed from the data when the packet lies within one page.
+    pub data: Cow<'a, [u8]>,
+    /// Granule position of the page the packet ends on, if it is the last
+    /// packet ending there.
+    pub granule: Option<i64>,
+    /// Offset of the page after the one the packet ends on.
+    pub next_page: usize,
+}
+
+/// Iterator over the packets of stream `serial` from the page at an offset
+/// on. A segment of 255 bytes continues the packet, within the page or onto
+/// the next page of the stream; a packet left unfinished by a truncated or
+/// missing page is dropped.
+pub(crate) struct Packets<'a> {
+    data: &'a [u8],
+    serial: u32,
+    /// Offset of the next page to read.
+    offset: usize,
+    /// Unread segments of the current page, and where their data starts.
+    segments: &'a [u8],
+    pos: usize,
+    /// Segment index of the last packet ending on the current page.
+    last_end: Option<usize>,
+    granule: i64,
+    /// Segments read so far of a packet spanning segments or pages.
+    current: Vec<u8>,
+    segment: usize,
+}
+
+impl<'a> Packets<'a> {
+    pub(crate) fn new(data: &'a [u8], serial: u32, offset: usize) -> Self {
+        Packets {
+            data,
+            serial,
+            offset,
+            segments: &[],
+            pos: 0,
+            last_end: None,
+            granule: 0,
+            current: Vec::new(),
+            segment: 0,
+        }
+    }
+}
+
+impl<'a> Iterator for Packets<'a> {
+    type Item = OggPacket<'a>;
+
+    fn next(&mut self) -> Option<OggPacket<'a>> {
+        loop {
+            while let Some((&seg, rest)) = self.segments.split_first() {
+                let chunk = &self.data[self.pos..self.pos + seg as usize];
+                let index = self.segment;
+                self.segments = rest;
+                self.pos += seg as usize;
+                self.segment += 1;
+                if seg == 255 {
+                    self.current.extend_from_slice(chunk);
+                    continue;
+                }
+                let data = if self.current.is_empty() {
+                    Cow::Borrowed(chunk)
+                } else {
+                    self.current.extend_from_slice(chunk);
+                    Cow::Owned(std::mem::take(&mut self.current))
+                };
+                let granule = (Some(index) == self.last_end).then_some(self.granule);
+                return Some(OggPacket { data, granule, next_page: self.offset });
+            }
+
+            let (page_serial, page_size) = ogg_page_header(self.data, self.offset)?;
+            if self.offset + page_size > self.data.len() {
+                return None;
+            }
+            let d = &self.data[self.offset..];
+            self.offset += page_size;
+            if page_serial != self.serial {
+                continue;
+            }
+            let segments = &d[27..27 + d[26] as usize];
+            self.granule = i64::from_le_bytes([d[6], d[7], d[8], d[9], d[10], d[11], d[12], d[13]]);
+            self.last_end = segments.iter().rposition(|&s| s < 255);
+            self.pos = self.offset - page_size + 27 + segments.len();
+            self.segments = segments;
+            self.segment = 0;
+        }
+    }
+}
+
+/// Block flag of each mode in a Vorbis setup header. The codebooks and
+/// floors before the modes can't be skipped without decoding them, so the
+/// modes are read backward from the framing bit, taking the longest run
+/// preceded by a matching mode count (as ffmpeg's Vorbis parser does).
+fn vorbis_mode_blockflags(setup: &[u8]) -> Option<Vec<bool>> {
+    if !setup.starts_with(b"\x05vorbis") {
+        return None;
+    }
+    let bit = |i: usize| (setup[i / 8] >> (i % 8)) & 1;
+    // Read `n` bits ending at `pos`, moving backward; the last bit written is
+    // the most significant
+    let read = |pos: &mut usize, n: usize| -> Option<u32> {
+        if *pos < n {
+            return None;
+        }
+        let mut value = 0;
+        for _ in 0..n {
+            *pos -= 1;
+            value = (value << 1) | bit(*pos) as u32;
+        }
+        Some(value)
+    };
+
+    let mut pos = setup.len() * 8;
+    while read(&mut pos, 1)? == 0 {}
+
+    let mut flags = Vec::new();
+    let mut count = None;
+    while flags.len() < 64 {
+        // Mode fields in reverse: mapping, transform type, window type, block flag
+        let (Some(mapping), Some(transform), Some(window)) =
+            (read(&mut pos, 8), read(&mut pos, 16), read(&mut pos, 16)) else { break };
+        if mapping > 63 || transform != 0 || window != 0 {
+            break;
+        }
+        let Some(flag) = read(&mut pos, 1) else { break };
+        flags.push(flag == 1);
+        let mut peek = pos;
+        if read(&mut peek, 6) == Some(flags.len() as u32 - 1) {
+            count = Some(flags.len());
+        }
+    }
+    flags.truncate(count?);
+    flags.reverse();
+    Some(flags)
+}
+
+/// Block size of a Vorbis audio packet, from the mode number after its
+/// packet type bit. `None` for empty or non-audio packets.
+fn vorbis_packet_blocksize(packet: &[u8], mode_flags: &[bool], blocksizes: (u32, u32)) -> Option<u32> {
+    let &first = packet.first()?;
+    if first & 1 != 0 || mode_flags.is_empty() {
+        return None;
+    }
+    let mode_bits = usize::BITS - (mode_flags.len() - 1).leading_zeros();
+    let bits = u16::from_le_bytes([first, packet.get(1).copied().unwrap_or(0)]);
+    let mode = (bits >> 1) as usize & ((1 << mode_bits) - 1);
+    Some(if *mode_flags.get(mode)? { blocksizes.1 } else { blocksizes.0 })
+}
+
 /// Parsed OGG Vorbis audio info.
 #[derive(Debug, Clone)]
 pub struct OggVorbisInfo {
     pub length: f64,
     pub channels: u8,
     pub sample_rate: u32,
-    pub bitrate: u32,       // nominal bitrate
+    pub bitrate: u32,       // nominal bitrate, or derived from the file size if unset
+    /// Nominal bitrate field of the identification header, as stored.
+    pub bitrate_nominal: u32,
     pub bitrate_max: u32,
     pub bitrate_min: u32,
+    pub bitrate_mode: BitrateMode,
+}
+
+/// Bitrate mode from the identification header's bitrate fields, which are
+/// signed on disk with 0 or -1 meaning unset. Only a stream whose nominal,
+/// maximum and minimum bitrates all agree is constant bitrate.
+pub fn vorbis_bitrate_mode(nominal: u32, max: u32, min: u32) -> BitrateMode {
+    if (nominal as i32) > 0 && nominal == max && nominal == min {
+        BitrateMode::CBR
+    } else {
+        BitrateMode::VBR
+    }
 }
 
 /// Complete OGG Vorbis file handler.
@@ -173,6 +408,9 @@ pub struct OggVorbisFile {
     pub info: OggVorbisInfo,
     pub tags: VorbisComment,
     pub path: String,
+    /// Byte offset of the first audio page, after the three Vorbis headers.
+    /// Set by `ensure_full_parse`.
+    pub audio_offset: usize,
     raw_comment_data: Vec<u8>,
     tags_parsed: bool,
     page1_size: usize,
@@ -212,6 +450,12 @@ fn ogg_first_packet(data: &[u8], offset: usize) -> Option<&[u8]> {
     Some(&data[pkt_start..pkt_start + packet_size])
 }
 
+/// Offset of the first page after the comment and setup packets of stream
+/// `serial`, scanning from `offset` (the page after the identification header).
+fn find_audio_offset(data: &[u8], offset: usize, serial: u32) -> Option<usize> {
+    Packets::new(data, serial, offset).nth(1).map(|setup| setup.next_page)
+}
+
 impl OggVorbisFile {
     pub fn open(path: &str) -> Result<Self> {
         let data = std::fs::read(path)?;
@@ -248,11 +492,14 @@ impl OggVorbisFile {
                 channels,
                 sample_rate,
                 bitrate,
+                bitrate_nominal: bitrate,
                 bitrate_max: 0,
                 bitrate_min: 0,
+                bitrate_mode: BitrateMode::Unknown,
             },
             tags: VorbisComment::new(),
             path: path.to_string(),
+            audio_offset: 0,
             raw_comment_data: Vec::new(),
             tags_parsed: true,
             page1_size,
@@ -262,6 +509,23 @@ impl OggVorbisFile {
 
     /// Complete parsing: duration, bitrate, and comment data from original file data.
     pub fn ensure_full_parse(&mut self, data: &[u8]) {
+        let _ = self.ensure_full_parse_with(data, ParseOptions::default());
+    }
+
+    /// Like `ensure_full_parse`; in strict mode a comment header page that
+    /// runs past the end of the data is an error.
+    pub fn ensure_full_parse_with(&mut self, data: &[u8], options: ParseOptions) -> Result<()> {
+        if options.strict {
+            if let Some((_, page_size)) = ogg_page_header(data, self.page1_size) {
+                if self.page1_size + page_size > data.len() {
+                    return Err(MutagenError::Truncated(format!(
+                        "comment page truncated: {} bytes declared, {} available",
+                        page_size, data.len() - self.page1_size
+                    )));
+                }
+            }
+        }
+
         // Parse bitrate_max/min from identification packet
         if let Some(id_packet) = ogg_first_packet(data, 0) {
             if id_packet.len() >= 28 {
@@ -271,6 +535,9 @@ impl OggVorbisFile {
                 self.info.bitrate_min = u32::from_le_bytes([
                     id_packet[24], id_packet[25], id_packet[26], id_packet[27],
                 ]);
+                self.info.bitrate_mode = vorbis_bitrate_mode(
+                    self.info.bitrate, self.info.bitrate_max, self.info.bitrate_min,
+                );
             }
         }
 
@@ -282,8 +549,10 @@ impl OggVorbisFile {
             }
         }
 
-        // Duration from last page
-        if let Some(granule) = find_last_granule(data, self.serial) {
+        self.audio_offset = find_audio_offset(data, self.page1_size, self.serial).unwrap_or(0);
+
+        // Duration from the first and last audio pages
+        if let Some(granule) = vorbis_sample_count(data, self.serial) {
             if granule > 0 && self.info.sample_rate > 0 {
                 self.info.length = granule as f64 / self.info.sample_rate as f64;
             }
@@ -293,21 +562,37 @@ impl OggVorbisFile {
         if self.info.bitrate == 0 && self.info.length > 0.0 {
             self.info.bitrate = (data.len() as f64 * 8.0 / self.info.length) as u32;
         }
+        Ok(())
     }
 
     /// Ensure VorbisComment tags are parsed (lazy initialization).
     pub fn ensure_tags(&mut self) {
+        let _ = self.ensure_tags_with(ParseOptions::default());
+    }
+
+    /// Like `ensure_tags`, returning the Vorbis comment error in strict mode.
+    pub fn ensure_tags_with(&mut self, options: ParseOptions) -> Result<()> {
         if !self.tags_parsed {
             self.tags_parsed = true;
-            if let Ok(vc) = VorbisComment::parse(&self.raw_comment_data, true) {
-                self.tags = vc;
-            }
+            let parsed = VorbisComment::parse_with(&self.raw_comment_data, true, options);
             self.raw_comment_data = Vec::new(); // Free memory
+            match parsed {
+                Ok(vc) => self.tags = vc,
+                Err(e) if options.strict => return Err(e),
+                Err(_) => {}
+            }
         }
+        Ok(())
     }
 
     /// Save tags back to the OGG file.
     pub fn save(&self) -> Result<()> {
+        self.save_with(false)
+    }
+
+    /// Like `save`; with `dedupe`, repeated identical `KEY=value` comments
+    /// are written once.
+    pub fn save_with(&self, dedupe: bool) -> Result<()> {
         // For now, read-only support. Writing OGG is complex (page rewriting).
         // A full implementation would rebuild the comment pages.
         let mut file = std::fs::OpenOptions::new().read(true).write(true).open(&self.path)?;
@@ -321,7 +606,11 @@ impl OggVorbisFile {
         // Build new comment packet
         let mut comment_packet = Vec::new();
         comment_packet.extend_from_slice(b"\x03vorbis");
-        comment_packet.extend_from_slice(&self.tags.render(true));
+        comment_packet.extend_from_slice(&if dedupe {
+            self.tags.render_deduped(true)
+        } else {
+            self.tags.render(true)
+        });
 
         // Build new comment page segments
         let mut segments = Vec::new();
@@ -357,6 +646,7 @@ impl OggVorbisFile {
         file.write_all(&new_page)?;
         file.write_all(&existing[rest_start..])?;
         file.flush()?;
+        crate::common::cache::invalidate(&self.path);
 
         Ok(())
     }
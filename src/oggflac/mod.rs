@@ -0,0 +1,106 @@
+//! FLAC in an Ogg container: a `\x7FFLAC` mapping header carrying the
+//! StreamInfo, then one packet per remaining metadata block.
+
+use crate::common::error::{MutagenError, Result};
+use crate::flac::{BlockType, StreamInfo};
+use crate::ogg::{find_last_granule, OggPage, Packets};
+use crate::vorbis::VorbisComment;
+
+/// Mapping header packet type and signature.
+const SIGNATURE: &[u8] = b"\x7FFLAC";
+
+/// Header packets read when the mapping header leaves the count unset (0).
+const MAX_HEADER_PACKETS: usize = 64;
+
+/// Complete Ogg FLAC file: stream info and Vorbis comment.
+#[derive(Debug)]
+pub struct OggFLACFile {
+    pub info: StreamInfo,
+    pub tags: Option<VorbisComment>,
+    pub path: String,
+    pub serial: u32,
+}
+
+impl OggFLACFile {
+    pub fn open(path: &str) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        Self::parse(&data, path)
+    }
+
+    /// Parse the mapping header and the metadata packets after it. The
+    /// length comes from the StreamInfo sample count, or the last granule
+    /// position when the count is unset.
+    pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        let first = OggPage::parse(data, 0)?;
+        let serial = first.serial_number;
+        let id = first.packets.first()
+            .filter(|p| is_mapping_header(p))
+            .ok_or_else(|| MutagenError::Ogg("Not an Ogg FLAC stream".into()))?;
+        // 5-byte signature, major/minor version, 16-bit header count, "fLaC",
+        // then the StreamInfo block with its 4-byte header
+        if id.len() < 51 || &id[9..13] != b"fLaC" {
+            return Err(MutagenError::Ogg("Ogg FLAC mapping header too short".into()));
+        }
+        if id[5] != 1 {
+            return Err(MutagenError::Ogg(format!("unsupported Ogg FLAC mapping version {}.{}", id[5], id[6])));
+        }
+        let mut info = StreamInfo::parse(&id[17..51])?;
+
+        let count = u16::from_be_bytes([id[7], id[8]]) as usize;
+        let limit = if count > 0 { count } else { MAX_HEADER_PACKETS };
+        let mut tags = None;
+        // The mapping header is the first packet; the metadata blocks follow
+        for packet in Packets::new(data, serial, 0).skip(1).take(limit) {
+            let packet = packet.data;
+            let Some(&header) = packet.first() else { break };
+            if packet.len() >= 4 && BlockType::from_byte(header & 0x7F) == BlockType::VorbisComment {
+                tags = VorbisComment::parse(&packet[4..], false).ok();
+            }
+            if header & 0x80 != 0 {
+                break;
+            }
+        }
+
+        if info.total_samples == 0 && info.sample_rate > 0 {
+            if let Some(granule) = find_last_granule(data, serial).filter(|&g| g > 0) {
+                info.total_samples = granule as u64;
+                info.length = granule as f64 / info.sample_rate as f64;
+            }
+        }
+
+        Ok(OggFLACFile {
+            info,
+            tags,
+            path: path.to_string(),
+            serial,
+        })
+    }
+
+    /// Content-only: Ogg FLAC has no extension of its own and usually
+    /// shares `.ogg`/`.oga` with Vorbis, so the mapping header must outscore
+    /// an `.ogg` extension match for Ogg Vorbis.
+    pub fn score(_path: &str, data: &[u8]) -> u32 {
+        let mut score = 0u32;
+        if data.len() >= 4 && &data[0..4] == b"OggS" {
+            score += 1;
+            if is_oggflac(data) {
+                score += 3;
+            }
+        }
+        score
+    }
+}
+
+fn is_mapping_header(packet: &[u8]) -> bool {
+    packet.starts_with(SIGNATURE)
+}
+
+/// Whether the first packet of the first Ogg page is an Ogg FLAC mapping
+/// header. Only the first page header is read.
+pub fn is_oggflac(data: &[u8]) -> bool {
+    if data.len() < 27 || &data[0..4] != b"OggS" {
+        return false;
+    }
+    let header_size = 27 + data[26] as usize;
+    data.get(header_size..header_size + SIGNATURE.len()) == Some(SIGNATURE)
+}
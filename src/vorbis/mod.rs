@@ -1,5 +1,23 @@
 use crate::common::error::{MutagenError, Result};
-use std::collections::HashMap;
+use crate::common::options::ParseOptions;
+use std::collections::{HashMap, HashSet};
+
+/// Vendor string written into comment blocks created from scratch.
+pub const DEFAULT_VENDOR: &str = "mutagen-rs";
+
+/// Offset of the first plausible comment entry (LE32 length, then
+/// `KEY=value` with a printable ASCII key) at or after `from`, used to step
+/// over an entry whose declared length runs past the end of the data.
+pub fn next_entry(data: &[u8], from: usize) -> Option<usize> {
+    (from..data.len().saturating_sub(4)).find(|&pos| {
+        let len = u32::from_le_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let Some(raw) = data.get(pos + 4..pos + 4 + len) else { return false };
+        match memchr::memchr(b'=', raw) {
+            Some(eq) => eq > 0 && raw[..eq].iter().all(|&b| (0x20..=0x7D).contains(&b)),
+            None => false,
+        }
+    })
+}
 
 /// A Vorbis comment: list of key=value pairs with a vendor string.
 #[derive(Debug, Clone)]
@@ -11,16 +29,32 @@ pub struct VorbisComment {
 impl VorbisComment {
     pub fn new() -> Self {
         VorbisComment {
-            vendor: String::new(),
+            vendor: DEFAULT_VENDOR.to_string(),
             comments: Vec::new(),
         }
     }
 
+    /// Replace the vendor string written at the start of the block.
+    pub fn set_vendor(&mut self, vendor: &str) {
+        self.vendor = vendor.to_string();
+    }
+
     /// Parse a Vorbis comment block from bytes.
     /// `framing` controls whether to expect a framing bit at the end (true for OGG, false for FLAC).
-    pub fn parse(data: &[u8], _framing: bool) -> Result<Self> {
+    pub fn parse(data: &[u8], framing: bool) -> Result<Self> {
+        Self::parse_with(data, framing, ParseOptions::default())
+    }
+
+    /// Like `parse`; in strict mode a comment that runs past the end of the
+    /// block is an error instead of ending the comment list early.
+    pub fn parse_with(data: &[u8], _framing: bool, options: ParseOptions) -> Result<Self> {
+        // A zero-length block, or a bare zero vendor length, is an empty
+        // comment rather than a damaged one
+        if data.is_empty() || data == [0, 0, 0, 0] {
+            return Ok(VorbisComment { vendor: String::new(), comments: Vec::new() });
+        }
         if data.len() < 4 {
-            return Err(MutagenError::InvalidData("Vorbis comment too short".into()));
+            return Err(MutagenError::Truncated("Vorbis comment too short".into()));
         }
 
         let mut pos = 0;
@@ -32,7 +66,7 @@ impl VorbisComment {
         pos += 4;
 
         if pos + vendor_len > data.len() {
-            return Err(MutagenError::InvalidData("Vendor string extends past data".into()));
+            return Err(MutagenError::Truncated("Vendor string extends past data".into()));
         }
 
         let vendor = match std::str::from_utf8(&data[pos..pos + vendor_len]) {
@@ -42,7 +76,7 @@ impl VorbisComment {
         pos += vendor_len;
 
         if pos + 4 > data.len() {
-            return Err(MutagenError::InvalidData("No comment count".into()));
+            return Err(MutagenError::Truncated("No comment count".into()));
         }
 
         // Comment count (LE32)
@@ -53,8 +87,13 @@ impl VorbisComment {
 
         let mut comments = Vec::with_capacity(count.min(64));
 
-        for _ in 0..count {
+        for i in 0..count {
             if pos + 4 > data.len() {
+                if options.strict {
+                    return Err(MutagenError::Truncated(format!(
+                        "Vorbis comment truncated: {} of {} comments present", i, count
+                    )));
+                }
                 break;
             }
 
@@ -64,7 +103,20 @@ impl VorbisComment {
             pos += 4;
 
             if pos + comment_len > data.len() {
-                break;
+                if options.strict {
+                    return Err(MutagenError::Truncated(format!(
+                        "Vorbis comment {} truncated: {} bytes declared, {} available",
+                        i, comment_len, data.len() - pos
+                    )));
+                }
+                // Skip the entry whose length is corrupt, keeping any valid ones after it
+                match next_entry(data, pos) {
+                    Some(next) => {
+                        pos = next;
+                        continue;
+                    }
+                    None => break,
+                }
             }
 
             let raw = &data[pos..pos + comment_len];
@@ -109,6 +161,22 @@ impl VorbisComment {
 
     /// Serialize to bytes.
     pub fn render(&self, framing: bool) -> Vec<u8> {
+        let comments: Vec<&(String, String)> = self.comments.iter().collect();
+        self.render_comments(&comments, framing)
+    }
+
+    /// Like `render`, but drops comments repeating an earlier `KEY=value`
+    /// pair (keys compared case-insensitively). Order and distinct values
+    /// of a key are kept.
+    pub fn render_deduped(&self, framing: bool) -> Vec<u8> {
+        let mut seen = HashSet::new();
+        let comments: Vec<&(String, String)> = self.comments.iter()
+            .filter(|(key, value)| seen.insert((key.to_ascii_uppercase(), value.as_str())))
+            .collect();
+        self.render_comments(&comments, framing)
+    }
+
+    fn render_comments(&self, comments: &[&(String, String)], framing: bool) -> Vec<u8> {
         let mut data = Vec::new();
 
         // Vendor string
@@ -117,10 +185,10 @@ impl VorbisComment {
         data.extend_from_slice(vendor_bytes);
 
         // Comment count
-        data.extend_from_slice(&(self.comments.len() as u32).to_le_bytes());
+        data.extend_from_slice(&(comments.len() as u32).to_le_bytes());
 
         // Comments
-        for (key, value) in &self.comments {
+        for (key, value) in comments {
             let comment = format!("{}={}", key, value);
             let comment_bytes = comment.as_bytes();
             data.extend_from_slice(&(comment_bytes.len() as u32).to_le_bytes());
@@ -0,0 +1,169 @@
+use crate::apev2::{self, APEv2Tags};
+use crate::common::error::{MutagenError, Result};
+
+/// Sample rates indexed by bits 23-26 of the block flags.
+const RATES: [u32; 15] = [
+    6000, 8000, 9600, 11025, 12000, 16000, 22050, 24000,
+    32000, 44100, 48000, 64000, 88200, 96000, 192000,
+];
+
+const FLAG_MONO: u32 = 0x4;
+const FLAG_HYBRID: u32 = 0x8;
+const FLAG_INITIAL_BLOCK: u32 = 0x800;
+const FLAG_DSD: u32 = 0x8000_0000;
+
+/// A WavPack block header (32 bytes, little-endian).
+#[derive(Debug, Clone, Copy)]
+pub struct BlockHeader {
+    /// Size of the block minus the first 8 bytes.
+    pub block_size: u32,
+    pub version: u16,
+    pub track_no: u8,
+    pub index_no: u8,
+    /// Total samples in the file, or 0xFFFFFFFF if unknown.
+    pub total_samples: u32,
+    pub block_index: u32,
+    pub block_samples: u32,
+    pub flags: u32,
+    pub crc: u32,
+}
+
+impl BlockHeader {
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        if data.len() < 32 || &data[0..4] != b"wvpk" {
+            return Err(MutagenError::WavPack("not a WavPack block".into()));
+        }
+        let u32_at = |i: usize| u32::from_le_bytes([data[i], data[i + 1], data[i + 2], data[i + 3]]);
+        let header = BlockHeader {
+            block_size: u32_at(4),
+            version: u16::from_le_bytes([data[8], data[9]]),
+            track_no: data[10],
+            index_no: data[11],
+            total_samples: u32_at(12),
+            block_index: u32_at(16),
+            block_samples: u32_at(20),
+            flags: u32_at(24),
+            crc: u32_at(28),
+        };
+        if header.block_size < 24 {
+            return Err(MutagenError::WavPack("invalid block size".into()));
+        }
+        Ok(header)
+    }
+
+    /// Total size of the block on disk, including the 8-byte preamble.
+    pub fn total_size(&self) -> usize {
+        self.block_size as usize + 8
+    }
+}
+
+/// WavPack stream information.
+#[derive(Debug, Clone)]
+pub struct WavPackInfo {
+    pub length: f64,
+    pub channels: u32,
+    pub sample_rate: u32,
+    pub bits_per_sample: u32,
+    pub bitrate: u32,
+    /// Stream version from the first block header (e.g. 0x403).
+    pub version: u16,
+    /// False for hybrid mode, where the file alone decodes lossily.
+    pub lossless: bool,
+}
+
+impl WavPackInfo {
+    /// Parse stream info from the first block, walking the remaining blocks
+    /// when the header does not carry a usable sample count.
+    pub fn parse(data: &[u8], audio_end: usize) -> Result<Self> {
+        let header = BlockHeader::parse(data)?;
+
+        let rate_index = ((header.flags >> 23) & 0xF) as usize;
+        let mut sample_rate = RATES.get(rate_index).copied().unwrap_or(0);
+        let mut bits_per_sample = ((header.flags & 3) + 1) * 8;
+        let channels = if header.flags & FLAG_MONO != 0 { 1 } else { 2 };
+
+        if header.flags & FLAG_DSD != 0 {
+            sample_rate *= 4;
+            bits_per_sample = 1;
+        }
+
+        let samples = if header.total_samples == u32::MAX || header.block_index != 0 {
+            count_samples(&data[..audio_end.min(data.len())])
+        } else {
+            header.total_samples as u64
+        };
+
+        let length = if sample_rate > 0 { samples as f64 / sample_rate as f64 } else { 0.0 };
+        let bitrate = if length > 0.0 { (audio_end as f64 * 8.0 / length) as u32 } else { 0 };
+
+        Ok(WavPackInfo {
+            length,
+            channels,
+            sample_rate,
+            bits_per_sample,
+            bitrate,
+            version: header.version,
+            lossless: header.flags & FLAG_HYBRID == 0,
+        })
+    }
+}
+
+/// Sum block sample counts over all blocks. Multichannel streams split each
+/// interval over several blocks, so only those starting a new interval count.
+fn count_samples(data: &[u8]) -> u64 {
+    let mut samples = 0u64;
+    let mut pos = 0usize;
+    while let Some(Ok(header)) = data.get(pos..).map(BlockHeader::parse) {
+        if header.flags & FLAG_INITIAL_BLOCK != 0 {
+            samples += header.block_samples as u64;
+        }
+        match pos.checked_add(header.total_size()) {
+            Some(next) if next <= data.len() => pos = next,
+            _ => break,
+        }
+    }
+    samples
+}
+
+/// Complete WavPack file: stream info + APEv2 tags.
+#[derive(Debug)]
+pub struct WavPackFile {
+    pub info: WavPackInfo,
+    pub tags: Option<APEv2Tags>,
+    pub path: String,
+}
+
+impl WavPackFile {
+    pub fn open(path: &str) -> Result<Self> {
+        let data = std::fs::read(path)?;
+        Self::parse(&data, path)
+    }
+
+    pub fn parse(data: &[u8], path: &str) -> Result<Self> {
+        let audio_end = match apev2::find_apev2(data) {
+            Some(loc) => loc.start,
+            None if crate::id3::id3v1::find_id3v1(data).is_some() => data.len() - 128,
+            None => data.len(),
+        };
+        let info = WavPackInfo::parse(data, audio_end)?;
+        let tags = APEv2Tags::from_data(data)?;
+
+        Ok(WavPackFile {
+            info,
+            tags,
+            path: path.to_string(),
+        })
+    }
+
+    pub fn score(path: &str, data: &[u8]) -> u32 {
+        let mut score = 0u32;
+        let ext = path.rsplit('.').next().unwrap_or("");
+        if ext.eq_ignore_ascii_case("wv") {
+            score += 2;
+        }
+        if data.len() >= 4 && &data[0..4] == b"wvpk" {
+            score += 3;
+        }
+        score
+    }
+}
@@ -0,0 +1,37 @@
+//! Embedding check: with the `mimalloc` feature off, a downstream crate must be
+//! able to install its own global allocator alongside this one. Run with
+//! `cargo test --no-default-features --test custom_allocator`.
+#![cfg(not(feature = "mimalloc"))]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+#[test]
+fn parses_with_downstream_allocator() {
+    let path = format!("{}/test_files/silence-44-s.mp3", env!("CARGO_MANIFEST_DIR"));
+    if !std::path::Path::new(&path).exists() {
+        return;
+    }
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let file = mutagen_rs::open(&path).unwrap();
+    assert!(file.info().sample_rate > 0);
+    assert!(ALLOCATIONS.load(Ordering::Relaxed) > before);
+}
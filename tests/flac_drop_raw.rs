@@ -0,0 +1,47 @@
+use mutagen_rs::flac::FLACFile;
+
+fn scratch_copy(name: &str) -> Option<String> {
+    let src = format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name);
+    if !std::path::Path::new(&src).exists() {
+        return None;
+    }
+    let dst = std::env::temp_dir().join(format!("mutagen-rs-drop-raw-{}-{}", std::process::id(), name));
+    std::fs::copy(&src, &dst).unwrap();
+    Some(dst.to_string_lossy().into_owned())
+}
+
+#[test]
+fn drop_raw_releases_comment_bytes() {
+    let Some(path) = scratch_copy("silence-44-s.flac") else { return };
+    let mut flac = FLACFile::open(&path).unwrap();
+    assert!(flac.vc_raw.is_some());
+    assert!(flac.tags.is_none());
+
+    flac.drop_raw();
+    assert!(flac.vc_raw.is_none());
+    let title = flac.tags.as_ref().unwrap().get("TITLE").join("/");
+    assert!(!title.is_empty());
+
+    // save re-reads everything else from disk
+    flac.tags.as_mut().unwrap().set("ALBUM", vec!["Dropped".to_string()]);
+    flac.save().unwrap();
+    let mut reread = FLACFile::open(&path).unwrap();
+    let tags = reread.get_tags().unwrap();
+    assert_eq!(tags.get("TITLE").join("/"), title);
+    assert_eq!(tags.get("ALBUM"), ["Dropped"]);
+    assert_eq!(reread.info.total_samples, flac.info.total_samples);
+    assert_eq!(reread.lazy_pictures.len(), flac.lazy_pictures.len());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn drop_raw_keeps_unparseable_comment() {
+    let Some(path) = scratch_copy("silence-44-s.flac") else { return };
+    let mut flac = FLACFile::open(&path).unwrap();
+    // A comment block too short to hold even the vendor length
+    flac.vc_raw = Some(vec![0xFF, 0xFF]);
+    flac.drop_raw();
+    assert!(flac.tags.is_none());
+    assert!(flac.vc_raw.is_some());
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,55 @@
+use mutagen_rs::flac::{BlockType, FLACFile};
+
+fn read_test_file(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name)).ok()
+}
+
+// silence-44-s.flac: 44.1 kHz, 162496 samples, audio starts at byte 4186,
+// seek points at samples 0, 41472, 50688, 87552 and 105984.
+#[test]
+fn offset_at_seek_point() {
+    let Some(data) = read_test_file("silence-44-s.flac") else { return };
+    let flac = FLACFile::parse(&data, "silence-44-s.flac").unwrap();
+    assert_eq!(flac.seek_points.len(), 5);
+    assert_eq!(flac.audio_offset(), 4186);
+
+    let at_point = flac.byte_offset_for_time(87552.0 / 44100.0).unwrap();
+    assert_eq!(at_point, 4186 + 25022);
+}
+
+#[test]
+fn offset_between_seek_points() {
+    let Some(data) = read_test_file("silence-44-s.flac") else { return };
+    let flac = FLACFile::parse(&data, "silence-44-s.flac").unwrap();
+
+    // Halfway between the points at 87552 (25022) and 105984 (30284)
+    let mid = flac.byte_offset_for_time(96768.0 / 44100.0).unwrap();
+    assert!((4186 + 27650..=4186 + 27656).contains(&mid), "{}", mid);
+
+    assert_eq!(flac.byte_offset_for_time(0.0), Some(4186));
+    assert_eq!(flac.byte_offset_for_time(-1.0), None);
+    assert_eq!(flac.byte_offset_for_time(flac.info.length + 1.0), None);
+}
+
+#[test]
+fn proportional_without_seektable() {
+    let Some(mut data) = read_test_file("silence-44-s.flac") else { return };
+    // Turn the SeekTable block into padding
+    let mut pos = 4;
+    loop {
+        let header = data[pos];
+        if BlockType::from_byte(header & 0x7F) == BlockType::SeekTable {
+            data[pos] = (header & 0x80) | BlockType::Padding.to_byte();
+        }
+        pos += 4 + u32::from_be_bytes([0, data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        if header & 0x80 != 0 {
+            break;
+        }
+    }
+    let flac = FLACFile::parse(&data, "silence-44-s.flac").unwrap();
+    assert!(flac.seek_points.is_empty());
+
+    let audio_size = (data.len() - 4186) as f64;
+    let half = flac.byte_offset_for_time(flac.info.length / 2.0).unwrap();
+    assert!((half as f64 - (4186.0 + audio_size / 2.0)).abs() <= 1.0, "{}", half);
+}
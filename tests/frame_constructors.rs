@@ -0,0 +1,98 @@
+use mutagen_rs::id3::frames::{picture_key_desc, split_picture_key, Frame};
+use mutagen_rs::id3::specs::{Encoding, PictureType};
+use mutagen_rs::id3::tags::ID3Tags;
+
+#[test]
+fn text() {
+    let frame = Frame::text("TPE1", ["A", "B"]);
+    assert_eq!(frame.hash_key().as_str(), "TPE1");
+    match frame {
+        Frame::Text(f) => {
+            assert_eq!(f.id, "TPE1");
+            assert_eq!(f.encoding, Encoding::Utf8);
+            assert_eq!(f.text, ["A", "B"]);
+        }
+        other => panic!("not a text frame: {:?}", other),
+    }
+}
+
+#[test]
+fn user_text() {
+    let frame = Frame::user_text("MusicBrainz Album Id", vec!["abc".to_string()]);
+    assert_eq!(frame.hash_key().as_str(), "TXXX:MusicBrainz Album Id");
+    match frame {
+        Frame::UserText(f) => {
+            assert_eq!(f.id, "TXXX");
+            assert_eq!(f.desc, "MusicBrainz Album Id");
+            assert_eq!(f.text, ["abc"]);
+        }
+        other => panic!("not a TXXX frame: {:?}", other),
+    }
+}
+
+#[test]
+fn comment() {
+    let frame = Frame::comment("eng", "note", "Some text");
+    assert_eq!(frame.hash_key().as_str(), "COMM:note:eng");
+    assert_eq!(frame.language(), Some("eng"));
+    match frame {
+        Frame::Comment(f) => {
+            assert_eq!(f.id, "COMM");
+            assert_eq!(f.encoding, Encoding::Utf8);
+            assert_eq!(f.desc, "note");
+            assert_eq!(f.text, "Some text");
+        }
+        other => panic!("not a comment frame: {:?}", other),
+    }
+}
+
+#[test]
+fn picture() {
+    let front = Frame::picture("image/png", PictureType::CoverFront, "", b"\x89PNG".to_vec());
+    assert_eq!(front.hash_key().as_str(), "APIC:");
+    let back = Frame::picture("image/jpeg", PictureType::CoverBack, "back", vec![0xFF, 0xD8]);
+    assert_eq!(back.hash_key().as_str(), "APIC:back:4");
+    match back {
+        Frame::Picture(f) => {
+            assert_eq!(f.id, "APIC");
+            assert_eq!(f.mime, "image/jpeg");
+            assert_eq!(f.pic_type, PictureType::CoverBack);
+            assert_eq!(f.desc, "back");
+            assert_eq!(f.data, [0xFF, 0xD8]);
+        }
+        other => panic!("not a picture frame: {:?}", other),
+    }
+}
+
+#[test]
+fn picture_key_round_trip() {
+    assert_eq!(split_picture_key("back:4"), ("back", 4));
+    assert_eq!(split_picture_key("front"), ("front", 3));
+    // A trailing `:3` or out-of-range number belongs to a front cover's description
+    assert_eq!(split_picture_key("Disc:3"), ("Disc:3", 3));
+    assert_eq!(split_picture_key("Take:99"), ("Take:99", 3));
+
+    // `Disc:2` is ambiguous without the type
+    let front = Frame::picture("image/png", PictureType::CoverFront, "Disc:2", vec![]);
+    assert_eq!(front.hash_key().as_str(), "APIC:Disc:2");
+    assert_eq!(picture_key_desc("Disc:2", PictureType::CoverFront as u8), Some("Disc:2"));
+    assert_eq!(picture_key_desc("Disc:2", PictureType::OtherFileIcon as u8), Some("Disc"));
+    assert_eq!(picture_key_desc("Disc", PictureType::CoverBack as u8), None);
+
+    let mut tags = ID3Tags::new();
+    tags.add(front);
+    tags.rename("APIC:Disc:2", "APIC:Disc:5").unwrap();
+    match tags.get("APIC:Disc:5") {
+        Some(Frame::Picture(f)) => {
+            assert_eq!(f.desc, "Disc:5");
+            assert_eq!(f.pic_type, PictureType::CoverFront);
+        }
+        other => panic!("not a picture frame: {:?}", other),
+    }
+}
+
+#[test]
+fn written_as_utf16_for_v23() {
+    let data = Frame::text("TIT2", ["Title"]).write_data(3).unwrap();
+    assert_eq!(data[0], Encoding::Utf16 as u8);
+}
@@ -0,0 +1,42 @@
+use mutagen_rs::id3::frames::Frame;
+use mutagen_rs::id3::tags::ID3Tags;
+use mutagen_rs::id3::load_id3;
+
+fn load(name: &str) -> Option<ID3Tags> {
+    let path = format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name);
+    if !std::path::Path::new(&path).exists() {
+        return None;
+    }
+    Some(load_id3(&path).unwrap().0)
+}
+
+#[test]
+fn export_import_round_trip() {
+    for name in ["silence-44-s.mp3", "id3v22-test.mp3"] {
+        let Some(source) = load(name) else { continue };
+        let version = if source.version.0 == 3 { 3 } else { 4 };
+        let mut copy = ID3Tags::new();
+        copy.import_frames(&source.export_frames().unwrap(), version).unwrap();
+        assert_eq!(copy.keys(), source.keys(), "{}", name);
+        assert_eq!(copy.raw_frames(), source.raw_frames(), "{}", name);
+    }
+}
+
+#[test]
+fn import_replaces_matching_keys() {
+    let mut source = ID3Tags::new();
+    source.add(Frame::text("TIT2", ["Imported"]));
+    source.add(Frame::text("TALB", ["Album"]));
+    let mut target = ID3Tags::new();
+    target.add(Frame::text("TIT2", ["Old"]));
+    target.add(Frame::text("TPE1", ["Kept"]));
+
+    target.import_frames(&source.export_frames().unwrap(), 4).unwrap();
+    assert_eq!(target.keys(), ["TPE1", "TIT2", "TALB"]);
+    assert_eq!(target.decode_first("TIT2").unwrap().text_values(), ["Imported"]);
+}
+
+#[test]
+fn import_rejects_unknown_version() {
+    assert!(ID3Tags::new().import_frames(b"", 5).is_err());
+}
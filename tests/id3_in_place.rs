@@ -0,0 +1,75 @@
+use mutagen_rs::id3::frames::Frame;
+use mutagen_rs::id3::header::ID3Header;
+use mutagen_rs::id3::writer::RenderOptions;
+use mutagen_rs::id3::{load_id3, save_id3, save_id3_in_place, save_id3_in_place_preview};
+
+/// A scratch copy of `name`, saved once so its tag has default padding.
+fn padded_copy(name: &str) -> Option<String> {
+    let src = format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name);
+    if !std::path::Path::new(&src).exists() {
+        return None;
+    }
+    let dst = std::env::temp_dir().join(format!("mutagen-rs-in-place-{}-{}", std::process::id(), name));
+    std::fs::copy(&src, &dst).unwrap();
+    let path = dst.to_string_lossy().into_owned();
+    let (tags, _) = load_id3(&path).unwrap();
+    save_id3(&path, &tags, 4).unwrap();
+    Some(path)
+}
+
+/// Tag size and the bytes after the tag.
+fn split(path: &str) -> (usize, Vec<u8>) {
+    let data = std::fs::read(path).unwrap();
+    let size = ID3Header::parse(&data, 0).unwrap().full_size() as usize;
+    (size, data[size..].to_vec())
+}
+
+#[test]
+fn fits_in_padding() {
+    let Some(path) = padded_copy("silence-44-s.mp3") else { return };
+    let (size, audio) = split(&path);
+    let (mut tags, _) = load_id3(&path).unwrap();
+    tags.delall("TIT2");
+    tags.add(Frame::text("TIT2", ["Written in place"]));
+
+    assert!(save_id3_in_place(&path, &tags, 4, RenderOptions::default()).unwrap());
+    assert_eq!(split(&path), (size, audio));
+    let (reread, _) = load_id3(&path).unwrap();
+    assert_eq!(reread.decode_first("TIT2").unwrap().text_values(), ["Written in place"]);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn grows_beyond_padding() {
+    let Some(path) = padded_copy("silence-44-s.mp3") else { return };
+    let (size, audio) = split(&path);
+    let (mut tags, _) = load_id3(&path).unwrap();
+    tags.add(Frame::user_text("notes", ["x".repeat(4096)]));
+
+    assert!(!save_id3_in_place(&path, &tags, 4, RenderOptions::default()).unwrap());
+    let (new_size, new_audio) = split(&path);
+    assert!(new_size > size);
+    assert_eq!(new_audio, audio);
+    let (reread, _) = load_id3(&path).unwrap();
+    assert_eq!(reread.decode_first("TXXX:notes").unwrap().text_values(), ["x".repeat(4096)]);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn preview_matches_in_place_write() {
+    let Some(path) = padded_copy("silence-44-s.mp3") else { return };
+    let (mut tags, _) = load_id3(&path).unwrap();
+    tags.delall("TIT2");
+    tags.add(Frame::text("TIT2", ["Planned in place"]));
+
+    let plan = save_id3_in_place_preview(&path, &tags, 4, RenderOptions::default()).unwrap();
+    assert!(!plan.full_rewrite);
+    assert_eq!(plan.new_tag.len(), plan.old_tag_size);
+    assert!(save_id3_in_place(&path, &tags, 4, RenderOptions::default()).unwrap());
+    assert_eq!(&std::fs::read(&path).unwrap()[..plan.new_tag.len()], plan.new_tag.as_slice());
+
+    tags.add(Frame::user_text("notes", ["x".repeat(4096)]));
+    let plan = save_id3_in_place_preview(&path, &tags, 4, RenderOptions::default()).unwrap();
+    assert!(plan.full_rewrite);
+    std::fs::remove_file(&path).unwrap();
+}
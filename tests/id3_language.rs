@@ -0,0 +1,54 @@
+use mutagen_rs::id3::frames::{normalize_language, parse_frame, CommentFrame, Frame};
+use mutagen_rs::id3::load_id3_from_data;
+use mutagen_rs::id3::specs::Encoding;
+use mutagen_rs::id3::writer::render_tag;
+
+/// A minimal ID3v2.4 tag holding one frame.
+fn tag_with_frame(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let syncsafe = |n: usize| [(n >> 21) as u8 & 0x7F, (n >> 14) as u8 & 0x7F, (n >> 7) as u8 & 0x7F, n as u8 & 0x7F];
+    let mut frame = id.to_vec();
+    frame.extend_from_slice(&syncsafe(body.len()));
+    frame.extend_from_slice(&[0, 0]);
+    frame.extend_from_slice(body);
+    let mut tag = b"ID3\x04\x00\x00".to_vec();
+    tag.extend_from_slice(&syncsafe(frame.len()));
+    tag.extend_from_slice(&frame);
+    tag
+}
+
+#[test]
+fn blank_codes_normalized() {
+    for lang in ["", "\0\0\0", "   ", " \0 "] {
+        assert_eq!(normalize_language(lang), "XXX", "{:?}", lang);
+    }
+    assert_eq!(normalize_language("eng"), "eng");
+    let frame = parse_frame("USLT", b"\x00   \x00lyrics").unwrap();
+    assert_eq!(frame.language(), Some("XXX"));
+}
+
+#[test]
+fn null_language_resaved_as_xxx() {
+    let data = tag_with_frame(b"COMM", b"\x00\x00\x00\x00note\x00text");
+    let (tags, _) = load_id3_from_data(&data).unwrap();
+    assert_eq!(tags.keys(), ["COMM:note:XXX"]);
+    let frame = tags.decode_first("COMM:note:XXX").unwrap();
+    assert_eq!(frame.language(), Some("XXX"));
+
+    let saved = render_tag(&tags, 4).unwrap();
+    let comm = saved.windows(4).position(|w| w == b"COMM").unwrap();
+    assert_eq!(&saved[comm + 11..comm + 14], b"XXX");
+    assert_eq!(load_id3_from_data(&saved).unwrap().0.keys(), ["COMM:note:XXX"]);
+}
+
+#[test]
+fn short_language_written_as_xxx() {
+    let frame = Frame::Comment(CommentFrame {
+        id: "COMM".to_string(),
+        encoding: Encoding::Latin1,
+        lang: "\0".to_string(),
+        desc: String::new(),
+        text: "hi".to_string(),
+    });
+    assert_eq!(&frame.write_data(4).unwrap()[1..4], b"XXX");
+    assert_eq!(Frame::comment("", "", "hi").language(), Some("XXX"));
+}
@@ -0,0 +1,73 @@
+use mutagen_rs::id3::frames::Frame;
+use mutagen_rs::id3::header::find_id3v2_header;
+use mutagen_rs::id3::{load_id3, load_id3_from_data, save_id3};
+use mutagen_rs::mp3::MP3File;
+
+const JUNK: &[u8] = b"\x00JUNK\xFF\xFB\x01\x02\x03";
+
+/// A copy of `name` with `JUNK` written before its ID3v2 tag.
+fn junk_copy(name: &str) -> Option<(String, Vec<u8>)> {
+    let src = format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let original = std::fs::read(&src).ok()?;
+    let mut data = JUNK.to_vec();
+    data.extend_from_slice(&original);
+    let dst = std::env::temp_dir().join(format!("mutagen-rs-junk-{}-{}", std::process::id(), name));
+    std::fs::write(&dst, &data).unwrap();
+    Some((dst.to_string_lossy().into_owned(), original))
+}
+
+#[test]
+fn tag_found_after_junk() {
+    let Some((path, original)) = junk_copy("silence-44-s.mp3") else { return };
+    let (expected, _) = load_id3_from_data(&original).unwrap();
+    let data = std::fs::read(&path).unwrap();
+    assert_eq!(find_id3v2_header(&data), Some(JUNK.len() as u64));
+
+    let (tags, header) = load_id3(&path).unwrap();
+    assert_eq!(header.unwrap().offset, JUNK.len() as u64);
+    assert_eq!(tags.keys(), expected.keys());
+    assert_eq!(load_id3_from_data(&data).unwrap().0.keys(), expected.keys());
+
+    let mp3 = MP3File::open(&path).unwrap();
+    assert_eq!(mp3.tags.keys(), expected.keys());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn save_keeps_leading_junk() {
+    let Some((path, _)) = junk_copy("silence-44-s.mp3") else { return };
+    let (mut tags, _) = load_id3(&path).unwrap();
+    tags.delall("TIT2");
+    tags.add(Frame::text("TIT2", ["Behind junk"]));
+    save_id3(&path, &tags, 4).unwrap();
+
+    let data = std::fs::read(&path).unwrap();
+    assert_eq!(&data[..JUNK.len()], JUNK);
+    assert_eq!(&data[JUNK.len()..JUNK.len() + 3], b"ID3");
+    let (reread, _) = load_id3(&path).unwrap();
+    assert_eq!(reread.decode_first("TIT2").unwrap().text_values(), ["Behind junk"]);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn trailing_tag_after_audio_ignored() {
+    for (name, length) in [("audacious-trailing-id32-apev2.mp3", 1.08), ("audacious-trailing-id32-id31.mp3", 3.818)] {
+        let path = format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name);
+        let Ok(data) = std::fs::read(&path) else { continue };
+        assert_eq!(find_id3v2_header(&data), None, "{}", name);
+        let mp3 = MP3File::open(&path).unwrap();
+        assert!(mp3.id3_header.is_none(), "{}", name);
+        assert!((mp3.info.length - length).abs() < 1e-3, "{}: {}", name, mp3.info.length);
+        assert!(mp3.info.bitrate > 0, "{}", name);
+    }
+}
+
+#[test]
+fn implausible_header_ignored() {
+    // "ID3" followed by a non-syncsafe size is not a tag
+    let mut data = vec![0u8; 64];
+    data[20..30].copy_from_slice(b"ID3\x04\x00\x00\x00\x00\x80\x00");
+    assert_eq!(find_id3v2_header(&data), None);
+    data[28] = 0x01;
+    assert_eq!(find_id3v2_header(&data), Some(20));
+}
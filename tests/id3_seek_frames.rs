@@ -0,0 +1,46 @@
+use mutagen_rs::id3::frames::{parse_frame, AudioSeekPointIndexFrame, Frame, SeekFrame};
+
+#[test]
+fn seek_round_trip() {
+    let frame = Frame::Seek(SeekFrame { id: "SEEK".to_string(), offset: 0x0001_E240 });
+    let data = frame.write_data(4).unwrap();
+    assert_eq!(data, [0x00, 0x01, 0xE2, 0x40]);
+    match parse_frame("SEEK", &data).unwrap() {
+        Frame::Seek(f) => assert_eq!(f.offset, 123_456),
+        other => panic!("not a SEEK frame: {:?}", other),
+    }
+    assert!(parse_frame("SEEK", &[0, 1]).is_err());
+}
+
+#[test]
+fn aspi_round_trip() {
+    for bits in [8, 16] {
+        let frame = Frame::AudioSeekPointIndex(AudioSeekPointIndexFrame {
+            id: "ASPI".to_string(),
+            data_start: 2048,
+            data_length: 1_000_000,
+            bits_per_point: bits,
+            fractions: vec![0, 64, 128, 192],
+        });
+        let data = frame.write_data(4).unwrap();
+        assert_eq!(data.len(), 11 + 4 * (bits as usize / 8));
+        match parse_frame("ASPI", &data).unwrap() {
+            Frame::AudioSeekPointIndex(f) => {
+                assert_eq!((f.data_start, f.data_length, f.bits_per_point), (2048, 1_000_000, bits));
+                assert_eq!(f.fractions, [0, 64, 128, 192]);
+            }
+            other => panic!("not an ASPI frame: {:?}", other),
+        }
+    }
+}
+
+#[test]
+fn aspi_truncated_points_dropped() {
+    // Claims 3 16-bit points but holds one and a half
+    let mut data = vec![0, 0, 0, 0, 0, 0, 0x10, 0, 0, 3, 16];
+    data.extend_from_slice(&[0x12, 0x34, 0x56]);
+    match parse_frame("ASPI", &data).unwrap() {
+        Frame::AudioSeekPointIndex(f) => assert_eq!(f.fractions, [0x1234]),
+        other => panic!("not an ASPI frame: {:?}", other),
+    }
+}
@@ -0,0 +1,51 @@
+use mutagen_rs::id3::frames::{Frame, UrlFrame};
+use mutagen_rs::id3::tags::ID3Tags;
+
+fn woar(url: &str) -> Frame {
+    Frame::Url(UrlFrame { id: "WOAR".to_string(), url: url.to_string() })
+}
+
+fn urls(tags: &ID3Tags, key: &str) -> Vec<String> {
+    tags.getall(key).into_iter().map(|f| f.text_values().join("")).collect()
+}
+
+fn tagged() -> ID3Tags {
+    let mut tags = ID3Tags::new();
+    tags.add(Frame::text("TIT2", ["Title"]));
+    tags.add(woar("http://example.com/a"));
+    tags.add(woar("http://example.com/b"));
+    tags.add(Frame::text("TPE1", ["Artist"]));
+    tags
+}
+
+#[test]
+fn delall_by_frame_id() {
+    let mut tags = tagged();
+    tags.delall("WOAR");
+    assert_eq!(tags.keys(), ["TIT2", "TPE1"]);
+
+    let mut tags = tagged();
+    tags.delall("WOAR:http://example.com/b");
+    assert_eq!(urls(&tags, "WOAR"), ["http://example.com/a"]);
+}
+
+#[test]
+fn pop_by_frame_id() {
+    let mut tags = tagged();
+    let popped: Vec<String> = tags.pop("WOAR").iter().map(|f| f.text_values().join("")).collect();
+    assert_eq!(popped, ["http://example.com/a", "http://example.com/b"]);
+    assert!(tags.getall("WOAR").is_empty());
+    assert_eq!(tags.keys(), ["TIT2", "TPE1"]);
+}
+
+#[test]
+fn setall_replaces_every_slot() {
+    let mut tags = tagged();
+    tags.setall("WOAR", vec![woar("http://example.com/c"), woar("http://example.com/d")]);
+    assert_eq!(urls(&tags, "WOAR"), ["http://example.com/c", "http://example.com/d"]);
+    assert_eq!(
+        tags.keys(),
+        ["TIT2", "WOAR:http://example.com/c", "WOAR:http://example.com/d", "TPE1"]
+    );
+    assert!(tags.get("WOAR").is_none());
+}
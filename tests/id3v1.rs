@@ -0,0 +1,44 @@
+use mutagen_rs::id3::frames::{Frame, TextFrame};
+use mutagen_rs::id3::id3v1::{make_id3v1, parse_id3v1};
+use mutagen_rs::id3::specs::{self, Encoding};
+
+fn text(id: &str, value: &str) -> Frame {
+    Frame::Text(TextFrame { id: id.to_string(), encoding: Encoding::Latin1, text: vec![value.to_string()] })
+}
+
+fn genre_of(tag: &[u8]) -> Option<Vec<String>> {
+    parse_id3v1(tag).unwrap().into_iter().find_map(|f| match f {
+        Frame::Text(f) if f.id == "TCON" => Some(f.text),
+        _ => None,
+    })
+}
+
+#[test]
+fn genre_round_trip() {
+    let tag = make_id3v1(&[text("TIT2", "Title"), text("TCON", "Jazz")]);
+    assert_eq!(tag[127], 8);
+    assert_eq!(genre_of(&tag), Some(vec!["Jazz".to_string()]));
+}
+
+#[test]
+fn numeric_genre_reference() {
+    let tag = make_id3v1(&[text("TCON", "(8)")]);
+    assert_eq!(tag[127], 8);
+}
+
+#[test]
+fn missing_or_unknown_genre_is_255() {
+    for frames in [vec![text("TIT2", "Title")], vec![text("TCON", "Not A Genre")]] {
+        let tag = make_id3v1(&frames);
+        assert_eq!(tag[127], 255);
+        assert_eq!(genre_of(&tag), None);
+    }
+}
+
+#[test]
+fn mp4_gnre_is_one_based() {
+    assert_eq!(specs::genre_name(8), Some("Jazz"));
+    assert_eq!(specs::mp4_genre_name(9), Some("Jazz"));
+    assert_eq!(specs::mp4_genre_name(0), None);
+    assert_eq!(specs::genre_index("Jazz"), Some(8));
+}
@@ -0,0 +1,28 @@
+use mutagen_rs::common::groups::{KeyGroups, LINEAR_KEY_LIMIT};
+
+fn naive(input: &[(String, u32)]) -> Vec<(String, Vec<u32>)> {
+    let mut groups: Vec<(String, Vec<u32>)> = Vec::new();
+    for (key, value) in input {
+        match groups.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1.push(*value),
+            None => groups.push((key.clone(), vec![*value])),
+        }
+    }
+    groups
+}
+
+#[test]
+fn matches_linear_grouping() {
+    for keys in [1, LINEAR_KEY_LIMIT, LINEAR_KEY_LIMIT + 1, 500] {
+        // Interleave keys so groups span the switch to the index
+        let input: Vec<(String, u32)> = (0..keys as u32 * 3)
+            .map(|i| (format!("KEY{}", (i * 7) % keys as u32), i))
+            .collect();
+        let mut groups = KeyGroups::new();
+        for (k, v) in &input {
+            groups.push(k.clone(), *v);
+        }
+        assert_eq!(groups.len(), keys);
+        assert_eq!(groups.into_vec(), naive(&input), "{} keys", keys);
+    }
+}
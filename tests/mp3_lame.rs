@@ -0,0 +1,15 @@
+use mutagen_rs::mp3::header::find_sync;
+use mutagen_rs::mp3::xing::XingHeader;
+
+fn read_test_file(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name)).ok()
+}
+
+#[test]
+fn lame_delay_and_padding() {
+    let Some(data) = read_test_file("lame.mp3") else { return };
+    let (pos, frame) = find_sync(&data, 0).unwrap();
+    let xing = XingHeader::parse(&data[pos..], frame.version, frame.channel_mode).unwrap();
+    let lame = xing.lame_header.unwrap();
+    assert_eq!((lame.encoder_delay, lame.encoder_padding), (576, 1315));
+}
@@ -0,0 +1,66 @@
+use std::time::{Duration, Instant};
+
+use mutagen_rs::mp3::header::find_sync;
+
+fn read_test_file(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name)).ok()
+}
+
+/// `len` bytes of junk without 0xFF bytes.
+fn junk(len: usize) -> Vec<u8> {
+    let mut state = 0x2545_F491u32;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            (state as u8).min(0xFE)
+        })
+        .collect()
+}
+
+/// 1 MB of junk with a header that parses (but is not followed by another
+/// frame) planted every 64 bytes, except near the end.
+fn junk_with_false_syncs(header: &[u8]) -> Vec<u8> {
+    let mut junk = junk(1 << 20);
+    let len = junk.len();
+    for pos in (0..len - 4096).step_by(64) {
+        junk[pos..pos + 4].copy_from_slice(header);
+    }
+    junk
+}
+
+#[test]
+fn resync_over_leading_junk() {
+    let Some(data) = read_test_file("silence-44-s.mp3") else { return };
+    let (first, _) = find_sync(&data, 0).unwrap();
+    let audio = &data[first..];
+
+    let mut file = junk_with_false_syncs(&audio[..4]);
+    let junk_len = file.len();
+    file.extend_from_slice(audio);
+
+    let start = Instant::now();
+    let (pos, frame) = find_sync(&file, 0).unwrap();
+    let elapsed = start.elapsed();
+
+    assert_eq!(pos, junk_len);
+    assert!(frame.frame_length > 0);
+    assert!(elapsed < Duration::from_millis(500), "{:?}", elapsed);
+}
+
+#[test]
+fn false_sync_just_before_first_frame() {
+    let Some(data) = read_test_file("silence-44-s.mp3") else { return };
+    let (first, frame) = find_sync(&data, 0).unwrap();
+    let audio = &data[first..];
+    assert!(frame.frame_length > 100);
+
+    // The false header's predicted next frame lands inside the real first frame
+    let mut file = junk(1000);
+    file[900..904].copy_from_slice(&audio[..4]);
+    file.extend_from_slice(audio);
+
+    let (pos, _) = find_sync(&file, 0).unwrap();
+    assert_eq!(pos, 1000);
+}
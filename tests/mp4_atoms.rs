@@ -0,0 +1,34 @@
+use mutagen_rs::mp4::atom::{find_all_path, find_atom_path, AtomIter};
+
+fn read_test_file(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name)).ok()
+}
+
+// ep7.m4b: an audio track and a chapter text track.
+#[test]
+fn all_traks_found() {
+    let Some(data) = read_test_file("ep7.m4b") else { return };
+    let traks = find_all_path(&data, &[b"moov", b"trak"]);
+    assert_eq!(traks.len(), 2);
+    assert!(traks.iter().all(|t| &t.name == b"trak"));
+    assert_eq!(find_atom_path(&data, &[b"moov", b"trak"]).unwrap().offset, traks[0].offset);
+
+    let moov = find_atom_path(&data, &[b"moov"]).unwrap();
+    let iter = AtomIter::new(&data, moov.data_offset, moov.data_offset + moov.data_size);
+    assert_eq!(iter.filter_name(b"trak").count(), 2);
+}
+
+#[test]
+fn nested_matches_followed() {
+    let Some(data) = read_test_file("ep7.m4b") else { return };
+    let hdlrs = find_all_path(&data, &[b"moov", b"trak", b"mdia", b"hdlr"]);
+    assert_eq!(hdlrs.len(), 2);
+    assert!(find_all_path(&data, &[b"moov", b"nope"]).is_empty());
+    assert!(find_all_path(&data, &[]).is_empty());
+}
+
+#[test]
+fn single_track_file() {
+    let Some(data) = read_test_file("has-tags.m4a") else { return };
+    assert_eq!(find_all_path(&data, &[b"moov", b"trak"]).len(), 1);
+}
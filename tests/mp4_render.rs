@@ -0,0 +1,78 @@
+use mutagen_rs::mp4::{render_item, MP4File, MP4TagValue, MP4Tags};
+
+fn atom(name: &[u8; 4], body: &[u8]) -> Vec<u8> {
+    let mut out = (8 + body.len() as u32).to_be_bytes().to_vec();
+    out.extend_from_slice(name);
+    out.extend_from_slice(body);
+    out
+}
+
+/// Wrap a rendered `ilst` in `moov/udta/meta` and parse it back.
+fn reparse(tags: &MP4Tags) -> MP4Tags {
+    let mut meta = vec![0u8; 4];
+    meta.extend_from_slice(&tags.render_ilst().unwrap());
+    let moov = atom(b"moov", &atom(b"udta", &atom(b"meta", &meta)));
+    let mut data = atom(b"ftyp", b"M4A \0\0\0\0");
+    data.extend_from_slice(&moov);
+    let mut file = MP4File::parse(&data, "render.m4a").unwrap();
+    file.ensure_parsed_with_data(&data);
+    file.tags
+}
+
+#[test]
+fn integer_and_flag_layout() {
+    let cpil = render_item("cpil", &MP4TagValue::Bool(true)).unwrap();
+    assert_eq!(&cpil[8..], atom(b"data", &[0, 0, 0, 21, 0, 0, 0, 0, 1]).as_slice());
+
+    // tmpo is at least two bytes wide
+    let tmpo = render_item("tmpo", &MP4TagValue::Integer(vec![120])).unwrap();
+    assert_eq!(&tmpo[8..], atom(b"data", &[0, 0, 0, 21, 0, 0, 0, 0, 0, 120]).as_slice());
+
+    // Other integers take the narrowest width that holds them
+    let rtng = render_item("rtng", &MP4TagValue::Integer(vec![1])).unwrap();
+    assert_eq!(rtng.len(), 8 + 16 + 1);
+    let big = render_item("akID", &MP4TagValue::Integer(vec![70_000])).unwrap();
+    assert_eq!(&big[24..], [0, 1, 0x11, 0x70]);
+    let plid = render_item("plID", &MP4TagValue::Integer(vec![5])).unwrap();
+    assert_eq!(plid.len(), 8 + 16 + 8);
+
+    let disk = render_item("disk", &MP4TagValue::IntPair(vec![(1, 2)])).unwrap();
+    assert_eq!(&disk[8..], atom(b"data", &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 2, 0, 0]).as_slice());
+    assert!(render_item("trkn", &MP4TagValue::IntPair(vec![(70_000, 0)])).is_err());
+}
+
+#[test]
+fn round_trip() {
+    let mut tags = MP4Tags::new();
+    tags.set_bool("cpil", true).unwrap();
+    tags.set_int("tmpo", 120).unwrap();
+    tags.set_pair("disk", (1, 2)).unwrap();
+    tags.set_pair("trkn", (3, 12)).unwrap();
+    tags.set_text("\u{00a9}nam", vec!["Title".to_string()]).unwrap();
+    tags.set_text("----:com.apple.iTunes:MOOD", vec!["Calm".to_string()]).unwrap();
+
+    let read = reparse(&tags);
+    assert_eq!(read.keys(), tags.keys());
+    assert!(matches!(read.get("cpil"), Some(MP4TagValue::Bool(true))));
+    assert!(matches!(read.get("tmpo"), Some(MP4TagValue::Integer(v)) if v == &[120]));
+    assert_eq!(read.disc_number(), (Some(1), Some(2)));
+    assert_eq!(read.track_number(), (Some(3), Some(12)));
+    assert_eq!(read.get_text("\u{00a9}nam").as_deref(), Some("Title"));
+    assert_eq!(read.get_text("----:com.apple.iTunes:MOOD").as_deref(), Some("Calm"));
+}
+
+#[test]
+fn genre_round_trip() {
+    let mut tags = MP4Tags::new();
+    tags.set_text("gnre", vec!["Jazz".to_string()]).unwrap();
+
+    let gnre = render_item("gnre", tags.get("gnre").unwrap()).unwrap();
+    assert_eq!(&gnre[8..], atom(b"data", &[0, 0, 0, 0, 0, 0, 0, 0, 0, 9]).as_slice());
+    let read = reparse(&tags);
+    assert_eq!(read.get_text("gnre").as_deref(), Some("Jazz"));
+    assert_eq!(reparse(&read).get_text("gnre").as_deref(), Some("Jazz"));
+
+    // Names outside the ID3v1 list can only be stored as free text
+    let custom = render_item("gnre", &MP4TagValue::Text(vec!["Chiptune".to_string()])).unwrap();
+    assert_eq!(&custom[4..8], b"\xa9gen");
+}
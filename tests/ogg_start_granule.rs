@@ -0,0 +1,22 @@
+use mutagen_rs::ogg::{vorbis_sample_count, vorbis_start_granule};
+
+fn read(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name)).ok()
+}
+
+#[test]
+fn streams_encoded_from_zero() {
+    for name in ["empty.ogg", "multipage-setup.ogg", "multipagecomment.ogg"] {
+        let Some(data) = read(name) else { continue };
+        let serial = u32::from_le_bytes([data[14], data[15], data[16], data[17]]);
+        assert_eq!(vorbis_start_granule(&data, serial), Some(0), "{}", name);
+    }
+}
+
+#[test]
+fn not_vorbis() {
+    let Some(data) = read("empty.oggflac") else { return };
+    let serial = u32::from_le_bytes([data[14], data[15], data[16], data[17]]);
+    assert_eq!(vorbis_start_granule(&data, serial), None);
+    assert!(vorbis_sample_count(&data, serial).is_some());
+}
@@ -0,0 +1,98 @@
+use mutagen_rs::common::probe::FileFormat;
+use mutagen_rs::AudioFile;
+
+fn test_file(name: &str) -> Option<String> {
+    let path = format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name);
+    if std::path::Path::new(&path).exists() { Some(path) } else { None }
+}
+
+#[test]
+fn open_each_format() {
+    let cases = [
+        ("silence-44-s.mp3", FileFormat::MP3),
+        ("silence-44-s.flac", FileFormat::FLAC),
+        ("empty.ogg", FileFormat::OggVorbis),
+        ("empty.oggflac", FileFormat::OggFLAC),
+        ("has-tags.m4a", FileFormat::MP4),
+        ("silence-44-s.wv", FileFormat::WavPack),
+        ("silence-1.wma", FileFormat::ASF),
+        ("mac-399.ape", FileFormat::MonkeysAudio),
+    ];
+    for (name, format) in cases {
+        let Some(path) = test_file(name) else { continue };
+        let file = mutagen_rs::open(&path).unwrap();
+        assert_eq!(file.format(), format, "{}", name);
+        assert!(file.info().sample_rate > 0, "{}", name);
+    }
+}
+
+#[test]
+fn common_artist_across_formats() {
+    for name in ["silence-44-s.mp3", "silence-44-s.flac", "has-tags.m4a", "issue_29.wma"] {
+        let Some(path) = test_file(name) else { continue };
+        let file = mutagen_rs::open(&path).unwrap();
+        let tags = file.tags().expect(name);
+        assert!(tags.artist().is_some_and(|a| !a.is_empty()), "{}", name);
+    }
+}
+
+#[test]
+fn parse_from_memory() {
+    let Some(path) = test_file("silence-44-s.flac") else { return };
+    let data = std::fs::read(&path).unwrap();
+    let file = AudioFile::parse(&data, "no-extension").unwrap();
+    assert_eq!(file.format(), FileFormat::FLAC);
+    assert_eq!(file.info().bits_per_sample, Some(16));
+}
+
+#[test]
+fn ogg_flac_named_ogg() {
+    let Some(path) = test_file("empty.oggflac") else { return };
+    let data = std::fs::read(&path).unwrap();
+    let file = AudioFile::parse(&data, "song.ogg").unwrap();
+    assert_eq!(file.format(), FileFormat::OggFLAC);
+    assert!((file.info().length - 3.68).abs() < 0.01);
+    assert!(file.tags().is_some());
+}
+
+#[test]
+fn duration_matches_full_parse() {
+    for name in ["silence-44-s.mp3", "silence-44-s.flac", "empty.ogg", "empty.oggflac", "has-tags.m4a", "silence-1.wma"] {
+        let Some(path) = test_file(name) else { continue };
+        let data = std::fs::read(&path).unwrap();
+        let full = mutagen_rs::open(&path).unwrap().info().length;
+        assert_eq!(mutagen_rs::file::duration(&data, &path).unwrap(), full, "{}", name);
+    }
+}
+
+#[test]
+fn mp4_info_only_matches_open() {
+    let Some(path) = test_file("has-tags.m4a") else { return };
+    let data = std::fs::read(&path).unwrap();
+    let info = mutagen_rs::mp4::MP4File::info_only(&data, &path).unwrap();
+    let AudioFile::MP4(full) = mutagen_rs::open(&path).unwrap() else { panic!("not MP4") };
+    assert!(!full.tags.keys().is_empty());
+    assert_eq!(info.length, full.info.length);
+    assert_eq!(info.bitrate, full.info.bitrate);
+    assert_eq!(info.codec, full.info.codec);
+}
+
+#[test]
+fn lossless_and_sample_depth() {
+    let cases = [
+        ("silence-44-s.mp3", false, None),
+        ("silence-44-s.flac", true, Some(16)),
+        ("empty.ogg", false, None),
+        ("empty.oggflac", true, Some(16)),
+        ("has-tags.m4a", false, None),
+        ("alac.m4a", true, Some(16)),
+        ("silence-44-s.wv", true, Some(16)),
+        ("mac-399.ape", true, Some(16)),
+    ];
+    for (name, lossless, depth) in cases {
+        let Some(path) = test_file(name) else { continue };
+        let info = mutagen_rs::open(&path).unwrap().info();
+        assert_eq!(info.lossless, lossless, "{}", name);
+        assert_eq!(info.bits_per_sample, depth, "{}", name);
+    }
+}
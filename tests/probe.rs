@@ -0,0 +1,38 @@
+use mutagen_rs::common::probe::{self, FileFormat, HEADER_SIZE};
+use std::io::Read;
+
+/// A 100 MB stream of `header` followed by zeros that counts the bytes read.
+struct LargeStream {
+    header: Vec<u8>,
+    pos: usize,
+    read: usize,
+}
+
+const LEN: usize = 100 * 1024 * 1024;
+
+impl Read for LargeStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = buf.len().min(LEN - self.pos);
+        for (i, b) in buf[..n].iter_mut().enumerate() {
+            *b = self.header.get(self.pos + i).copied().unwrap_or(0);
+        }
+        self.pos += n;
+        self.read += n;
+        Ok(n)
+    }
+}
+
+#[test]
+fn detect_reads_only_the_header() {
+    let mut stream = LargeStream { header: b"fLaC\x00\x00\x00\x22".to_vec(), pos: 0, read: 0 };
+    let format = probe::detect_reader("audio.bin", &mut stream).unwrap();
+    assert_eq!(format, Some(FileFormat::FLAC));
+    assert!(stream.read <= HEADER_SIZE);
+}
+
+#[test]
+fn detect_unknown_stops_at_header() {
+    let mut stream = LargeStream { header: Vec::new(), pos: 0, read: 0 };
+    assert_eq!(probe::detect_reader("audio.bin", &mut stream).unwrap(), None);
+    assert!(stream.read <= HEADER_SIZE);
+}
@@ -0,0 +1,96 @@
+use mutagen_rs::common::error::MutagenError;
+use mutagen_rs::flac::{FLACFile, SaveOptions};
+use mutagen_rs::id3;
+use mutagen_rs::id3::easy::EasyKey;
+use mutagen_rs::id3::writer::RenderOptions;
+use mutagen_rs::vorbis::VorbisComment;
+
+/// Copy a test file to a scratch path unique to `tag`.
+fn scratch_copy(name: &str, tag: &str) -> Option<String> {
+    let src = format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name);
+    if !std::path::Path::new(&src).exists() {
+        return None;
+    }
+    let dst = std::env::temp_dir().join(format!("mutagen-rs-verify-{}-{}-{}", std::process::id(), tag, name));
+    std::fs::copy(&src, &dst).unwrap();
+    Some(dst.to_string_lossy().into_owned())
+}
+
+/// Overwrite the first occurrence of `needle` in the file with `replacement`,
+/// standing in for a writer that emitted the wrong bytes.
+fn corrupt(path: &str, needle: &[u8], replacement: &[u8]) {
+    let mut data = std::fs::read(path).unwrap();
+    let pos = data.windows(needle.len()).position(|w| w == needle).expect("needle not found");
+    data[pos..pos + replacement.len()].copy_from_slice(replacement);
+    std::fs::write(path, data).unwrap();
+}
+
+#[test]
+fn id3_save_verifies() {
+    let Some(path) = scratch_copy("silence-44-s.mp3", "id3-ok") else { return };
+    let (mut tags, _) = id3::load_id3(&path).unwrap();
+    EasyKey::Text("TIT2").set(&mut tags, vec!["Verified Title".to_string()]);
+    for version in [3, 4] {
+        let options = RenderOptions::default();
+        id3::save_id3_with(&path, &tags, version, options).unwrap();
+        id3::verify_id3(&path, &tags, version, options).unwrap();
+    }
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn id3_verify_catches_corrupt_write() {
+    let Some(path) = scratch_copy("silence-44-s.mp3", "id3-bad") else { return };
+    let (mut tags, _) = id3::load_id3(&path).unwrap();
+    EasyKey::Text("TIT2").set(&mut tags, vec!["Verified Title".to_string()]);
+    let options = RenderOptions::default();
+    id3::save_id3_with(&path, &tags, 4, options).unwrap();
+
+    corrupt(&path, b"Verified Title", b"Vexified");
+    let err = id3::verify_id3(&path, &tags, 4, options).unwrap_err();
+    assert!(matches!(err, MutagenError::Verify(_)), "{:?}", err);
+
+    // A frame id the reader skips drops the frame altogether
+    corrupt(&path, b"TIT2", b"ZZZZ");
+    let err = id3::verify_id3(&path, &tags, 4, options).unwrap_err();
+    assert!(matches!(err, MutagenError::Verify(_)), "{:?}", err);
+    std::fs::remove_file(&path).unwrap();
+}
+
+fn flac_with_title(path: &str, title: &str) -> FLACFile {
+    let mut flac = FLACFile::open(path).unwrap();
+    flac.ensure_tags();
+    flac.tags.get_or_insert_with(VorbisComment::new).set("TITLE", vec![title.to_string()]);
+    flac
+}
+
+#[test]
+fn flac_save_verifies() {
+    let Some(path) = scratch_copy("silence-44-s.flac", "flac-ok") else { return };
+    let flac = flac_with_title(&path, "Verified Title");
+    flac.save_with(SaveOptions { verify: true, ..Default::default() }).unwrap();
+
+    let flac = flac_with_title(&path, "Again");
+    flac.save_with(SaveOptions { preserve_order: true, dedupe: true, verify: true, ..Default::default() }).unwrap();
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn flac_verify_catches_corrupt_write() {
+    let Some(path) = scratch_copy("silence-44-s.flac", "flac-bad") else { return };
+    let flac = flac_with_title(&path, "Verified Title");
+    flac.save().unwrap();
+    flac.verify(false).unwrap();
+
+    corrupt(&path, b"Verified Title", b"Vexified");
+    let err = flac.verify(false).unwrap_err();
+    assert!(matches!(err, MutagenError::Verify(_)), "{:?}", err);
+
+    // Audio lost off the end of the file
+    let len = std::fs::metadata(&path).unwrap().len();
+    std::fs::OpenOptions::new().write(true).open(&path).unwrap().set_len(len - 100).unwrap();
+    corrupt(&path, b"Vexified", b"Verified");
+    let err = flac.verify(false).unwrap_err();
+    assert!(matches!(err, MutagenError::Verify(_)), "{:?}", err);
+    std::fs::remove_file(&path).unwrap();
+}
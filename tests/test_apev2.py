@@ -0,0 +1,106 @@
+"""APEv2 tag writing tests for mutagen_rs."""
+import os
+import shutil
+import struct
+
+import pytest
+
+from mutagen_rs.mutagen_rs import APEv2, APEError, WavPack
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def copy_test_file(tmp_path, name):
+    src = os.path.join(TEST_DIR, name)
+    if not os.path.exists(src):
+        pytest.skip(f"Test file not found: {src}")
+    dst = tmp_path / name
+    shutil.copy(src, dst)
+    return str(dst)
+
+
+def footer(data, end):
+    """(size, item count, flags) of the APEv2 footer ending at `end`."""
+    assert data[end - 32:end - 24] == b"APETAGEX"
+    return struct.unpack("<III", data[end - 20:end - 8])
+
+
+class TestMP3:
+
+    def test_write_keeps_id3v1_last(self, tmp_path):
+        path = copy_test_file(tmp_path, "silence-44-s.mp3")
+        with open(path, "rb") as f:
+            original = f.read()
+        assert original[-128:-125] == b"TAG"
+
+        tags = APEv2()
+        tags["Title"] = "Title"
+        tags["Artist"] = ["A", "B"]
+        tags.save(path)
+
+        with open(path, "rb") as f:
+            data = f.read()
+        assert data[-128:] == original[-128:]
+        size, count, flags = footer(data, len(data) - 128)
+        assert count == 2
+        assert flags & (1 << 31) and not flags & (1 << 29)
+        # Header, items and footer sit between the audio and the ID3v1 tag
+        assert data[:len(original) - 128] == original[:-128]
+        assert len(data) == len(original) + size + 32
+
+        tags = APEv2(path)
+        assert tags["Title"] == "Title"
+        assert tags["artist"] == ["A", "B"]
+
+    def test_save_replaces_existing(self, tmp_path):
+        path = copy_test_file(tmp_path, "silence-44-s.mp3")
+        tags = APEv2()
+        tags["Title"] = "First"
+        tags.save(path)
+        size = os.path.getsize(path)
+
+        tags = APEv2(path)
+        tags["Title"] = "Again"
+        tags.save()
+        assert os.path.getsize(path) == size
+        assert APEv2(path)["Title"] == "Again"
+
+    def test_binary_item(self, tmp_path):
+        path = copy_test_file(tmp_path, "silence-44-s.mp3")
+        tags = APEv2()
+        tags["Cover Art (Front)"] = b"cover.jpg\x00\xff\xd8"
+        tags.save(path)
+        assert APEv2(path)["Cover Art (Front)"] == b"cover.jpg\x00\xff\xd8"
+
+    def test_delete(self, tmp_path):
+        path = copy_test_file(tmp_path, "silence-44-s.mp3")
+        size = os.path.getsize(path)
+        tags = APEv2()
+        tags["Title"] = "Title"
+        tags.save(path)
+        tags.delete(path)
+        assert os.path.getsize(path) == size
+        with pytest.raises(APEError):
+            APEv2(path)
+
+    def test_invalid_key(self):
+        tags = APEv2()
+        with pytest.raises(ValueError):
+            tags["ID3"] = "x"
+        with pytest.raises(ValueError):
+            tags["k"] = "x"
+
+
+class TestWavPack:
+
+    def test_save_through_file_tags(self, tmp_path):
+        path = copy_test_file(tmp_path, "silence-44-s.wv")
+        tags = WavPack(path).tags
+        del tags["Title"]
+        tags["Album"] = "Album"
+        tags.save()
+
+        f = WavPack(path)
+        assert "Title" not in f.tags
+        assert f.tags["Album"] == "Album"
+        assert f.info.sample_rate == 44100
@@ -120,6 +120,11 @@ class TestFLACCompat:
         rust = mutagen_rs.FLAC(flac_file)
         assert orig.info.channels == rust.info.channels
 
+    def test_info_bitrate_mode(self, flac_file):
+        # Lossless streams always report VBR
+        assert mutagen_rs.FLAC(flac_file).info.bitrate_mode == 2
+        assert mutagen_rs.mutagen_rs.FLAC(flac_file).info.bitrate_mode == 2
+
     def test_tag_keys(self, flac_file):
         orig = FLAC(flac_file)
         rust = mutagen_rs.FLAC(flac_file)
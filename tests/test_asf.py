@@ -0,0 +1,76 @@
+"""ASF/WMA stream info and attribute tests for mutagen_rs."""
+import os
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import ASF as NativeASF
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    return path
+
+
+class TestASFInfo:
+
+    def test_stream_info(self):
+        f = NativeASF(get_test_file("silence-1.wma"))
+        assert abs(f.info.length - 3.7) < 0.05
+        assert f.info.sample_rate == 48000
+        assert f.info.channels == 2
+        assert f.info.bitrate // 1000 == 64
+        assert f.info.codec_type == "Windows Media Audio 9 Standard"
+        assert f.info.codec_name.startswith("Windows Media Audio 9.1")
+
+    def test_lossless(self):
+        f = NativeASF(get_test_file("silence-3.wma"))
+        assert f.info.sample_rate == 44100
+        assert f.info.codec_type == "Windows Media Audio 9 Lossless"
+
+    def test_not_asf(self):
+        with pytest.raises(mutagen_rs.ASFError):
+            NativeASF(get_test_file("silence-44-s.flac"))
+
+    def test_object_size_overflow(self, tmp_path):
+        with open(get_test_file("silence-1.wma"), "rb") as f:
+            data = bytearray(f.read())
+        # First header object's 64-bit size, set so that pos + size wraps
+        data[30 + 16:30 + 24] = (2**64 - 1).to_bytes(8, "little")
+        path = tmp_path / "overflow.wma"
+        path.write_bytes(bytes(data))
+        with pytest.raises(mutagen_rs.ASFError):
+            NativeASF(str(path))
+
+
+class TestASFTags:
+
+    def test_content_description(self):
+        f = NativeASF(get_test_file("silence-1.wma"))
+        assert f["Title"] == ["test"]
+        assert isinstance(f.tags, mutagen_rs.ASFTags)
+        assert f.tags["Title"] == ["test"]
+
+    def test_extended_attributes(self):
+        f = NativeASF(get_test_file("issue_29.wma"))
+        assert f["Author"] == ["Kaizers Orchestra"]
+        assert f["WM/AlbumTitle"] == ["Live at Vega"]
+        assert f["WM/Track"] == [5]
+        assert f["IsVBR"] == [False]
+        assert isinstance(f["WM/MCDI"][0], bytes)
+
+    def test_missing_key(self):
+        f = NativeASF(get_test_file("silence-1.wma"))
+        assert "WM/AlbumTitle" not in f
+        with pytest.raises(KeyError):
+            f["WM/AlbumTitle"]
+
+    def test_file_dispatch(self):
+        f = mutagen_rs.File(get_test_file("silence-1.wma"))
+        assert f.info.sample_rate == 48000
+        assert abs(f.info.length - 3.7) < 0.05
+        assert f["Title"] == ["test"]
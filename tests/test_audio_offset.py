@@ -0,0 +1,62 @@
+"""audio_offset tests: offsets computed by hand from the file bytes."""
+import os
+import struct
+
+import pytest
+
+from mutagen_rs.mutagen_rs import MP3, FLAC, OggVorbis, MP4
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def read_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    with open(path, "rb") as f:
+        return path, f.read()
+
+
+def test_mp3():
+    path, data = read_test_file("silence-44-s.mp3")
+    size = (data[6] << 21) | (data[7] << 14) | (data[8] << 7) | data[9]
+    offset = 10 + size
+    while not (data[offset] == 0xFF and data[offset + 1] & 0xE0 == 0xE0):
+        offset += 1
+    assert MP3(path).audio_offset == offset
+
+
+def test_flac():
+    path, data = read_test_file("silence-44-s.flac")
+    offset = 4
+    while True:
+        header = data[offset]
+        offset += 4 + int.from_bytes(data[offset + 1:offset + 4], "big")
+        if header & 0x80:
+            break
+    assert FLAC(path).audio_offset == offset
+    assert data[offset:offset + 2] == b"\xff\xf8"
+
+
+def test_ogg():
+    path, data = read_test_file("multipage-setup.ogg")
+    # Skip pages until three header packets have been completed
+    offset, packets = 0, 0
+    while packets < 3:
+        num_seg = data[offset + 26]
+        lacing = data[offset + 27:offset + 27 + num_seg]
+        packets += sum(1 for s in lacing if s < 255)
+        offset += 27 + num_seg + sum(lacing)
+    assert OggVorbis(path).audio_offset == offset
+    assert data[offset:offset + 4] == b"OggS"
+
+
+def test_mp4():
+    path, data = read_test_file("has-tags.m4a")
+    offset = 0
+    while True:
+        size, name = struct.unpack(">I4s", data[offset:offset + 8])
+        if name == b"mdat":
+            break
+        offset += size
+    assert MP4(path).audio_offset == offset + 8
@@ -0,0 +1,67 @@
+"""File data cache tests for mutagen_rs."""
+import os
+import shutil
+
+import pytest
+
+import mutagen_rs
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    return path
+
+
+@pytest.fixture
+def small_cache():
+    _, capacity = mutagen_rs.file_cache_info()
+    mutagen_rs.clear_file_cache()
+    mutagen_rs.set_file_cache_capacity(2)
+    yield
+    mutagen_rs.set_file_cache_capacity(capacity)
+
+
+def flac_copies(tmp_path, n):
+    src = get_test_file("silence-44-s.flac")
+    paths = []
+    for i in range(n):
+        dst = tmp_path / f"copy{i}.flac"
+        shutil.copy(src, dst)
+        paths.append(str(dst))
+    return paths
+
+
+class TestFileCache:
+
+    def test_evicts_past_capacity(self, tmp_path, small_cache):
+        for path in flac_copies(tmp_path, 3):
+            assert mutagen_rs.detect_format(path) == "flac"
+        assert mutagen_rs.file_cache_info() == (2, 2)
+
+    def test_clear_file_cache(self, tmp_path, small_cache):
+        path = flac_copies(tmp_path, 1)[0]
+        mutagen_rs.detect_format(path)
+        assert mutagen_rs.file_cache_info()[0] == 1
+        mutagen_rs.clear_file_cache()
+        assert mutagen_rs.file_cache_info()[0] == 0
+
+    def test_shrinking_capacity_evicts(self, tmp_path, small_cache):
+        for path in flac_copies(tmp_path, 2):
+            mutagen_rs.detect_format(path)
+        mutagen_rs.set_file_cache_capacity(1)
+        assert mutagen_rs.file_cache_info() == (1, 1)
+
+    def test_save_invalidates(self, tmp_path):
+        dst = tmp_path / "save.mp3"
+        shutil.copy(get_test_file("silence-44-s.mp3"), dst)
+        path = str(dst)
+        mutagen_rs.detect_format(path)
+
+        tags = mutagen_rs.ID3(path)
+        tags["TIT2"] = "Fresh"
+        tags.save()
+        assert mutagen_rs.mutagen_rs.file_open(path)["TIT2"] == "Fresh"
@@ -0,0 +1,120 @@
+"""FLAC stream and comment tests for mutagen_rs (no reference mutagen needed)."""
+import os
+import shutil
+
+import pytest
+
+from mutagen_rs.mutagen_rs import FLAC
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    return path
+
+
+class TestVendor:
+
+    def test_libflac_vendor(self):
+        f = FLAC(get_test_file("silence-44-s.flac"))
+        assert f.vendor.startswith("reference libFLAC")
+        assert f.tags.vendor == f.vendor
+
+    def test_no_comment_block(self):
+        f = FLAC(get_test_file("no-tags.flac"))
+        assert f.vendor is None
+
+
+def syncsafe(n):
+    return bytes([(n >> 21) & 0x7F, (n >> 14) & 0x7F, (n >> 7) & 0x7F, n & 0x7F])
+
+
+class TestLeadingID3:
+
+    @pytest.fixture
+    def prefixed(self, tmp_path):
+        body = b"\x03ID3 Title"
+        frames = b"TIT2" + syncsafe(len(body)) + b"\x00\x00" + body
+        tag = b"ID3\x04\x00\x00" + syncsafe(len(frames) + 64) + frames + b"\x00" * 64
+        with open(get_test_file("silence-44-s.flac"), "rb") as f:
+            flac = f.read()
+        path = tmp_path / "id3.flac"
+        path.write_bytes(tag + flac)
+        return str(path), tag
+
+    def test_both_tags_readable(self, prefixed):
+        path, _ = prefixed
+        f = FLAC(path)
+        assert f["TITLE"] == FLAC(get_test_file("silence-44-s.flac"))["TITLE"]
+        assert f.id3_tags()["TIT2"] == "ID3 Title"
+
+    def test_id3_region_survives_save(self, prefixed):
+        path, tag = prefixed
+        FLAC(path).save()
+        with open(path, "rb") as f:
+            data = f.read()
+        assert data[:len(tag)] == tag
+        assert data[len(tag):len(tag) + 4] == b"fLaC"
+        f = FLAC(path)
+        assert f.id3_tags()["TIT2"] == "ID3 Title"
+        assert "TITLE" in f
+
+    def test_plain_file(self):
+        assert FLAC(get_test_file("silence-44-s.flac")).id3_tags() is None
+
+
+class TestLossless:
+
+    def test_flac_info(self):
+        info = FLAC(get_test_file("silence-44-s.flac")).info
+        assert info.lossless is True
+        assert info.bits_per_sample == 16
+
+
+def with_empty_comment_block(data, body):
+    """Swap the VorbisComment block body for `body`."""
+    out, pos = bytearray(b"fLaC"), 4
+    while True:
+        header = data[pos]
+        size = int.from_bytes(data[pos + 1:pos + 4], "big")
+        block = body if header & 0x7F == 4 else data[pos + 4:pos + 4 + size]
+        out += bytes([header]) + len(block).to_bytes(3, "big") + block
+        pos += 4 + size
+        if header & 0x80:
+            return bytes(out) + data[pos:]
+
+
+class TestPreserveMtime:
+
+    def test_save_keeps_mtime(self, tmp_path):
+        path = str(tmp_path / "mtime.flac")
+        shutil.copy(get_test_file("silence-44-s.flac"), path)
+        os.utime(path, (1_000_000_000, 1_000_000_000))
+        f = FLAC(path)
+        f.save(preserve_mtime=True)
+        assert os.stat(path).st_mtime == 1_000_000_000
+
+        f.save()
+        assert os.stat(path).st_mtime != 1_000_000_000
+
+
+class TestEmptyComment:
+
+    @pytest.mark.parametrize("body", [b"", b"\x00" * 4, b"\x00" * 8])
+    def test_empty_block(self, tmp_path, body):
+        with open(get_test_file("silence-44-s.flac"), "rb") as f:
+            data = f.read()
+        path = tmp_path / "empty-vc.flac"
+        path.write_bytes(with_empty_comment_block(data, body))
+        f = FLAC(str(path))
+        assert f.keys() == []
+        assert f.vendor == ""
+
+        f.save()
+        f = FLAC(str(path))
+        assert f.keys() == []
+        assert f.vendor == ""
+        assert f.info.total_samples == FLAC(get_test_file("silence-44-s.flac")).info.total_samples
@@ -0,0 +1,80 @@
+"""FLAC picture tests: dimensions are sniffed from the image data."""
+import os
+import shutil
+import struct
+import zlib
+
+import pytest
+
+from mutagen_rs.mutagen_rs import FLAC
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+@pytest.fixture
+def flac_copy(tmp_path):
+    src = os.path.join(TEST_DIR, "silence-44-s.flac")
+    if not os.path.exists(src):
+        pytest.skip(f"Test file not found: {src}")
+    dst = tmp_path / "picture.flac"
+    shutil.copy(src, dst)
+    return str(dst)
+
+
+def png(width, height):
+    def chunk(kind, body):
+        return struct.pack(">I", len(body)) + kind + body + struct.pack(">I", zlib.crc32(kind + body))
+    rows = b"".join(b"\x00" + b"\x80" * (width * 3) for _ in range(height))
+    return (b"\x89PNG\r\n\x1a\n"
+            + chunk(b"IHDR", struct.pack(">IIBBBBB", width, height, 8, 2, 0, 0, 0))
+            + chunk(b"IDAT", zlib.compress(rows))
+            + chunk(b"IEND", b""))
+
+
+def jpeg(width, height):
+    app0 = b"\xff\xe0" + struct.pack(">H", 16) + b"JFIF\x00\x01\x01\x00\x00\x01\x00\x01\x00\x00"
+    sof0 = b"\xff\xc0" + struct.pack(">HBHHB", 17, 8, height, width, 3) + b"\x01\x22\x00\x02\x11\x01\x03\x11\x01"
+    return b"\xff\xd8" + app0 + sof0 + b"\xff\xd9"
+
+
+def pictures(path):
+    """(type, mime, desc, width, height, depth, colors) of each Picture block."""
+    with open(path, "rb") as f:
+        data = f.read()
+    out, pos = [], 4
+    while True:
+        header = data[pos]
+        size = int.from_bytes(data[pos + 1:pos + 4], "big")
+        if header & 0x7F == 6:
+            body = data[pos + 4:pos + 4 + size]
+            pic_type, mime_len = struct.unpack(">II", body[:8])
+            mime = body[8:8 + mime_len].decode()
+            p = 8 + mime_len
+            desc_len, = struct.unpack(">I", body[p:p + 4])
+            desc = body[p + 4:p + 4 + desc_len].decode()
+            p += 4 + desc_len
+            out.append((pic_type, mime, desc) + struct.unpack(">IIII", body[p:p + 16]))
+        pos += 4 + size
+        if header & 0x80:
+            return out
+
+
+class TestAddPicture:
+
+    def test_png(self, flac_copy):
+        image = png(200, 200)
+        f = FLAC(flac_copy)
+        f.add_picture(image, desc="cover")
+        f.save()
+        # The fixture's own 1x1 picture stays first
+        assert pictures(flac_copy)[1:] == [(3, "image/png", "cover", 200, 200, 24, 0)]
+
+    def test_jpeg(self, flac_copy):
+        f = FLAC(flac_copy)
+        f.add_picture(jpeg(640, 480), type=4)
+        f.save()
+        assert pictures(flac_copy)[-1] == (4, "image/jpeg", "", 640, 480, 24, 0)
+
+    def test_unrecognized(self, flac_copy):
+        with pytest.raises(ValueError):
+            FLAC(flac_copy).add_picture(b"GIF89a\x01\x00\x01\x00")
@@ -0,0 +1,152 @@
+"""FLAC save tests: metadata block order and comment deduplication."""
+import os
+import shutil
+import struct
+
+import pytest
+
+from mutagen_rs.mutagen_rs import FLAC
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+STREAMINFO, PADDING, SEEKTABLE, VORBIS_COMMENT, CUESHEET, PICTURE = 0, 1, 3, 4, 5, 6
+
+
+@pytest.fixture
+def flac_copy(tmp_path):
+    src = os.path.join(TEST_DIR, "silence-44-s.flac")
+    if not os.path.exists(src):
+        pytest.skip(f"Test file not found: {src}")
+    dst = tmp_path / "order.flac"
+    shutil.copy(src, dst)
+    return str(dst)
+
+
+def block_types(path):
+    with open(path, "rb") as f:
+        data = f.read()
+    out, pos = [], 4
+    while True:
+        header = data[pos]
+        out.append(header & 0x7F)
+        pos += 4 + int.from_bytes(data[pos + 1:pos + 4], "big")
+        if header & 0x80:
+            return out
+
+
+class TestBlockOrder:
+
+    def test_fixture_layout(self, flac_copy):
+        assert block_types(flac_copy) == [STREAMINFO, SEEKTABLE, VORBIS_COMMENT, CUESHEET, PICTURE, PADDING]
+
+    def test_default_is_canonical(self, flac_copy):
+        FLAC(flac_copy).save()
+        assert block_types(flac_copy) == [STREAMINFO, VORBIS_COMMENT, PICTURE, SEEKTABLE, CUESHEET, PADDING]
+
+    def test_preserve_order_keeps_seektable_first(self, flac_copy):
+        FLAC(flac_copy).save(preserve_order=True)
+        assert block_types(flac_copy) == [STREAMINFO, SEEKTABLE, VORBIS_COMMENT, CUESHEET, PICTURE, PADDING]
+        f = FLAC(flac_copy)
+        assert f["TITLE"] == FLAC(os.path.join(TEST_DIR, "silence-44-s.flac"))["TITLE"]
+
+    def test_preserve_order_added_picture_follows_existing(self, flac_copy):
+        with open(os.path.join(TEST_DIR, "image.jpg"), "rb") as img:
+            image = img.read()
+        f = FLAC(flac_copy)
+        f.add_picture(image)
+        f.save(preserve_order=True)
+        assert block_types(flac_copy) == [STREAMINFO, SEEKTABLE, VORBIS_COMMENT, CUESHEET, PICTURE, PICTURE, PADDING]
+
+
+def with_comments(path, comments):
+    """Rewrite the VorbisComment block of `path` to hold `comments`."""
+    with open(path, "rb") as f:
+        data = f.read()
+    body = struct.pack("<I", 6) + b"vendor" + struct.pack("<I", len(comments))
+    for c in comments:
+        body += struct.pack("<I", len(c)) + c
+    out, pos = bytearray(b"fLaC"), 4
+    while True:
+        header = data[pos]
+        size = int.from_bytes(data[pos + 1:pos + 4], "big")
+        block = body if header & 0x7F == VORBIS_COMMENT else data[pos + 4:pos + 4 + size]
+        out += bytes([header]) + len(block).to_bytes(3, "big") + block
+        pos += 4 + size
+        if header & 0x80:
+            break
+    with open(path, "wb") as f:
+        f.write(bytes(out) + data[pos:])
+
+
+class TestDedupe:
+
+    def test_duplicates_written_once(self, flac_copy):
+        with_comments(flac_copy, [b"ARTIST=X", b"TITLE=T", b"ARTIST=X", b"ARTIST=Y"])
+        assert FLAC(flac_copy)["ARTIST"] == ["X", "X", "Y"]
+        FLAC(flac_copy).save(dedupe=True)
+        f = FLAC(flac_copy)
+        assert f["ARTIST"] == ["X", "Y"]
+        assert f["TITLE"] == ["T"]
+
+    def test_default_keeps_duplicates(self, flac_copy):
+        with_comments(flac_copy, [b"ARTIST=X", b"ARTIST=X"])
+        FLAC(flac_copy).save()
+        assert FLAC(flac_copy)["ARTIST"] == ["X", "X"]
+
+
+class TestVerify:
+
+    def test_verify_passes(self, flac_copy):
+        with_comments(flac_copy, [b"TITLE=Verified", b"ARTIST=X", b"ARTIST=X"])
+        FLAC(flac_copy).save(verify=True)
+        assert FLAC(flac_copy)["TITLE"] == ["Verified"]
+
+    def test_verify_with_options(self, flac_copy):
+        with open(os.path.join(TEST_DIR, "image.jpg"), "rb") as img:
+            image = img.read()
+        f = FLAC(flac_copy)
+        f.add_picture(image)
+        f.save(preserve_order=True, dedupe=True, verify=True)
+        assert block_types(flac_copy).count(PICTURE) == 2
+
+
+
+def split_audio(path):
+    """(metadata length, audio bytes) of `path`."""
+    with open(path, "rb") as f:
+        data = f.read()
+    pos = 4
+    while True:
+        header = data[pos]
+        pos += 4 + int.from_bytes(data[pos + 1:pos + 4], "big")
+        if header & 0x80:
+            return pos, data[pos:]
+
+
+class TestPaddingReuse:
+
+    def test_fits_in_padding(self, flac_copy):
+        before = split_audio(flac_copy)
+        with open(os.path.join(TEST_DIR, "image.jpg"), "rb") as img:
+            image = img.read()
+        f = FLAC(flac_copy)
+        f.add_picture(image)
+        assert f.save(padding_reuse=True) is True
+        assert split_audio(flac_copy) == before
+        assert block_types(flac_copy) == [STREAMINFO, VORBIS_COMMENT, PICTURE, PICTURE, SEEKTABLE, CUESHEET, PADDING]
+        assert FLAC(flac_copy).save(verify=True, padding_reuse=True) is True
+
+    def test_grows_beyond_padding(self, flac_copy):
+        length, audio = split_audio(flac_copy)
+        with open(os.path.join(TEST_DIR, "image.jpg"), "rb") as img:
+            image = img.read() + b"\x00" * 4096
+        f = FLAC(flac_copy)
+        f.add_picture(image)
+        assert f.save(padding_reuse=True) is False
+        new_length, new_audio = split_audio(flac_copy)
+        assert new_length > length
+        assert new_audio == audio
+        assert block_types(flac_copy).count(PICTURE) == 2
+
+    def test_default_returns_none(self, flac_copy):
+        assert FLAC(flac_copy).save() is None
@@ -0,0 +1,71 @@
+"""from_bytes: parsing in-memory buffers matches parsing the file path."""
+import os
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import MP3, FLAC, OggVorbis, MP4, WavPack, ASF, MonkeysAudio
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+CASES = [
+    (MP3, "silence-44-s.mp3"),
+    (FLAC, "silence-44-s.flac"),
+    (OggVorbis, "multipage-setup.ogg"),
+    (MP4, "has-tags.m4a"),
+    (WavPack, "silence-44-s.wv"),
+    (ASF, "issue_29.wma"),
+    (MonkeysAudio, "mac-399.ape"),
+]
+
+
+def read_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    with open(path, "rb") as f:
+        return path, f.read()
+
+
+@pytest.mark.parametrize("cls,name", CASES)
+def test_matches_path(cls, name):
+    path, data = read_test_file(name)
+    from_path = cls(path)
+    from_bytes = cls.from_bytes(data)
+    # None of the Monkey's Audio samples carry tags
+    assert from_bytes.keys() or cls is MonkeysAudio
+    assert sorted(from_bytes.keys()) == sorted(from_path.keys())
+    for key in from_path.keys():
+        assert from_bytes[key] == from_path[key], key
+    assert from_bytes.info.length == from_path.info.length
+    assert from_bytes.info.sample_rate == from_path.info.sample_rate
+
+
+@pytest.mark.parametrize("wrap", [bytearray, memoryview])
+def test_buffer_types(wrap):
+    path, data = read_test_file("silence-44-s.flac")
+    f = FLAC.from_bytes(wrap(data))
+    assert f["TITLE"] == FLAC(path)["TITLE"]
+
+
+def test_non_contiguous_buffer():
+    path, data = read_test_file("silence-44-s.mp3")
+    doubled = bytes(b for byte in data for b in (byte, 0))
+    view = memoryview(doubled)[::2]
+    assert not view.c_contiguous
+    assert MP3.from_bytes(view)["TIT2"] == MP3(path)["TIT2"]
+
+
+def test_rejects_str():
+    with pytest.raises(TypeError):
+        FLAC.from_bytes("fLaC")
+
+
+def test_invalid_data():
+    with pytest.raises(Exception):
+        FLAC.from_bytes(b"not a flac file")
+
+
+def test_package_factories():
+    path, data = read_test_file("has-tags.m4a")
+    assert mutagen_rs.MP4.from_bytes(data).keys() == MP4(path).keys()
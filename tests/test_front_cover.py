@@ -0,0 +1,99 @@
+"""front_cover: the front cover image of each tagged format, None otherwise."""
+import base64
+import os
+import struct
+
+import pytest
+
+import mutagen_rs
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+PNG = b"\x89PNG\r\n\x1a\n" + b"\x00" * 24
+
+
+def read_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    with open(path, "rb") as f:
+        return path, f.read()
+
+
+def flac_picture(pic_type, data, mime=b"image/png"):
+    """Body of a FLAC Picture block (also the METADATA_BLOCK_PICTURE payload)."""
+    return (struct.pack(">II", pic_type, len(mime)) + mime + struct.pack(">I", 0)
+            + struct.pack(">IIIII", 1, 1, 24, 0, len(data)) + data)
+
+
+def ogg_crc(data):
+    crc = 0
+    for byte in data:
+        crc ^= byte << 24
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x04C11DB7) if crc & 0x80000000 else (crc << 1)
+            crc &= 0xFFFFFFFF
+    return crc
+
+
+def ogg_page(serial, seq, granule, packet, header_type=0):
+    lacing = [255] * (len(packet) // 255) + [len(packet) % 255]
+    header = struct.pack("<4sBBqIII", b"OggS", 0, header_type, granule, serial, seq, 0)
+    page = header + bytes([len(lacing)]) + bytes(lacing) + packet
+    return page[:22] + struct.pack("<I", ogg_crc(page)) + page[26:]
+
+
+def test_mp3(tmp_path):
+    _, data = read_test_file("silence-44-s.mp3")
+    audio = data[10 + ((data[6] << 21) | (data[7] << 14) | (data[8] << 7) | data[9]):]
+
+    def apic(pic_type, desc, image):
+        body = b"\x00image/png\x00" + bytes([pic_type]) + desc + b"\x00" + image
+        return b"APIC" + struct.pack(">IH", len(body), 0) + body
+
+    frames = apic(4, b"back", b"BACK") + apic(3, b"front", PNG)
+    size = len(frames)
+    header = b"ID3\x03\x00\x00" + bytes([(size >> 21) & 0x7F, (size >> 14) & 0x7F,
+                                         (size >> 7) & 0x7F, size & 0x7F])
+    path = tmp_path / "cover.mp3"
+    path.write_bytes(header + frames + audio)
+    assert mutagen_rs.front_cover(str(path)) == ("image/png", PNG)
+
+
+def test_flac(tmp_path):
+    _, data = read_test_file("silence-44-s.flac")
+    # Insert two Picture blocks after STREAMINFO, which is not the last block
+    end = 8 + int.from_bytes(data[5:8], "big")
+    blocks = b""
+    for body in (flac_picture(0, b"OTHER"), flac_picture(3, PNG)):
+        blocks += b"\x06" + len(body).to_bytes(3, "big") + body
+    path = tmp_path / "cover.flac"
+    path.write_bytes(data[:end] + blocks + data[end:])
+    assert mutagen_rs.front_cover(str(path)) == ("image/png", PNG)
+
+
+def test_mp4():
+    path, _ = read_test_file("covr-with-name.m4a")
+    mime, image = mutagen_rs.front_cover(path)
+    assert mime in ("image/jpeg", "image/png")
+    assert image
+
+
+def test_ogg(tmp_path):
+    serial = 0x1234
+    ident = b"\x01vorbis" + struct.pack("<IBIiii", 0, 2, 44100, 0, 128000, 0) + b"\xb8\x01"
+    picture = b"METADATA_BLOCK_PICTURE=" + base64.b64encode(flac_picture(3, PNG))
+    vendor = b"mutagen_rs"
+    comment = (b"\x03vorbis" + struct.pack("<I", len(vendor)) + vendor
+               + struct.pack("<II", 1, len(picture)) + picture + b"\x01")
+    path = tmp_path / "cover.ogg"
+    path.write_bytes(ogg_page(serial, 0, 0, ident, header_type=0x02)
+                     + ogg_page(serial, 1, 0, comment)
+                     + ogg_page(serial, 2, 44100, b"\x00" * 200, header_type=0x04))
+    assert mutagen_rs.front_cover(str(path)) == ("image/png", PNG)
+
+
+@pytest.mark.parametrize("name", ["no-tags.m4a", "no-tags.flac", "silence-44-s.mp3"])
+def test_untagged(name):
+    path, _ = read_test_file(name)
+    assert mutagen_rs.front_cover(path) is None
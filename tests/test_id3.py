@@ -0,0 +1,1193 @@
+"""ID3v2 reader/writer tests for mutagen_rs (no reference mutagen needed)."""
+import os
+import shutil
+import struct
+import warnings
+import zlib
+
+import pytest
+
+import mutagen_rs
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    return os.path.join(TEST_DIR, name)
+
+
+def syncsafe(n):
+    return bytes([(n >> 21) & 0x7F, (n >> 14) & 0x7F, (n >> 7) & 0x7F, n & 0x7F])
+
+
+def v23_frame(frame_id, payload):
+    return frame_id.encode() + struct.pack(">IH", len(payload), 0) + payload
+
+
+def v23_text(frame_id, text):
+    return v23_frame(frame_id, b"\x00" + text.encode("latin-1"))
+
+
+def make_v23_tag(frames, padding=64, crc=None):
+    """Build a v2.3 tag with an extended header; crc=None computes the right one."""
+    if crc is None:
+        crc = zlib.crc32(frames) & 0xFFFFFFFF
+    ext = struct.pack(">IHII", 10, 0x8000, padding, crc)
+    body = ext + frames + b"\x00" * padding
+    return b"ID3\x03\x00\x40" + syncsafe(len(body)) + body
+
+
+@pytest.fixture
+def mp3_copy(tmp_path):
+    src = get_test_file("silence-44-s.mp3")
+    if not os.path.exists(src):
+        pytest.skip(f"Test file not found: {src}")
+    dst = tmp_path / "test.mp3"
+    shutil.copy(src, dst)
+    return str(dst)
+
+
+def audio_of(path):
+    with open(path, "rb") as f:
+        data = f.read()
+    size = (data[6] << 21) | (data[7] << 14) | (data[8] << 7) | data[9]
+    return data[10 + size:]
+
+
+class TestExtendedHeader:
+
+    def write(self, tmp_path, tag):
+        path = tmp_path / "ext.id3"
+        path.write_bytes(tag + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_crc_validates(self, tmp_path):
+        frames = v23_text("TIT2", "Title") + v23_text("TPE1", "Artist")
+        path = self.write(tmp_path, make_v23_tag(frames))
+        tags = mutagen_rs.ID3(path, strict=True)
+        ext = tags.extended_header
+        assert ext["size"] == 10
+        assert ext["padding"] == 64
+        assert ext["crc"] == zlib.crc32(frames) & 0xFFFFFFFF
+        assert tags.crc_valid is True
+        assert tags["TIT2"] == "Title"
+
+    def test_crc_mismatch_strict(self, tmp_path):
+        frames = v23_text("TIT2", "Title")
+        path = self.write(tmp_path, make_v23_tag(frames, crc=0xDEADBEEF))
+        tags = mutagen_rs.ID3(path)
+        assert tags.crc_valid is False
+        assert tags["TIT2"] == "Title"
+        with pytest.raises(mutagen_rs.ID3Error):
+            mutagen_rs.ID3(path, strict=True)
+
+    def test_no_extended_header(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags.extended_header is None
+        assert tags.crc_valid is None
+
+    def test_save_with_crc(self, mp3_copy):
+        audio = audio_of(mp3_copy)
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.save(crc=True)
+        reread = mutagen_rs.ID3(mp3_copy, strict=True)
+        assert reread.extended_header["crc"] is not None
+        assert reread.crc_valid is True
+        assert sorted(reread.keys()) == sorted(tags.keys())
+        assert audio_of(mp3_copy) == audio
+
+
+class TestSelectiveDelete:
+
+    @pytest.fixture
+    def combined(self, tmp_path):
+        src = get_test_file("id3v1v2-combined.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        dst = tmp_path / "combined.mp3"
+        shutil.copy(src, dst)
+        return str(dst)
+
+    def read(self, path):
+        with open(path, "rb") as f:
+            return f.read()
+
+    def test_delete_v2_keeps_v1(self, combined):
+        mutagen_rs.ID3(combined).delete(v1=False, v2=True)
+        data = self.read(combined)
+        assert not data.startswith(b"ID3")
+        assert data[-128:-125] == b"TAG"
+
+    def test_delete_v1_keeps_v2(self, combined):
+        before = self.read(combined)
+        mutagen_rs.ID3(combined).delete(v1=True, v2=False)
+        data = self.read(combined)
+        assert data.startswith(b"ID3")
+        assert data == before[:-128]
+
+    def test_delete_both(self, combined):
+        mutagen_rs.ID3(combined).delete()
+        data = self.read(combined)
+        assert not data.startswith(b"ID3")
+        assert data[-128:-125] != b"TAG"
+
+    def test_mp3_delete_flags(self, combined):
+        from mutagen_rs.mutagen_rs import MP3 as NativeMP3
+        NativeMP3(combined).delete(v1=False)
+        data = self.read(combined)
+        assert not data.startswith(b"ID3")
+        assert data[-128:-125] == b"TAG"
+
+    @pytest.fixture
+    def with_apev2(self, mp3_copy):
+        """ID3v2 + audio + APEv2 + ID3v1; returns the path and the audio."""
+        tags = mutagen_rs.APEv2()
+        tags["Title"] = "APE Title"
+        tags.save(mp3_copy)
+        data = self.read(mp3_copy)
+        # The APEv2 header is the first preamble; audio ends right before it
+        return mp3_copy, audio_of(mp3_copy)[:data.index(b"APETAGEX") - len(data)]
+
+    def test_apev2_kept_by_default(self, with_apev2):
+        path, _ = with_apev2
+        mutagen_rs.ID3(path).delete(v1=False)
+        assert mutagen_rs.APEv2(path)["Title"] == "APE Title"
+
+    def test_delete_apev2_only(self, with_apev2):
+        path, audio = with_apev2
+        mutagen_rs.ID3(path).delete(v1=False, v2=False, apev2=True)
+        data = self.read(path)
+        assert data.startswith(b"ID3")
+        assert data[-128:-125] == b"TAG"
+        assert b"APETAGEX" not in data
+        assert audio_of(path)[:-128] == audio
+
+    def test_delete_all(self, with_apev2):
+        path, audio = with_apev2
+        from mutagen_rs.mutagen_rs import MP3 as NativeMP3
+        NativeMP3(path).delete(apev2=True)
+        assert self.read(path) == audio
+
+
+class TestPreserveMtime:
+
+    @pytest.fixture
+    def mp3_copy(self, tmp_path):
+        src = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        dst = tmp_path / "mtime.mp3"
+        shutil.copy(src, dst)
+        os.utime(dst, (1_000_000_000, 1_000_000_000))
+        return str(dst)
+
+    def test_id3_save(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "Kept mtime"
+        tags.save(preserve_mtime=True)
+        assert os.stat(mp3_copy).st_mtime == 1_000_000_000
+        assert mutagen_rs.ID3(mp3_copy)["TIT2"] == "Kept mtime"
+
+    def test_mp3_save(self, mp3_copy):
+        f = mutagen_rs.mutagen_rs.MP3(mp3_copy)
+        f.save(preserve_mtime=True)
+        assert os.stat(mp3_copy).st_mtime == 1_000_000_000
+
+    def test_default_updates_mtime(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "New mtime"
+        tags.save()
+        assert os.stat(mp3_copy).st_mtime != 1_000_000_000
+
+
+class TestPaddingReuse:
+
+    def test_in_place_then_rewrite(self, tmp_path):
+        src = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        path = str(tmp_path / "padded.mp3")
+        shutil.copy(src, path)
+        mutagen_rs.ID3(path).save()
+        size = os.path.getsize(path)
+
+        tags = mutagen_rs.ID3(path)
+        tags["TIT2"] = "Same size"
+        assert tags.save(padding_reuse=True) is True
+        assert os.path.getsize(path) == size
+        assert mutagen_rs.ID3(path)["TIT2"] == "Same size"
+
+        tags["TIT2"] = "x" * 4096
+        assert tags.save(padding_reuse=True) is False
+        assert os.path.getsize(path) > size
+        assert tags.save() is None
+
+    def test_dry_run_plans_in_place_write(self, tmp_path):
+        src = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        path = str(tmp_path / "padded.mp3")
+        shutil.copy(src, path)
+        mutagen_rs.ID3(path).save(padding=100)
+        with open(path, "rb") as f:
+            before = f.read()
+
+        tags = mutagen_rs.ID3(path)
+        tags["TIT2"] = "Same size"
+        plan = tags.save(dry_run=True, padding_reuse=True, padding=4000)
+        assert plan["full_rewrite"] is False
+        assert len(plan["new_tag"]) == plan["old_tag_size"]
+        assert tags.save(dry_run=True, padding=4000)["full_rewrite"] is True
+        with open(path, "rb") as f:
+            assert f.read() == before
+
+        tags.save(padding_reuse=True, padding=4000)
+        with open(path, "rb") as f:
+            assert f.read(len(plan["new_tag"])) == plan["new_tag"]
+
+        tags["TIT2"] = "x" * 4096
+        plan = tags.save(dry_run=True, padding_reuse=True, padding=0)
+        assert plan["full_rewrite"] is True
+        assert plan["new_tag"][-1:] != b"\x00"
+
+
+class TestExportFrames:
+
+    def test_copy_between_tags(self, tmp_path):
+        src = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        source = mutagen_rs.ID3(src)
+        data = source.export_frames()
+        assert isinstance(data, bytes) and not data.startswith(b"ID3")
+
+        dst = tmp_path / "copy.mp3"
+        shutil.copy(get_test_file("no-tags.mp3"), dst)
+        target = mutagen_rs.ID3(str(dst))
+        target.import_frames(data, version=source.version[0])
+        assert sorted(target.keys()) == sorted(source.keys())
+        for key in source.keys():
+            assert target[key] == source[key]
+
+        target.save()
+        saved = mutagen_rs.ID3(str(dst))
+        assert saved["TIT2"] == source["TIT2"]
+
+
+class TestFrameOrder:
+
+    def write(self, tmp_path):
+        apic = v23_frame("APIC", b"\x00image/png\x00\x03\x00" + b"\x89PNG" * 16)
+        frames = apic + v23_text("TXXX", "") + v23_text("TIT2", "Title")
+        body = frames + b"\x00" * 16
+        path = tmp_path / "order.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def read(self, path):
+        with open(path, "rb") as f:
+            return f.read()
+
+    def test_canonical_order_on_save(self, tmp_path):
+        path = self.write(tmp_path)
+        data = self.read(path)
+        assert data.index(b"APIC") < data.index(b"TIT2")
+        mutagen_rs.ID3(path).save()
+        data = self.read(path)
+        assert data.index(b"TIT2") < data.index(b"APIC")
+        assert mutagen_rs.ID3(path)["TIT2"] == "Title"
+
+    def test_preserve_order(self, tmp_path):
+        path = self.write(tmp_path)
+        mutagen_rs.ID3(path).save(preserve_order=True)
+        data = self.read(path)
+        assert data.index(b"APIC") < data.index(b"TIT2")
+
+
+class TestCorruptTagSize:
+
+    def test_save_with_oversized_size_field(self, mp3_copy):
+        audio = audio_of(mp3_copy)
+        frames = v23_text("TIT2", "Title") + b"\x00" * 32
+        with open(mp3_copy, "wb") as f:
+            f.write(b"ID3\x03\x00\x00" + syncsafe(0x0FFFFFFF) + frames + audio)
+
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags["TIT2"] == "Title"
+        tags.save()
+        assert audio_of(mp3_copy) == audio
+        assert mutagen_rs.ID3(mp3_copy)["TIT2"] == "Title"
+
+
+class TestITunesSyncsafeV23:
+    """iTunes wrote some v2.3 tags with syncsafe frame sizes."""
+
+    def write(self, tmp_path, syncsafe_sizes):
+        title = "T" * 200
+        frames = b""
+        for frame_id, text in (("TIT2", title), ("TPE1", "Artist")):
+            payload = b"\x00" + text.encode("latin-1")
+            size = syncsafe(len(payload)) if syncsafe_sizes else struct.pack(">I", len(payload))
+            frames += frame_id.encode() + size + b"\x00\x00" + payload
+        body = frames + b"\x00" * 64
+        path = tmp_path / "itunes.mp3"
+        audio = audio_of(get_test_file("silence-44-s.mp3"))
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body + audio)
+        return str(path), title
+
+    def test_syncsafe_sizes(self, tmp_path):
+        path, title = self.write(tmp_path, syncsafe_sizes=True)
+        tags = mutagen_rs.ID3(path)
+        assert tags["TIT2"] == title
+        assert tags["TPE1"] == "Artist"
+        fast = mutagen_rs._fast_read(path)
+        assert fast["TIT2"] == title
+
+    def test_normal_sizes_unchanged(self, tmp_path):
+        path, title = self.write(tmp_path, syncsafe_sizes=False)
+        tags = mutagen_rs.ID3(path)
+        assert tags["TIT2"] == title
+        assert tags["TPE1"] == "Artist"
+
+
+class TestValidate:
+
+    def write(self, tmp_path, frames):
+        body = frames + b"\x00" * 16
+        path = tmp_path / "validate.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_clean_tag(self, tmp_path):
+        frames = (v23_text("TIT2", "Title") + v23_text("TRCK", "3/12")
+                  + v23_frame("COMM", b"\x00eng\x00hello"))
+        assert mutagen_rs.ID3(self.write(tmp_path, frames)).validate() == []
+
+    def test_bad_comment_language(self, tmp_path):
+        path = self.write(tmp_path, v23_frame("COMM", b"\x0012\x00\x00hello"))
+        warnings = mutagen_rs.ID3(path).validate()
+        assert len(warnings) == 1
+        assert warnings[0].startswith("COMM") and "language" in warnings[0]
+
+    def test_non_numeric_track(self, tmp_path):
+        path = self.write(tmp_path, v23_text("TRCK", "abc"))
+        warnings = mutagen_rs.ID3(path).validate()
+        assert warnings == ["TRCK: non-numeric value \"abc\""]
+
+    def test_bad_mime(self, tmp_path):
+        apic = v23_frame("APIC", b"\x00not a mime\x00\x03\x00data")
+        warnings = mutagen_rs.ID3(self.write(tmp_path, apic)).validate()
+        assert len(warnings) == 1 and "MIME" in warnings[0]
+
+
+class TestTextPadding:
+
+    def read_value(self, tmp_path, payload):
+        body = v23_frame("TPE1", payload) + b"\x00" * 16
+        path = tmp_path / "padded.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return mutagen_rs.ID3(str(path))["TPE1"]
+
+    def test_latin1_trailing_nulls(self, tmp_path):
+        assert self.read_value(tmp_path, b"\x00Artist\x00\x00\x00") == "Artist"
+
+    def test_utf16_trailing_nulls(self, tmp_path):
+        payload = b"\x01" + "\ufeffArtist".encode("utf-16-le") + b"\x00" * 4
+        assert self.read_value(tmp_path, payload) == "Artist"
+
+    def test_utf16_odd_padding(self, tmp_path):
+        payload = b"\x01" + "\ufeffArtist".encode("utf-16-le") + b"\x00" * 5
+        assert self.read_value(tmp_path, payload) == "Artist"
+
+    def test_utf16_multi_value_boms(self, tmp_path):
+        payload = (b"\x01" + "\ufeffOne".encode("utf-16-le") + b"\x00\x00"
+                   + "\ufeffTwo".encode("utf-16-le") + b"\x00\x00")
+        assert self.read_value(tmp_path, payload) == ["One", "Two"]
+
+
+class TestSeparatorPolicy:
+
+    def write(self, tmp_path, version, frames):
+        body = frames + b"\x00" * 16
+        path = tmp_path / "multi.mp3"
+        path.write_bytes(b"ID3" + bytes([version, 0, 0]) + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_v24_null_separated(self, tmp_path):
+        frame = b"TPE1" + syncsafe(4) + b"\x00\x00" + b"\x00A\x00B"
+        tags = mutagen_rs.ID3(self.write(tmp_path, 4, frame))
+        assert tags.text_values("TPE1") == ["A", "B"]
+
+    def test_v23_slash_kept_by_default(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write(tmp_path, 3, v23_text("TPE1", "A/B")))
+        assert tags.text_values("TPE1") == ["A/B"]
+        assert tags["TPE1"] == "A/B"
+
+    def test_v23_legacy_split(self, tmp_path):
+        frames = v23_text("TPE1", "A/B") + v23_text("TCON", "Rock; Pop")
+        tags = mutagen_rs.ID3(self.write(tmp_path, 3, frames))
+        assert tags.text_values("TPE1", legacy_separators=True) == ["A", "B"]
+        assert tags.text_values("TCON", legacy_separators=True) == ["Rock", "Pop"]
+
+    def test_title_slash_untouched(self, tmp_path):
+        path = self.write(tmp_path, 3, v23_text("TIT2", "AC/DC; Live"))
+        assert mutagen_rs.ID3(path).text_values("TIT2", legacy_separators=True) == ["AC/DC; Live"]
+        assert mutagen_rs.mutagen_rs.MP3(path).text_values("TIT2", legacy_separators=True) == ["AC/DC; Live"]
+
+    def test_missing_frame(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write(tmp_path, 3, v23_text("TIT2", "x")))
+        assert tags.text_values("TPE1") == []
+
+
+class TestRawFrames:
+
+    def test_tit2_bytes_match_disk(self, tmp_path):
+        payload = b"\x00Title\x00"
+        frames = v23_frame("TIT2", payload) + v23_text("TPE1", "Artist")
+        body = frames + b"\x00" * 16
+        path = tmp_path / "raw.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+
+        raw = dict(mutagen_rs.ID3(str(path)).raw_frames())
+        on_disk = path.read_bytes()
+        # TIT2 body follows the 10-byte tag header and 10-byte frame header
+        assert raw["TIT2"] == on_disk[20:20 + len(payload)]
+        assert raw["TPE1"] == b"\x00Artist"
+
+    def test_unknown_frame_kept_verbatim(self, tmp_path):
+        payload = bytes(range(32))
+        body = v23_frame("XYZW", payload) + b"\x00" * 16
+        path = tmp_path / "unknown.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        assert mutagen_rs.ID3(str(path)).raw_frames() == [("XYZW", payload)]
+
+
+class TestSaveDryRun:
+
+    def test_preview_matches_real_save(self, mp3_copy):
+        before = open(mp3_copy, "rb").read()
+        tags = mutagen_rs.ID3(mp3_copy)
+        plan = tags.save(dry_run=True)
+        assert open(mp3_copy, "rb").read() == before
+        assert plan["old_tag_size"] == plan["audio_start"]
+        assert plan["full_rewrite"] == (len(plan["new_tag"]) != plan["audio_start"])
+
+        tags.save()
+        after = open(mp3_copy, "rb").read()
+        assert after[:len(plan["new_tag"])] == plan["new_tag"]
+        assert after[len(plan["new_tag"]):] == before[plan["audio_start"]:]
+
+    def test_untagged_file(self, tmp_path):
+        path = tmp_path / "bare.mp3"
+        path.write_bytes(b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        plan = mutagen_rs.ID3(str(path)).save(dry_run=True)
+        assert plan["old_tag_size"] == 0
+        assert plan["audio_start"] == 0
+        assert plan["full_rewrite"]
+
+
+class TestSaveOptions:
+
+    def test_unknown_option_rejected(self, mp3_copy):
+        before = open(mp3_copy, "rb").read()
+        with pytest.raises(TypeError, match="paddding"):
+            mutagen_rs.ID3(mp3_copy).save(paddding=0)
+        with pytest.raises(TypeError):
+            mutagen_rs.ID3(mp3_copy).save(verify="yes")
+        with pytest.raises(TypeError):
+            mutagen_rs.mutagen_rs.MP3(mp3_copy).save(paddding=0)
+        assert open(mp3_copy, "rb").read() == before
+
+    def test_signature(self):
+        for save in (mutagen_rs.ID3.save, mutagen_rs.mutagen_rs.MP3.save):
+            assert "padding_reuse=False" in save.__text_signature__
+        assert "preserve_links" in mutagen_rs.ID3.save.__doc__
+
+    def test_options_combine(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "Combined"
+        plan = tags.save(dry_run=True, crc=True, padding=0, v2_version=3)
+        assert plan["new_tag"][3] == 3
+        assert plan["new_tag"][5] & 0x40
+        assert plan["new_tag"][-1:] != b"\x00"
+        assert tags.save(filename=mp3_copy, crc=True, verify=True, preserve_mtime=True) is None
+        assert mutagen_rs.ID3(mp3_copy)["TIT2"] == "Combined"
+
+
+class TestGet:
+
+    def test_missing_key_returns_default(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags.get("TXXX:missing", []) == []
+        assert tags.get("TXXX:missing") is None
+
+    def test_present_key(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags.get("TPE1") == tags["TPE1"]
+
+
+class TestEasyID3:
+
+    def test_musicbrainz_trackid_maps_to_txxx(self, mp3_copy):
+        easy = mutagen_rs.EasyID3(mp3_copy)
+        easy["musicbrainz_trackid"] = "8d3e3e2a-0000-4000-8000-000000000001"
+        assert "TXXX:MusicBrainz Release Track Id" in easy.frame_keys()
+        easy.save()
+
+        raw = mutagen_rs.ID3(mp3_copy)
+        assert raw["TXXX:MusicBrainz Release Track Id"]
+        reread = mutagen_rs.EasyID3(mp3_copy)
+        assert reread["musicbrainz_trackid"] == ["8d3e3e2a-0000-4000-8000-000000000001"]
+
+    def test_get_set_delete(self, mp3_copy):
+        easy = mutagen_rs.EasyID3(mp3_copy)
+        easy["acoustid_id"] = "abc"
+        easy["MusicBrainz_AlbumId"] = ["id1"]
+        assert easy["acoustid_id"] == ["abc"]
+        assert easy.get("musicbrainz_albumid") == ["id1"]
+        assert "TXXX:MusicBrainz Album Id" in easy.frame_keys()
+        del easy["acoustid_id"]
+        assert "acoustid_id" not in easy
+        assert easy.get("acoustid_id", []) == []
+        with pytest.raises(KeyError):
+            del easy["acoustid_id"]
+
+    def test_text_keys(self, mp3_copy):
+        easy = mutagen_rs.EasyID3(mp3_copy)
+        assert easy["artist"] == mutagen_rs.ID3(mp3_copy).text_values("TPE1")
+        assert "artist" in easy.keys()
+
+    def test_unknown_key(self, mp3_copy):
+        easy = mutagen_rs.EasyID3(mp3_copy)
+        with pytest.raises(KeyError):
+            easy["not_a_key"] = "x"
+
+    def test_itunes_sort_frames(self, mp3_copy):
+        frames = (v23_text("XSOP", "Beatles, The") + v23_text("XSOA", "White Album, The")
+                  + v23_text("XSOT", "Birthday"))
+        with open(mp3_copy, "wb") as f:
+            f.write(make_v23_tag(frames) + audio_of(get_test_file("silence-44-s.mp3")))
+        easy = mutagen_rs.EasyID3(mp3_copy)
+        assert easy["artistsort"] == ["Beatles, The"]
+        assert easy["albumsort"] == ["White Album, The"]
+        assert easy["titlesort"] == ["Birthday"]
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags["TSOP"] == "Beatles, The"
+        assert "XSOP" not in tags
+
+    def test_itunes_sort_frames_fast_read(self, mp3_copy):
+        frames = v23_text("XSOP", "Beatles, The") + v23_text("XSOT", "Birthday")
+        with open(mp3_copy, "wb") as f:
+            f.write(make_v23_tag(frames) + audio_of(get_test_file("silence-44-s.mp3")))
+        mutagen_rs.clear_cache()
+        fast = mutagen_rs._fast_read(mp3_copy)
+        assert fast["TSOP"] == "Beatles, The"
+        assert fast["TSOT"] == "Birthday"
+        assert "XSOP" not in fast
+        for f in (mutagen_rs.MP3(mp3_copy), mutagen_rs.File(mp3_copy)):
+            assert f["TSOP"] == ["Beatles, The"]
+            assert "XSOP" not in f
+        batch = mutagen_rs._rust_batch_open([mp3_copy])[mp3_copy]["tags"]
+        assert batch["TSOP"] == "Beatles, The"
+        assert "XSOP" not in batch
+
+
+class TestPadding:
+
+    def tag_of(self, path):
+        with open(path, "rb") as f:
+            data = f.read()
+        size = (data[6] << 21) | (data[7] << 14) | (data[8] << 7) | data[9]
+        return data[10:10 + size]
+
+    def test_no_padding(self, mp3_copy):
+        audio = audio_of(mp3_copy)
+        mutagen_rs.ID3(mp3_copy).save(padding=0)
+        body = self.tag_of(mp3_copy)
+        assert body[-1:] != b"\x00"
+        assert audio_of(mp3_copy) == audio
+
+    def test_fixed_padding(self, mp3_copy):
+        mutagen_rs.ID3(mp3_copy).save(padding=0)
+        unpadded = len(self.tag_of(mp3_copy))
+        mutagen_rs.ID3(mp3_copy).save(padding=4096)
+        body = self.tag_of(mp3_copy)
+        assert len(body) == unpadded + 4096
+        assert body[unpadded:] == b"\x00" * 4096
+
+    def test_mp3_save_padding(self, mp3_copy):
+        mutagen_rs.ID3(mp3_copy).save(padding=0)
+        unpadded = len(self.tag_of(mp3_copy))
+        mutagen_rs.mutagen_rs.MP3(mp3_copy).save(padding=100)
+        assert len(self.tag_of(mp3_copy)) == unpadded + 100
+
+
+class TestPodcastFrames:
+
+    FEED = "http://example.com/feed.xml"
+
+    def write_podcast(self, tmp_path):
+        frames = (v23_text("TIT2", "Episode 1")
+                  + v23_frame("PCST", struct.pack(">I", 1))
+                  + v23_text("WFED", self.FEED)
+                  + v23_text("TGID", "http://example.com/ep1")
+                  + v23_text("TDES", "An episode"))
+        body = frames + b"\x00" * 16
+        path = tmp_path / "podcast.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_feed_url(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write_podcast(tmp_path))
+        assert tags.getall("WFED") == [self.FEED]
+        assert tags["TGID"] == "http://example.com/ep1"
+        assert tags["TDES"] == "An episode"
+
+    def test_podcast_flag(self, tmp_path):
+        assert mutagen_rs.ID3(self.write_podcast(tmp_path))["PCST"] is True
+
+    def test_native_mp3(self, tmp_path):
+        f = mutagen_rs.mutagen_rs.MP3(self.write_podcast(tmp_path))
+        assert f["WFED"] == self.FEED
+        assert f["PCST"] is True
+
+    def test_round_trip(self, tmp_path):
+        path = self.write_podcast(tmp_path)
+        mutagen_rs.ID3(path).save()
+        tags = mutagen_rs.ID3(path)
+        assert tags.getall("WFED") == [self.FEED]
+        assert tags["PCST"] is True
+
+
+def v22_frame(frame_id, payload):
+    return frame_id.encode() + len(payload).to_bytes(3, "big") + payload
+
+
+class TestV22Comments:
+
+    def write_v22(self, tmp_path):
+        frames = (v22_frame("TT2", b"\x00Title\x00")
+                  + v22_frame("COM", b"\x00eng" + b"first\x00" + b"One")
+                  + v22_frame("COM", b"\x00eng" + b"second\x00" + b"Two")
+                  + v22_frame("COM", b"\x01deu" + "dritte".encode("utf-16") + b"\x00\x00"
+                              + "Drei".encode("utf-16")))
+        body = frames + b"\x00" * 16
+        path = tmp_path / "v22.mp3"
+        path.write_bytes(b"ID3\x02\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_distinct_keys(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write_v22(tmp_path))
+        keys = set(tags.keys())
+        assert {"COMM:first:eng", "COMM:second:eng", "COMM:dritte:deu"} <= keys
+        assert tags["COMM:first:eng"] == "One"
+        assert tags["COMM:second:eng"] == "Two"
+        assert tags["COMM:dritte:deu"] == "Drei"
+
+    def test_native_and_fast_paths(self, tmp_path):
+        path = self.write_v22(tmp_path)
+        f = mutagen_rs.mutagen_rs.MP3(path)
+        assert f["COMM:first:eng"] == "One"
+        assert f["COMM:second:eng"] == "Two"
+        fast = mutagen_rs._fast_read(path)
+        assert fast["COMM:first:eng"] == "One"
+        assert fast["COMM:second:eng"] == "Two"
+
+
+class TestITunesComments:
+
+    SMPB = (" 00000000 00000840 000001C4 0000000000A30D7C 00000000 00000000"
+            " 00000000 00000000 00000000 00000000 00000000 00000000")
+
+    def write_itunes(self, tmp_path):
+        frames = (v23_text("TIT2", "Gapless")
+                  + v23_frame("COMM", b"\x00eng" + b"iTunSMPB\x00" + self.SMPB.encode()))
+        body = frames + b"\x00" * 16
+        path = tmp_path / "itunes.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_gapless_info(self, tmp_path):
+        info = mutagen_rs.ID3(self.write_itunes(tmp_path)).gapless_info()
+        assert info == {"encoder_delay": 0x840, "padding": 0x1C4, "original_samples": 0xA30D7C}
+
+    def test_normalization(self):
+        path = get_test_file("id3v22-test.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        tags = mutagen_rs.ID3(path)
+        assert tags.normalization() == [0x44E, 0x61, 0x9B67, 0x44C3, 0x22478,
+                                        0x22182, 0x7FCC, 0x7E5C, 0x2245E, 0x2214E]
+        assert tags.gapless_info() is None
+
+    def test_absent(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags.gapless_info() is None
+        assert tags.normalization() is None
+
+
+class TestRename:
+
+    def write_tags(self, tmp_path):
+        frames = (v23_text("TIT2", "Title")
+                  + v23_frame("COMM", b"\x00eng" + b"\x00" + b"A comment")
+                  + v23_frame("TXXX", b"\x00old\x00value")
+                  + v23_text("TPE1", "Artist"))
+        body = frames + b"\x00" * 16
+        path = tmp_path / "rename.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_comment(self, tmp_path):
+        path = self.write_tags(tmp_path)
+        tags = mutagen_rs.ID3(path)
+        tags.rename("COMM::eng", "COMM:desc2:eng")
+        assert tags.keys() == ["TIT2", "COMM:desc2:eng", "TXXX:old", "TPE1"]
+        assert tags["COMM:desc2:eng"] == "A comment"
+        tags.save()
+        reread = mutagen_rs.ID3(path)
+        assert reread["COMM:desc2:eng"] == "A comment"
+        assert "COMM::eng" not in reread
+
+    def test_user_text(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write_tags(tmp_path))
+        tags.rename("TXXX:old", "TXXX:new")
+        assert tags["TXXX:new"] == "value"
+        assert "TXXX:old" not in tags
+
+    def test_collision(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write_tags(tmp_path))
+        tags["TXXX:taken"] = "x"
+        with pytest.raises(ValueError):
+            tags.rename("TXXX:old", "TXXX:taken")
+        assert tags["TXXX:old"] == "value"
+
+    def test_invalid(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write_tags(tmp_path))
+        with pytest.raises(KeyError):
+            tags.rename("TXXX:missing", "TXXX:new")
+        with pytest.raises(ValueError):
+            tags.rename("COMM::eng", "TXXX:comment")
+        with pytest.raises(ValueError):
+            tags.rename("COMM::eng", "COMM:desc:english")
+        with pytest.raises(ValueError):
+            tags.rename("TIT2", "TIT2:x")
+
+
+class TestRepeatableURLFrames:
+
+    def write_urls(self, tmp_path):
+        frames = (v23_text("TIT2", "Title")
+                  + v23_frame("WOAR", b"http://example.com/a")
+                  + v23_frame("WOAR", b"http://example.com/b")
+                  + v23_frame("WCOM", b"http://shop.example.com/\x00")
+                  + v23_frame("WOAF", b"http://example.com/file")
+                  + v23_frame("WXXX", b"\x00label\x00http://example.com/x"))
+        body = frames + b"\x00" * 16
+        path = tmp_path / "urls.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(body)) + body
+                         + b"\xff\xfb\x90\x00" + b"\x00" * 64)
+        return str(path)
+
+    def test_keys(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write_urls(tmp_path))
+        keys = tags.keys()
+        assert "WOAR:http://example.com/a" in keys
+        assert "WOAR:http://example.com/b" in keys
+        assert "WCOM:http://shop.example.com/" in keys
+        assert "WOAF" in keys
+        assert "WXXX:label" in keys
+
+    def test_both_retained(self, tmp_path):
+        path = self.write_urls(tmp_path)
+        tags = mutagen_rs.ID3(path)
+        assert tags.getall("WOAR") == ["http://example.com/a", "http://example.com/b"]
+        tags.save()
+        reread = mutagen_rs.ID3(path)
+        assert reread.getall("WOAR") == ["http://example.com/a", "http://example.com/b"]
+
+    def test_fast_paths(self, tmp_path):
+        path = self.write_urls(tmp_path)
+        assert mutagen_rs.mutagen_rs.MP3(path)["WOAR:http://example.com/b"] == "http://example.com/b"
+        fast = mutagen_rs._fast_read(path)
+        assert fast["WOAR:http://example.com/a"] == "http://example.com/a"
+        assert fast["WOAR:http://example.com/b"] == "http://example.com/b"
+        assert fast["WOAF"] == "http://example.com/file"
+
+    def test_delete_and_pop_by_frame_id(self, tmp_path):
+        path = self.write_urls(tmp_path)
+        tags = mutagen_rs.ID3(path)
+        del tags["WOAR"]
+        assert tags.getall("WOAR") == []
+        assert "WOAF" in tags.keys()
+        tags = mutagen_rs.ID3(path)
+        assert tags.pop("WOAR") == ["http://example.com/a", "http://example.com/b"]
+        assert tags.getall("WOAR") == []
+
+
+class TestPop:
+
+    def test_pop_title(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        title = tags["TIT2"]
+        assert tags.pop("TIT2") == title
+        assert "TIT2" not in tags
+        assert "TIT2" not in tags.keys()
+
+    def test_default(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags.pop("TXXX:missing") is None
+        assert tags.pop("TXXX:missing", "fallback") == "fallback"
+
+    def test_several_frames(self, mp3_copy):
+        # The fixture carries two TPE1 frames
+        tags = mutagen_rs.ID3(mp3_copy)
+        assert tags.pop("TPE1") == ["piman", "jzig"]
+        assert tags.getall("TPE1") == []
+
+    def test_move_between_tags(self, mp3_copy):
+        src = mutagen_rs.ID3(mp3_copy)
+        dst = mutagen_rs.ID3()
+        dst["TALB"] = src.pop("TALB")
+        assert "TALB" not in src
+        assert dst["TALB"] == "Quod Libet Test Data"
+
+
+class TestTimestampFrames:
+
+    def test_tdor_parses_year(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TDOR"] = "1999-04-01"
+        ts = tags.timestamp("TDOR")
+        assert (ts["year"], ts["month"], ts["day"], ts["hour"]) == (1999, 4, 1, None)
+        assert ts["text"] == "1999-04-01"
+
+    def test_full_precision(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TDRL"] = "2001-02-03T04:05:06"
+        ts = tags.timestamp("TDRL")
+        assert (ts["hour"], ts["minute"], ts["second"]) == (4, 5, 6)
+        assert tags.timestamp("TDRC")["year"] == 2004
+        assert tags.timestamp("TIT2") is None
+
+    def test_v23_save_converts(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TDOR"] = "1999-04-01"
+        tags["TDEN"] = "2020-01-01T10:00"
+        tags["TDRL"] = "2001"
+        with warnings.catch_warnings(record=True) as caught:
+            warnings.simplefilter("always")
+            tags.save(v2_version=3)
+        assert sorted(str(w.message).split(":")[0] for w in caught) == ["TDEN", "TDRL"]
+
+        saved = mutagen_rs.ID3(mp3_copy)
+        assert saved.version[0] == 3
+        assert saved["TORY"] == "1999"
+        for key in ("TDOR", "TDEN", "TDRL"):
+            assert key not in saved
+        # The in-memory tag is left as it was
+        assert "TDOR" in tags
+
+    def test_v24_save_keeps_frames(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TDOR"] = "1999-04-01"
+        tags.save(v2_version=4)
+        saved = mutagen_rs.ID3(mp3_copy)
+        assert saved.version[0] == 4
+        assert saved.timestamp("TDOR")["month"] == 4
+
+
+class TestSetall:
+
+    def test_picture(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.setall("APIC:front", [{"data": b"\x89PNG fake", "mime": "image/png", "type": 3}])
+        tags.save()
+        pic = mutagen_rs.ID3(mp3_copy)["APIC:front"]
+        assert (pic["mime"], pic["type"], pic["desc"], pic["data"]) == ("image/png", 3, "front", b"\x89PNG fake")
+
+    def test_comments(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.setall("COMM:note:eng", ["first", {"text": "second"}])
+        tags.save()
+        assert mutagen_rs.ID3(mp3_copy).getall("COMM:note:eng") == ["first", "second"]
+
+    def test_text_replaces_frames(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.setall("TPE1", ["a", "b"])
+        assert tags["TPE1"] == ["a", "b"]
+        tags.setall("TXXX:mood", ["calm"])
+        assert tags["TXXX:mood"] == "calm"
+
+    def test_unsupported_key(self, mp3_copy):
+        with pytest.raises(ValueError):
+            mutagen_rs.ID3(mp3_copy).setall("POPM:x", [{}])
+
+
+class TestAdd:
+
+    def test_two_comments(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        before = set(tags.keys())
+        tags.add({"FrameID": "COMM", "text": "first", "desc": "one"})
+        tags.add({"FrameID": "COMM", "text": "second", "desc": "two", "lang": "deu"})
+        tags.save()
+        saved = mutagen_rs.ID3(mp3_copy)
+        assert saved["COMM:one:eng"] == "first"
+        assert saved["COMM:two:deu"] == "second"
+        assert before <= set(saved.keys())
+
+    def test_appends_under_same_key(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.setall("COMM:note:eng", ["first"])
+        tags.add({"text": "second"}, key="COMM:note:eng")
+        assert tags.getall("COMM:note:eng") == ["first", "second"]
+
+    def test_text_and_picture(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.add({"FrameID": "TXXX", "desc": "mood", "text": ["calm", "warm"]})
+        tags.add({"FrameID": "APIC", "data": b"\x89PNG fake", "mime": "image/png", "type": 4, "desc": "back"})
+        assert tags["TXXX:mood"] == ["calm", "warm"]
+        assert tags["APIC:back:4"]["type"] == 4
+
+    def test_missing_frame_id(self, mp3_copy):
+        with pytest.raises(ValueError):
+            mutagen_rs.ID3(mp3_copy).add({"text": "x"})
+        with pytest.raises(ValueError):
+            mutagen_rs.ID3(mp3_copy).add({"FrameID": "POPM"})
+
+
+def frame_body(tag, frame_id):
+    """Body of the first v2.3 frame `frame_id` in a rendered tag."""
+    pos = 10
+    while pos + 10 <= len(tag) and tag[pos:pos + 4] != b"\x00\x00\x00\x00":
+        size = struct.unpack(">I", tag[pos + 4:pos + 8])[0]
+        if tag[pos:pos + 4] == frame_id:
+            return tag[pos + 10:pos + 10 + size]
+        pos += 10 + size
+    return None
+
+
+class TestFrameInfo:
+
+    def write(self, tmp_path):
+        title = v23_frame("TIT2", b"\x01" + "Titel".encode("utf-16"))
+        comment = v23_frame("COMM", b"\x00deuNotiz\x00Hallo")
+        path = tmp_path / "info.mp3"
+        path.write_bytes(make_v23_tag(title + comment) + audio_of(get_test_file("silence-44-s.mp3")))
+        return str(path)
+
+    def test_utf16_text_frame(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write(tmp_path))
+        assert tags["TIT2"] == "Titel"
+        assert tags.frame_info("TIT2") == {"encoding": 1, "language": None, "description": None}
+
+    def test_comment(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write(tmp_path))
+        assert tags.frame_info("COMM:Notiz:deu") == {"encoding": 0, "language": "deu", "description": "Notiz"}
+
+    def test_missing_key(self, tmp_path):
+        assert mutagen_rs.ID3(self.write(tmp_path)).frame_info("TXXX:missing") is None
+
+
+class TestPictureKeys:
+
+    def write(self, tmp_path):
+        front = v23_frame("APIC", b"\x00image/png\x00\x03\x00" + b"front")
+        back = v23_frame("APIC", b"\x00image/png\x00\x04\x00" + b"back")
+        path = tmp_path / "covers.mp3"
+        audio = audio_of(get_test_file("silence-44-s.mp3"))
+        path.write_bytes(make_v23_tag(front + back) + audio)
+        return str(path)
+
+    def test_same_description_different_types(self, tmp_path):
+        path = self.write(tmp_path)
+        tags = mutagen_rs.ID3(path)
+        assert tags["APIC:"]["data"] == b"front"
+        assert tags["APIC::4"]["data"] == b"back"
+        assert len(tags.getall("APIC")) == 2
+
+    def test_all_paths_keep_both(self, tmp_path):
+        path = self.write(tmp_path)
+        native = mutagen_rs.mutagen_rs.MP3(path)
+        fast = mutagen_rs._fast_read(path)
+        for f in (native, fast):
+            assert f["APIC:"]["type"] == 3
+            assert f["APIC::4"]["type"] == 4
+
+    def test_survives_save(self, tmp_path):
+        path = self.write(tmp_path)
+        mutagen_rs.ID3(path).save()
+        tags = mutagen_rs.ID3(path)
+        assert (tags["APIC:"]["data"], tags["APIC::4"]["data"]) == (b"front", b"back")
+
+    def test_rename_keeps_type(self, tmp_path):
+        tags = mutagen_rs.ID3(self.write(tmp_path))
+        tags.rename("APIC::4", "APIC:back:4")
+        assert tags["APIC:back:4"]["desc"] == "back"
+        with pytest.raises(ValueError):
+            tags.rename("APIC:back:4", "APIC:back")
+
+    def test_setall_type_from_key(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.setall("APIC::4", [{"data": b"back"}])
+        assert tags["APIC::4"]["type"] == 4
+
+    def test_front_cover_description_ending_in_digits(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags.setall("APIC:Disc:2", [{"data": b"front", "type": 3}])
+        assert tags["APIC:Disc:2"]["desc"] == "Disc:2"
+        assert tags["APIC:Disc:2"]["type"] == 3
+        tags.rename("APIC:Disc:2", "APIC:Disc:3")
+        assert tags["APIC:Disc:3"]["desc"] == "Disc:3"
+        assert tags["APIC:Disc:3"]["type"] == 3
+
+
+class TestV23TextEncoding:
+
+    def test_ascii_defaults_to_utf16(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "Title"
+        body = frame_body(tags.save(dry_run=True)["new_tag"], b"TIT2")
+        assert body == b"\x01\xff\xfe" + "Title".encode("utf-16-le")
+
+    def test_prefer_latin1_ascii(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "Title"
+        body = frame_body(tags.save(dry_run=True, prefer_latin1=True)["new_tag"], b"TIT2")
+        assert body == b"\x00Title"
+
+    def test_prefer_latin1_falls_back_for_cyrillic(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "Привет"
+        tags.save(prefer_latin1=True)
+        with open(mp3_copy, "rb") as f:
+            body = frame_body(f.read(), b"TIT2")
+        assert body == b"\x01\xff\xfe" + "Привет".encode("utf-16-le")
+        assert mutagen_rs.ID3(mp3_copy)["TIT2"] == "Привет"
+
+    def test_prefer_latin1_roundtrip(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "Café"
+        tags.save(prefer_latin1=True)
+        with open(mp3_copy, "rb") as f:
+            assert frame_body(f.read(), b"TIT2") == b"\x00Caf\xe9"
+        assert mutagen_rs.ID3(mp3_copy)["TIT2"] == "Café"
+
+
+class TestFlatItems:
+
+    def test_multi_value_text(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TCON"] = ["Rock", "Jazz"]
+        rows = tags.flat_items()
+        assert [v for k, v in rows if k == "TCON"] == ["Rock", "Jazz"]
+
+    def test_one_row_per_frame(self, mp3_copy):
+        # Two TPE1 frames in the fixture, one value each
+        rows = mutagen_rs.ID3(mp3_copy).flat_items()
+        assert [v for k, v in rows if k == "TPE1"] == ["piman", "jzig"]
+        assert ("TALB", "Quod Libet Test Data") in rows
+
+
+class TestGetallById:
+
+    def test_comments_with_different_descriptions(self, tmp_path):
+        frames = (v23_frame("COMM", b"\x00engnote\x00first")
+                  + v23_frame("COMM", b"\x00deuNotiz\x00second")
+                  + v23_frame("TXXX", b"\x00COMM\x00not a comment"))
+        path = tmp_path / "comments.mp3"
+        path.write_bytes(make_v23_tag(frames) + audio_of(get_test_file("silence-44-s.mp3")))
+        tags = mutagen_rs.ID3(str(path))
+        assert sorted(tags.getall_by_id("COMM")) == ["first", "second"]
+        assert tags.getall_by_id("TXXX") == ["not a comment"]
+
+    def test_missing_id(self, mp3_copy):
+        assert mutagen_rs.ID3(mp3_copy).getall_by_id("APIC") == []
+
+
+class TestGroupedFrames:
+
+    def write(self, tmp_path, version):
+        # TIT2 with the grouping flag and group id 0x07, then a plain TPE1
+        title = b"\x00Grouped"
+        if version == 4:
+            grouped = b"TIT2" + syncsafe(1 + len(title)) + b"\x00\x40" + b"\x07" + title
+            plain = b"TPE1" + syncsafe(7) + b"\x00\x00" + b"\x00Artist"
+        else:
+            grouped = b"TIT2" + struct.pack(">IH", 1 + len(title), 0x0020) + b"\x07" + title
+            plain = v23_text("TPE1", "Artist")
+        frames = grouped + plain
+        path = tmp_path / f"grouped{version}.mp3"
+        path.write_bytes(bytes([0x49, 0x44, 0x33, version, 0, 0]) + syncsafe(len(frames)) + frames
+                         + audio_of(get_test_file("silence-44-s.mp3")))
+        return str(path)
+
+    @pytest.mark.parametrize("version", [3, 4])
+    def test_group_byte_stripped(self, tmp_path, version):
+        path = self.write(tmp_path, version)
+        tags = mutagen_rs.ID3(path)
+        assert tags["TIT2"] == "Grouped"
+        assert tags["TPE1"] == "Artist"
+        mutagen_rs.clear_cache()
+        assert mutagen_rs.File(path)["TIT2"] == ["Grouped"]
+
+    @pytest.mark.parametrize("version", [3, 4])
+    def test_group_id_survives_save(self, tmp_path, version):
+        path = self.write(tmp_path, version)
+        tags = mutagen_rs.ID3(path)
+        tags.save(v2_version=version)
+        with open(path, "rb") as f:
+            data = f.read()
+        pos = data.index(b"TIT2")
+        assert data[pos + 9] == (0x40 if version == 4 else 0x20)
+        assert data[pos + 10] == 0x07
+        assert mutagen_rs.ID3(path)["TIT2"] == "Grouped"
+
+
+class TestSaveVerify:
+
+    @pytest.mark.parametrize("version", [3, 4])
+    def test_verify_passes(self, mp3_copy, version):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TIT2"] = "Verified"
+        tags.save(v2_version=version, verify=True)
+        assert mutagen_rs.ID3(mp3_copy)["TIT2"] == "Verified"
+
+    def test_mp3_save(self, mp3_copy):
+        mutagen_rs.MP3(mp3_copy).save(verify=True)
+        assert issubclass(mutagen_rs.VerificationError, mutagen_rs.MutagenError)
+
+
+class TestRenderV1:
+
+    def test_fields_at_v1_offsets(self):
+        tags = mutagen_rs.ID3()
+        tags["TIT2"] = "A title longer than the thirty byte v1 field"
+        tags["TPE1"] = "Artist"
+        tags["TCON"] = "Rock"
+        tags["TRCK"] = "7/12"
+        block = tags.render_v1()
+        assert len(block) == 128
+        assert block[:3] == b"TAG"
+        assert block[3:33] == b"A title longer than the thirty"
+        assert block[33:63] == b"Artist".ljust(30, b"\x00")
+        assert block[63:93] == b"\x00" * 30
+        assert block[125:127] == b"\x00\x07"
+        assert block[127] == 17
+
+    def test_from_file_and_empty_tag(self, mp3_copy):
+        block = mutagen_rs.ID3(mp3_copy).render_v1()
+        assert block[:3] == b"TAG"
+        assert block[3:33].rstrip(b"\x00").decode("latin-1") == mutagen_rs.ID3(mp3_copy)["TIT2"]
+        assert mutagen_rs.ID3().render_v1()[127] == 255
@@ -0,0 +1,77 @@
+"""Chapter and cue markers across formats via mutagen_rs.markers()."""
+import os
+import struct
+
+import pytest
+
+import mutagen_rs
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    return path
+
+
+def syncsafe(n):
+    return bytes([(n >> 21) & 0x7F, (n >> 14) & 0x7F, (n >> 7) & 0x7F, n & 0x7F])
+
+
+def v23_frame(frame_id, payload):
+    return frame_id + struct.pack(">IH", len(payload), 0) + payload
+
+
+def chap(element_id, start_ms, end_ms, title=None):
+    body = element_id + b"\x00" + struct.pack(">IIII", start_ms, end_ms, 0xFFFFFFFF, 0xFFFFFFFF)
+    if title is not None:
+        body += v23_frame(b"TIT2", b"\x00" + title.encode("latin-1"))
+    return v23_frame(b"CHAP", body)
+
+
+class TestMP3:
+
+    def write(self, tmp_path, frames):
+        with open(get_test_file("silence-44-s.mp3"), "rb") as f:
+            data = f.read()
+        audio = data[10 + ((data[6] << 21) | (data[7] << 14) | (data[8] << 7) | data[9]):]
+        path = tmp_path / "chapters.mp3"
+        path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(frames)) + frames + audio)
+        return str(path)
+
+    def test_chap_frames(self, tmp_path):
+        # Written out of order; markers come back sorted by start
+        frames = chap(b"ch1", 1500, 3000, "Second") + chap(b"ch0", 0, 1500, "First")
+        assert mutagen_rs.markers(self.write(tmp_path, frames)) == [(0.0, "First"), (1.5, "Second")]
+
+    def test_untitled_chapter_uses_element_id(self, tmp_path):
+        path = self.write(tmp_path, chap(b"intro", 250, 1000))
+        assert mutagen_rs.markers(path) == [(0.25, "intro")]
+
+    def test_no_chapters(self):
+        assert mutagen_rs.markers(get_test_file("silence-44-s.mp3")) == []
+
+
+class TestFLAC:
+
+    def test_cuesheet(self):
+        # Three tracks at 0 s, 1 s and 2 s at 44.1 kHz; the lead-out is dropped
+        assert mutagen_rs.markers(get_test_file("silence-44-s.flac")) == [
+            (0.0, "Track 01"), (1.0, "Track 02"), (2.0, "Track 03"),
+        ]
+
+    def test_no_cuesheet(self):
+        assert mutagen_rs.markers(get_test_file("no-tags.flac")) == []
+
+
+class TestMP4:
+
+    def test_matches_chapters(self):
+        path = get_test_file("nero-chapters.m4b")
+        assert mutagen_rs.markers(path) == mutagen_rs.mutagen_rs.MP4(path).chapters()
+
+
+def test_format_without_markers():
+    assert mutagen_rs.markers(get_test_file("silence-44-s.wv")) == []
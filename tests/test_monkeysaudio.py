@@ -0,0 +1,54 @@
+"""Monkey's Audio stream info tests for mutagen_rs."""
+import os
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import MonkeysAudio as NativeMonkeysAudio
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    return path
+
+
+class TestMonkeysAudioInfo:
+
+    @pytest.mark.parametrize("name, version", [
+        ("mac-399.ape", 3.99),
+        ("mac-396.ape", 3.96),
+    ])
+    def test_stream_info(self, name, version):
+        f = NativeMonkeysAudio(get_test_file(name))
+        assert f.info.version == pytest.approx(version)
+        assert f.info.sample_rate == 44100
+        assert f.info.channels == 2
+        assert f.info.bits_per_sample == 16
+        assert f.info.length == pytest.approx(3.68, abs=0.01)
+
+    def test_old_header(self):
+        f = NativeMonkeysAudio(get_test_file("mac-390-hdr.ape"))
+        assert f.info.version == pytest.approx(3.90)
+        assert f.info.length == pytest.approx(15.63, abs=0.01)
+
+    def test_pprint(self):
+        f = NativeMonkeysAudio(get_test_file("mac-399.ape"))
+        assert f.info.pprint().startswith("Monkey's Audio 3.99")
+
+    def test_not_monkeysaudio(self):
+        with pytest.raises(mutagen_rs.MonkeysAudioHeaderError):
+            NativeMonkeysAudio(get_test_file("silence-44-s.flac"))
+
+    def test_no_tags(self):
+        f = NativeMonkeysAudio(get_test_file("mac-399.ape"))
+        assert f.tags is None
+        assert f.keys() == []
+
+    def test_file_dispatch(self):
+        f = mutagen_rs.File(get_test_file("mac-399.ape"))
+        assert f.info.sample_rate == 44100
+        assert f.info.length == pytest.approx(3.68, abs=0.01)
@@ -0,0 +1,285 @@
+"""MP3 stream parsing tests for mutagen_rs (no reference mutagen needed)."""
+import os
+import shutil
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import MP3 as NativeMP3
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    return os.path.join(TEST_DIR, name)
+
+
+def crc16(data, crc=0xFFFF):
+    for b in data:
+        crc ^= b << 8
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x8005) if crc & 0x8000 else (crc << 1)
+            crc &= 0xFFFF
+    return crc
+
+
+def protected_frame(seed, corrupt=False):
+    """MPEG1 Layer III, 128 kbps, 44.1 kHz, stereo, CRC-protected (417 bytes)."""
+    header = b"\xff\xfa\x90\x00"
+    side_info = bytes((seed + i) & 0x7F for i in range(32))
+    crc = crc16(header[2:4] + side_info)
+    if corrupt:
+        crc ^= 0x0100
+    frame = header + crc.to_bytes(2, "big") + side_info
+    return frame + b"\x00" * (417 - len(frame))
+
+
+def write_stream(tmp_path, name, frames):
+    path = tmp_path / name
+    path.write_bytes(b"".join(frames))
+    return str(path)
+
+
+class TestFrameCRC:
+
+    def test_valid_crc_accepted(self, tmp_path):
+        path = write_stream(tmp_path, "ok.mp3", [protected_frame(i) for i in range(20)])
+        f = NativeMP3(path, check_crc=True)
+        assert f.info.protected
+        assert f.info.sample_rate == 44100
+        assert f.info.bitrate == 128000
+
+    def test_corrupt_crc_rejected(self, tmp_path):
+        frames = [protected_frame(i, corrupt=True) for i in range(20)]
+        path = write_stream(tmp_path, "bad.mp3", frames)
+        # Without the check the stream is accepted as before
+        assert NativeMP3(path).info.sample_rate == 44100
+        with pytest.raises(mutagen_rs.HeaderNotFoundError):
+            NativeMP3(path, check_crc=True)
+
+    def test_skips_corrupt_first_frame(self, tmp_path):
+        frames = [protected_frame(0, corrupt=True)] + [protected_frame(i) for i in range(1, 20)]
+        path = write_stream(tmp_path, "first.mp3", frames)
+        f = NativeMP3(path, check_crc=True)
+        # Sync lands on the second frame, so one frame's worth of audio is skipped
+        assert f.info.length < NativeMP3(path).info.length
+
+
+class TestNumberPairs:
+
+    @pytest.fixture
+    def mp3_copy(self, tmp_path):
+        src = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        dst = tmp_path / "numbers.mp3"
+        shutil.copy(src, dst)
+        return str(dst)
+
+    def test_trck_tpos(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TRCK"] = "3/12"
+        tags["TPOS"] = "1/2"
+        tags.save()
+        f = NativeMP3(mp3_copy)
+        assert f.track_number() == (3, 12)
+        assert f.disc_number() == (1, 2)
+
+    def test_number_without_total(self, mp3_copy):
+        tags = mutagen_rs.ID3(mp3_copy)
+        tags["TRCK"] = "7"
+        del tags["TPOS"]
+        tags.save()
+        f = NativeMP3(mp3_copy)
+        assert f.track_number() == (7, None)
+        assert f.disc_number() == (None, None)
+
+
+class TestLAMEInfo:
+
+    def load(self, name):
+        path = get_test_file(name)
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        return NativeMP3(path).info
+
+    def test_vbr_settings(self):
+        info = self.load("lame.mp3")
+        assert info.encoder_info == "LAME3.99r"
+        assert info.encoder_settings == "-V 2"
+        assert info.bitrate_mode == 2
+
+    def test_vbr_quality_397(self):
+        assert self.load("lame397v9short.mp3").encoder_settings == "-V 9"
+
+    def test_vbr_old_398(self):
+        assert self.load("silence-44-s-mpeg2.mp3").encoder_settings == "-V 9 --vbr-old"
+
+    def test_cbr_settings(self):
+        info = self.load("lame-peak.mp3")
+        assert info.encoder_settings == "-b 128"
+        assert info.bitrate_mode == 1
+
+    def test_no_lame_header(self):
+        assert self.load("silence-44-s.mp3").encoder_settings == ""
+
+    def test_decoded_length(self):
+        info = self.load("silence-44-s-mpeg2.mp3")
+        # 24 kHz MPEG-2; delay + padding trim 1999 samples (~0.083 s)
+        assert info.decoded_length < info.length
+        assert info.length - info.decoded_length == pytest.approx(1999 / 24000, abs=1e-4)
+        assert self.load("silence-44-s.mp3").decoded_length is None
+
+    def test_bitrate_excludes_xing_frame(self):
+        # A 128 kbps CBR encode: counting the Info frame overshot by ~25%
+        assert self.load("lame-peak.mp3").bitrate == pytest.approx(128000, rel=0.01)
+
+
+class TestChannelMode:
+
+    def test_joint_stereo(self):
+        path = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        info = NativeMP3(path).info
+        assert info.mode_str() == "Joint Stereo"
+        assert info.mode_extension == 2
+        assert info.ms_stereo
+        assert not info.intensity_stereo
+
+    def test_intensity_and_ms(self, tmp_path):
+        path = get_test_file("no-tags.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        with open(path, "rb") as f:
+            data = bytearray(f.read())
+        # First frame header at offset 0: set both mode extension bits
+        data[3] |= 0x30
+        dst = tmp_path / "both.mp3"
+        dst.write_bytes(bytes(data))
+        info = NativeMP3(str(dst)).info
+        assert info.mode_extension == 3
+        assert info.intensity_stereo and info.ms_stereo
+
+
+class TestToJSON:
+
+    def test_tagged_mp3(self):
+        import json
+        path = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        f = NativeMP3(path)
+        data = json.loads(f.to_json())
+        assert data["sample_rate"] == 44100
+        assert data["channels"] == f.info.channels
+        assert data["length"] == pytest.approx(f.info.length)
+        assert data["bitrate"] == f.info.bitrate
+        assert data["tags"]["TIT2"] == f["TIT2"]
+
+
+class TestTagOnly:
+
+    def write(self, tmp_path):
+        src = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        with open(src, "rb") as f:
+            data = f.read()
+        size = (data[6] << 21) | (data[7] << 14) | (data[8] << 7) | data[9]
+        return write_stream(tmp_path, "stub.mp3", [data[:10 + size]])
+
+    def test_tags_readable(self, tmp_path):
+        path = self.write(tmp_path)
+        f = NativeMP3(path)
+        assert f.info.length == 0
+        assert not f.info.has_audio
+        full = NativeMP3(get_test_file("silence-44-s.mp3"))
+        assert f["TIT2"] == full["TIT2"]
+        assert mutagen_rs.MP3(path)["TIT2"] == mutagen_rs.MP3(get_test_file("silence-44-s.mp3"))["TIT2"]
+
+    def test_has_audio(self):
+        path = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        assert NativeMP3(path).info.has_audio
+
+    def test_no_tag_still_fails(self, tmp_path):
+        path = write_stream(tmp_path, "empty.mp3", [b"\x00" * 64])
+        with pytest.raises(Exception):
+            NativeMP3(path)
+
+
+class TestTrailingID3v2:
+
+    @pytest.mark.parametrize("name,length,bitrate", [
+        ("audacious-trailing-id32-apev2.mp3", 1.08, 16800),
+        ("audacious-trailing-id32-id31.mp3", 3.818, 32000),
+    ])
+    def test_stream_info_kept(self, name, length, bitrate):
+        path = get_test_file(name)
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        for info in (mutagen_rs.MP3(path).info, NativeMP3(path).info):
+            assert info.length == pytest.approx(length, abs=1e-3)
+            assert info.bitrate == bitrate
+
+
+class TestAddTags:
+
+    def test_untagged_file(self):
+        path = get_test_file("no-tags.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        f = NativeMP3(path)
+        assert not f.has_tags
+        assert f.tags is None
+        f.add_tags()
+        assert f.has_tags
+        assert f.tags.keys() == []
+        with pytest.raises(mutagen_rs.ID3Error):
+            f.add_tags()
+
+    def test_tagged_file(self):
+        path = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        f = NativeMP3(path)
+        assert f.has_tags
+        with pytest.raises(mutagen_rs.ID3Error):
+            f.add_tags()
+
+
+class TestSave:
+
+    @pytest.fixture
+    def mp3_copy(self, tmp_path):
+        src = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(src):
+            pytest.skip(f"Test file not found: {src}")
+        dst = tmp_path / "save.mp3"
+        shutil.copy(src, dst)
+        return str(dst)
+
+    def test_crc_and_version(self, mp3_copy):
+        NativeMP3(mp3_copy).save(crc=True, v2_version=3)
+        tags = mutagen_rs.ID3(mp3_copy, strict=True)
+        assert tags.version == (3, 0)
+        assert tags.crc_valid is True
+
+    def test_options_match_id3(self, mp3_copy):
+        plan = NativeMP3(mp3_copy).save(dry_run=True, padding=0, v2_version=3)
+        assert plan == mutagen_rs.ID3(mp3_copy).save(dry_run=True, padding=0, v2_version=3)
+        with pytest.raises(TypeError):
+            NativeMP3(mp3_copy).save(filename=mp3_copy)
+
+
+class TestLossless:
+
+    def test_mp3_is_lossy(self):
+        path = get_test_file("silence-44-s.mp3")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        info = NativeMP3(path).info
+        assert info.lossless is False
+        assert info.bits_per_sample is None
@@ -0,0 +1,509 @@
+"""MP4 reader tests for mutagen_rs (no reference mutagen needed)."""
+import os
+import struct
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import MP4 as NativeMP4
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    return os.path.join(TEST_DIR, name)
+
+
+def atom(name, payload=b""):
+    if isinstance(name, str):
+        name = name.encode("latin-1")
+    return struct.pack(">I", 8 + len(payload)) + name + payload
+
+
+def data_atom(type_indicator, payload):
+    return atom("data", struct.pack(">II", type_indicator, 0) + payload)
+
+
+def text_item(name, text):
+    return atom(name, data_atom(1, text.encode("utf-8")))
+
+
+def pair_item(name, number, total):
+    return atom(name, data_atom(0, struct.pack(">HHHH", 0, number, total, 0)))
+
+
+def freeform_item(mean, name, value):
+    return atom("----", atom("mean", b"\x00" * 4 + mean.encode())
+                + atom("name", b"\x00" * 4 + name.encode())
+                + data_atom(1, value.encode("utf-8")))
+
+
+def udta_atom(items, meta_prefix=True):
+    meta_hdlr = atom("hdlr", b"\x00" * 8 + b"mdirappl" + b"\x00" * 9)
+    prefix = b"\x00" * 4 if meta_prefix else b""
+    return atom("udta", atom("meta", prefix + meta_hdlr + atom("ilst", items)))
+
+
+def build_mp4(items=b"", duration=441000, timescale=44100, sample_rate=44100,
+              channels=2, codec=b"mp4a", extra_entry=b"", moov_extra=b"", meta_prefix=True,
+              trak_extra=b"", stbl_extra=b""):
+    """Minimal single-track audio MP4 with an optional ilst payload;
+    `meta_prefix=False` writes `meta` without its version/flags word."""
+    mvhd = atom("mvhd", struct.pack(">B3xIIII", 0, 0, 0, timescale, duration) + b"\x00" * 80)
+    hdlr = atom("hdlr", b"\x00" * 8 + b"soun" + b"\x00" * 12)
+    entry_body = (b"\x00" * 6 + struct.pack(">H", 1) + b"\x00" * 8
+                  + struct.pack(">HHHHI", channels, 16, 0, 0, sample_rate << 16)
+                  + extra_entry)
+    entry = struct.pack(">I", 8 + len(entry_body)) + codec + entry_body
+    stsd = atom("stsd", struct.pack(">II", 0, 1) + entry)
+    trak = atom("trak", atom("mdia", hdlr + atom("minf", atom("stbl", stsd + stbl_extra))) + trak_extra)
+    moov_children = mvhd + trak + moov_extra
+    if items:
+        moov_children += udta_atom(items, meta_prefix)
+    ftyp = atom("ftyp", b"M4A \x00\x00\x00\x00M4A mp42isom")
+    return ftyp + atom("moov", moov_children) + atom("mdat", b"\x00" * 256)
+
+
+def esds_atom(max_bitrate, avg_bitrate):
+    """esds with an ES_Descriptor wrapping a DecoderConfigDescriptor (AAC LC)."""
+    dec_config = struct.pack(">BB3sII", 0x40, 0x15, b"\x00\x00\x00", max_bitrate, avg_bitrate)
+    es = struct.pack(">HB", 1, 0) + b"\x04" + bytes([len(dec_config)]) + dec_config
+    return atom("esds", b"\x00" * 4 + b"\x03\x80\x80\x80" + bytes([len(es)]) + es)
+
+
+def write_mp4(tmp_path, data, name="test.m4a"):
+    path = tmp_path / name
+    path.write_bytes(data)
+    return str(path)
+
+
+class TestNumberPairs:
+
+    def test_trkn_disk(self, tmp_path):
+        items = pair_item("trkn", 3, 12) + pair_item("disk", 1, 2)
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(items)))
+        assert f.track_number() == (3, 12)
+        assert f.disc_number() == (1, 2)
+
+    def test_missing(self, tmp_path):
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(text_item(b"\xa9nam", "x"))))
+        assert f.track_number() == (None, None)
+        assert f.disc_number() == (None, None)
+
+    def test_zero_total(self, tmp_path):
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(pair_item("trkn", 5, 0))))
+        assert f.track_number() == (5, None)
+
+
+class TestDateAndISRC:
+
+    def test_itunes_date_and_isrc(self, tmp_path):
+        items = (text_item(b"\xa9day", "2018-05-01T07:00:00Z")
+                 + freeform_item("com.apple.iTunes", "ISRC", "USRC17607839"))
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(items)))
+        assert f.tags.date() == "2018-05-01"
+        assert f.tags.isrc() == "USRC17607839"
+        assert f["----:com.apple.iTunes:ISRC"] == [b"USRC17607839"]
+
+    def test_year_only_date(self, tmp_path):
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(text_item(b"\xa9day", "2018"))))
+        assert f.tags.date() == "2018"
+        assert f.tags.isrc() is None
+
+    def test_freeform_keys(self):
+        path = get_test_file("alac.m4a")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        keys = NativeMP4(path).keys()
+        assert "----" not in keys
+        assert any(k.startswith("----:com.apple.iTunes:") for k in keys)
+
+
+class TestTypedSetters:
+
+    def tags(self, tmp_path):
+        return NativeMP4(write_mp4(tmp_path, build_mp4(text_item(b"\xa9nam", "Old")))).tags
+
+    def test_set_and_read_back(self, tmp_path):
+        tags = self.tags(tmp_path)
+        tags["©nam"] = "New Title"
+        tags["trkn"] = (3, 12)
+        tags["cpil"] = True
+        tags["tmpo"] = 120
+        tags["----:com.apple.iTunes:MOOD"] = "Calm"
+        assert tags["©nam"] == "New Title"
+        assert tags["trkn"] == (3, 12)
+        assert tags["cpil"] is True
+        assert tags["tmpo"] == 120
+        assert tags["----:com.apple.iTunes:MOOD"] == [b"Calm"]
+        assert tags.keys().count("©nam") == 1
+
+    def test_text_list(self, tmp_path):
+        tags = self.tags(tmp_path)
+        tags["©ART"] = ["A", "B"]
+        assert tags["©ART"] == ["A", "B"]
+
+    def test_lists(self, tmp_path):
+        tags = self.tags(tmp_path)
+        tags["tmpo"] = [120]
+        tags["rtng"] = [1, 2]
+        tags["trkn"] = [(3, 12)]
+        tags["disk"] = [(1, 2), (2, 2)]
+        tags["----:com.apple.iTunes:MOOD"] = [b"Calm", b"Warm"]
+        png = b"\x89PNG\r\n\x1a\n" + b"\x00" * 8
+        tags["covr"] = [b"\xff\xd8\xff\x00", png]
+        assert tags["tmpo"] == 120
+        assert tags["rtng"] == [1, 2]
+        assert tags["trkn"] == (3, 12)
+        assert tags["disk"] == [(1, 2), (2, 2)]
+        assert tags["----:com.apple.iTunes:MOOD"] == [b"Calm", b"Warm"]
+        assert [c["format"] for c in tags["covr"]] == [13, 14]
+        assert tags["covr"][1]["data"] == png
+
+    def test_invalid_keys(self, tmp_path):
+        tags = self.tags(tmp_path)
+        for key in ["abc", "toolong", "----:mean", "----::name", "\u0100nam"]:
+            with pytest.raises(ValueError):
+                tags[key] = "x"
+
+
+class TestBitrateMode:
+
+    def test_equal_max_and_avg_is_cbr(self, tmp_path):
+        path = write_mp4(tmp_path, build_mp4(extra_entry=esds_atom(128000, 128000)))
+        assert NativeMP4(path).info.bitrate_mode == 1
+        assert mutagen_rs.MP4(path).info.bitrate_mode == 1
+
+    def test_avg_below_max_is_vbr(self, tmp_path):
+        path = write_mp4(tmp_path, build_mp4(extra_entry=esds_atom(256000, 128000)))
+        assert NativeMP4(path).info.bitrate_mode == 2
+
+    def test_no_esds_is_unknown(self, tmp_path):
+        path = write_mp4(tmp_path, build_mp4())
+        assert NativeMP4(path).info.bitrate_mode == 0
+
+
+class TestMetaWithoutPrefix:
+
+    def test_native(self, tmp_path):
+        items = text_item(b"\xa9nam", "Title") + pair_item("trkn", 3, 12)
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(items, meta_prefix=False)))
+        assert f["\xa9nam"] == "Title"
+        assert f.track_number() == (3, 12)
+
+    def test_fast_read(self, tmp_path):
+        items = text_item(b"\xa9nam", "Title")
+        f = mutagen_rs.MP4(write_mp4(tmp_path, build_mp4(items, meta_prefix=False)))
+        assert f["\xa9nam"] == ["Title"]
+
+
+class TestUdtaOutsideMoov:
+
+    def test_trak_udta(self, tmp_path):
+        trak_udta = udta_atom(text_item(b"\xa9nam", "Track Title"))
+        path = write_mp4(tmp_path, build_mp4(trak_extra=trak_udta))
+        assert NativeMP4(path)["\xa9nam"] == "Track Title"
+        assert mutagen_rs.MP4(path)["\xa9nam"] == ["Track Title"]
+
+    def test_top_level_udta(self, tmp_path):
+        data = build_mp4() + udta_atom(text_item(b"\xa9ART", "Camera"))
+        path = write_mp4(tmp_path, data, name="clip.mp4")
+        assert NativeMP4(path)["\xa9ART"] == "Camera"
+        assert mutagen_rs.MP4(path)["\xa9ART"] == ["Camera"]
+
+    def test_moov_udta_is_primary(self, tmp_path):
+        trak_udta = udta_atom(text_item(b"\xa9nam", "Track Title") + text_item(b"\xa9ART", "Artist"))
+        data = build_mp4(text_item(b"\xa9nam", "Movie Title"), trak_extra=trak_udta)
+        path = write_mp4(tmp_path, data)
+        for f in (NativeMP4(path), mutagen_rs.MP4(path)):
+            assert f.keys() == ["\xa9nam", "\xa9ART"]
+        assert NativeMP4(path)["\xa9nam"] == "Movie Title"
+        assert NativeMP4(path)["\xa9ART"] == "Artist"
+        assert mutagen_rs.MP4(path)["\xa9nam"] == ["Movie Title"]
+
+
+class TestReplayGain:
+
+    def test_freeform_values(self, tmp_path):
+        items = (freeform_item("com.apple.iTunes", "replaygain_track_gain", "-7.30 dB")
+                 + freeform_item("com.apple.iTunes", "replaygain_track_peak", "0.988525")
+                 + freeform_item("com.apple.iTunes", "REPLAYGAIN_ALBUM_GAIN", "+1.25 dB"))
+        rg = NativeMP4(write_mp4(tmp_path, build_mp4(items))).replaygain()
+        assert rg["track_gain"] == pytest.approx(-7.30)
+        assert rg["track_peak"] == pytest.approx(0.988525)
+        assert rg["album_gain"] == pytest.approx(1.25)
+        assert rg["album_peak"] is None
+
+    def test_missing_or_garbage(self, tmp_path):
+        items = freeform_item("com.apple.iTunes", "replaygain_track_gain", "loud")
+        rg = NativeMP4(write_mp4(tmp_path, build_mp4(items))).replaygain()
+        assert rg == {"track_gain": None, "track_peak": None, "album_gain": None, "album_peak": None}
+
+
+class TestGet:
+
+    def test_get(self, tmp_path):
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(text_item(b"\xa9nam", "Title"))))
+        tags = f.tags
+        assert tags.get("\xa9nam") == tags["\xa9nam"]
+        assert tags.get("\xa9ART", []) == []
+        assert tags.get("\xa9ART") is None
+
+
+def chpl_atom(chapters, version=1):
+    """Nero chapter list; `chapters` is [(start_seconds, title)]."""
+    body = struct.pack(">B3x", version) + (b"\x00" * 4 if version == 1 else b"")
+    body += bytes([len(chapters)])
+    for start, title in chapters:
+        title = title.encode("utf-8")
+        body += struct.pack(">QB", int(start * 10_000_000), len(title)) + title
+    return atom("udta", atom("chpl", body))
+
+
+def chapter_track_mp4(titles, delta=5000, timescale=1000, stts_count=None, stsz_count=None):
+    """MP4 whose audio track references a QuickTime text track of chapter titles.
+    `stts_count`/`stsz_count` override the declared sample counts."""
+    samples = [struct.pack(">H", len(t.encode())) + t.encode() for t in titles]
+
+    def build(mdat_offset):
+        offsets, pos = [], mdat_offset
+        for sample in samples:
+            offsets.append(pos)
+            pos += len(sample)
+        tkhd = atom("tkhd", struct.pack(">B3xIII", 0, 0, 0, 2) + b"\x00" * 72)
+        mdhd = atom("mdhd", struct.pack(">B3xIIII", 0, 0, 0, timescale, delta * len(titles)) + b"\x00" * 4)
+        stbl = atom("stbl",
+                    atom("stts", struct.pack(">II", 0, 1) + struct.pack(">II", stts_count or len(titles), delta))
+                    + atom("stsc", struct.pack(">II", 0, 1) + struct.pack(">III", 1, 1, 1))
+                    + atom("stsz", struct.pack(">III", 0, 0, stsz_count or len(samples))
+                           + b"".join(struct.pack(">I", len(x)) for x in samples))
+                    + atom("stco", struct.pack(">II", 0, len(offsets))
+                           + b"".join(struct.pack(">I", o) for o in offsets)))
+        text_trak = atom("trak", tkhd + atom("mdia", mdhd + atom("minf", stbl)))
+        audio = build_mp4(moov_extra=text_trak)
+        # Point the audio track at track 2 through tref/chap
+        tref = atom("tref", atom("chap", struct.pack(">I", 2)))
+        moov_at = audio.index(b"moov") - 4
+        trak_at = audio.index(b"trak") - 4
+        moov_size, trak_size = (struct.unpack(">I", audio[i:i + 4])[0] for i in (moov_at, trak_at))
+        audio = (audio[:moov_at] + struct.pack(">I", moov_size + len(tref)) + audio[moov_at + 4:trak_at]
+                 + struct.pack(">I", trak_size + len(tref)) + audio[trak_at + 4:trak_at + 8] + tref
+                 + audio[trak_at + 8:])
+        return audio + atom("mdat", b"".join(samples))
+
+    data = build(0)
+    return build(len(data) - sum(len(x) for x in samples))
+
+
+class TestChapters:
+
+    def test_text_track(self, tmp_path):
+        f = NativeMP4(write_mp4(tmp_path, chapter_track_mp4(["Opening", "Middle", "Close"])))
+        assert f.chapters() == [(0.0, "Opening"), (5.0, "Middle"), (10.0, "Close")]
+
+    @pytest.mark.parametrize("counts", [{"stts_count": 4_000_000_000}, {"stsz_count": 4_000_000_000}])
+    def test_huge_sample_counts(self, tmp_path, counts):
+        path = write_mp4(tmp_path, chapter_track_mp4(["Opening", "Middle", "Close"], **counts))
+        assert NativeMP4(path).chapters() == [(0.0, "Opening"), (5.0, "Middle"), (10.0, "Close")]
+        assert mutagen_rs.MP4(path).info.length > 0
+
+    def test_nero_chpl(self, tmp_path):
+        chpl = chpl_atom([(0.0, "Intro"), (12.5, "Chapter Two"), (60.25, "End")])
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(moov_extra=chpl), "book.m4b"))
+        chapters = f.chapters()
+        assert len(chapters) == 3
+        assert chapters[0] == (0.0, "Intro")
+        assert chapters[1][0] == pytest.approx(12.5)
+        assert chapters[2][1] == "End"
+
+    def test_nero_chpl_version_0(self, tmp_path):
+        chpl = chpl_atom([(1.0, "One")], version=0)
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(moov_extra=chpl)))
+        assert f.chapters() == [(1.0, "One")]
+
+    def test_nero_chapters_fixture(self):
+        path = get_test_file("nero-chapters.m4b")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        chapters = NativeMP4(path).chapters()
+        assert len(chapters) == 112
+        assert chapters[0] == (0.0, "001")
+        assert chapters[1][0] == pytest.approx(17.507)
+
+    def test_text_track_fixture(self):
+        path = get_test_file("ep7.m4b")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        assert NativeMP4(path).chapters() == [(0.0, "Chapter 1")]
+
+    def test_no_chapters(self, tmp_path):
+        assert NativeMP4(write_mp4(tmp_path, build_mp4())).chapters() == []
+
+
+class TestToJSON:
+
+    def test_to_json(self, tmp_path):
+        import json
+        f = NativeMP4(write_mp4(tmp_path, build_mp4(text_item(b"\xa9nam", "Title"))))
+        data = json.loads(f.to_json())
+        assert data["sample_rate"] == 44100
+        assert data["length"] == pytest.approx(10.0)
+        assert data["tags"]["\xa9nam"] == "Title"
+
+
+def aac_esds(object_type, channel_config=2, rate_index=4, sbr_rate_index=None):
+    """esds whose DecoderSpecificInfo starts with the given audio object type,
+    sampling frequency index (4 = 44.1 kHz) and channel configuration; with
+    `sbr_rate_index`, followed by the SBR extension rate and an AAC LC core."""
+    fields = [(object_type, 5), (rate_index, 4), (channel_config, 4)]
+    if sbr_rate_index is not None:
+        fields += [(sbr_rate_index, 4), (2, 5)]
+    value, nbits = 0, 0
+    for field, width in fields:
+        value, nbits = (value << width) | field, nbits + width
+    nbytes = (nbits + 7) // 8
+    asc = (value << (nbytes * 8 - nbits)).to_bytes(nbytes, "big")
+    dec_config = (struct.pack(">BB3sII", 0x40, 0x15, b"\x00\x00\x00", 128000, 128000)
+                  + b"\x05" + bytes([len(asc)]) + asc)
+    es = struct.pack(">HB", 1, 0) + b"\x04" + bytes([len(dec_config)]) + dec_config
+    return atom("esds", b"\x00" * 4 + b"\x03" + bytes([len(es)]) + es)
+
+
+class TestCodecDescription:
+
+    @pytest.mark.parametrize("object_type, expected", [
+        (2, "MPEG-4 AAC LC"),
+        (5, "MPEG-4 AAC HE"),
+        (29, "MPEG-4 AAC HEv2"),
+    ])
+    def test_aac_profiles(self, tmp_path, object_type, expected):
+        data = build_mp4(extra_entry=aac_esds(object_type))
+        path = write_mp4(tmp_path, data)
+        assert NativeMP4(path).info.codec_description == expected
+        assert mutagen_rs._fast_read(path)["codec_description"] == expected
+
+    def test_aac_lc_fixture(self):
+        path = get_test_file("has-tags.m4a")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        assert NativeMP4(path).info.codec_description == "MPEG-4 AAC LC"
+
+    def test_alac_fixture(self):
+        path = get_test_file("alac.m4a")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        info = NativeMP4(path).info
+        assert info.codec == "alac"
+        assert info.codec_description == "Apple Lossless"
+        assert info.lossless
+        assert info.bits_per_sample == 16
+
+    def test_aac_is_lossy(self):
+        path = get_test_file("has-tags.m4a")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        assert not NativeMP4(path).info.lossless
+
+
+class TestChannelConfiguration:
+
+    @pytest.mark.parametrize("object_type, channel_config, expected", [
+        (2, 6, 6),
+        (2, 7, 8),
+        (5, 1, 1),
+        (29, 1, 2),
+    ])
+    def test_esds_overrides_sample_entry(self, tmp_path, object_type, channel_config, expected):
+        data = build_mp4(channels=2, extra_entry=aac_esds(object_type, channel_config))
+        path = write_mp4(tmp_path, data)
+        assert NativeMP4(path).info.channels == expected
+        assert mutagen_rs._fast_read(path)["channels"] == expected
+        assert mutagen_rs._fast_info(path)["channels"] == expected
+
+    def test_pce_config_keeps_sample_entry(self, tmp_path):
+        path = write_mp4(tmp_path, build_mp4(channels=2, extra_entry=aac_esds(2, 0)))
+        assert NativeMP4(path).info.channels == 2
+
+
+class TestSampleRate:
+
+    def test_he_aac_reports_sbr_rate(self, tmp_path):
+        # 24 kHz core in the sample entry, SBR doubling it to 48 kHz
+        data = build_mp4(sample_rate=24000, extra_entry=aac_esds(5, sbr_rate_index=3, rate_index=6))
+        path = write_mp4(tmp_path, data)
+        assert NativeMP4(path).info.sample_rate == 48000
+        assert mutagen_rs._fast_read(path)["sample_rate"] == 48000
+        assert mutagen_rs._fast_info(path)["sample_rate"] == 48000
+
+    def test_lc_uses_esds_rate(self, tmp_path):
+        data = build_mp4(sample_rate=44100, extra_entry=aac_esds(2, rate_index=3))
+        assert NativeMP4(write_mp4(tmp_path, data)).info.sample_rate == 48000
+
+    def test_fractional_entry_rate_rounds(self, tmp_path):
+        data = build_mp4(sample_rate=0)
+        pos = data.index(b"mp4a") + 4 + 24
+        data = data[:pos] + struct.pack(">I", (22050 << 16) | 0xC000) + data[pos + 4:]
+        assert NativeMP4(write_mp4(tmp_path, data)).info.sample_rate == 22051
+
+
+class TestBitrate:
+
+    COVER = atom("covr", data_atom(13, b"\xff\xd8\xff" + b"\x00" * 200_000))
+
+    def test_excludes_cover_art(self, tmp_path):
+        # 10 s; only the 256-byte mdat payload counts
+        data = build_mp4(self.COVER)
+        path = write_mp4(tmp_path, data)
+        whole_file = len(data) * 8 // 10
+        assert NativeMP4(path).info.bitrate == 256 * 8 // 10
+        assert NativeMP4(path).info.bitrate < whole_file // 100
+        assert mutagen_rs.MP4(path).info.bitrate == 256 * 8 // 10
+
+    def test_stsz_sample_sizes(self, tmp_path):
+        sizes = [100, 120, 80]
+        stsz = atom("stsz", struct.pack(">III", 0, 0, len(sizes)) + struct.pack(">3I", *sizes))
+        path = write_mp4(tmp_path, build_mp4(self.COVER, stbl_extra=stsz))
+        assert NativeMP4(path).info.bitrate == 300 * 8 // 10
+
+    def test_stsz_fixed_sample_size(self, tmp_path):
+        stsz = atom("stsz", struct.pack(">III", 0, 50, 4))
+        path = write_mp4(tmp_path, build_mp4(stbl_extra=stsz))
+        assert NativeMP4(path).info.bitrate == 200 * 8 // 10
+
+
+class TestDuration:
+
+    def test_matches_info_length(self):
+        path = get_test_file("has-tags.m4a")
+        if not os.path.exists(path):
+            pytest.skip(f"Test file not found: {path}")
+        length = NativeMP4(path).info.length
+        assert length > 0
+        assert mutagen_rs.duration(path) == length
+
+    def test_other_formats(self):
+        for name in ("silence-44-s.mp3", "silence-44-s.flac"):
+            path = get_test_file(name)
+            assert mutagen_rs.duration(path) == mutagen_rs.File(path).info.length
+
+
+class TestCoverFormat:
+
+    PNG = b"\x89PNG\r\n\x1a\n" + b"\x00" * 16
+    BMP = b"BM" + b"\x00" * 16
+
+    def test_implicit_type_sniffs_png(self, tmp_path):
+        path = write_mp4(tmp_path, build_mp4(atom("covr", data_atom(0, self.PNG))))
+        covers = NativeMP4(path)["covr"]
+        assert covers == [{"data": self.PNG, "format": 14}]
+        assert mutagen_rs._fast_read(path)["covr"] == self.PNG
+        assert mutagen_rs.front_cover(path) == ("image/png", self.PNG)
+
+    def test_bmp_and_mixed_types(self, tmp_path):
+        covr = atom("covr", data_atom(27, self.BMP) + data_atom(0, self.BMP) + data_atom(13, b"\xff\xd8\xff"))
+        path = write_mp4(tmp_path, build_mp4(covr))
+        assert [c["format"] for c in NativeMP4(path)["covr"]] == [27, 27, 13]
+
+    def test_unknown_type_kept(self, tmp_path):
+        path = write_mp4(tmp_path, build_mp4(atom("covr", data_atom(99, b"not an image"))))
+        assert NativeMP4(path)["covr"] == [{"data": b"not an image", "format": 99}]
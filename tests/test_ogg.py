@@ -0,0 +1,171 @@
+"""Ogg Vorbis tests for mutagen_rs (mostly synthesized streams)."""
+import os
+import struct
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import OggVorbis as NativeOggVorbis
+
+
+def ogg_crc(data):
+    crc = 0
+    for byte in data:
+        crc ^= byte << 24
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x04C11DB7) if crc & 0x80000000 else (crc << 1)
+            crc &= 0xFFFFFFFF
+    return crc
+
+
+def ogg_page(serial, seq, granule, packet, header_type=0):
+    lacing = [255] * (len(packet) // 255) + [len(packet) % 255]
+    header = struct.pack("<4sBBqIII", b"OggS", 0, header_type, granule, serial, seq, 0)
+    page = header + bytes([len(lacing)]) + bytes(lacing) + packet
+    crc = ogg_crc(page)
+    return page[:22] + struct.pack("<I", crc) + page[26:]
+
+
+def vorbis_headers(serial, sample_rate=44100, channels=2, bitrates=(0, 128000, 0)):
+    """Identification + comment pages; `bitrates` is (max, nominal, min)."""
+    ident = (b"\x01vorbis" + struct.pack("<IBIiii", 0, channels, sample_rate, *bitrates)
+             + b"\xb8\x01")
+    vendor = b"mutagen_rs"
+    comment = b"\x03vorbis" + struct.pack("<I", len(vendor)) + vendor + struct.pack("<I", 0) + b"\x01"
+    return (ogg_page(serial, 0, 0, ident, header_type=0x02)
+            + ogg_page(serial, 1, 0, comment))
+
+
+class TestLastGranule:
+
+    def test_final_page_beyond_default_window(self, tmp_path):
+        vorbis, other = 0x1111, 0x2222
+        data = vorbis_headers(vorbis)
+        data += ogg_page(other, 0, 0, b"\x00" * 32, header_type=0x02)
+        data += ogg_page(vorbis, 2, 441000, b"\x00" * 200, header_type=0x04)
+        # ~100 KB of another multiplexed stream after the last Vorbis page
+        for seq in range(1, 26):
+            data += ogg_page(other, seq, seq * 1000, b"\x01" * 4000)
+        path = tmp_path / "muxed.ogg"
+        path.write_bytes(data)
+
+        f = NativeOggVorbis(str(path))
+        assert f.info.length == pytest.approx(10.0)
+        assert mutagen_rs._fast_info(str(path))["length"] == pytest.approx(10.0)
+
+    def test_final_page_within_window(self, tmp_path):
+        serial = 0x1111
+        data = vorbis_headers(serial)
+        data += ogg_page(serial, 2, 88200, b"\x00" * 200, header_type=0x04)
+        path = tmp_path / "short.ogg"
+        path.write_bytes(data)
+        assert NativeOggVorbis(str(path)).info.length == pytest.approx(2.0)
+
+
+def shift_granules(data, offset):
+    """Add `offset` to the granule position of every audio page, as if the
+    stream had been cut from a longer one."""
+    out, pos = bytearray(), 0
+    while pos < len(data):
+        nsegs = data[pos + 26]
+        size = 27 + nsegs + sum(data[pos + 27:pos + 27 + nsegs])
+        page = bytearray(data[pos:pos + size])
+        granule = struct.unpack("<q", page[6:14])[0]
+        if granule > 0:
+            page[6:14] = struct.pack("<q", granule + offset)
+            page[22:26] = b"\x00" * 4
+            page[22:26] = struct.pack("<I", ogg_crc(page))
+        out += page
+        pos += size
+    return bytes(out)
+
+
+class TestStartGranule:
+
+    def test_cut_stream_length(self, tmp_path):
+        source = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files", "multipage-setup.ogg")
+        if not os.path.exists(source):
+            pytest.skip(f"Test file not found: {source}")
+        with open(source, "rb") as f:
+            data = f.read()
+        path = tmp_path / "cut.ogg"
+        path.write_bytes(shift_granules(data, 44100 * 60))
+        # The last granule alone would give 64.13 s
+        f = NativeOggVorbis(str(path))
+        assert f.info.length == pytest.approx(182080 / 44100)
+        assert mutagen_rs._fast_info(str(path))["length"] == pytest.approx(182080 / 44100)
+        assert mutagen_rs._fast_read(str(path))["length"] == pytest.approx(182080 / 44100)
+
+class TestBitrateMode:
+
+    def write(self, tmp_path, bitrates):
+        serial = 0x1111
+        data = vorbis_headers(serial, bitrates=bitrates)
+        data += ogg_page(serial, 2, 88200, b"\x00" * 200, header_type=0x04)
+        path = tmp_path / "mode.ogg"
+        path.write_bytes(data)
+        return str(path)
+
+    def test_nominal_only_is_vbr(self, tmp_path):
+        path = self.write(tmp_path, (0, 128000, 0))
+        assert NativeOggVorbis(path).info.bitrate_mode == 2
+        assert mutagen_rs.OggVorbis(path).info.bitrate_mode == 2
+
+    def test_granule_derived_is_vbr(self, tmp_path):
+        path = self.write(tmp_path, (-1, 0, -1))
+        f = NativeOggVorbis(path)
+        assert f.info.bitrate > 0
+        assert f.info.bitrate_mode == 2
+
+    def test_equal_bounds_is_cbr(self, tmp_path):
+        path = self.write(tmp_path, (128000, 128000, 128000))
+        assert NativeOggVorbis(path).info.bitrate_mode == 1
+        assert mutagen_rs.OggVorbis(path).info.bitrate_mode == 1
+
+    def test_header_fields(self, tmp_path):
+        info = NativeOggVorbis(self.write(tmp_path, (192000, 160000, 96000))).info
+        assert (info.bitrate_nominal, info.bitrate_upper, info.bitrate_lower) == (160000, 192000, 96000)
+        assert info.bitrate == 160000
+
+    def test_unset_fields_kept_apart_from_bitrate(self, tmp_path):
+        info = NativeOggVorbis(self.write(tmp_path, (-1, 0, -1))).info
+        assert (info.bitrate_nominal, info.bitrate_upper, info.bitrate_lower) == (0, -1, -1)
+        assert info.bitrate > 0
+
+
+class TestGet:
+
+    def test_get(self, tmp_path):
+        path = tmp_path / "get.ogg"
+        path.write_bytes(vorbis_headers(0x1111)
+                         + ogg_page(0x1111, 2, 44100, b"\x00" * 200, header_type=0x04))
+        tags = NativeOggVorbis(str(path)).tags
+        tags["title"] = "Title"
+        assert tags.get("title") == ["Title"]
+        assert tags.get("artist", []) == []
+        assert tags.get("artist") is None
+
+
+class TestVendor:
+
+    def test_set_vendor(self, tmp_path):
+        path = tmp_path / "vendor.ogg"
+        path.write_bytes(vorbis_headers(0x1111)
+                         + ogg_page(0x1111, 2, 44100, b"\x00" * 200, header_type=0x04))
+        tags = NativeOggVorbis(str(path)).tags
+        assert tags.vendor == "mutagen_rs"
+        tags.vendor = "Lavf58.76.100"
+        assert tags.vendor == "Lavf58.76.100"
+
+
+class TestToJSON:
+
+    def test_to_json(self, tmp_path):
+        import json
+        path = tmp_path / "json.ogg"
+        path.write_bytes(vorbis_headers(0x1111)
+                         + ogg_page(0x1111, 2, 88200, b"\x00" * 200, header_type=0x04))
+        data = json.loads(NativeOggVorbis(str(path)).to_json())
+        assert data["sample_rate"] == 44100
+        assert data["length"] == pytest.approx(2.0)
+        assert data["tags"] == {}
@@ -0,0 +1,91 @@
+"""Ogg FLAC tests for mutagen_rs (no reference mutagen needed)."""
+import os
+import struct
+
+import pytest
+
+from mutagen_rs.mutagen_rs import OggFLAC, detect_format, file_open
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    return path
+
+
+def ogg_crc(data):
+    crc = 0
+    for byte in data:
+        crc ^= byte << 24
+        for _ in range(8):
+            crc = ((crc << 1) ^ 0x04C11DB7 if crc & 0x80000000 else crc << 1) & 0xFFFFFFFF
+    return crc
+
+
+def page_size(data, pos):
+    nsegs = data[pos + 26]
+    return 27 + nsegs + sum(data[pos + 27:pos + 27 + nsegs])
+
+
+def replace_second_page(data, packet):
+    """Swap the packet on page 1 (the comment block) for `packet`."""
+    start = page_size(data, 0)
+    end = start + page_size(data, start)
+    lacing = bytes([255] * (len(packet) // 255) + [len(packet) % 255])
+    page = bytearray(data[start:start + 26] + bytes([len(lacing)]) + lacing + packet)
+    page[22:26] = b"\x00\x00\x00\x00"
+    page[22:26] = struct.pack("<I", ogg_crc(page))
+    return data[:start] + bytes(page) + data[end:]
+
+
+@pytest.fixture
+def tagged(tmp_path):
+    with open(get_test_file("empty.oggflac"), "rb") as f:
+        data = f.read()
+    comments = [b"TITLE=Ogg FLAC Title", b"ARTIST=Someone"]
+    body = struct.pack("<I", 6) + b"vendor" + struct.pack("<I", len(comments))
+    for c in comments:
+        body += struct.pack("<I", len(c)) + c
+    packet = b"\x04" + len(body).to_bytes(3, "big") + body
+    path = tmp_path / "tagged.ogg"
+    path.write_bytes(replace_second_page(data, packet))
+    return str(path)
+
+
+class TestOggFLAC:
+
+    def test_info(self):
+        f = OggFLAC(get_test_file("empty.oggflac"))
+        assert f.info.lossless
+        assert f.info.sample_rate == 44100
+        assert f.info.channels == 2
+        assert f.info.bits_per_sample == 16
+        assert f.info.length == pytest.approx(3.68, abs=0.01)
+
+    def test_vendor(self):
+        f = OggFLAC(get_test_file("empty.oggflac"))
+        assert f.tags.vendor.startswith("reference libFLAC")
+        assert f.keys() == []
+
+    def test_tags(self, tagged):
+        f = OggFLAC(tagged)
+        assert f["TITLE"] == ["Ogg FLAC Title"]
+        assert f["ARTIST"] == ["Someone"]
+        assert "ALBUM" not in f
+
+    def test_file_open_ogg_extension(self, tagged):
+        f = file_open(tagged)
+        assert isinstance(f, OggFLAC)
+        assert f["TITLE"] == ["Ogg FLAC Title"]
+
+    def test_detect(self, tagged):
+        assert detect_format(get_test_file("empty.oggflac")) == "oggflac"
+        assert detect_format(tagged) == "oggflac"
+        assert detect_format(get_test_file("empty.ogg")) == "ogg"
+
+    def test_forced_format(self):
+        f = file_open(get_test_file("empty.oggflac"), format="oggflac")
+        assert isinstance(f, OggFLAC)
@@ -0,0 +1,104 @@
+"""Format detection tests for mutagen_rs."""
+import os
+import shutil
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import file_open, MP4Error
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    return os.path.join(TEST_DIR, name)
+
+
+def copy_as(tmp_path, name, new_name):
+    src = get_test_file(name)
+    if not os.path.exists(src):
+        pytest.skip(f"Test file not found: {src}")
+    dst = tmp_path / new_name
+    shutil.copy(src, dst)
+    return str(dst)
+
+
+@pytest.mark.parametrize("name, expected", [
+    ("silence-44-s.mp3", "mp3"),
+    ("silence-44-s.flac", "flac"),
+    ("empty.ogg", "ogg"),
+    ("has-tags.m4a", "mp4"),
+    ("silence-44-s.wv", "wavpack"),
+    ("silence-1.wma", "asf"),
+    ("mac-399.ape", "monkeysaudio"),
+])
+def test_detect_known_files(name, expected):
+    path = get_test_file(name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    assert mutagen_rs.detect_format(path) == expected
+
+
+def test_content_beats_extension(tmp_path):
+    assert mutagen_rs.detect_format(copy_as(tmp_path, "silence-44-s.flac", "audio.bin")) == "flac"
+    assert mutagen_rs.detect_format(copy_as(tmp_path, "has-tags.m4a", "audio.mp3")) == "mp4"
+
+
+def test_mp3_with_oggs_in_art(tmp_path):
+    # An ID3-tagged MP3 whose tag data contains an "OggS" capture pattern,
+    # misleadingly named .ogg: content evidence (ID3 header) must beat the
+    # extension-only Ogg match.
+    path = copy_as(tmp_path, "silence-44-s.mp3", "tricky.ogg")
+    with open(path, "rb") as f:
+        data = bytearray(f.read())
+    data[200:204] = b"OggS"
+    with open(path, "wb") as f:
+        f.write(data)
+    assert mutagen_rs.detect_format(path) == "mp3"
+
+
+def test_unknown(tmp_path):
+    path = tmp_path / "junk.xyz"
+    path.write_bytes(b"\x00" * 1024)
+    assert mutagen_rs.detect_format(str(path)) is None
+
+
+def test_large_unknown_file_not_read(tmp_path):
+    # file_open scores only the header of a sparse 100 MB file, so an
+    # unrecognized one is rejected without being loaded into the cache.
+    path = tmp_path / "large.bin"
+    with open(path, "wb") as f:
+        f.truncate(100 * 1024 * 1024)
+    mutagen_rs.clear_cache()
+    with pytest.raises(ValueError):
+        file_open(str(path))
+    assert mutagen_rs.file_cache_info()[0] == 0
+
+
+def test_forced_format(tmp_path):
+    path = copy_as(tmp_path, "silence-44-s.flac", "audio.dat")
+    f = file_open(path, format="flac")
+    assert type(f).__name__ == "FLAC"
+    assert f["TITLE"] == file_open(get_test_file("silence-44-s.flac"))["TITLE"]
+    assert type(file_open(path, format="FLAC")).__name__ == "FLAC"
+
+
+def test_forced_format_errors(tmp_path):
+    path = copy_as(tmp_path, "silence-44-s.flac", "audio.dat")
+    with pytest.raises(ValueError, match="Unknown format"):
+        file_open(path, format="aiff")
+    # Forcing the wrong format reports that parser's error
+    with pytest.raises(MP4Error):
+        file_open(path, format="mp4")
+
+
+def test_unrecognized_file_is_mutagen_error(tmp_path):
+    path = tmp_path / "junk.xyz"
+    path.write_bytes(b"\x00" * 1024)
+    with pytest.raises(mutagen_rs.MutagenError) as excinfo:
+        file_open(str(path))
+    assert isinstance(excinfo.value, mutagen_rs.InvalidDataError)
+    # Still a ValueError for callers that caught that before
+    assert isinstance(excinfo.value, ValueError)
+    with pytest.raises(mutagen_rs.MutagenError):
+        mutagen_rs.File(str(path))
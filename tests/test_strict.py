@@ -0,0 +1,151 @@
+"""Strict parse mode: truncated metadata raises TruncatedError with
+strict=True and yields the tags read before the damage otherwise."""
+import os
+import struct
+
+import pytest
+
+from mutagen_rs.mutagen_rs import file_open, ID3Error, OggError, TruncatedError
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def read_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    with open(path, "rb") as f:
+        return f.read()
+
+
+def syncsafe(n):
+    return bytes([(n >> 21) & 0x7F, (n >> 14) & 0x7F, (n >> 7) & 0x7F, n & 0x7F])
+
+
+def v23_frame(frame_id, body, size=None):
+    size = len(body) if size is None else size
+    return frame_id + struct.pack(">IH", size, 0) + body
+
+
+def vc_block(comments, declared=None):
+    """Vorbis comment body; `declared` overrides the last comment's length."""
+    out = struct.pack("<I", 6) + b"vendor" + struct.pack("<I", len(comments))
+    for i, c in enumerate(comments):
+        n = declared if declared is not None and i == len(comments) - 1 else len(c)
+        out += struct.pack("<I", n) + c
+    return out
+
+
+def flac_with_blocks(data, replace):
+    """Rebuild a FLAC file, passing each (type, body) through `replace`."""
+    out, pos = bytearray(b"fLaC"), 4
+    while True:
+        header = data[pos]
+        size = int.from_bytes(data[pos + 1:pos + 4], "big")
+        body = data[pos + 4:pos + 4 + size]
+        pos += 4 + size
+        header, body = replace(header, body)
+        out += bytes([header]) + len(body).to_bytes(3, "big") + body
+        if header & 0x80:
+            break
+    return bytes(out) + data[pos:]
+
+
+@pytest.fixture
+def truncated_mp3(tmp_path):
+    data = read_test_file("silence-44-s.mp3")
+    audio = data[10 + ((data[6] << 21) | (data[7] << 14) | (data[8] << 7) | data[9]):]
+    if audio[-128:-125] == b"TAG":
+        audio = audio[:-128]
+    # TPE1 claims 200 bytes but the tag ends after 5
+    frames = v23_frame(b"TIT2", b"\x00Hello") + v23_frame(b"TPE1", b"\x00Arti", size=200)
+    path = tmp_path / "truncated.mp3"
+    path.write_bytes(b"ID3\x03\x00\x00" + syncsafe(len(frames)) + frames + audio)
+    return str(path)
+
+
+@pytest.fixture
+def truncated_flac(tmp_path):
+    data = read_test_file("silence-44-s.flac")
+
+    def replace(header, body):
+        if header & 0x7F == 4:
+            body = vc_block([b"TITLE=Hello", b"ARTIST=Artist"], declared=500)
+        return header, body
+
+    path = tmp_path / "truncated.flac"
+    path.write_bytes(flac_with_blocks(data, replace))
+    return str(path)
+
+
+class TestMP3:
+    def test_lenient_keeps_earlier_frames(self, truncated_mp3):
+        f = file_open(truncated_mp3)
+        assert f["TIT2"] == "Hello"
+        assert "TPE1" not in f
+
+    def test_strict_raises(self, truncated_mp3):
+        with pytest.raises(ID3Error):
+            file_open(truncated_mp3, strict=True)
+
+    def test_strict_accepts_intact_file(self):
+        path = os.path.join(TEST_DIR, "silence-44-s.mp3")
+        assert file_open(path, strict=True)["TIT2"] == file_open(path)["TIT2"]
+
+
+class TestFLAC:
+    def test_lenient_keeps_earlier_comments(self, truncated_flac):
+        f = file_open(truncated_flac)
+        assert f["TITLE"] == ["Hello"]
+        assert "ARTIST" not in f
+
+    def test_strict_raises(self, truncated_flac):
+        with pytest.raises(TruncatedError):
+            file_open(truncated_flac, strict=True)
+
+    def test_oversized_entry_skipped(self, tmp_path):
+        data = read_test_file("silence-44-s.flac")
+        body = vc_block([b"TITLE=Hello", b"ARTIST=Artist", b"ALBUM=Album"])
+        pos = body.index(b"ARTIST") - 4
+        body = body[:pos] + struct.pack("<I", 0x7FFFFFFF) + body[pos + 4:]
+
+        def replace(header, old):
+            return header, body if header & 0x7F == 4 else old
+
+        path = tmp_path / "oversized.flac"
+        path.write_bytes(flac_with_blocks(data, replace))
+        f = file_open(str(path))
+        assert f["TITLE"] == ["Hello"]
+        assert f["ALBUM"] == ["Album"]
+        assert "ARTIST" not in f
+        with pytest.raises(TruncatedError):
+            file_open(str(path), strict=True)
+
+    def test_strict_truncated_block(self, tmp_path):
+        data = read_test_file("silence-44-s.flac")
+        # Cut the file inside the second metadata block
+        first = 4 + 4 + int.from_bytes(data[5:8], "big")
+        path = tmp_path / "short.flac"
+        path.write_bytes(data[:first + 10])
+        file_open(str(path))
+        with pytest.raises(TruncatedError):
+            file_open(str(path), strict=True)
+
+
+class TestOggVorbis:
+    def test_truncated_comment_page(self, tmp_path):
+        data = read_test_file("multipage-setup.ogg")
+        page1 = 27 + data[26] + sum(data[27:27 + data[26]])
+        path = tmp_path / "short.ogg"
+        path.write_bytes(data[:page1 + 60])
+        assert file_open(str(path)).keys() == []
+        with pytest.raises(TruncatedError) as excinfo:
+            file_open(str(path), strict=True)
+        assert not isinstance(excinfo.value, OggError)
+
+    def test_not_ogg_is_not_truncated(self, tmp_path):
+        path = tmp_path / "bogus.ogg"
+        path.write_bytes(b"NotOggS" + b"\x00" * 100)
+        with pytest.raises(Exception) as excinfo:
+            file_open(str(path))
+        assert not isinstance(excinfo.value, TruncatedError)
@@ -0,0 +1,75 @@
+"""WavPack stream info and APEv2 tag tests for mutagen_rs."""
+import os
+
+import pytest
+
+import mutagen_rs
+from mutagen_rs.mutagen_rs import WavPack as NativeWavPack
+
+TEST_DIR = os.path.join(os.path.dirname(os.path.dirname(__file__)), "test_files")
+
+
+def get_test_file(name):
+    path = os.path.join(TEST_DIR, name)
+    if not os.path.exists(path):
+        pytest.skip(f"Test file not found: {path}")
+    return path
+
+
+class TestWavPackInfo:
+
+    def test_stream_info(self):
+        f = NativeWavPack(get_test_file("silence-44-s.wv"))
+        assert f.info.sample_rate == 44100
+        assert f.info.channels == 2
+        assert f.info.bits_per_sample == 16
+        assert f.info.version == 0x403
+        assert abs(f.info.length - 3.68471) < 1e-4
+        assert f.info.bitrate > 0
+
+    def test_unknown_total_samples(self):
+        f = NativeWavPack(get_test_file("no_length.wv"))
+        assert f.info.sample_rate == 44100
+        assert abs(f.info.length - 3.705) < 1e-3
+
+    def test_dsd(self):
+        f = NativeWavPack(get_test_file("dsd.wv"))
+        assert f.info.sample_rate == 352800
+        assert f.info.bits_per_sample == 1
+        assert f.info.channels == 2
+        assert abs(f.info.length - 0.01) < 1e-6
+
+    @pytest.mark.parametrize("size", [72, 5000])
+    def test_truncated_block(self, tmp_path, size):
+        with open(get_test_file("no_length.wv"), "rb") as f:
+            data = f.read()[:size]
+        path = tmp_path / "truncated.wv"
+        path.write_bytes(data)
+        f = NativeWavPack(str(path))
+        assert f.info.sample_rate == 44100
+        assert f.info.length < 3.71
+        assert mutagen_rs.WavPack(str(path)).info.sample_rate == 44100
+
+    def test_not_wavpack(self):
+        with pytest.raises(mutagen_rs.WavPackError):
+            NativeWavPack(get_test_file("silence-44-s.flac"))
+
+
+class TestWavPackTags:
+
+    def test_apev2_tags(self):
+        f = NativeWavPack(get_test_file("silence-44-s.wv"))
+        assert isinstance(f.tags, mutagen_rs.APEv2)
+        assert "artist" in f.tags
+        assert f.tags["Title"] == f["Title"]
+
+    def test_no_tags(self):
+        f = NativeWavPack(get_test_file("no_length.wv"))
+        assert f.tags is None
+        assert f.keys() == []
+
+    def test_file_dispatch(self):
+        path = get_test_file("silence-44-s.wv")
+        f = mutagen_rs.File(path)
+        assert f.info.sample_rate == 44100
+        assert abs(f.info.length - 3.68471) < 1e-4
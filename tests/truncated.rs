@@ -0,0 +1,60 @@
+use mutagen_rs::common::error::MutagenError;
+use mutagen_rs::common::options::ParseOptions;
+use mutagen_rs::flac::FLACFile;
+use mutagen_rs::ogg::OggPage;
+use mutagen_rs::vorbis::VorbisComment;
+
+fn read_test_file(name: &str) -> Option<Vec<u8>> {
+    std::fs::read(format!("{}/test_files/{}", env!("CARGO_MANIFEST_DIR"), name)).ok()
+}
+
+#[test]
+fn ogg_page_cut_off() {
+    let Some(data) = read_test_file("multipage-setup.ogg") else { return };
+    let page = OggPage::parse(&data, 0).unwrap();
+    for end in [20, 27 + 1, page.size - 1] {
+        let err = OggPage::parse(&data[..end], 0).unwrap_err();
+        assert!(matches!(err, MutagenError::Truncated(_)), "{}: {:?}", end, err);
+    }
+    // Wrong magic is still a format error
+    assert!(matches!(OggPage::parse(&data, 1), Err(MutagenError::Ogg(_))));
+}
+
+#[test]
+fn vorbis_comment_cut_off() {
+    let mut vc = VorbisComment::new();
+    vc.set("TITLE", vec!["Hello".to_string()]);
+    let data = vc.render(false);
+    for end in [2, 8, data.len() - 20] {
+        let err = VorbisComment::parse(&data[..end], false).unwrap_err();
+        assert!(matches!(err, MutagenError::Truncated(_)), "{}: {:?}", end, err);
+    }
+}
+
+#[test]
+fn flac_block_cut_off_in_strict_mode() {
+    let Some(data) = read_test_file("silence-44-s.flac") else { return };
+    let err = FLACFile::parse_with(&data[..100], "short.flac", ParseOptions::strict()).unwrap_err();
+    assert!(matches!(err, MutagenError::Truncated(_)), "{:?}", err);
+}
+
+#[test]
+fn oversized_comment_length_is_skipped() {
+    let mut vc = VorbisComment::new();
+    vc.set("TITLE", vec!["Hello".to_string()]);
+    vc.set("ARTIST", vec!["Artist".to_string()]);
+    vc.set("ALBUM", vec!["Album".to_string()]);
+    let mut data = vc.render(false);
+
+    // Corrupt the ARTIST entry's length to claim far more than the block holds
+    let pos = data.windows(6).position(|w| w == b"ARTIST").unwrap() - 4;
+    data[pos..pos + 4].copy_from_slice(&0x7FFF_FFFFu32.to_le_bytes());
+
+    let parsed = VorbisComment::parse(&data, false).unwrap();
+    assert_eq!(parsed.get("TITLE"), vec!["Hello"]);
+    assert_eq!(parsed.get("ALBUM"), vec!["Album"]);
+    assert!(parsed.get("ARTIST").is_empty());
+
+    let err = VorbisComment::parse_with(&data, false, ParseOptions::strict()).unwrap_err();
+    assert!(matches!(err, MutagenError::Truncated(_)), "{:?}", err);
+}
@@ -0,0 +1,59 @@
+use mutagen_rs::id3::unsynch::{decode, encode};
+
+/// Deterministic xorshift inputs drawn mostly from the bytes that matter to
+/// unsynchronisation (0xFF, 0x00, 0xE0..), with lengths up to 64.
+fn inputs() -> impl Iterator<Item = Vec<u8>> {
+    let mut state = 0x2545_F491_4F6C_DD1Du64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    (0..2000).map(move |_| {
+        let len = (next() % 65) as usize;
+        (0..len)
+            .map(|_| match next() % 4 {
+                0 => 0xFF,
+                1 => 0x00,
+                2 => 0xE0 | (next() as u8 & 0x1F),
+                _ => next() as u8,
+            })
+            .collect()
+    })
+}
+
+#[test]
+fn round_trip() {
+    for input in inputs() {
+        assert_eq!(decode(&encode(&input)).unwrap(), input, "{:02X?}", input);
+    }
+}
+
+#[test]
+fn encoded_has_no_false_sync() {
+    for input in inputs() {
+        let encoded = encode(&input);
+        for pair in encoded.windows(2) {
+            assert!(!(pair[0] == 0xFF && pair[1] >= 0xE0), "{:02X?}", input);
+        }
+        assert_ne!(encoded.last(), Some(&0xFF));
+    }
+}
+
+#[test]
+fn trailing_ff() {
+    assert_eq!(encode(&[0x41, 0xFF]), [0x41, 0xFF, 0x00]);
+    assert_eq!(decode(&[0x41, 0xFF, 0x00]).unwrap(), [0x41, 0xFF]);
+    // A trailing 0xFF with no inserted zero (a non-conforming tagger) is kept
+    assert_eq!(decode(&[0x41, 0xFF]).unwrap(), [0x41, 0xFF]);
+    assert_eq!(decode(&[0xFF]).unwrap(), [0xFF]);
+}
+
+#[test]
+fn ff_before_real_zero() {
+    // Source 0xFF 0x00 encodes as 0xFF 0x00 0x00; only the inserted zero goes
+    assert_eq!(encode(&[0xFF, 0x00]), [0xFF, 0x00, 0x00]);
+    assert_eq!(decode(&[0xFF, 0x00, 0x00]).unwrap(), [0xFF, 0x00]);
+    assert_eq!(decode(&[0xFF, 0x00, 0xFF, 0x00]).unwrap(), [0xFF, 0xFF]);
+}
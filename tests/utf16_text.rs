@@ -0,0 +1,37 @@
+use mutagen_rs::id3::frames::{parse_text_frame, Frame};
+use mutagen_rs::id3::specs::{decode_text, Encoding};
+
+fn text_values(data: &[u8]) -> Vec<String> {
+    match parse_text_frame("TIT2", data).unwrap() {
+        Frame::Text(f) => f.text,
+        other => panic!("not a text frame: {:?}", other),
+    }
+}
+
+#[test]
+fn lone_bom_is_empty() {
+    assert_eq!(decode_text(&[0xFF, 0xFE], Encoding::Utf16).unwrap(), "");
+    assert_eq!(decode_text(&[0xFE, 0xFF], Encoding::Utf16Be).unwrap(), "");
+    assert!(text_values(&[0x01, 0xFF, 0xFE]).is_empty());
+}
+
+#[test]
+fn odd_trailing_byte_dropped() {
+    assert_eq!(decode_text(&[0xFF, 0xFE, b'H', 0, b'i', 0, b'!'], Encoding::Utf16).unwrap(), "Hi");
+    assert_eq!(decode_text(&[0, b'H', 0, b'i', 0], Encoding::Utf16Be).unwrap(), "Hi");
+    assert_eq!(decode_text(b"H", Encoding::Utf16).unwrap(), "");
+}
+
+#[test]
+fn bom_overrides_declared_utf16be() {
+    assert_eq!(text_values(&[0x02, 0xFE, 0xFF, 0, b'H', 0, b'i']), ["Hi"]);
+    assert_eq!(text_values(&[0x02, 0xFF, 0xFE, b'H', 0, b'i', 0]), ["Hi"]);
+    assert_eq!(text_values(&[0x02, 0, b'H', 0, b'i']), ["Hi"]);
+}
+
+#[test]
+fn unpaired_surrogate_replaced() {
+    // High surrogate D83D with no low half, then "A"
+    let data = [0xFF, 0xFE, 0x3D, 0xD8, b'A', 0];
+    assert_eq!(decode_text(&data, Encoding::Utf16).unwrap(), "\u{FFFD}A");
+}
@@ -0,0 +1,28 @@
+use mutagen_rs::vorbis::VorbisComment;
+
+fn comment(entries: &[(&str, &str)]) -> VorbisComment {
+    let mut vc = VorbisComment::new();
+    vc.comments = entries.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+    vc
+}
+
+#[test]
+fn identical_lines_written_once() {
+    let vc = comment(&[("ARTIST", "X"), ("TITLE", "T"), ("ARTIST", "X")]);
+    let parsed = VorbisComment::parse(&vc.render_deduped(false), false).unwrap();
+    assert_eq!(parsed.comments, vec![
+        ("ARTIST".to_string(), "X".to_string()),
+        ("TITLE".to_string(), "T".to_string()),
+    ]);
+
+    // `render` keeps every entry for a faithful round trip
+    let parsed = VorbisComment::parse(&vc.render(false), false).unwrap();
+    assert_eq!(parsed.get("ARTIST"), vec!["X", "X"]);
+}
+
+#[test]
+fn multi_values_and_key_case() {
+    let vc = comment(&[("ARTIST", "X"), ("artist", "X"), ("ARTIST", "Y"), ("ARTIST", "x")]);
+    let parsed = VorbisComment::parse(&vc.render_deduped(true), true).unwrap();
+    assert_eq!(parsed.get("ARTIST"), vec!["X", "Y", "x"]);
+}
@@ -0,0 +1,24 @@
+use mutagen_rs::vorbis::{VorbisComment, DEFAULT_VENDOR};
+
+#[test]
+fn new_comment_has_default_vendor() {
+    let vc = VorbisComment::new();
+    assert_eq!(vc.vendor, DEFAULT_VENDOR);
+    let data = vc.render(false);
+    assert_eq!(&data[..4], &(DEFAULT_VENDOR.len() as u32).to_le_bytes());
+    assert_eq!(&data[4..4 + DEFAULT_VENDOR.len()], DEFAULT_VENDOR.as_bytes());
+}
+
+#[test]
+fn set_vendor_is_rendered_first() {
+    let mut vc = VorbisComment::new();
+    vc.set("TITLE", vec!["Hello".to_string()]);
+    vc.set_vendor("Lavf58.76.100");
+    let data = vc.render(true);
+    assert_eq!(&data[..4], &13u32.to_le_bytes());
+    assert_eq!(&data[4..17], b"Lavf58.76.100");
+
+    let parsed = VorbisComment::parse(&data, true).unwrap();
+    assert_eq!(parsed.vendor, "Lavf58.76.100");
+    assert_eq!(parsed.get("TITLE"), vec!["Hello"]);
+}